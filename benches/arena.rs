@@ -5,15 +5,16 @@ use criterion::criterion_group;
 use criterion::criterion_main;
 use rand::rng;
 use rs_poker::arena::Agent;
+use rs_poker::arena::Chips;
 use rs_poker::arena::GameState;
 use rs_poker::arena::HoldemSimulationBuilder;
 use rs_poker::arena::agent::RandomAgent;
 use rs_poker::arena::agent::RandomPotControlAgent;
 
-const STARTING_STACK: f32 = 100_000.0;
-const ANTE: f32 = 50.0;
-const SMALL_BLIND: f32 = 250.0;
-const BIG_BLIND: f32 = 500.0;
+const STARTING_STACK: Chips = Chips::new(100_000);
+const ANTE: Chips = Chips::new(50);
+const SMALL_BLIND: Chips = Chips::new(250);
+const BIG_BLIND: Chips = Chips::new(500);
 
 const DEFAULT_FOLD: f64 = 0.15;
 const DEFAULT_CALL: f64 = 0.5;
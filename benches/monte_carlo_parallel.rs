@@ -0,0 +1,32 @@
+#[macro_use]
+extern crate criterion;
+extern crate rs_poker;
+
+use criterion::Criterion;
+use rs_poker::core::Hand;
+use rs_poker::holdem::MonteCarloGame;
+
+fn four_player_game() -> MonteCarloGame {
+    let hands = ["AdAh", "KsKc", "QhJh", "7c6c"]
+        .iter()
+        .map(|s| Hand::new_from_str(s).expect("Should be able to create a hand."))
+        .collect();
+    MonteCarloGame::new(hands).expect("Should be able to create a game.")
+}
+
+fn serial_four_players(c: &mut Criterion) {
+    let mut g = four_player_game();
+    c.bench_function("Serial 10k sims 4 players", move |b| {
+        b.iter(|| g.estimate_equity(10_000))
+    });
+}
+
+fn parallel_four_players(c: &mut Criterion) {
+    let g = four_player_game();
+    c.bench_function("Parallel 10k sims 4 players", move |b| {
+        b.iter(|| g.estimate_equity_parallel(10_000))
+    });
+}
+
+criterion_group!(benches, serial_four_players, parallel_four_players);
+criterion_main!(benches);
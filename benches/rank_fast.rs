@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate criterion;
+extern crate rand;
+extern crate rs_poker;
+
+use criterion::Criterion;
+use rs_poker::core::{Deck, FlatDeck, Hand, Rankable};
+
+fn seven_card_hands() -> Vec<Hand> {
+    let mut d: FlatDeck = Deck::default().into();
+    (0..1000)
+        .map(|_| {
+            d.shuffle(&mut rand::rng());
+            Hand::new_with_cards(d.sample(7))
+        })
+        .collect()
+}
+
+fn rank_seven(c: &mut Criterion) {
+    let hands = seven_card_hands();
+    c.bench_function("rank() 1000 7 card hands", move |b| {
+        b.iter(|| hands.iter().map(|h| h.rank()).max())
+    });
+}
+
+fn rank_fast_seven(c: &mut Criterion) {
+    let hands = seven_card_hands();
+    // Build the tables outside of the timing.
+    hands[0].rank_fast();
+    c.bench_function("rank_fast() 1000 7 card hands", move |b| {
+        b.iter(|| hands.iter().map(|h| h.rank_fast()).max())
+    });
+}
+
+criterion_group!(benches, rank_seven, rank_fast_seven);
+criterion_main!(benches);
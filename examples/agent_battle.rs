@@ -1,7 +1,7 @@
 extern crate rs_poker;
 
 use rs_poker::arena::{
-    AgentGenerator, CloneHistorianGenerator, HistorianGenerator,
+    AgentGenerator, Chips, CloneHistorianGenerator, HistorianGenerator,
     agent::{CloneAgentGenerator, RandomAgentGenerator, RandomPotControlAgent},
     competition::{HoldemCompetition, StandardSimulationIterator},
     game_state::RandomGameStateGenerator,
@@ -33,8 +33,14 @@ fn main() {
     // Starting stack of at least 10 big blinds (10x10=100 chips)
     // Starting stack of no more than 1000 big blinds (10x1000=10000 chips)
     // This isn't deep stack poker at it's finest.
-    let game_state_gen =
-        RandomGameStateGenerator::new(agent_gens.len(), 100.0, 10000.0, 10.0, 5.0, 0.0);
+    let game_state_gen = RandomGameStateGenerator::new(
+        agent_gens.len(),
+        Chips::new(100),
+        Chips::new(10000),
+        Chips::new(10),
+        Chips::new(5),
+        Chips::ZERO,
+    );
     let simulation_gen = StandardSimulationIterator::new(agent_gens, hist_gens, game_state_gen);
     let mut comp = HoldemCompetition::new(simulation_gen);
     for _i in 0..5000 {
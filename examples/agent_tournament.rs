@@ -1,13 +1,13 @@
 use std::vec;
 
 use rs_poker::arena::{
-    AgentGenerator,
+    AgentGenerator, Chips,
     agent::{CallingAgentGenerator, RandomAgentGenerator},
     competition::SingleTableTournamentBuilder,
 };
 
 fn main() {
-    let stacks = vec![100.0, 100.0, 50.0];
+    let stacks = [100, 100, 50].map(Chips::new).to_vec();
 
     let agent_builders: Vec<Box<dyn AgentGenerator>> = vec![
         Box::new(CallingAgentGenerator),
@@ -15,8 +15,13 @@ fn main() {
         Box::<RandomAgentGenerator>::default(),
     ];
 
-    let game_state =
-        rs_poker::arena::game_state::GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+    let game_state = rs_poker::arena::game_state::GameState::new_starting(
+        stacks,
+        Chips::new(10),
+        Chips::new(5),
+        Chips::ZERO,
+        0,
+    );
 
     let tournament = SingleTableTournamentBuilder::default()
         .agent_generators(agent_builders)
@@ -1,7 +1,7 @@
-use rs_poker::arena::GameState;
 use rs_poker::arena::cfr::{
     CFRState, ExportFormat, NodeData, PlayerData, TerminalData, export_cfr_state,
 };
+use rs_poker::arena::{Chips, GameState};
 use std::path::Path;
 
 /// Creates an example CFR state tree for demonstration purposes.
@@ -9,7 +9,13 @@ use std::path::Path;
 /// call, and raise actions.
 fn create_example_cfr() -> CFRState {
     // Create a game state with 2 players
-    let game_state = GameState::new_starting(vec![100.0; 2], 10.0, 5.0, 0.0, 0);
+    let game_state = GameState::new_starting(
+        vec![Chips::new(100); 2],
+        Chips::new(10),
+        Chips::new(5),
+        Chips::ZERO,
+        0,
+    );
     let mut cfr_state = CFRState::new(game_state);
 
     // Root -> Player 0 decision
@@ -1,5 +1,5 @@
 use rs_poker::arena::{
-    Agent, Historian, HoldemSimulationBuilder,
+    Agent, Chips, Historian, HoldemSimulationBuilder,
     cfr::{
         BasicCFRActionGenerator, CFRAgent, ExportFormat, PerRoundFixedGameStateIteratorGen,
         StateStore, export_cfr_state,
@@ -9,9 +9,14 @@ use rs_poker::arena::{
 
 fn run_simulation(num_agents: usize, export_path: Option<std::path::PathBuf>) {
     // Create a game state with the specified number of agents
-    let stacks = vec![500.0; num_agents];
-    let game_state =
-        rs_poker::arena::game_state::GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+    let stacks = vec![Chips::new(500); num_agents];
+    let game_state = rs_poker::arena::game_state::GameState::new_starting(
+        stacks,
+        Chips::new(10),
+        Chips::new(5),
+        Chips::ZERO,
+        0,
+    );
 
     let mut state_store = StateStore::new();
 
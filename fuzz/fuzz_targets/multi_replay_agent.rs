@@ -14,6 +14,7 @@ use rs_poker::arena::{
     test_util::assert_valid_game_state,
     test_util::assert_valid_round_data,
     Agent,
+    Chips,
     GameState,
     HoldemSimulation,
 HoldemSimulationBuilder,
@@ -21,20 +22,20 @@ HoldemSimulationBuilder,
 
 use libfuzzer_sys::fuzz_target;
 
-const MIN_BLIND: f32 = 1e-15;
+const MAX_STACK: Chips = Chips::new(1_000_000);
 
 #[derive(Debug, Clone, arbitrary::Arbitrary)]
 struct PlayerInput {
-    pub stack: f32,
+    pub stack: Chips,
     pub actions: Vec<AgentAction>,
 }
 
 #[derive(Debug, Clone, arbitrary::Arbitrary)]
 struct MultiInput {
     pub players: Vec<PlayerInput>,
-    pub sb: f32,
-    pub bb: f32,
-    pub ante: f32,
+    pub sb: Chips,
+    pub bb: Chips,
+    pub ante: Chips,
     pub dealer_idx: usize,
     pub seed: u64,
 }
@@ -45,7 +46,7 @@ fn build_agent(actions: Vec<AgentAction>) -> Box<dyn Agent> {
 
 fn input_good(input: &MultiInput) -> bool {
     for player in &input.players {
-        if player.stack.is_nan() || player.stack.is_infinite() || player.stack.is_sign_negative() {
+        if player.stack < Chips::ZERO {
             return false;
         }
     }
@@ -58,30 +59,13 @@ fn input_good(input: &MultiInput) -> bool {
         return false;
     }
 
-    // Handle floating point weirdness
-    if input.ante.is_sign_negative()
-        || input.ante.is_nan()
-        || input.ante.is_infinite()
-        || input.ante < 0.00
-    {
+    if input.ante < Chips::ZERO {
         return false;
     }
-    if input.sb.is_sign_negative()
-        || input.sb.is_nan()
-        || input.sb.is_infinite()
-        || input.sb < input.ante
-        || input.sb < 0.00
-        || (input.sb > 0.0 && input.sb < MIN_BLIND)
-    {
+    if input.sb < input.ante {
         return false;
     }
-    if input.bb.is_sign_negative()
-        || input.bb.is_nan()
-        || input.bb.is_infinite()
-        || input.bb < input.sb
-        || input.bb < 1.0
-        || (input.bb > 0.0 && input.bb < MIN_BLIND)
-    {
+    if input.bb < input.sb || input.bb < Chips::new(1) {
         return false;
     }
 
@@ -91,14 +75,14 @@ fn input_good(input: &MultiInput) -> bool {
         .iter()
         .map(|p| p.stack)
         .clone()
-        .reduce(f32::min)
-        .unwrap_or(0.0);
+        .min()
+        .unwrap_or_default();
 
     if input.bb + input.ante > min_stack {
         return false;
     }
 
-    if input.bb > 100_000_000.0 {
+    if input.bb > MAX_STACK {
         return false;
     }
 
@@ -107,11 +91,7 @@ fn input_good(input: &MultiInput) -> bool {
         for action in &player.actions {
             match action {
                 AgentAction::Bet(bet) => {
-                    if bet.is_sign_negative()
-                        || bet.is_nan()
-                        || bet.is_infinite()
-                        || (*bet == 0.0 || *bet < input.bb)
-                    {
+                    if *bet < input.bb {
                         return false;
                     }
                 }
@@ -132,10 +112,10 @@ fuzz_target!(|input: MultiInput| {
         return;
     }
 
-    let stacks: Vec<f32> = input
+    let stacks: Vec<Chips> = input
         .players
         .iter()
-        .map(|pi| (pi.stack).clamp(0.0, 100_000_000.0))
+        .map(|pi| pi.stack.clamp(Chips::ZERO, MAX_STACK))
         .collect();
 
     let agents: Vec<Box<dyn Agent>> = input
@@ -1,12 +1,10 @@
 #![no_main]
 
-extern crate approx;
 extern crate arbitrary;
 extern crate libfuzzer_sys;
 extern crate rand;
 extern crate rs_poker;
 
-use approx::assert_relative_ne;
 use rand::{rngs::StdRng, SeedableRng};
 
 use rs_poker::arena::{
@@ -15,7 +13,7 @@ use rs_poker::arena::{
     game_state::Round,
     historian,
     test_util::{assert_valid_game_state, assert_valid_history, assert_valid_round_data},
-    Agent, GameState, HoldemSimulation, HoldemSimulationBuilder,
+    Agent, Chips, GameState, HoldemSimulation, HoldemSimulationBuilder,
 };
 
 use libfuzzer_sys::fuzz_target;
@@ -28,8 +26,8 @@ struct Input {
 }
 
 fuzz_target!(|input: Input| {
-    let stacks = vec![50.0; 2];
-    let game_state = GameState::new_starting(stacks, 2.0, 1.0, 0.0, 0);
+    let stacks = vec![Chips::new(50); 2];
+    let game_state = GameState::new_starting(stacks, Chips::new(2), Chips::new(1), Chips::ZERO, 0);
     let agents: Vec<Box<dyn Agent>> = vec![
         Box::<VecReplayAgent>::new(VecReplayAgent::new(input.dealer_actions)),
         Box::<VecReplayAgent>::new(VecReplayAgent::new(input.sb_actions)),
@@ -50,7 +48,7 @@ fuzz_target!(|input: Input| {
     sim.run(&mut rng);
 
     assert_eq!(Round::Complete, sim.game_state.round);
-    assert_relative_ne!(0.0_f32, sim.game_state.player_bet.iter().sum());
+    assert_ne!(Chips::ZERO, sim.game_state.player_bet.iter().sum());
 
     assert_valid_round_data(&sim.game_state.round_data);
     assert_valid_game_state(&sim.game_state);
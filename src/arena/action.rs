@@ -1,5 +1,6 @@
 use crate::core::{Card, Hand, PlayerBitSet, Rank};
 
+use super::Chips;
 use super::game_state::Round;
 
 /// Represents an action that an agent can take in a game.
@@ -12,7 +13,7 @@ pub enum AgentAction {
     /// Matches the current bet.
     Call,
     /// Bets the specified amount of money.
-    Bet(f32),
+    Bet(Chips),
     /// Go all-in
     AllIn,
 }
@@ -21,16 +22,28 @@ pub enum AgentAction {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The game has started.
 pub struct GameStartPayload {
-    pub ante: f32,
-    pub small_blind: f32,
-    pub big_blind: f32,
+    pub ante: Chips,
+    pub small_blind: Chips,
+    pub big_blind: Chips,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlayerSitPayload {
     pub idx: usize,
-    pub player_stack: f32,
+    pub player_stack: Chips,
+}
+
+/// Whether a dealt card can be seen by everyone at the table or only by the
+/// player it was dealt to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardVisibility {
+    /// Dealt face down. Only the player holding it knows the card.
+    #[default]
+    Hidden,
+    /// Dealt face up. Every observer knows the card.
+    Visible,
 }
 
 /// Each player is dealt a card. This is the payload for the event.
@@ -39,6 +52,17 @@ pub struct PlayerSitPayload {
 pub struct DealStartingHandPayload {
     pub card: Card,
     pub idx: usize,
+    /// Who can see the card. Hold'em deals every hole card hidden, games
+    /// like stud deal some cards face up.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub visibility: CardVisibility,
+}
+
+impl DealStartingHandPayload {
+    /// Is this card public information for every observer at the table.
+    pub fn is_visible(&self) -> bool {
+        self.visibility == CardVisibility::Visible
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -47,6 +71,11 @@ pub enum ForcedBetType {
     Ante,
     SmallBlind,
     BigBlind,
+    /// The forced opening bet in stud from the lowest card showing.
+    BringIn,
+    /// An extra blind posted by the player after the big blind, which buys
+    /// them the last action preflop.
+    Straddle,
 }
 
 /// A player tried to play an action and failed
@@ -56,8 +85,8 @@ pub struct ForcedBetPayload {
     /// A bet that the player is forced to make
     /// The amount is the forced amount, not the final
     /// amount which could be lower if that puts the player all in.
-    pub bet: f32,
-    pub player_stack: f32,
+    pub bet: Chips,
+    pub player_stack: Chips,
     pub idx: usize,
     pub forced_bet_type: ForcedBetType,
 }
@@ -71,26 +100,26 @@ pub struct PlayedActionPayload {
 
     pub idx: usize,
     pub round: Round,
-    pub player_stack: f32,
+    pub player_stack: Chips,
 
-    pub starting_pot: f32,
-    pub final_pot: f32,
+    pub starting_pot: Chips,
+    pub final_pot: Chips,
 
-    pub starting_bet: f32,
-    pub final_bet: f32,
+    pub starting_bet: Chips,
+    pub final_bet: Chips,
 
-    pub starting_min_raise: f32,
-    pub final_min_raise: f32,
+    pub starting_min_raise: Chips,
+    pub final_min_raise: Chips,
 
-    pub starting_player_bet: f32,
-    pub final_player_bet: f32,
+    pub starting_player_bet: Chips,
+    pub final_player_bet: Chips,
 
     pub players_active: PlayerBitSet,
     pub players_all_in: PlayerBitSet,
 }
 
 impl PlayedActionPayload {
-    pub fn raise_amount(&self) -> f32 {
+    pub fn raise_amount(&self) -> Chips {
         self.final_bet - self.starting_bet
     }
 }
@@ -108,11 +137,48 @@ pub struct FailedActionPayload {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AwardPayload {
-    pub total_pot: f32,
-    pub award_amount: f32,
+    pub total_pot: Chips,
+    pub award_amount: Chips,
     pub rank: Option<Rank>,
     pub hand: Option<Hand>,
     pub idx: usize,
+    /// The rake taken from the pot this award came from. Only the first
+    /// award from each pot has it, so adding it up over a hand gives the
+    /// hand's total rake.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rake: Chips,
+}
+
+/// The pot once a betting round is over.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PotUpdatePayload {
+    /// The betting round that just finished.
+    pub round: Round,
+    /// Every chip bet so far this hand, including the finished round.
+    pub total_pot: Chips,
+}
+
+/// A card of one of the extra boards dealt when the board is run more than
+/// once.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DealRunoutPayload {
+    /// Which board the card is on. The first board is dealt as community
+    /// cards, so this starts at 1 for the second board.
+    pub runout: usize,
+    pub card: Card,
+}
+
+/// A player's turn to show their hand at showdown.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShowdownPayload {
+    pub idx: usize,
+    /// The player's hand with the board, or `None` if they mucked.
+    pub hand: Option<Hand>,
+    /// The rank of the hand shown, `None` if they mucked.
+    pub rank: Option<Rank>,
 }
 
 /// Represents an action that can happen in a game.
@@ -137,6 +203,18 @@ pub enum Action {
     ForcedBet(ForcedBetPayload),
     /// A community card has been dealt.
     DealCommunity(Card),
+    /// A card of another run of the board. These replace the first board's
+    /// cards from the point everyone was all in, and come right before the
+    /// showdown.
+    DealRunout(DealRunoutPayload),
+    /// A card was burned face down before dealing a street.
+    BurnCard(Card),
+    /// A betting round finished. This comes right before the round
+    /// advances.
+    PotUpdate(PotUpdatePayload),
+    /// A player at showdown showed or mucked their hand. These come in the
+    /// order the players have to show, before the pots are awarded.
+    Showdown(ShowdownPayload),
     /// There was some pot given to a player
     Award(AwardPayload),
 }
@@ -147,7 +225,19 @@ mod tests {
 
     #[test]
     fn test_bet() {
-        let a = AgentAction::Bet(100.0);
-        assert_eq!(AgentAction::Bet(100.0), a);
+        let a = AgentAction::Bet(Chips::new(100));
+        assert_eq!(AgentAction::Bet(Chips::new(100)), a);
+    }
+
+    #[test]
+    fn test_card_visibility_default_hidden() {
+        assert_eq!(CardVisibility::Hidden, CardVisibility::default());
+
+        let payload = DealStartingHandPayload {
+            card: Card::new(crate::core::Value::Ace, crate::core::Suit::Spade),
+            idx: 0,
+            visibility: CardVisibility::Visible,
+        };
+        assert!(payload.is_visible());
     }
 }
@@ -12,6 +12,10 @@ impl Agent for AllInAgent {
     fn act(self: &mut AllInAgent, _id: u128, game_state: &GameState) -> AgentAction {
         AgentAction::Bet(game_state.current_player_stack() + game_state.current_round_bet())
     }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(*self))
+    }
 }
 
 /// Default `AgentGenerator` for `AllInAgent`.
@@ -0,0 +1,154 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::arena::{GameState, LegalActions, action::AgentAction};
+
+/// The future an `AsyncAgent` returns with its decision.
+pub type AgentFuture<'a> = Pin<Box<dyn Future<Output = AgentAction> + 'a>>;
+
+/// An agent that decides asynchronously, for example one that asks a model
+/// server what to do. Pass these to
+/// [`crate::arena::HoldemSimulation::run_async`].
+///
+/// The simulation doesn't pick a runtime. The future from `run_async` can
+/// be driven by whatever executor the caller is using.
+pub trait AsyncAgent {
+    /// Decide what to do. This is the async version of
+    /// [`super::Agent::act_with_legal_actions`].
+    fn act<'a>(
+        &'a mut self,
+        id: u128,
+        game_state: &'a GameState,
+        legal_actions: &'a LegalActions,
+    ) -> AgentFuture<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::{Context, Poll, Waker};
+
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{
+        Agent, Chips, HoldemSimulationBuilder,
+        agent::CallingAgent,
+        errors::HoldemSimulationError,
+        game_state::Round,
+        historian::{HistoryRecord, VecHistorian},
+    };
+
+    use super::*;
+
+    /// Calls, but only after making the simulation wait once.
+    struct SlowCallingAgent;
+
+    impl AsyncAgent for SlowCallingAgent {
+        fn act<'a>(
+            &'a mut self,
+            _id: u128,
+            game_state: &'a GameState,
+            _legal_actions: &'a LegalActions,
+        ) -> AgentFuture<'a> {
+            let mut waited = false;
+            let bet = game_state.current_round_bet();
+            Box::pin(std::future::poll_fn(move |cx| {
+                if waited {
+                    Poll::Ready(AgentAction::Bet(bet))
+                } else {
+                    waited = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            }))
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    fn run(use_async: bool) -> (GameState, Vec<HistoryRecord>) {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(300);
+        if use_async {
+            let mut agents: Vec<Box<dyn AsyncAgent>> =
+                vec![Box::new(SlowCallingAgent), Box::new(SlowCallingAgent)];
+            block_on(sim.run_async(&mut agents, &mut rng)).unwrap();
+        } else {
+            sim.run(&mut rng);
+        }
+        (sim.game_state, records.take())
+    }
+
+    #[test]
+    fn test_async_calling_agents_finish() {
+        let (game_state, records) = run(true);
+        assert_eq!(Round::Complete, game_state.round);
+        assert_eq!(
+            Chips::new(20),
+            game_state.player_winnings.iter().sum::<Chips>()
+        );
+
+        // The historians see the same actions in the same order as they
+        // would with the same agents running synchronously.
+        let (_, sync_records) = run(false);
+        let actions = |records: &[HistoryRecord]| {
+            records
+                .iter()
+                .map(|r| format!("{:?}", r.action))
+                .filter(|a| !a.starts_with("GameStart"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(actions(&sync_records), actions(&records));
+    }
+
+    #[test]
+    fn test_async_errors() {
+        let build = |max_actions| {
+            HoldemSimulationBuilder::default()
+                .game_state(GameState::new_starting(
+                    vec![Chips::new(100); 2],
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    0,
+                ))
+                .max_actions(max_actions)
+                .build()
+                .unwrap()
+        };
+        let mut rng = StdRng::seed_from_u64(300);
+
+        let mut agents: Vec<Box<dyn AsyncAgent>> = vec![Box::new(SlowCallingAgent)];
+        let result = block_on(build(10).run_async(&mut agents, &mut rng));
+        assert_eq!(Err(HoldemSimulationError::AgentCount(2)), result);
+
+        let mut agents: Vec<Box<dyn AsyncAgent>> =
+            vec![Box::new(SlowCallingAgent), Box::new(SlowCallingAgent)];
+        let mut sim = build(2);
+        let result = block_on(sim.run_async(&mut agents, &mut rng));
+        assert_eq!(Err(HoldemSimulationError::StepLimit), result);
+        assert_eq!(2, sim.actions_taken);
+        assert!(sim.more_rounds());
+    }
+}
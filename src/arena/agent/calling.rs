@@ -12,6 +12,10 @@ impl Agent for CallingAgent {
     fn act(self: &mut CallingAgent, _id: u128, game_state: &GameState) -> AgentAction {
         AgentAction::Bet(game_state.current_round_bet())
     }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(*self))
+    }
 }
 
 /// Default `AgentGenerator` for `CallingAgent`.
@@ -26,14 +30,15 @@ impl AgentGenerator for CallingAgentGenerator {
 
 #[cfg(test)]
 mod tests {
-    use crate::arena::HoldemSimulationBuilder;
+    use crate::arena::{Chips, HoldemSimulationBuilder};
 
     use super::*;
 
     #[test_log::test]
     fn test_call_agents() {
-        let stacks = vec![100.0; 4];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 4];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let mut rng = rand::rng();
         let mut sim = HoldemSimulationBuilder::default()
             .game_state(game_state)
@@ -50,7 +55,13 @@ mod tests {
 
         assert_eq!(sim.game_state.num_active_players(), 4);
 
-        assert_ne!(0.0, sim.game_state.player_winnings.iter().sum::<f32>());
-        assert_eq!(40.0, sim.game_state.player_winnings.iter().sum::<f32>());
+        assert_ne!(
+            Chips::ZERO,
+            sim.game_state.player_winnings.iter().sum::<Chips>()
+        );
+        assert_eq!(
+            Chips::new(40),
+            sim.game_state.player_winnings.iter().sum::<Chips>()
+        );
     }
 }
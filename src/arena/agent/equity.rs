@@ -0,0 +1,232 @@
+use std::cell::RefCell;
+
+use rand::{Rng, SeedableRng, rng, rngs::StdRng};
+
+use crate::arena::{Chips, action::AgentAction, game_state::GameState};
+
+use super::{Agent, AgentGenerator};
+
+/// An agent that plays by how often its hand wins.
+///
+/// On every decision it runs `iterations` Monte Carlo runouts of its hand
+/// against random opponent hands on the current board. If that equity is
+/// below the pot odds it folds, or checks when that's free. Otherwise it
+/// calls, and raises when its equity is better than an even share of the
+/// pot. `aggression` scales how big those raises are, at `1.0` a hand that
+/// always wins raises the size of the pot. With `0.0` the agent never
+/// raises.
+#[derive(Debug, Clone)]
+pub struct EquityAgent {
+    iterations: usize,
+    aggression: f32,
+    num_opponents: Option<usize>,
+    rng: StdRng,
+}
+
+impl EquityAgent {
+    pub fn new(iterations: usize, aggression: f32) -> Self {
+        Self::new_with_rng(iterations, aggression, &mut rng())
+    }
+
+    /// Create a new agent whose runouts are all seeded from `rng`.
+    pub fn new_with_rng<R: Rng>(iterations: usize, aggression: f32, rng: &mut R) -> Self {
+        Self {
+            iterations,
+            aggression,
+            num_opponents: None,
+            rng: StdRng::from_rng(rng),
+        }
+    }
+
+    /// Simulate against this many random hands. By default that's every
+    /// other player still in the hand.
+    pub fn num_opponents(mut self, num_opponents: usize) -> Self {
+        self.num_opponents = Some(num_opponents);
+        self
+    }
+
+    fn opponents(&self, game_state: &GameState) -> usize {
+        self.num_opponents
+            .unwrap_or_else(|| {
+                let in_hand = game_state.player_active | game_state.player_all_in;
+                in_hand.count().saturating_sub(1)
+            })
+            .max(1)
+    }
+
+    /// The share of the pot the player to act is expected to win.
+    pub fn equity(&mut self, game_state: &GameState) -> f32 {
+        game_state.hero_equity(
+            game_state.to_act_idx(),
+            self.opponents(game_state),
+            self.iterations,
+            &mut self.rng,
+        )
+    }
+}
+
+impl Agent for EquityAgent {
+    fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+        let round_data = &game_state.round_data;
+        let bet = round_data.bet;
+        let call = bet - round_data.current_player_bet();
+        let pot_after_call = game_state.total_pot + call;
+
+        let equity = self.equity(game_state);
+        let pot_odds = if call > Chips::ZERO {
+            call.as_f32() / pot_after_call.as_f32()
+        } else {
+            0.0
+        };
+        if equity < pot_odds {
+            return AgentAction::Fold;
+        }
+
+        // How far above an even split of the pot this hand is, from zero
+        // for an even share up to one for a hand that always wins.
+        let fair_share = 1.0 / (self.opponents(game_state) as f32 + 1.0);
+        let edge = ((equity - fair_share) / (1.0 - fair_share)).max(0.0);
+        let raise = pot_after_call.scale(self.aggression * edge);
+
+        if raise > Chips::ZERO
+            && raise >= round_data.min_raise
+            && game_state.current_player_can_raise()
+        {
+            AgentAction::Bet(bet + raise)
+        } else {
+            AgentAction::Call
+        }
+    }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// `AgentGenerator` for `EquityAgent`. Each agent it builds gets its own
+/// rng, drawn from the generator's.
+#[derive(Debug, Clone)]
+pub struct EquityAgentGenerator {
+    iterations: usize,
+    aggression: f32,
+    rng: RefCell<StdRng>,
+}
+
+impl EquityAgentGenerator {
+    pub fn new(iterations: usize, aggression: f32) -> Self {
+        Self::new_with_rng(iterations, aggression, &mut rng())
+    }
+
+    /// Create a generator whose agents are all seeded from `rng`.
+    pub fn new_with_rng<R: Rng>(iterations: usize, aggression: f32, rng: &mut R) -> Self {
+        Self {
+            iterations,
+            aggression,
+            rng: RefCell::new(StdRng::from_rng(rng)),
+        }
+    }
+}
+
+impl AgentGenerator for EquityAgentGenerator {
+    fn generate(&self, _game_state: &GameState) -> Box<dyn Agent> {
+        Box::new(EquityAgent::new_with_rng(
+            self.iterations,
+            self.aggression,
+            &mut *self.rng.borrow_mut(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        arena::{HoldemSimulationBuilder, game_state::Round, test_util::assert_valid_game_state},
+        core::{Card, Hand},
+    };
+
+    use super::*;
+
+    const BOARD: &str = "AsKsQsJs2h";
+
+    /// Heads up on the river facing a bet of 80 into a pot of 80.
+    fn facing_river_bet(hole_cards: &str) -> GameState {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for _ in 0..9 {
+            game_state.advance_round();
+        }
+        assert_eq!(Round::River, game_state.round);
+
+        game_state.board = Hand::new_from_str(BOARD)
+            .unwrap()
+            .iter()
+            .collect::<Vec<Card>>();
+        game_state.hands[0] = Hand::new_from_str(&format!("{hole_cards}{BOARD}")).unwrap();
+        game_state.hands[1] = Hand::new_from_str(&format!("8d8c{BOARD}")).unwrap();
+
+        assert_eq!(1, game_state.to_act_idx());
+        game_state.do_bet(Chips::new(80), false).unwrap();
+        assert_eq!(0, game_state.to_act_idx());
+        game_state
+    }
+
+    #[test]
+    fn test_folds_trash_to_big_bet() {
+        let game_state = facing_river_bet("3c4d");
+        let mut agent = EquityAgent::new_with_rng(1_000, 1.0, &mut StdRng::seed_from_u64(291));
+        assert!(agent.equity(&game_state) < 0.5);
+        assert_eq!(AgentAction::Fold, agent.act(0, &game_state));
+    }
+
+    #[test]
+    fn test_calls_the_nuts() {
+        let game_state = facing_river_bet("Ts9c");
+        let mut agent = EquityAgent::new_with_rng(1_000, 0.0, &mut StdRng::seed_from_u64(291));
+        assert_eq!(1.0, agent.equity(&game_state));
+        assert_eq!(AgentAction::Call, agent.act(0, &game_state));
+    }
+
+    #[test]
+    fn test_raises_the_nuts_when_aggressive() {
+        let game_state = facing_river_bet("Ts9c");
+        let mut agent = EquityAgent::new_with_rng(1_000, 1.0, &mut StdRng::seed_from_u64(291));
+        // Raise the size of the pot after calling.
+        assert_eq!(
+            AgentAction::Bet(Chips::new(80 + 160)),
+            agent.act(0, &game_state)
+        );
+    }
+
+    /// Play a hand with agents from a generator seeded with `seed`.
+    fn run_seeded(seed: u64) -> GameState {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut rng = StdRng::seed_from_u64(seed);
+        let generator = EquityAgentGenerator::new_with_rng(100, 0.5, &mut rng);
+        let agents: Vec<Box<dyn Agent>> = (0..3).map(|_| generator.generate(&game_state)).collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .build()
+            .unwrap();
+        sim.run(&mut rng);
+        sim.game_state
+    }
+
+    #[test]
+    fn test_equity_agent_in_sim() {
+        let game_state = run_seeded(291);
+        assert_valid_game_state(&game_state);
+        assert_eq!(game_state, run_seeded(291));
+    }
+}
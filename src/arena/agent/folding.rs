@@ -15,6 +15,10 @@ impl Agent for FoldingAgent {
             AgentAction::Fold
         }
     }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(*self))
+    }
 }
 
 /// Default Generator for `FoldingAgent`.
@@ -29,19 +33,19 @@ impl AgentGenerator for FoldingAgentGenerator {
 
 #[cfg(test)]
 mod tests {
-    use approx::assert_relative_eq;
     use rand::{SeedableRng, rngs::StdRng};
 
-    use crate::arena::{HoldemSimulationBuilder, game_state::Round};
+    use crate::arena::{Chips, HoldemSimulationBuilder, game_state::Round};
 
     use super::*;
 
     #[test_log::test]
     fn test_folding_agents() {
-        let stacks = vec![100.0; 2];
+        let stacks = vec![Chips::new(100); 2];
         let mut rng = StdRng::seed_from_u64(420);
 
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let mut sim = HoldemSimulationBuilder::default()
             .game_state(game_state)
             .agents(vec![Box::new(FoldingAgent {}), Box::new(FoldingAgent {})])
@@ -53,9 +57,9 @@ mod tests {
         assert_eq!(sim.game_state.num_active_players(), 1);
         assert_eq!(sim.game_state.round, Round::Complete);
 
-        assert_relative_eq!(15.0_f32, sim.game_state.player_bet.iter().sum());
+        assert_eq!(Chips::new(15), sim.game_state.player_bet.iter().sum());
 
-        assert_relative_eq!(15.0_f32, sim.game_state.player_winnings.iter().sum());
-        assert_relative_eq!(15.0_f32, sim.game_state.player_winnings[1]);
+        assert_eq!(Chips::new(15), sim.game_state.player_winnings.iter().sum());
+        assert_eq!(Chips::new(15), sim.game_state.player_winnings[1]);
     }
 }
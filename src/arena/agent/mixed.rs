@@ -0,0 +1,197 @@
+use rand::{
+    Rng, SeedableRng, distr::Distribution, distr::weighted::WeightedIndex, rng, rngs::StdRng,
+};
+
+use crate::arena::{LegalActions, action::AgentAction, game_state::GameState};
+
+use super::{Agent, AgentGenerator};
+
+/// An agent that plays a mixed strategy built out of simpler agents.
+///
+/// For every decision one of the sub agents is picked at random according
+/// to the weights, and that agent chooses the action. So weights of
+/// `[0.7, 0.3]` over a folding agent and an all in agent fold 70% of the
+/// time and shove the other 30%.
+pub struct MixedAgent {
+    agents: Vec<Box<dyn Agent>>,
+    distribution: WeightedIndex<f64>,
+    rng: StdRng,
+}
+
+impl MixedAgent {
+    /// Create a new `MixedAgent`.
+    ///
+    /// `weights` doesn't need to sum to one, each agent is picked in
+    /// proportion to its weight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of weights doesn't match the number of agents,
+    /// if any weight is negative, or if they are all zero.
+    pub fn new(agents: Vec<Box<dyn Agent>>, weights: Vec<f64>) -> Self {
+        Self::new_with_rng(agents, weights, &mut rng())
+    }
+
+    /// Create a new `MixedAgent` that picks its sub agents with an rng
+    /// seeded from `rng`. The sub agents keep their own randomness.
+    pub fn new_with_rng<R: Rng>(
+        agents: Vec<Box<dyn Agent>>,
+        weights: Vec<f64>,
+        rng: &mut R,
+    ) -> Self {
+        assert_eq!(
+            agents.len(),
+            weights.len(),
+            "MixedAgent needs one weight per agent"
+        );
+        let distribution = WeightedIndex::new(weights).expect("MixedAgent weights must be valid");
+        Self {
+            agents,
+            distribution,
+            rng: StdRng::from_rng(rng),
+        }
+    }
+}
+
+impl Agent for MixedAgent {
+    fn act(&mut self, id: u128, game_state: &GameState) -> AgentAction {
+        let idx = self.distribution.sample(&mut self.rng);
+        self.agents[idx].act(id, game_state)
+    }
+
+    fn act_with_legal_actions(
+        &mut self,
+        id: u128,
+        game_state: &GameState,
+        legal_actions: &LegalActions,
+    ) -> AgentAction {
+        let idx = self.distribution.sample(&mut self.rng);
+        self.agents[idx].act_with_legal_actions(id, game_state, legal_actions)
+    }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        let agents = self
+            .agents
+            .iter()
+            .map(|a| a.clone_agent())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Box::new(Self {
+            agents,
+            distribution: self.distribution.clone(),
+            rng: self.rng.clone(),
+        }))
+    }
+}
+
+/// `AgentGenerator` for `MixedAgent`.
+pub struct MixedAgentGenerator {
+    generators: Vec<Box<dyn AgentGenerator>>,
+    weights: Vec<f64>,
+}
+
+impl MixedAgentGenerator {
+    pub fn new(generators: Vec<Box<dyn AgentGenerator>>, weights: Vec<f64>) -> Self {
+        Self {
+            generators,
+            weights,
+        }
+    }
+}
+
+impl AgentGenerator for MixedAgentGenerator {
+    fn generate(&self, game_state: &GameState) -> Box<dyn Agent> {
+        let agents = self
+            .generators
+            .iter()
+            .map(|g| g.generate(game_state))
+            .collect();
+        Box::new(MixedAgent::new(agents, self.weights.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arena::{
+        Chips, HoldemSimulationBuilder,
+        agent::{
+            AllInAgent, CallingAgent, CallingAgentGenerator, FoldingAgent, FoldingAgentGenerator,
+        },
+        test_util::assert_valid_game_state,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_mixing_frequencies() {
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(FoldingAgent),
+            Box::new(CallingAgent),
+            Box::new(AllInAgent),
+        ];
+        let weights = vec![0.5, 0.3, 0.2];
+        let mut agent = MixedAgent::new(agents, weights.clone());
+
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        // Get to preflop with the blinds in so that folding is an option.
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+
+        let mut counts = [0; 3];
+        let iterations = 20_000;
+        for _ in 0..iterations {
+            match agent.act(0, &game_state) {
+                AgentAction::Fold => counts[0] += 1,
+                AgentAction::Bet(bet) if bet == Chips::new(10) => counts[1] += 1,
+                AgentAction::Bet(_) => counts[2] += 1,
+                other => panic!("Unexpected action {other:?}"),
+            }
+        }
+
+        for (count, weight) in counts.iter().zip(weights.iter()) {
+            let freq = f64::from(*count) / f64::from(iterations);
+            assert!((freq - weight).abs() < 0.02, "{freq} vs {weight}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_weights() {
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(FoldingAgent)];
+        MixedAgent::new(agents, vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_mixed_agent_generator() {
+        let generator = MixedAgentGenerator::new(
+            vec![
+                Box::<FoldingAgentGenerator>::default(),
+                Box::<CallingAgentGenerator>::default(),
+            ],
+            vec![1.0, 1.0],
+        );
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let agents: Vec<Box<dyn Agent>> = (0..3).map(|_| generator.generate(&game_state)).collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .build()
+            .unwrap();
+        sim.run(&mut rand::rng());
+        assert_valid_game_state(&sim.game_state);
+    }
+}
@@ -3,12 +3,19 @@
 //!
 //! Some basic agents are provided as a way of testing baseline value.
 mod all_in;
+#[cfg(feature = "async")]
+mod async_agent;
 mod calling;
+mod equity;
 mod folding;
+mod mixed;
+mod policy;
 mod random;
 mod replay;
+mod scripted;
+mod tournament;
 
-use super::{Historian, action::AgentAction, game_state::GameState};
+use super::{Historian, LegalActions, action::AgentAction, game_state::GameState};
 /// This is the trait that you need to implement in order to implenet
 /// different strategies. It's up to you to to implement the logic and state.
 ///
@@ -19,12 +26,36 @@ pub trait Agent {
     /// This is the method that will be called by the game to get the action
     fn act(&mut self, id: u128, game_state: &GameState) -> AgentAction;
 
+    /// The method the simulation calls to get the action. It's given what
+    /// the player is allowed to do along with the game state, so an agent
+    /// doesn't have to work out the legal bet sizes itself.
+    ///
+    /// The default ignores `legal_actions` and calls `act`.
+    fn act_with_legal_actions(
+        &mut self,
+        id: u128,
+        game_state: &GameState,
+        _legal_actions: &LegalActions,
+    ) -> AgentAction {
+        self.act(id, game_state)
+    }
+
     // Some Agents may need to be able to see the changes in the game
     // state. This is the method that will be called to create historians
     // when starting a new simulation game.
     fn historian(&self) -> Option<Box<dyn Historian>> {
         None
     }
+
+    /// Copy this agent, with all of its current state, for a cloned
+    /// [`super::HoldemSimulation`]. Agents that are `Clone` can return
+    /// `Some(Box::new(self.clone()))`.
+    ///
+    /// The default is `None`, a simulation with an agent that can't be
+    /// copied can't be cloned.
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        None
+    }
 }
 
 /// AgentBuilder is a trait that is used to build agents for tournaments
@@ -70,7 +101,14 @@ where
 }
 
 pub use all_in::{AllInAgent, AllInAgentGenerator};
+#[cfg(feature = "async")]
+pub use async_agent::{AgentFuture, AsyncAgent};
 pub use calling::{CallingAgent, CallingAgentGenerator};
+pub use equity::{EquityAgent, EquityAgentGenerator};
 pub use folding::{FoldingAgent, FoldingAgentGenerator};
-pub use random::{RandomAgent, RandomAgentGenerator, RandomPotControlAgent};
+pub use mixed::{MixedAgent, MixedAgentGenerator};
+pub use policy::{Abstraction, PolicyAgent, PolicyAgentGenerator, StateKey};
+pub use random::{RandomAgent, RandomAgentGenerator, RandomPotControlAgent, RandomSizingAgent};
 pub use replay::{SliceReplayAgent, VecReplayAgent};
+pub use scripted::ScriptedAgent;
+pub use tournament::{TournamentAgent, TournamentAgentGenerator};
@@ -0,0 +1,311 @@
+use std::{cell::RefCell, collections::HashMap, sync::Arc};
+
+use rand::{
+    Rng, SeedableRng, distr::Distribution, distr::weighted::WeightedIndex, rng, rngs::StdRng,
+};
+
+use crate::{
+    arena::{
+        action::AgentAction,
+        game_state::{GameState, Round},
+    },
+    core::{Card, Hand},
+    holdem::preflop_strength,
+};
+
+use super::{Agent, AgentGenerator};
+
+/// A coarse description of the situation a player is in, used to look up
+/// what a [`PolicyAgent`] should do. Many game states share a key, which is
+/// what keeps a policy table small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateKey {
+    /// The betting round.
+    pub round: Round,
+    /// Which bucket the size of the pot falls in.
+    pub pot_bucket: u8,
+    /// Which bucket the strength of the player's hand falls in.
+    pub hand_bucket: u8,
+}
+
+impl StateKey {
+    /// The abstraction a `PolicyAgent` uses unless it's given another one,
+    /// for the player to act.
+    ///
+    /// The pot bucket is how many times the pot, in big blinds, can be
+    /// halved before it's under one big blind. So `0` is under one big
+    /// blind, `1` is under two, `2` under four, and so on.
+    ///
+    /// The hand bucket before the flop is the decile of the hand's
+    /// `preflop_strength`, `0` for the weakest tenth of hands and `9` for
+    /// the strongest. After the flop it's the category of the best made
+    /// hand, `0` for high card up to `8` for a straight flush.
+    pub fn for_player_to_act(game_state: &GameState) -> Self {
+        let idx = game_state.to_act_idx();
+        let pot_in_bb = game_state.total_pot.as_f32() / game_state.big_blind.as_f32();
+        let pot_bucket = if pot_in_bb < 1.0 {
+            0
+        } else {
+            pot_in_bb.log2() as u8 + 1
+        };
+
+        let hand_bucket = if game_state.board.is_empty() {
+            let hole: Vec<Card> = game_state.known_cards(idx);
+            if hole.len() == 2 {
+                let strength = preflop_strength(&Hand::new_with_cards(hole));
+                ((strength * 10.0) as u8).min(9)
+            } else {
+                0
+            }
+        } else {
+            game_state.rank_hand(idx).category()
+        };
+
+        Self {
+            round: game_state.round,
+            pot_bucket,
+            hand_bucket,
+        }
+    }
+}
+
+/// Turns a game state into the key its policy is looked up with.
+pub type Abstraction = Arc<dyn Fn(&GameState) -> StateKey>;
+
+/// An agent that plays a fixed mixed strategy from a table, for example
+/// one read out of a solver.
+///
+/// On every decision the game state is abstracted into a [`StateKey`], and
+/// one of the actions for that key is picked at random in proportion to
+/// its weight. In a situation the table doesn't cover the agent folds.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rs_poker::arena::Chips;
+/// use rs_poker::arena::action::AgentAction;
+/// use rs_poker::arena::agent::{PolicyAgent, StateKey};
+/// use rs_poker::arena::game_state::Round;
+///
+/// // Always raise big hands preflop, otherwise the agent folds.
+/// let mut policy = HashMap::new();
+/// for pot_bucket in 0..4 {
+///     let key = StateKey {
+///         round: Round::Preflop,
+///         pot_bucket,
+///         hand_bucket: 9,
+///     };
+///     policy.insert(key, vec![(AgentAction::Bet(Chips::new(30)), 1.0)]);
+/// }
+/// let agent = PolicyAgent::new(policy);
+/// ```
+pub struct PolicyAgent {
+    policy: HashMap<StateKey, (Vec<AgentAction>, WeightedIndex<f32>)>,
+    abstraction: Abstraction,
+    rng: StdRng,
+}
+
+impl PolicyAgent {
+    /// Create a new `PolicyAgent` using [`StateKey::for_player_to_act`].
+    ///
+    /// The weights for a key don't need to sum to one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any key has no actions, a negative weight, or weights
+    /// that are all zero.
+    pub fn new(policy: HashMap<StateKey, Vec<(AgentAction, f32)>>) -> Self {
+        Self::new_with_rng(policy, &mut rng())
+    }
+
+    /// Create a new `PolicyAgent` that picks its actions with an rng seeded
+    /// from `rng`.
+    pub fn new_with_rng<R: Rng>(
+        policy: HashMap<StateKey, Vec<(AgentAction, f32)>>,
+        rng: &mut R,
+    ) -> Self {
+        let policy = policy
+            .into_iter()
+            .map(|(key, actions)| {
+                let (actions, weights): (Vec<_>, Vec<_>) = actions.into_iter().unzip();
+                let distribution =
+                    WeightedIndex::new(weights).expect("PolicyAgent weights must be valid");
+                (key, (actions, distribution))
+            })
+            .collect();
+        Self {
+            policy,
+            abstraction: Arc::new(StateKey::for_player_to_act),
+            rng: StdRng::from_rng(rng),
+        }
+    }
+
+    /// Look the policy up with `abstraction` instead of
+    /// [`StateKey::for_player_to_act`].
+    pub fn with_abstraction(mut self, abstraction: Abstraction) -> Self {
+        self.abstraction = abstraction;
+        self
+    }
+}
+
+impl Agent for PolicyAgent {
+    fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+        let key = (self.abstraction)(game_state);
+        match self.policy.get(&key) {
+            Some((actions, distribution)) => actions[distribution.sample(&mut self.rng)].clone(),
+            None => AgentAction::Fold,
+        }
+    }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(Self {
+            policy: self.policy.clone(),
+            abstraction: self.abstraction.clone(),
+            rng: self.rng.clone(),
+        }))
+    }
+}
+
+/// `AgentGenerator` for `PolicyAgent`. Every agent it builds shares the
+/// policy and abstraction.
+pub struct PolicyAgentGenerator {
+    policy: HashMap<StateKey, Vec<(AgentAction, f32)>>,
+    abstraction: Abstraction,
+    rng: RefCell<StdRng>,
+}
+
+impl PolicyAgentGenerator {
+    pub fn new(policy: HashMap<StateKey, Vec<(AgentAction, f32)>>) -> Self {
+        Self::new_with_rng(policy, &mut rng())
+    }
+
+    /// Create a generator whose agents each get an rng drawn from `rng`.
+    pub fn new_with_rng<R: Rng>(
+        policy: HashMap<StateKey, Vec<(AgentAction, f32)>>,
+        rng: &mut R,
+    ) -> Self {
+        Self {
+            policy,
+            abstraction: Arc::new(StateKey::for_player_to_act),
+            rng: RefCell::new(StdRng::from_rng(rng)),
+        }
+    }
+
+    /// Build agents that use `abstraction`.
+    pub fn with_abstraction(mut self, abstraction: Abstraction) -> Self {
+        self.abstraction = abstraction;
+        self
+    }
+}
+
+impl AgentGenerator for PolicyAgentGenerator {
+    fn generate(&self, _game_state: &GameState) -> Box<dyn Agent> {
+        let agent = PolicyAgent::new_with_rng(self.policy.clone(), &mut *self.rng.borrow_mut());
+        Box::new(agent.with_abstraction(self.abstraction.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{Chips, HoldemSimulationBuilder, action::Action, historian::VecHistorian};
+
+    use super::*;
+
+    #[test]
+    fn test_always_fold_policy() {
+        // Every situation maps to the same key, and that key always folds.
+        let key = StateKey {
+            round: Round::Preflop,
+            pot_bucket: 0,
+            hand_bucket: 0,
+        };
+        let policy = HashMap::from([(key, vec![(AgentAction::Fold, 1.0)])]);
+        let abstraction: Abstraction = Arc::new(move |_: &GameState| key);
+
+        for seed in 0..10 {
+            let historian = VecHistorian::new();
+            let records = historian.get_storage();
+            let mut rng = StdRng::seed_from_u64(seed);
+            let agents: Vec<Box<dyn Agent>> = (0..3)
+                .map(|_| -> Box<dyn Agent> {
+                    Box::new(
+                        PolicyAgent::new_with_rng(policy.clone(), &mut rng)
+                            .with_abstraction(abstraction.clone()),
+                    )
+                })
+                .collect();
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(GameState::new_starting(
+                    vec![Chips::new(100); 3],
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    0,
+                ))
+                .agents(agents)
+                .historians(vec![Box::new(historian)])
+                .build()
+                .unwrap();
+            sim.run(&mut rng);
+
+            let decisions: Vec<AgentAction> = records
+                .borrow()
+                .iter()
+                .filter_map(|r| match &r.action {
+                    Action::PlayedAction(payload) => Some(payload.action.clone()),
+                    _ => None,
+                })
+                .collect();
+            // The button and the small blind fold to the big blind.
+            assert_eq!(vec![AgentAction::Fold; 2], decisions);
+            assert_eq!(
+                [100, 95, 105].map(Chips::new).to_vec(),
+                sim.game_state.stacks
+            );
+        }
+    }
+
+    #[test]
+    fn test_mixes_by_weight() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        game_state.hands[0] = Hand::new_from_str("AsAd").unwrap();
+
+        let key = StateKey::for_player_to_act(&game_state);
+        assert_eq!(Round::Preflop, key.round);
+        assert_eq!(9, key.hand_bucket);
+        // 15 in a 10 big blind game is 1.5 big blinds.
+        assert_eq!(1, key.pot_bucket);
+
+        let policy = HashMap::from([(
+            key,
+            vec![
+                (AgentAction::Call, 3.0),
+                (AgentAction::Bet(Chips::new(30)), 1.0),
+            ],
+        )]);
+        let mut agent = PolicyAgent::new_with_rng(policy, &mut StdRng::seed_from_u64(324));
+        let calls = (0..4_000)
+            .filter(|_| agent.act(0, &game_state) == AgentAction::Call)
+            .count();
+        assert!((2_800..3_200).contains(&calls), "{calls} calls");
+
+        // A situation the policy doesn't cover folds.
+        game_state.hands[0] = Hand::new_from_str("7s2d").unwrap();
+        assert_eq!(AgentAction::Fold, agent.act(0, &game_state));
+    }
+}
@@ -1,7 +1,10 @@
-use rand::{Rng, rng};
+use std::cell::RefCell;
+
+use rand::{Rng, SeedableRng, rng, rngs::StdRng};
 
 use crate::{
     arena::{
+        Chips, LegalActions,
         action::AgentAction,
         game_state::{GameState, Round},
     },
@@ -15,23 +18,34 @@ use super::{Agent, AgentGenerator};
 pub struct RandomAgent {
     percent_fold: Vec<f64>,
     percent_call: Vec<f64>,
+    rng: StdRng,
 }
 
 impl RandomAgent {
     pub fn new(percent_fold: Vec<f64>, percent_call: Vec<f64>) -> Self {
+        Self::new_with_rng(percent_fold, percent_call, &mut rng())
+    }
+
+    /// Create a new agent whose choices are all seeded from `rng`.
+    ///
+    /// With a seeded rng here and for the simulation, every hand the agent
+    /// plays can be reproduced exactly.
+    pub fn new_with_rng<R: Rng>(
+        percent_fold: Vec<f64>,
+        percent_call: Vec<f64>,
+        rng: &mut R,
+    ) -> Self {
         Self {
             percent_call,
             percent_fold,
+            rng: StdRng::from_rng(rng),
         }
     }
 }
 
 impl Default for RandomAgent {
     fn default() -> Self {
-        Self {
-            percent_fold: vec![0.25, 0.30, 0.50],
-            percent_call: vec![0.5, 0.6, 0.45],
-        }
+        Self::new(vec![0.25, 0.30, 0.50], vec![0.5, 0.6, 0.45])
     }
 }
 
@@ -42,8 +56,7 @@ impl Agent for RandomAgent {
         let player_stack = game_state.stacks[round_data.to_act_idx];
         let curr_bet = round_data.bet;
         let raise_count = round_data.total_raise_count;
-
-        let mut rng = rng();
+        let rng = &mut self.rng;
 
         // The min we can bet when not calling is the current bet plus the min raise
         // However it's possible that would put the player all in.
@@ -57,7 +70,7 @@ impl Agent for RandomAgent {
         // calling a pot sized bet (plus a little more for spicyness)
         //
         // That could be the same as the min
-        let pot_value = (round_data.num_players_need_action() as f32 + 1.0) * game_state.total_pot;
+        let pot_value = game_state.total_pot * (round_data.num_players_need_action() as i64 + 1);
         let max = (player_bet + player_stack).min(pot_value).max(min);
 
         // We shouldn't fold when checking is an option.
@@ -84,33 +97,162 @@ impl Agent for RandomAgent {
         } else if max > min {
             // If there's some range and the rng didn't choose another option. So bet some
             // amount.
-            AgentAction::Bet(rng.random_range(min..max))
+            AgentAction::Bet(Chips::new(rng.random_range(min.get()..max.get())))
         } else {
             AgentAction::Bet(max)
         }
     }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
+/// `AgentGenerator` for `RandomAgent`. Each agent it builds gets its own
+/// rng, drawn from the generator's.
 pub struct RandomAgentGenerator {
     percent_fold: Vec<f64>,
     percent_call: Vec<f64>,
+    rng: RefCell<StdRng>,
+}
+
+impl RandomAgentGenerator {
+    pub fn new(percent_fold: Vec<f64>, percent_call: Vec<f64>) -> Self {
+        Self::new_with_rng(percent_fold, percent_call, &mut rng())
+    }
+
+    /// Create a generator whose agents are all seeded from `rng`.
+    pub fn new_with_rng<R: Rng>(
+        percent_fold: Vec<f64>,
+        percent_call: Vec<f64>,
+        rng: &mut R,
+    ) -> Self {
+        Self {
+            percent_fold,
+            percent_call,
+            rng: RefCell::new(StdRng::from_rng(rng)),
+        }
+    }
 }
 
 impl AgentGenerator for RandomAgentGenerator {
     fn generate(&self, _game_state: &GameState) -> Box<dyn Agent> {
-        Box::new(RandomAgent::new(
+        Box::new(RandomAgent::new_with_rng(
             self.percent_fold.clone(),
             self.percent_call.clone(),
+            &mut *self.rng.borrow_mut(),
         ))
     }
 }
 
 impl Default for RandomAgentGenerator {
     fn default() -> Self {
+        Self::new(vec![0.25, 0.30, 0.50], vec![0.5, 0.6, 0.45])
+    }
+}
+
+/// An agent that folds and calls at fixed rates like `RandomAgent`, but
+/// sizes its bets from a weighted set of pot fractions.
+///
+/// Each size is a fraction of the pot after calling, so `1.0` is a pot
+/// sized raise and `0.5` half pot. The chosen size is clamped to the legal
+/// raises, and when raising isn't allowed the agent calls.
+#[derive(Debug, Clone)]
+pub struct RandomSizingAgent {
+    percent_fold: f64,
+    percent_call: f64,
+    sizes: Vec<(f32, f64)>,
+    rng: StdRng,
+}
+
+impl RandomSizingAgent {
+    /// `sizes` holds each pot fraction with its weight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no sizes or no size has a positive weight.
+    pub fn new(percent_fold: f64, percent_call: f64, sizes: Vec<(f32, f64)>) -> Self {
+        Self::new_with_rng(percent_fold, percent_call, sizes, &mut rng())
+    }
+
+    /// Create a new agent whose choices are all seeded from `rng`.
+    pub fn new_with_rng<R: Rng>(
+        percent_fold: f64,
+        percent_call: f64,
+        sizes: Vec<(f32, f64)>,
+        rng: &mut R,
+    ) -> Self {
+        assert!(
+            sizes.iter().any(|(_, weight)| *weight > 0.0),
+            "RandomSizingAgent needs a size with a positive weight"
+        );
         Self {
-            percent_fold: vec![0.25, 0.30, 0.50],
-            percent_call: vec![0.5, 0.6, 0.45],
+            percent_fold,
+            percent_call,
+            sizes,
+            rng: StdRng::from_rng(rng),
+        }
+    }
+
+    /// Pick a pot fraction, each with the chance of its share of the total
+    /// weight.
+    fn sample_fraction(&mut self) -> f32 {
+        let total: f64 = self.sizes.iter().map(|(_, weight)| weight.max(0.0)).sum();
+        let mut pick = self.rng.random_range(0.0..total);
+        for (fraction, weight) in &self.sizes {
+            let weight = weight.max(0.0);
+            if pick < weight {
+                return *fraction;
+            }
+            pick -= weight;
+        }
+        // Rounding can leave a sliver past the last weight.
+        self.sizes
+            .iter()
+            .rev()
+            .find(|(_, weight)| *weight > 0.0)
+            .map(|(fraction, _)| *fraction)
+            .unwrap()
+    }
+}
+
+impl Default for RandomSizingAgent {
+    /// A third, half, three quarters, full pot and a 1.5x overbet. The
+    /// smaller sizes are the most common.
+    fn default() -> Self {
+        Self::new(
+            0.25,
+            0.5,
+            vec![(0.33, 3.0), (0.5, 3.0), (0.75, 2.0), (1.0, 1.0), (1.5, 1.0)],
+        )
+    }
+}
+
+impl Agent for RandomSizingAgent {
+    fn act(&mut self, id: u128, game_state: &GameState) -> AgentAction {
+        self.act_with_legal_actions(id, game_state, &LegalActions::new(game_state))
+    }
+
+    fn act_with_legal_actions(
+        &mut self,
+        _id: u128,
+        game_state: &GameState,
+        legal_actions: &LegalActions,
+    ) -> AgentAction {
+        if !legal_actions.can_check && self.rng.random_bool(self.percent_fold) {
+            return AgentAction::Fold;
+        }
+        if !legal_actions.can_raise || self.rng.random_bool(self.percent_call) {
+            return AgentAction::Call;
         }
+
+        let pot_after_call = game_state.total_pot + legal_actions.call_amount;
+        let size = game_state.current_round_bet() + pot_after_call.scale(self.sample_fraction());
+        AgentAction::Bet(size.clamp(legal_actions.min_raise, legal_actions.max_raise))
+    }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(self.clone()))
     }
 }
 
@@ -124,12 +266,13 @@ impl Default for RandomAgentGenerator {
 #[derive(Debug, Clone)]
 pub struct RandomPotControlAgent {
     percent_call: Vec<f64>,
+    rng: StdRng,
 }
 
 impl RandomPotControlAgent {
-    fn expected_pot(&self, game_state: &GameState) -> f32 {
+    fn expected_pot(&self, game_state: &GameState) -> Chips {
         if game_state.round == Round::Preflop {
-            (3.0 * game_state.big_blind).max(game_state.total_pot)
+            (game_state.big_blind * 3).max(game_state.total_pot)
         } else {
             game_state.total_pot
         }
@@ -157,7 +300,7 @@ impl RandomPotControlAgent {
     }
 
     fn monte_carlo_based_action(
-        &self,
+        &mut self,
         game_state: &GameState,
         mut monte: MonteCarloGame,
     ) -> AgentAction {
@@ -174,7 +317,7 @@ impl RandomPotControlAgent {
         let to_act_idx = game_state.to_act_idx();
 
         // How much do I actually value the pot right now?
-        let my_value = values.get(to_act_idx).unwrap_or(&0.0_f32) * expected_pot;
+        let my_value = expected_pot.scale(*values.get(to_act_idx).unwrap_or(&0.0_f32));
 
         // What have we already put into the pot for the round?
         let bet_already = game_state.current_round_player_bet(to_act_idx);
@@ -191,8 +334,8 @@ impl RandomPotControlAgent {
         }
     }
 
-    fn random_action(&self, game_state: &GameState, max_value: f32) -> AgentAction {
-        let mut rng = rng();
+    fn random_action(&mut self, game_state: &GameState, max_value: Chips) -> AgentAction {
+        let rng = &mut self.rng;
         // Use the number of bets to determine the call percentage
         let round_data = &game_state.round_data;
         let raise_count = round_data.total_raise_count;
@@ -207,15 +350,22 @@ impl RandomPotControlAgent {
             let min_raise = round_data.min_raise;
             // We always give some room to bet
             let low = round_data.bet + min_raise;
-            let bet_value = rng.random_range(low..max_value.max(low + min_raise));
-
-            // Round the chosen value to take f32 to i32
-            AgentAction::Bet(bet_value)
+            let high = max_value.max(low + min_raise);
+            AgentAction::Bet(Chips::new(rng.random_range(low.get()..high.get())))
         }
     }
 
     pub fn new(percent_call: Vec<f64>) -> Self {
-        Self { percent_call }
+        Self::new_with_rng(percent_call, &mut rng())
+    }
+
+    /// Create a new agent whose choices and simulations are all seeded from
+    /// `rng`.
+    pub fn new_with_rng<R: Rng>(percent_call: Vec<f64>, rng: &mut R) -> Self {
+        Self {
+            percent_call,
+            rng: StdRng::from_rng(rng),
+        }
     }
 }
 
@@ -225,19 +375,26 @@ impl Agent for RandomPotControlAgent {
         // So replace all the hands but our own
         let clean_hands = self.clean_hands(game_state);
         // Now check if we can simulate that
-        if let Ok(monte) = MonteCarloGame::new(clean_hands) {
+        if let Ok(monte) = MonteCarloGame::new_with_rng(clean_hands, &mut self.rng) {
             self.monte_carlo_based_action(game_state, monte)
         } else {
             AgentAction::Fold
         }
     }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
     use crate::{
         arena::{
             HoldemSimulationBuilder,
+            historian::VecHistorian,
             test_util::{assert_valid_game_state, assert_valid_round_data},
         },
         core::Deck,
@@ -250,8 +407,9 @@ mod tests {
         let mut deck: Deck = Deck::default();
         let mut rng = rand::rng();
 
-        let stacks = vec![100.0; 5];
-        let mut game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 5];
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::<RandomAgent>::default(),
             Box::<RandomAgent>::default(),
@@ -275,20 +433,8 @@ mod tests {
 
         sim.run(&mut rng);
 
-        let min_stack = sim
-            .game_state
-            .stacks
-            .clone()
-            .into_iter()
-            .reduce(f32::min)
-            .unwrap();
-        let max_stack = sim
-            .game_state
-            .stacks
-            .clone()
-            .into_iter()
-            .reduce(f32::max)
-            .unwrap();
+        let min_stack = sim.game_state.stacks.clone().into_iter().min().unwrap();
+        let max_stack = sim.game_state.stacks.clone().into_iter().max().unwrap();
 
         assert_ne!(min_stack, max_stack, "There should have been some betting.");
 
@@ -298,8 +444,9 @@ mod tests {
 
     #[test_log::test]
     fn test_five_pot_control() {
-        let stacks = vec![100.0; 5];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 5];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::new(RandomPotControlAgent::new(vec![0.3])),
             Box::new(RandomPotControlAgent::new(vec![0.3])),
@@ -317,20 +464,8 @@ mod tests {
 
         sim.run(&mut rng);
 
-        let min_stack = sim
-            .game_state
-            .stacks
-            .clone()
-            .into_iter()
-            .reduce(f32::min)
-            .unwrap();
-        let max_stack = sim
-            .game_state
-            .stacks
-            .clone()
-            .into_iter()
-            .reduce(f32::max)
-            .unwrap();
+        let min_stack = sim.game_state.stacks.clone().into_iter().min().unwrap();
+        let max_stack = sim.game_state.stacks.clone().into_iter().max().unwrap();
 
         assert_ne!(min_stack, max_stack, "There should have been some betting.");
         assert_valid_round_data(&sim.game_state.round_data);
@@ -339,8 +474,9 @@ mod tests {
 
     #[test_log::test]
     fn test_random_agents_no_fold_get_all_rounds() {
-        let stacks = vec![100.0; 5];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 5];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::new(RandomAgent::new(vec![0.0], vec![0.75])),
             Box::new(RandomAgent::new(vec![0.0], vec![0.75])),
@@ -359,4 +495,117 @@ mod tests {
         assert!(sim.game_state.is_complete());
         assert_valid_game_state(&sim.game_state);
     }
+
+    #[test]
+    fn test_random_sizing_matches_weights() {
+        // On the flop with 40 in the pot and nothing to call.
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(1000); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for _ in 0..5 {
+            game_state.advance_round();
+        }
+        assert_eq!(Round::Flop, game_state.round);
+        game_state.total_pot = Chips::new(40);
+
+        let mut agent = RandomSizingAgent::new(0.0, 0.0, vec![(0.5, 1.0), (1.0, 3.0)]);
+        let samples = 10_000;
+        let mut half_pot = 0;
+        for _ in 0..samples {
+            match agent.act(0, &game_state) {
+                AgentAction::Bet(bet) if bet == Chips::new(20) => half_pot += 1,
+                AgentAction::Bet(bet) if bet == Chips::new(40) => {}
+                other => panic!("Unexpected action {other:?}"),
+            }
+        }
+        let share = half_pot as f64 / samples as f64;
+        assert!((share - 0.25).abs() < 0.03, "{share}");
+    }
+
+    #[test]
+    fn test_random_sizing_clamps_to_legal() {
+        // A tiny pot, so a third of it is under the minimum raise.
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(1000); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for _ in 0..5 {
+            game_state.advance_round();
+        }
+        game_state.total_pot = Chips::new(3);
+        let mut agent = RandomSizingAgent::new(0.0, 0.0, vec![(0.33, 1.0)]);
+        assert_eq!(AgentAction::Bet(Chips::new(10)), agent.act(0, &game_state));
+
+        // A huge overbet is capped at the stack.
+        game_state.total_pot = Chips::new(10000);
+        let mut agent = RandomSizingAgent::new(0.0, 0.0, vec![(2.0, 1.0)]);
+        assert_eq!(
+            AgentAction::Bet(Chips::new(1000)),
+            agent.act(0, &game_state)
+        );
+    }
+
+    #[test_log::test]
+    fn test_random_sizing_in_sim() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 4],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let agents: Vec<Box<dyn Agent>> = (0..4)
+            .map(|_| Box::<RandomSizingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .build()
+            .unwrap();
+        sim.run(&mut rand::rng());
+        assert_valid_game_state(&sim.game_state);
+    }
+
+    fn seeded_history(seed: u64) -> String {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(RandomAgent::new_with_rng(vec![0.1], vec![0.5], &mut rng)),
+            Box::new(RandomSizingAgent::new_with_rng(
+                0.1,
+                0.5,
+                vec![(0.5, 1.0), (1.0, 1.0)],
+                &mut rng,
+            )),
+            Box::new(RandomPotControlAgent::new_with_rng(vec![0.5], &mut rng)),
+        ];
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut rng);
+        format!("{:?}", records.borrow())
+    }
+
+    #[test]
+    fn test_seeded_agents_reproduce() {
+        assert_eq!(seeded_history(42), seeded_history(42));
+        assert_ne!(seeded_history(42), seeded_history(43));
+    }
 }
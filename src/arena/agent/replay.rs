@@ -56,6 +56,10 @@ impl Agent for VecReplayAgent {
             .get(idx)
             .map_or_else(|| self.default.clone(), |a| a.clone())
     }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
 impl<'a> Agent for SliceReplayAgent<'a> {
@@ -74,7 +78,7 @@ mod tests {
     use rand::{SeedableRng, rngs::StdRng};
 
     use crate::arena::{
-        Agent, GameState, HoldemSimulation, HoldemSimulationBuilder,
+        Agent, Chips, GameState, HoldemSimulation, HoldemSimulationBuilder,
         action::AgentAction,
         agent::VecReplayAgent,
         test_util::{assert_valid_game_state, assert_valid_round_data},
@@ -83,30 +87,31 @@ mod tests {
     #[test_log::test]
     fn test_all_in_for_less() {
         let agent_one = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(10.0),
-            AgentAction::Bet(0.0),
-            AgentAction::Bet(0.0),
-            AgentAction::Bet(690.0),
+            AgentAction::Bet(Chips::new(10)),
+            AgentAction::Bet(Chips::ZERO),
+            AgentAction::Bet(Chips::ZERO),
+            AgentAction::Bet(Chips::new(690)),
         ]));
         let agent_two = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(10.0),
-            AgentAction::Bet(0.0),
-            AgentAction::Bet(0.0),
-            AgentAction::Bet(690.0),
+            AgentAction::Bet(Chips::new(10)),
+            AgentAction::Bet(Chips::ZERO),
+            AgentAction::Bet(Chips::ZERO),
+            AgentAction::Bet(Chips::new(690)),
         ]));
         let agent_three = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(10.0),
-            AgentAction::Bet(0.0),
-            AgentAction::Bet(0.0),
-            AgentAction::Bet(90.0),
+            AgentAction::Bet(Chips::new(10)),
+            AgentAction::Bet(Chips::ZERO),
+            AgentAction::Bet(Chips::ZERO),
+            AgentAction::Bet(Chips::new(90)),
         ]));
         let agent_four = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(10.0),
+            AgentAction::Bet(Chips::new(10)),
             AgentAction::Fold,
         ]));
 
-        let stacks = vec![700.0, 900.0, 100.0, 800.0];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = [700, 900, 100, 800].map(Chips::new).to_vec();
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let agents: Vec<Box<dyn Agent>> = vec![agent_one, agent_two, agent_three, agent_four];
         let mut rng = StdRng::seed_from_u64(421);
 
@@ -124,11 +129,13 @@ mod tests {
     fn test_cant_bet_after_folds() {
         let agent_one = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![]));
         let agent_two = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![]));
-        let agent_three =
-            Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(100.0)]));
+        let agent_three = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(
+            Chips::new(100),
+        )]));
 
-        let stacks = vec![100.0, 100.0, 100.0];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = [100, 100, 100].map(Chips::new).to_vec();
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let agents: Vec<Box<dyn Agent>> = vec![agent_one, agent_two, agent_three];
         let mut rng = StdRng::seed_from_u64(421);
 
@@ -146,8 +153,8 @@ mod tests {
 
     #[test]
     fn test_another_three_player() {
-        let sb = 3.0;
-        let bb = 3.0;
+        let sb = Chips::new(3);
+        let bb = Chips::new(3);
 
         let agent_one = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
             AgentAction::Bet(bb),
@@ -159,8 +166,8 @@ mod tests {
         ]));
         let agent_three = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Fold]));
 
-        let stacks = vec![bb + 5.906776e-3, bb + 5.906776e-39, bb];
-        let game_state = GameState::new_starting(stacks, bb, sb, 0.0, 0);
+        let stacks = vec![bb + Chips::new(1), bb + Chips::new(1), bb];
+        let game_state = GameState::new_starting(stacks, bb, sb, Chips::ZERO, 0);
         let agents: Vec<Box<dyn Agent>> = vec![agent_one, agent_two, agent_three];
         let mut rng = StdRng::seed_from_u64(421);
 
@@ -182,16 +189,22 @@ mod tests {
         let agent_one = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Fold]));
         let agent_two = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Fold]));
         let agent_three =
-            Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(5.0)]));
+            Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(Chips::new(5))]));
         let agent_four =
-            Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(5.0)]));
+            Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(Chips::new(5))]));
         let agent_five = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(259.0),
+            AgentAction::Bet(Chips::new(259)),
             AgentAction::Fold,
         ]));
 
-        let stacks = vec![1000.0, 100.0, 1000.0, 5.0, 5.0, 1000.0];
-        let game_state = GameState::new_starting(stacks, 114.0, 96.0, 0.0, 210439175936 % 5);
+        let stacks = [1000, 100, 1000, 5, 5, 1000].map(Chips::new).to_vec();
+        let game_state = GameState::new_starting(
+            stacks,
+            Chips::new(114),
+            Chips::new(96),
+            Chips::ZERO,
+            210439175936 % 5,
+        );
         let agents: Vec<Box<dyn Agent>> = vec![
             agent_zero,
             agent_one,
@@ -221,26 +234,33 @@ mod tests {
         // a round both fold leaving orphaned money in the pot.
         let agent_one = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![]));
         let agent_two = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(259.0),
-            AgentAction::Bet(16711936.0),
+            AgentAction::Bet(Chips::new(259)),
+            AgentAction::Bet(Chips::new(16711936)),
         ]));
         let agent_three = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(259.0),
-            AgentAction::Bet(259.0),
-            AgentAction::Bet(259.0),
+            AgentAction::Bet(Chips::new(259)),
+            AgentAction::Bet(Chips::new(259)),
+            AgentAction::Bet(Chips::new(259)),
             AgentAction::Fold,
         ]));
-        let agent_four =
-            Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(57828.0)]));
+        let agent_four = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![AgentAction::Bet(
+            Chips::new(57828),
+        )]));
         let agent_five = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![
-            AgentAction::Bet(259.0),
-            AgentAction::Bet(259.0),
-            AgentAction::Bet(259.0),
+            AgentAction::Bet(Chips::new(259)),
+            AgentAction::Bet(Chips::new(259)),
+            AgentAction::Bet(Chips::new(259)),
             AgentAction::Fold,
         ]));
 
-        let stacks = vec![22784.0, 260.0, 65471.0, 255.0, 65471.0];
-        let game_state = GameState::new_starting(stacks, 114.0, 96.0, 0.0, 210439175936 % 5);
+        let stacks = [22784, 260, 65471, 255, 65471].map(Chips::new).to_vec();
+        let game_state = GameState::new_starting(
+            stacks,
+            Chips::new(114),
+            Chips::new(96),
+            Chips::ZERO,
+            210439175936 % 5,
+        );
         let agents: Vec<Box<dyn Agent>> =
             vec![agent_one, agent_two, agent_three, agent_four, agent_five];
         let mut rng = StdRng::seed_from_u64(0);
@@ -267,8 +287,9 @@ mod tests {
             AgentAction::Fold,
         ]));
         let agent_one = Box::<VecReplayAgent>::new(VecReplayAgent::new(vec![]));
-        let stacks = vec![2.8460483e26, 53477376.0];
-        let game_state = GameState::new_starting(stacks, 8365616.5, 0.0, 0.0, 1);
+        let stacks = [11_000_000, 5_347_737].map(Chips::new).to_vec();
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(836_561), Chips::ZERO, Chips::ZERO, 1);
         let agents: Vec<Box<dyn Agent>> = vec![agent_zero, agent_one];
         let mut rng = StdRng::seed_from_u64(0);
 
@@ -297,8 +318,9 @@ mod tests {
             AgentAction::Call,
         ]));
 
-        let stacks = vec![50000.0, 50000.0, 50000.0, 50000.0];
-        let game_state = GameState::new_starting(stacks, 50.0, 3.59e-43, 0.0, 1);
+        let stacks = [50000, 50000, 50000, 50000].map(Chips::new).to_vec();
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(50), Chips::new(1), Chips::ZERO, 1);
         let agents: Vec<Box<dyn Agent>> = vec![agent_zero, agent_one, agent_two, agent_three];
         let mut rng = StdRng::seed_from_u64(0);
 
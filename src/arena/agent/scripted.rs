@@ -0,0 +1,144 @@
+use crate::arena::{action::AgentAction, game_state::GameState};
+
+use super::Agent;
+
+/// An agent that plays exactly the actions it was given, in order.
+///
+/// Unlike `VecReplayAgent` there's no default action. Being asked to act
+/// after the script runs out panics, so a test fails loudly when the game
+/// doesn't go the way it was scripted. The actions go through the
+/// simulation like any other agent's, so an illegal scripted bet is still
+/// recorded as a `FailedAction`.
+#[derive(Debug, Clone)]
+pub struct ScriptedAgent {
+    actions: Vec<AgentAction>,
+    idx: usize,
+}
+
+impl ScriptedAgent {
+    pub fn new(actions: Vec<AgentAction>) -> Self {
+        Self { actions, idx: 0 }
+    }
+
+    /// The scripted actions that haven't been played yet.
+    pub fn remaining(&self) -> &[AgentAction] {
+        &self.actions[self.idx..]
+    }
+}
+
+impl Agent for ScriptedAgent {
+    fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+        let Some(action) = self.actions.get(self.idx) else {
+            panic!(
+                "ScriptedAgent ran out of actions after {} in round {}",
+                self.actions.len(),
+                game_state.round
+            );
+        };
+        self.idx += 1;
+        action.clone()
+    }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arena::{
+        Chips, HoldemSimulationBuilder, action::Action, game_state::Round, historian::VecHistorian,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_plays_script_in_order() {
+        let mut agent =
+            ScriptedAgent::new(vec![AgentAction::Call, AgentAction::Bet(Chips::new(20))]);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        assert_eq!(AgentAction::Call, agent.act(0, &game_state));
+        assert_eq!(&[AgentAction::Bet(Chips::new(20))], agent.remaining());
+        assert_eq!(AgentAction::Bet(Chips::new(20)), agent.act(0, &game_state));
+        assert!(agent.remaining().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "ran out of actions")]
+    fn test_panics_past_script() {
+        let mut agent = ScriptedAgent::new(vec![AgentAction::Fold]);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        agent.act(0, &game_state);
+        agent.act(0, &game_state);
+    }
+
+    #[test]
+    fn test_turn_bet_and_illegal_bet() {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+
+        // Player 2 checks to the turn, bets 50 there, then tries to bet less
+        // than the call on the river. Player 1 calls until it opens the river
+        // so player 2 is facing a bet.
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(ScriptedAgent::new(vec![
+                AgentAction::Call,
+                AgentAction::Call,
+                AgentAction::Call,
+                AgentAction::Bet(Chips::new(20)),
+            ])),
+            Box::new(ScriptedAgent::new(vec![
+                AgentAction::Call,
+                AgentAction::Call,
+                AgentAction::Bet(Chips::new(50)),
+                AgentAction::Bet(Chips::new(1)),
+            ])),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut rand::rng());
+
+        let records = records.borrow();
+        let turn_bet = records.iter().any(|r| match &r.action {
+            Action::PlayedAction(p) => {
+                p.idx == 1 && p.round == Round::Turn && p.action == AgentAction::Bet(Chips::new(50))
+            }
+            _ => false,
+        });
+        assert!(turn_bet);
+
+        let failed = records
+            .iter()
+            .find_map(|r| match &r.action {
+                Action::FailedAction(p) => Some(p.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(AgentAction::Bet(Chips::new(1)), failed.action);
+        assert_eq!(AgentAction::Fold, failed.result.action);
+        assert_eq!(Round::River, failed.result.round);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    arena::{Chips, action::AgentAction, game_state::GameState},
+    core::Hand,
+    holdem::MonteCarloGame,
+    simulated_icm::icm_pressure,
+};
+
+use super::{Agent, AgentGenerator};
+
+/// An agent that knows about tournament pay jumps.
+///
+/// It never raises. When facing a bet it estimates its equity against random
+/// hands and calls only if that beats the pot odds after they are adjusted
+/// with the ICM pressure from `icm_pressure`. Near the bubble that makes it
+/// fold hands that would be a profitable call in a cash game.
+#[derive(Debug, Clone)]
+pub struct TournamentAgent {
+    payments: Vec<i32>,
+    iterations: usize,
+}
+
+impl TournamentAgent {
+    /// Create a new agent for a tournament with the given payout schedule.
+    pub fn new(payments: Vec<i32>) -> Self {
+        Self {
+            payments,
+            iterations: 1_000,
+        }
+    }
+
+    /// The equity needed to call `to_call` more into a pot of `pot` with the
+    /// given ICM pressure.
+    pub fn required_equity(to_call: Chips, pot: Chips, pressure: f64) -> f64 {
+        let risk = to_call.as_f64() * pressure;
+        risk / (risk + pot.as_f64())
+    }
+
+    fn equity(&self, game_state: &GameState) -> f64 {
+        let hero_idx = game_state.to_act_idx();
+        let board = Hand::new_with_cards(game_state.board.clone());
+
+        // Only use our own cards, everyone else still in is a random hand.
+        let hands: Vec<Hand> = (0..game_state.num_players)
+            .filter(|idx| {
+                *idx == hero_idx
+                    || game_state.player_active.get(*idx)
+                    || game_state.player_all_in.get(*idx)
+            })
+            .map(|idx| {
+                if idx == hero_idx {
+                    game_state.hands[idx]
+                } else {
+                    board
+                }
+            })
+            .collect();
+        let hero_pos = (0..hero_idx)
+            .filter(|idx| game_state.player_active.get(*idx) || game_state.player_all_in.get(*idx))
+            .count();
+
+        match MonteCarloGame::new(hands) {
+            Ok(mut monte) => f64::from(monte.estimate_equity(self.iterations)[hero_pos]),
+            Err(_) => 0.0,
+        }
+    }
+}
+
+impl Agent for TournamentAgent {
+    fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+        let idx = game_state.to_act_idx();
+        let to_call = game_state.current_round_bet() - game_state.current_round_player_bet(idx);
+
+        // Checking is always free.
+        if to_call <= Chips::ZERO {
+            return AgentAction::Call;
+        }
+
+        // Pay jumps depend on where the stacks were before this hand.
+        let stacks: Vec<i32> = game_state
+            .starting_stacks
+            .iter()
+            .map(|s| s.get() as i32)
+            .collect();
+        let pressure = icm_pressure(&stacks, &self.payments, idx);
+        let required = Self::required_equity(to_call, game_state.total_pot, pressure);
+
+        if self.equity(game_state) >= required {
+            AgentAction::Call
+        } else {
+            AgentAction::Fold
+        }
+    }
+
+    fn clone_agent(&self) -> Option<Box<dyn Agent>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// `AgentGenerator` for `TournamentAgent`.
+#[derive(Debug, Clone)]
+pub struct TournamentAgentGenerator {
+    payments: Vec<i32>,
+}
+
+impl TournamentAgentGenerator {
+    pub fn new(payments: Vec<i32>) -> Self {
+        Self { payments }
+    }
+}
+
+impl AgentGenerator for TournamentAgentGenerator {
+    fn generate(&self, _game_state: &GameState) -> Box<dyn Agent> {
+        Box::new(TournamentAgent::new(self.payments.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arena::{
+        HoldemSimulationBuilder,
+        agent::AllInAgent,
+        test_util::{assert_valid_game_state, assert_valid_round_data},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_required_equity_grows_with_pressure() {
+        // Calling 100 into 200 needs a third of the pot in chip EV
+        let chip_ev = TournamentAgent::required_equity(Chips::new(100), Chips::new(200), 1.0);
+        assert!((chip_ev - 1.0 / 3.0).abs() < 1e-9);
+
+        let bubble = TournamentAgent::required_equity(Chips::new(100), Chips::new(200), 2.0);
+        assert!(bubble > chip_ev);
+    }
+
+    #[test_log::test]
+    fn test_tournament_agent_vs_all_in() {
+        let stacks = vec![Chips::new(100); 4];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(TournamentAgent::new(vec![50, 30, 20])),
+            Box::new(AllInAgent),
+            Box::new(TournamentAgent::new(vec![50, 30, 20])),
+            Box::new(TournamentAgent::new(vec![50, 30, 20])),
+        ];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .build()
+            .unwrap();
+
+        sim.run(&mut rand::rng());
+
+        assert_valid_round_data(&sim.game_state.round_data);
+        assert_valid_game_state(&sim.game_state);
+    }
+}
@@ -0,0 +1,600 @@
+use super::{Chips, GameState, game_state::Round};
+
+/// The most non-forced raises allowed on a single street in fixed limit.
+pub const FIXED_LIMIT_RAISE_CAP: u8 = 4;
+
+/// The rules for how much a player is allowed to bet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BettingStructure {
+    /// Any bet up to the player's whole stack.
+    #[default]
+    NoLimit,
+    /// The biggest raise is the size of the pot after calling.
+    PotLimit,
+    /// Every bet and raise is exactly `small_bet` preflop and on the flop,
+    /// and `big_bet` on the turn and river. After
+    /// [`FIXED_LIMIT_RAISE_CAP`] raises on a street, players can only call.
+    FixedLimit { small_bet: Chips, big_bet: Chips },
+}
+
+/// What the simulation does with a raise that isn't legal. That's one
+/// smaller than the minimum raise that isn't all in, or a raise from a
+/// player that a short all in didn't reopen the betting for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IllegalRaisePolicy {
+    /// Record a failed action and fold the player.
+    #[default]
+    Fold,
+    /// Record a failed action and have the player call instead.
+    Call,
+}
+
+/// Who posts the antes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnteStyle {
+    /// Every player dealt in posts the ante.
+    #[default]
+    PerPlayer,
+    /// Only the big blind posts an ante, for the whole table. The game
+    /// state's `ante` is then the total that's posted. The big blind comes
+    /// first, so a player that can't cover both posts as much of the ante
+    /// as is left after the blind.
+    BigBlindAnte,
+}
+
+/// What the player to act is allowed to do.
+///
+/// `min_raise` and `max_raise` are totals for the round, the same as the
+/// amount given to `AgentAction::Bet`. `call_amount` is how many more
+/// chips calling puts in, which is capped by the player's stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegalActions {
+    /// Nothing has to be put in to stay in the hand.
+    pub can_check: bool,
+    /// The chips it takes to call.
+    pub call_amount: Chips,
+    /// The player may raise. When false both raise sizes are the call.
+    pub can_raise: bool,
+    /// The smallest legal raise. If the player's stack can't cover a full
+    /// raise this is going all in.
+    pub min_raise: Chips,
+    /// The biggest legal raise, bounded by the player's stack and the
+    /// betting structure.
+    pub max_raise: Chips,
+}
+
+impl LegalActions {
+    /// The legal actions for the player to act in `game_state`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, GameState, betting::LegalActions};
+    ///
+    /// let mut game_state = GameState::new_starting(
+    ///     vec![Chips::new(100); 3],
+    ///     Chips::new(2),
+    ///     Chips::new(1),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// for _ in 0..3 {
+    ///     game_state.advance_round();
+    /// }
+    /// game_state.do_bet(Chips::new(1), true).unwrap();
+    /// game_state.do_bet(Chips::new(2), true).unwrap();
+    ///
+    /// let legal = LegalActions::new(&game_state);
+    /// assert!(!legal.can_check);
+    /// assert_eq!(Chips::new(2), legal.call_amount);
+    /// assert_eq!(Chips::new(4), legal.min_raise);
+    /// assert_eq!(Chips::new(100), legal.max_raise);
+    /// ```
+    pub fn new(game_state: &GameState) -> Self {
+        let bet = game_state.current_round_bet();
+        let player_bet = game_state.current_round_current_player_bet();
+        let stack = game_state.current_player_stack();
+        let all_in = player_bet + stack;
+        let call_amount = (bet - player_bet).clamp(Chips::ZERO, stack);
+
+        let max_raise = game_state.betting_structure.max_bet(game_state);
+        let can_raise = max_raise > bet;
+        let min_raise = if !can_raise {
+            max_raise
+        } else if game_state.betting_structure.is_fixed() {
+            // There's only one size of raise.
+            max_raise
+        } else {
+            all_in.min(bet + game_state.current_round_min_raise())
+        };
+
+        LegalActions {
+            can_check: call_amount <= Chips::ZERO,
+            call_amount,
+            can_raise,
+            min_raise,
+            max_raise,
+        }
+    }
+}
+
+impl BettingStructure {
+    /// The most the player to act can have bet this round once they have
+    /// acted. This is capped by the player's stack, and is only a call if
+    /// the betting hasn't been reopened for the player.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{BettingStructure, Chips, GameState};
+    ///
+    /// let mut game_state = GameState::new_starting(
+    ///     vec![Chips::new(100); 3],
+    ///     Chips::new(2),
+    ///     Chips::new(1),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// game_state.advance_round();
+    /// game_state.advance_round();
+    /// game_state.advance_round();
+    /// game_state.do_bet(Chips::new(1), true).unwrap();
+    /// game_state.do_bet(Chips::new(2), true).unwrap();
+    ///
+    /// // Calling 2 makes the pot 5, so the raise can be to 2 + 5.
+    /// assert_eq!(
+    ///     Chips::new(7),
+    ///     BettingStructure::PotLimit.max_bet(&game_state)
+    /// );
+    /// assert_eq!(
+    ///     Chips::new(100),
+    ///     BettingStructure::NoLimit.max_bet(&game_state)
+    /// );
+    /// ```
+    pub fn max_bet(&self, game_state: &GameState) -> Chips {
+        let player_bet = game_state.current_round_current_player_bet();
+        let all_in = player_bet + game_state.current_player_stack();
+        if !game_state.current_player_can_raise() {
+            return all_in.min(game_state.current_round_bet());
+        }
+        match self {
+            BettingStructure::NoLimit => all_in,
+            BettingStructure::PotLimit => {
+                let bet = game_state.current_round_bet();
+                let call = bet - player_bet;
+                let pot_after_call = game_state.total_pot + call;
+                all_in.min(bet + pot_after_call)
+            }
+            BettingStructure::FixedLimit { .. } => {
+                let bet = game_state.current_round_bet();
+                if game_state.round_data.total_raise_count >= FIXED_LIMIT_RAISE_CAP {
+                    all_in.min(bet)
+                } else {
+                    all_in.min(bet + self.bet_increment(game_state.round))
+                }
+            }
+        }
+    }
+
+    /// The size of a single bet or raise in fixed limit for `round`. No
+    /// limit and pot limit have no fixed size, so this is zero for them.
+    pub fn bet_increment(&self, round: Round) -> Chips {
+        match self {
+            BettingStructure::FixedLimit { small_bet, big_bet } => match round {
+                Round::Turn | Round::DealTurn | Round::River | Round::DealRiver => *big_bet,
+                _ => *small_bet,
+            },
+            _ => Chips::ZERO,
+        }
+    }
+
+    /// Is every raise forced to one legal size, rather than any size up to
+    /// [`BettingStructure::max_bet`].
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, BettingStructure::FixedLimit { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{
+        Agent, HoldemSimulation, HoldemSimulationBuilder,
+        action::{Action, AgentAction, FailedActionPayload},
+        game_state::Round,
+        historian::{HistoryRecord, VecHistorian},
+    };
+
+    use super::*;
+
+    /// Build the sim and play it up to the first voluntary preflop action.
+    /// It's three handed with stacks of 100 and blinds of 1 and 2.
+    fn preflop_sim(builder: HoldemSimulationBuilder) -> HoldemSimulation {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(2),
+            Chips::new(1),
+            Chips::ZERO,
+            0,
+        );
+        let mut sim = builder.game_state(game_state).build().unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        while sim.game_state.round != Round::Preflop {
+            sim.run_round(&mut rng);
+        }
+        sim.game_state.do_bet(Chips::new(1), true).unwrap();
+        sim.game_state.do_bet(Chips::new(2), true).unwrap();
+        sim.game_state.sb_posted = true;
+        sim.game_state.bb_posted = true;
+        sim
+    }
+
+    fn pot_limit_sim(clamp: bool, historian: VecHistorian) -> HoldemSimulation {
+        preflop_sim(
+            HoldemSimulationBuilder::default()
+                .betting_structure(BettingStructure::PotLimit)
+                .clamp_over_limit_bets(clamp)
+                .historians(vec![Box::new(historian)]),
+        )
+    }
+
+    #[test]
+    fn test_over_pot_bet_is_clamped() {
+        let mut sim = pot_limit_sim(true, VecHistorian::new());
+        let idx = sim.game_state.to_act_idx();
+        let max = BettingStructure::PotLimit.max_bet(&sim.game_state);
+        // Call amount of 2 plus the pot after calling, 1 + 2 + 2.
+        assert_eq!(Chips::new(2 + 5), max);
+
+        sim.run_agent_action(AgentAction::Bet(Chips::new(50)));
+        assert_eq!(Chips::new(7), sim.game_state.current_round_player_bet(idx));
+    }
+
+    #[test]
+    fn test_pot_limit_all_in_is_clamped() {
+        let mut sim = pot_limit_sim(true, VecHistorian::new());
+        let idx = sim.game_state.to_act_idx();
+        sim.run_agent_action(AgentAction::AllIn);
+        assert_eq!(Chips::new(7), sim.game_state.current_round_player_bet(idx));
+        assert!(sim.game_state.player_active.get(idx));
+    }
+
+    #[test]
+    fn test_pot_sized_bet_is_allowed() {
+        let mut sim = pot_limit_sim(false, VecHistorian::new());
+        let idx = sim.game_state.to_act_idx();
+        sim.run_agent_action(AgentAction::Bet(Chips::new(7)));
+        assert_eq!(Chips::new(7), sim.game_state.current_round_player_bet(idx));
+    }
+
+    #[test]
+    fn test_over_pot_bet_fails_without_clamp() {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = pot_limit_sim(false, historian);
+        let idx = sim.game_state.to_act_idx();
+
+        sim.run_agent_action(AgentAction::Bet(Chips::new(50)));
+        assert!(!sim.game_state.player_active.get(idx));
+
+        let last = records.borrow().last().unwrap().action.clone();
+        match last {
+            Action::FailedAction(payload) => {
+                assert_eq!(AgentAction::Bet(Chips::new(50)), payload.action);
+                assert_eq!(AgentAction::Fold, payload.result.action);
+            }
+            other => panic!("Expected a failed action, got {other:?}"),
+        }
+    }
+
+    fn failed_action(records: &[HistoryRecord]) -> FailedActionPayload {
+        match records.last().unwrap().action.clone() {
+            Action::FailedAction(payload) => payload,
+            other => panic!("Expected a failed action, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_under_raise_folds_by_default() {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim =
+            preflop_sim(HoldemSimulationBuilder::default().historians(vec![Box::new(historian)]));
+        let idx = sim.game_state.to_act_idx();
+
+        // The min raise is 2 so raising to 3 is too small.
+        sim.run_agent_action(AgentAction::Bet(Chips::new(3)));
+        assert!(!sim.game_state.player_active.get(idx));
+
+        let payload = failed_action(&records.borrow());
+        assert_eq!(AgentAction::Bet(Chips::new(3)), payload.action);
+        assert_eq!(AgentAction::Fold, payload.result.action);
+    }
+
+    #[test]
+    fn test_under_raise_calls_with_call_policy() {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = preflop_sim(
+            HoldemSimulationBuilder::default()
+                .illegal_raise_policy(IllegalRaisePolicy::Call)
+                .historians(vec![Box::new(historian)]),
+        );
+        let idx = sim.game_state.to_act_idx();
+
+        sim.run_agent_action(AgentAction::Bet(Chips::new(3)));
+        assert!(sim.game_state.player_active.get(idx));
+        assert_eq!(Chips::new(2), sim.game_state.current_round_player_bet(idx));
+
+        let payload = failed_action(&records.borrow());
+        assert_eq!(AgentAction::Bet(Chips::new(3)), payload.action);
+        assert_eq!(AgentAction::Call, payload.result.action);
+        assert_eq!(Chips::new(2), payload.result.final_player_bet);
+        assert_eq!(Chips::new(5), payload.result.final_pot);
+    }
+
+    #[test]
+    fn test_short_all_in_doesnt_reopen_with_call_policy() {
+        let mut sim = preflop_sim(
+            HoldemSimulationBuilder::default().illegal_raise_policy(IllegalRaisePolicy::Call),
+        );
+        // Under the gun raises to 10.
+        sim.run_agent_action(AgentAction::Bet(Chips::new(10)));
+        // The small blind folds, and the big blind is short and goes all in
+        // for 15. That raise of 5 is less than the 8 a full raise needs.
+        sim.run_agent_action(AgentAction::Fold);
+        sim.game_state.stacks[2] = Chips::new(13);
+        sim.run_agent_action(AgentAction::AllIn);
+        assert_eq!(Chips::new(15), sim.game_state.current_round_bet());
+
+        // Under the gun can't raise again, the all in is turned into a call.
+        assert_eq!(0, sim.game_state.to_act_idx());
+        assert!(!sim.game_state.current_player_can_raise());
+        sim.run_agent_action(AgentAction::AllIn);
+        assert_eq!(Chips::new(15), sim.game_state.current_round_player_bet(0));
+        assert!(sim.game_state.player_active.get(0));
+    }
+
+    /// Fixed limit at 2/4 with blinds of 1 and 2.
+    fn fixed_limit_sim() -> HoldemSimulation {
+        preflop_sim(HoldemSimulationBuilder::default().betting_structure(
+            BettingStructure::FixedLimit {
+                small_bet: Chips::new(2),
+                big_bet: Chips::new(4),
+            },
+        ))
+    }
+
+    #[test]
+    fn test_fixed_limit_snaps_bets() {
+        let mut sim = fixed_limit_sim();
+        let idx = sim.game_state.to_act_idx();
+        sim.run_agent_action(AgentAction::Bet(Chips::new(25)));
+        assert_eq!(Chips::new(4), sim.game_state.current_round_player_bet(idx));
+
+        // A raise that's too small is bumped up to the full increment.
+        let idx = sim.game_state.to_act_idx();
+        sim.run_agent_action(AgentAction::Bet(Chips::new(5)));
+        assert_eq!(Chips::new(6), sim.game_state.current_round_player_bet(idx));
+
+        let idx = sim.game_state.to_act_idx();
+        sim.run_agent_action(AgentAction::AllIn);
+        assert_eq!(Chips::new(8), sim.game_state.current_round_player_bet(idx));
+    }
+
+    #[test]
+    fn test_fixed_limit_fifth_raise_is_call() {
+        let mut sim = fixed_limit_sim();
+        for raises in 1..=FIXED_LIMIT_RAISE_CAP {
+            sim.run_agent_action(AgentAction::Bet(Chips::new(100)));
+            assert_eq!(
+                Chips::new(2 + 2 * i64::from(raises)),
+                sim.game_state.current_round_bet()
+            );
+        }
+        assert_eq!(
+            Chips::new(10),
+            BettingStructure::FixedLimit {
+                small_bet: Chips::new(2),
+                big_bet: Chips::new(4)
+            }
+            .max_bet(&sim.game_state)
+        );
+
+        let idx = sim.game_state.to_act_idx();
+        sim.run_agent_action(AgentAction::Bet(Chips::new(100)));
+        assert_eq!(Chips::new(10), sim.game_state.current_round_bet());
+        assert_eq!(Chips::new(10), sim.game_state.current_round_player_bet(idx));
+        assert_eq!(
+            FIXED_LIMIT_RAISE_CAP,
+            sim.game_state.round_data.total_raise_count
+        );
+        assert!(sim.game_state.player_active.get(idx));
+    }
+
+    #[test]
+    fn test_fixed_limit_increment_by_round() {
+        let structure = BettingStructure::FixedLimit {
+            small_bet: Chips::new(2),
+            big_bet: Chips::new(4),
+        };
+        assert_eq!(Chips::new(2), structure.bet_increment(Round::Preflop));
+        assert_eq!(Chips::new(2), structure.bet_increment(Round::Flop));
+        assert_eq!(Chips::new(4), structure.bet_increment(Round::Turn));
+        assert_eq!(Chips::new(4), structure.bet_increment(Round::River));
+        assert_eq!(
+            Chips::ZERO,
+            BettingStructure::PotLimit.bet_increment(Round::River)
+        );
+    }
+
+    #[test]
+    fn test_pot_limit_max_after_raise() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(1000); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        // Pot sized raise to 35.
+        game_state.do_bet(Chips::new(35), false).unwrap();
+
+        // Small blind needs 30 to call, making the pot 80.
+        let call = Chips::new(35 - 5);
+        let pot_after_call = game_state.total_pot + call;
+        assert_eq!(Chips::new(80), pot_after_call);
+        assert_eq!(
+            Chips::new(35) + pot_after_call,
+            BettingStructure::PotLimit.max_bet(&game_state)
+        );
+    }
+
+    #[test]
+    fn test_pot_limit_capped_by_stack() {
+        let mut game_state = GameState::new_starting(
+            [20, 1000, 1000].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        assert_eq!(
+            Chips::new(20),
+            BettingStructure::PotLimit.max_bet(&game_state)
+        );
+    }
+
+    #[test]
+    fn test_legal_actions_facing_blinds() {
+        let sim = preflop_sim(HoldemSimulationBuilder::default());
+        let legal = LegalActions::new(&sim.game_state);
+        assert!(!legal.can_check);
+        assert!(legal.can_raise);
+        assert_eq!(Chips::new(2), legal.call_amount);
+        assert_eq!(Chips::new(4), legal.min_raise);
+        assert_eq!(Chips::new(100), legal.max_raise);
+    }
+
+    #[test]
+    fn test_legal_actions_big_blind_option() {
+        let mut sim = preflop_sim(HoldemSimulationBuilder::default());
+        sim.run_agent_action(AgentAction::Call);
+        sim.run_agent_action(AgentAction::Call);
+        let legal = LegalActions::new(&sim.game_state);
+        assert!(legal.can_check);
+        assert_eq!(Chips::ZERO, legal.call_amount);
+        assert_eq!(Chips::new(4), legal.min_raise);
+    }
+
+    #[test]
+    fn test_legal_actions_short_stack() {
+        let mut game_state = GameState::new_starting(
+            [20, 3, 1000].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for _ in 0..3 {
+            game_state.advance_round();
+        }
+        // Seat 1 is all in for a short small blind. Seat 0's stack only
+        // just covers a full raise over the big blind.
+        game_state.do_bet(Chips::new(3), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        let legal = LegalActions::new(&game_state);
+        assert_eq!(Chips::new(10), legal.call_amount);
+        assert_eq!(Chips::new(20), legal.min_raise);
+        assert_eq!(Chips::new(20), legal.max_raise);
+    }
+
+    #[test]
+    fn test_legal_actions_with_limits() {
+        let sim = preflop_sim(HoldemSimulationBuilder::default().betting_structure(
+            BettingStructure::FixedLimit {
+                small_bet: Chips::new(2),
+                big_bet: Chips::new(4),
+            },
+        ));
+        let legal = LegalActions::new(&sim.game_state);
+        assert_eq!(Chips::new(4), legal.min_raise);
+        assert_eq!(Chips::new(4), legal.max_raise);
+
+        let sim = preflop_sim(
+            HoldemSimulationBuilder::default().betting_structure(BettingStructure::PotLimit),
+        );
+        let legal = LegalActions::new(&sim.game_state);
+        assert_eq!(Chips::new(4), legal.min_raise);
+        assert_eq!(Chips::new(7), legal.max_raise);
+    }
+
+    /// Always makes the smallest legal raise.
+    struct MinRaiseAgent;
+
+    impl Agent for MinRaiseAgent {
+        fn act(&mut self, _id: u128, _game_state: &GameState) -> AgentAction {
+            panic!("The simulation should pass the legal actions");
+        }
+
+        fn act_with_legal_actions(
+            &mut self,
+            _id: u128,
+            _game_state: &GameState,
+            legal_actions: &LegalActions,
+        ) -> AgentAction {
+            if legal_actions.can_raise {
+                AgentAction::Bet(legal_actions.min_raise)
+            } else {
+                AgentAction::Call
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_raises_never_fail() {
+        for seed in 0..20 {
+            let historian = VecHistorian::new();
+            let records = historian.get_storage();
+            let agents: Vec<Box<dyn Agent>> = vec![
+                Box::new(MinRaiseAgent),
+                Box::new(MinRaiseAgent),
+                Box::new(MinRaiseAgent),
+            ];
+            let game_state = GameState::new_starting(
+                [100, 55, 80].map(Chips::new).to_vec(),
+                Chips::new(2),
+                Chips::new(1),
+                Chips::ZERO,
+                0,
+            );
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(game_state)
+                .agents(agents)
+                .historians(vec![Box::new(historian)])
+                .build()
+                .unwrap();
+            sim.run(&mut StdRng::seed_from_u64(seed));
+            assert!(
+                records
+                    .borrow()
+                    .iter()
+                    .all(|r| !matches!(r.action, Action::FailedAction(_)))
+            );
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::MappedRwLockReadGuard;
 
 use tracing::event;
 
-use crate::arena::{GameState, action::AgentAction};
+use crate::arena::{Chips, GameState, action::AgentAction};
 
 use super::{CFRState, Node, NodeData, TraversalState};
 
@@ -134,7 +134,7 @@ impl ActionGenerator for BasicCFRActionGenerator {
         let mut res: Vec<AgentAction> = Vec::with_capacity(3);
         let to_call =
             game_state.current_round_bet() - game_state.current_round_current_player_bet();
-        if to_call > 0.0 {
+        if to_call > Chips::ZERO {
             res.push(AgentAction::Fold);
         }
         // Call, Match the current bet (if the bet is 0 this is a check)
@@ -175,8 +175,9 @@ mod tests {
 
     #[test]
     fn test_should_gen_2_actions() {
-        let stacks = vec![50.0; 2];
-        let game_state = GameState::new_starting(stacks, 2.0, 1.0, 0.0, 0);
+        let stacks = vec![Chips::new(50); 2];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(2), Chips::new(1), Chips::ZERO, 0);
         let action_generator = BasicCFRActionGenerator::new(
             CFRState::new(game_state.clone()),
             TraversalState::new_root(0),
@@ -193,12 +194,13 @@ mod tests {
 
     #[test]
     fn test_should_gen_3_actions() {
-        let stacks = vec![50.0; 2];
-        let mut game_state = GameState::new_starting(stacks, 2.0, 1.0, 0.0, 0);
+        let stacks = vec![Chips::new(50); 2];
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(2), Chips::new(1), Chips::ZERO, 0);
         game_state.advance_round();
         game_state.advance_round();
 
-        game_state.do_bet(10.0, false).unwrap();
+        game_state.do_bet(Chips::new(10), false).unwrap();
         let action_generator = BasicCFRActionGenerator::new(
             CFRState::new(game_state.clone()),
             TraversalState::new_root(0),
@@ -213,7 +215,7 @@ mod tests {
             0
         );
         assert_eq!(
-            action_generator.action_to_idx(&game_state, &AgentAction::Bet(10.0)),
+            action_generator.action_to_idx(&game_state, &AgentAction::Bet(Chips::new(10))),
             1
         );
         assert_eq!(
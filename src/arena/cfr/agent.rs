@@ -144,6 +144,7 @@ where
 
         sim.game_state
             .player_reward(self.traversal_state.player_idx())
+            .as_f32()
     }
 
     fn target_node_idx(&self) -> Option<usize> {
@@ -331,14 +332,20 @@ where
 #[cfg(test)]
 mod tests {
 
-    use crate::arena::GameState;
     use crate::arena::cfr::{BasicCFRActionGenerator, FixedGameStateIteratorGen};
+    use crate::arena::{Chips, GameState};
 
     use super::*;
 
     #[test]
     fn test_create_agent() {
-        let game_state = GameState::new_starting(vec![100.0; 3], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
         let mut state_store = StateStore::new();
         let (cfr_state, traversal_state) = state_store.new_state(game_state.clone(), 0);
         let _ = CFRAgent::<BasicCFRActionGenerator, FixedGameStateIteratorGen>::new(
@@ -353,8 +360,9 @@ mod tests {
     fn test_run_heads_up() {
         let num_agents = 2;
         // Zero is all in.
-        let stacks: Vec<f32> = vec![50.0, 50.0];
-        let game_state = GameState::new_starting(stacks, 5.0, 2.5, 0.0, 0);
+        let stacks = [50, 50].map(Chips::new).to_vec();
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(5), Chips::new(2), Chips::ZERO, 0);
         let mut state_store = StateStore::new();
 
         let agents: Vec<_> = (0..num_agents)
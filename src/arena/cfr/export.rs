@@ -405,6 +405,8 @@ pub fn export_cfr_state(
 
 #[cfg(test)]
 mod tests {
+    use crate::arena::Chips;
+
     use super::*;
     use crate::arena::GameState;
     use crate::arena::cfr::{CFRState, NodeData, PlayerData, TerminalData};
@@ -414,7 +416,13 @@ mod tests {
     /// This represents a simple poker game tree with different possible paths.
     fn create_test_cfr_state() -> CFRState {
         // Create a game state with 2 players
-        let game_state = GameState::new_starting(vec![100.0; 2], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
         let mut cfr_state = CFRState::new(game_state);
 
         // Root -> Player 0 decision
@@ -696,7 +704,13 @@ mod tests {
         let output_path = temp_dir.path().join("player_seats.dot");
 
         // Create a test CFR state with multiple player nodes
-        let game_state = GameState::new_starting(vec![100.0; 2], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
         let mut cfr_state = CFRState::new(game_state);
 
         // Add player nodes at different positions
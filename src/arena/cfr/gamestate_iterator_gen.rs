@@ -100,11 +100,19 @@ impl GameStateIteratorGen for PerRoundFixedGameStateIteratorGen {
 
 #[cfg(test)]
 mod tests {
+    use crate::arena::Chips;
+
     use super::*;
 
     #[test]
     fn test_simple() {
-        let game_state = GameState::new_starting(vec![100.0; 3], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
         let generator = FixedGameStateIteratorGen::new(3);
         let mut iter = generator.generate(&game_state);
 
@@ -116,7 +124,13 @@ mod tests {
 
     #[test]
     fn test_per_round() {
-        let mut game_state = GameState::new_starting(vec![100.0; 3], 10.0, 5.0, 0.0, 0);
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
         let generator = PerRoundFixedGameStateIteratorGen::new(2, 3, 4, 1);
 
         game_state.advance_round();
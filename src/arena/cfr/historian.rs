@@ -122,7 +122,9 @@ where
         let to_node_idx = self.ensure_target_node(NodeData::Terminal(TerminalData::default()))?;
         self.traversal_state.move_to(to_node_idx, 0);
 
-        let reward = game_state.player_reward(self.traversal_state.player_idx());
+        let reward = game_state
+            .player_reward(self.traversal_state.player_idx())
+            .as_f32();
 
         let mut node = self
             .cfr_state
@@ -156,6 +158,15 @@ where
         match action {
             // These are all assumed from game start and encoded in the root node.
             Action::GameStart(_) | Action::ForcedBet(_) | Action::PlayerSit(_) => Ok(()),
+            // No one sees a burned card so it doesn't change the tree.
+            Action::BurnCard(_) => Ok(()),
+            // The tree follows the first board, the others only change the
+            // awards in the terminal node.
+            Action::DealRunout(_) => Ok(()),
+            // Every hand is already known to the simulation at showdown.
+            Action::Showdown(_) => Ok(()),
+            // The pot is already part of the game state in each node.
+            Action::PotUpdate(_) => Ok(()),
             // For the final round we need to use that to get the final award amount
             Action::RoundAdvance(Round::Complete) => self.record_terminal(game_state),
             // We don't encode round advance in the tree because it never changes the outcome.
@@ -66,7 +66,9 @@ mod tests {
     use crate::arena::cfr::{BasicCFRActionGenerator, FixedGameStateIteratorGen, state_store};
     use crate::arena::game_state::{Round, RoundData};
 
-    use crate::arena::{Agent, GameState, HoldemSimulation, HoldemSimulationBuilder, test_util};
+    use crate::arena::{
+        Agent, Chips, GameState, HoldemSimulation, HoldemSimulationBuilder, test_util,
+    };
     use crate::core::{Hand, PlayerBitSet};
 
     use super::CFRAgent;
@@ -82,13 +84,17 @@ mod tests {
 
         let board = (hand_zero & hand_one).iter().collect();
         // Zero is all in.
-        let stacks: Vec<f32> = vec![0.0, 900.0];
-        let player_bet = vec![1000.0, 100.0];
-        let player_bet_round = vec![900.0, 0.0];
+        let stacks = [0, 900].map(Chips::new).to_vec();
+        let player_bet = [1000, 100].map(Chips::new).to_vec();
+        let player_bet_round = [900, 0].map(Chips::new).to_vec();
         // Create a game state where player 0 is all in and player 1 should make a
         // decision to call or fold
-        let round_data =
-            RoundData::new_with_bets(100.0, PlayerBitSet::new(num_agents), 1, player_bet_round);
+        let round_data = RoundData::new_with_bets(
+            Chips::new(100),
+            PlayerBitSet::new(num_agents),
+            1,
+            player_bet_round,
+        );
         let game_state = GameState::new(
             Round::River,
             round_data,
@@ -96,22 +102,22 @@ mod tests {
             vec![hand_zero, hand_one],
             stacks,
             player_bet,
-            5.0,
-            0.0,
-            0.0,
+            Chips::new(5),
+            Chips::ZERO,
+            Chips::ZERO,
             0,
         );
 
         let sim = run(game_state, 10);
 
         // Player 1 should not put any more bets in and should fold
-        assert_eq!(sim.game_state.player_bet[1], 100.0);
+        assert_eq!(sim.game_state.player_bet[1], Chips::new(100));
 
         // Player 0 should win the pot
-        assert_eq!(sim.game_state.stacks[0], 1100.0);
+        assert_eq!(sim.game_state.stacks[0], Chips::new(1100));
 
         // Player 1 didn't put any more in and didn't win
-        assert_eq!(sim.game_state.stacks[1], 900.0);
+        assert_eq!(sim.game_state.stacks[1], Chips::new(900));
     }
 
     #[test]
@@ -125,11 +131,15 @@ mod tests {
 
         let board = (hand_zero & hand_one).iter().collect();
         // Zero is all in.
-        let stacks: Vec<f32> = vec![0.0, 900.0];
-        let player_bet = vec![1000.0, 100.0];
-        let player_bet_round = vec![900.0, 0.0];
-        let round_data =
-            RoundData::new_with_bets(100.0, PlayerBitSet::new(num_agents), 1, player_bet_round);
+        let stacks = [0, 900].map(Chips::new).to_vec();
+        let player_bet = [1000, 100].map(Chips::new).to_vec();
+        let player_bet_round = [900, 0].map(Chips::new).to_vec();
+        let round_data = RoundData::new_with_bets(
+            Chips::new(100),
+            PlayerBitSet::new(num_agents),
+            1,
+            player_bet_round,
+        );
         let game_state = GameState::new(
             Round::River,
             round_data,
@@ -137,19 +147,19 @@ mod tests {
             vec![hand_zero, hand_one],
             stacks,
             player_bet,
-            5.0,
-            0.0,
-            0.0,
+            Chips::new(5),
+            Chips::ZERO,
+            Chips::ZERO,
             0,
         );
 
         let sim = run(game_state, 10);
 
         // Player 1 should not put any more bets in and should fold
-        assert_eq!(sim.game_state.player_bet[1], 1000.0);
+        assert_eq!(sim.game_state.player_bet[1], Chips::new(1000));
 
         // Player 1 should win the pot
-        assert_eq!(sim.game_state.stacks[1], 2000.0);
+        assert_eq!(sim.game_state.stacks[1], Chips::new(2000));
     }
 
     #[test]
@@ -163,7 +173,7 @@ mod tests {
         let result = run(game_state, 100);
 
         // Player 1 should not put any more bets in and should fold
-        assert_eq!(result.game_state.player_bet[1], 100.0);
+        assert_eq!(result.game_state.player_bet[1], Chips::new(100));
     }
 
     #[test]
@@ -176,7 +186,7 @@ mod tests {
         let result = run(game_state, 100);
 
         // Player 1 should not put any more bets in and should fold
-        assert_eq!(result.game_state.player_bet[1], 100.0);
+        assert_eq!(result.game_state.player_bet[1], Chips::new(100));
     }
 
     #[test]
@@ -188,7 +198,7 @@ mod tests {
         let result = run(game_state, 100);
 
         // Player 1 should not put any more bets in and should fold
-        assert_eq!(result.game_state.player_bet[1], 100.0);
+        assert_eq!(result.game_state.player_bet[1], Chips::new(100));
     }
 
     fn build_from_hands(hand_zero: Hand, hand_one: Hand, round: Round) -> GameState {
@@ -196,11 +206,15 @@ mod tests {
         let num_agents = 2;
 
         // Zero is all in.
-        let stacks: Vec<f32> = vec![0.0, 900.0];
-        let player_bet = vec![1000.0, 100.0];
-        let player_bet_round = vec![900.0, 0.0];
-        let round_data =
-            RoundData::new_with_bets(100.0, PlayerBitSet::new(num_agents), 1, player_bet_round);
+        let stacks = [0, 900].map(Chips::new).to_vec();
+        let player_bet = [1000, 100].map(Chips::new).to_vec();
+        let player_bet_round = [900, 0].map(Chips::new).to_vec();
+        let round_data = RoundData::new_with_bets(
+            Chips::new(100),
+            PlayerBitSet::new(num_agents),
+            1,
+            player_bet_round,
+        );
         GameState::new(
             round,
             round_data,
@@ -208,9 +222,9 @@ mod tests {
             vec![hand_zero, hand_one],
             stacks,
             player_bet,
-            5.0,
-            0.0,
-            0.0,
+            Chips::new(5),
+            Chips::ZERO,
+            Chips::ZERO,
             0,
         )
     }
@@ -47,10 +47,16 @@ pub struct CFRStateInternal {
 /// # Examples
 ///
 /// ```
-/// use rs_poker::arena::GameState;
 /// use rs_poker::arena::cfr::CFRState;
+/// use rs_poker::arena::{Chips, GameState};
 ///
-/// let game_state = GameState::new_starting(vec![100.0; 2], 10.0, 5.0, 0.0, 0);
+/// let game_state = GameState::new_starting(
+///     vec![Chips::new(100); 2],
+///     Chips::new(10),
+///     Chips::new(5),
+///     Chips::ZERO,
+///     0,
+/// );
 /// let cfr_state = CFRState::new(game_state);
 /// ```
 #[derive(Debug, Clone)]
@@ -182,13 +188,19 @@ impl TraversalState {
 mod tests {
     use crate::arena::cfr::{NodeData, PlayerData, TraversalState};
 
-    use crate::arena::GameState;
+    use crate::arena::{Chips, GameState};
 
     use super::CFRState;
 
     #[test]
     fn test_add_get_node() {
-        let mut state = CFRState::new(GameState::new_starting(vec![100.0; 3], 10.0, 5.0, 0.0, 0));
+        let mut state = CFRState::new(GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        ));
         let new_data = NodeData::Player(PlayerData {
             regret_matcher: None,
             player_idx: 0,
@@ -214,7 +226,13 @@ mod tests {
 
     #[test]
     fn test_node_get_not_exist() {
-        let state = CFRState::new(GameState::new_starting(vec![100.0; 3], 10.0, 5.0, 0.0, 0));
+        let state = CFRState::new(GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        ));
         // root node is always at index 0
         let root = state.get(0);
         assert!(root.is_some());
@@ -148,6 +148,8 @@ impl Default for StateStore {
 #[cfg(test)]
 mod tests {
 
+    use crate::arena::Chips;
+
     use super::*;
 
     #[test]
@@ -159,7 +161,13 @@ mod tests {
     #[test]
     fn test_push() {
         let mut state_store = StateStore::new();
-        let game_state = GameState::new_starting(vec![100.0; 3], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
         let (state, _traversal) = state_store.new_state(game_state.clone(), 0);
         assert_eq!(
             state_store.len(),
@@ -177,7 +185,13 @@ mod tests {
     fn test_push_len() {
         let mut state_store = StateStore::new();
 
-        let game_state = GameState::new_starting(vec![100.0; 3], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
 
         let _stores = (0..2)
             .map(|i| {
@@ -0,0 +1,197 @@
+//! Chip expected value of a spot, found by playing it out many times.
+//!
+//! Equity says what share of the pot a hand would win if every card was
+//! dealt with no more betting. Chip EV instead lets agents play out the rest
+//! of the hand, so it includes the chips won or lost on later streets.
+use rand::Rng;
+
+use super::{AgentGenerator, GameState, HoldemSimulationBuilder, errors::HoldemSimulationError};
+
+/// Play out `game_state` `runs` times and return the average chips each
+/// player ends the hand with, relative to their stack in `game_state`.
+///
+/// The hole cards and board in the game state are kept, the rest of the
+/// board is dealt at random each run. Agents are generated fresh for every
+/// run, one per player in seat order. Chips a player already put in the pot
+/// are not counted as lost, so for a player that is all in this is their
+/// share of the pot won on average.
+///
+/// Returns an error if the number of agents doesn't match the players or
+/// the simulation can't be built. Zero runs is all zeros.
+pub fn expected_chips<R: Rng>(
+    game_state: &GameState,
+    agents: &[Box<dyn AgentGenerator>],
+    runs: usize,
+    rand: &mut R,
+) -> Result<Vec<f64>, HoldemSimulationError> {
+    if agents.len() != game_state.num_players {
+        return Err(HoldemSimulationError::AgentCount(game_state.num_players));
+    }
+    let mut totals = vec![0.0; game_state.num_players];
+    if runs == 0 {
+        return Ok(totals);
+    }
+
+    for _ in 0..runs {
+        let run_agents = agents.iter().map(|g| g.generate(game_state)).collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state.clone())
+            .agents(run_agents)
+            .build()?;
+        sim.run(rand);
+
+        for (idx, total) in totals.iter_mut().enumerate() {
+            *total += f64::from(sim.game_state.player_reward(idx));
+        }
+    }
+
+    Ok(totals.iter().map(|t| t / runs as f64).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::cards;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::{
+        arena::{
+            Chips,
+            agent::{CallingAgentGenerator, FoldingAgentGenerator},
+            game_state::{Round, RoundData},
+        },
+        core::{Card, Hand, PlayerBitSet, Rankable},
+        holdem::board_completions,
+    };
+
+    use super::*;
+
+    /// Two players all in on the turn.
+    fn all_in_on_turn(board: &[Card], hero: &str, villain: &str) -> GameState {
+        let mut hero_hand = Hand::new_from_str(hero).unwrap();
+        hero_hand.extend(board.iter().cloned());
+        let mut villain_hand = Hand::new_from_str(villain).unwrap();
+        villain_hand.extend(board.iter().cloned());
+
+        GameState::new(
+            Round::Turn,
+            RoundData::new(2, Chips::new(10), PlayerBitSet::new(2), 1),
+            board.to_vec(),
+            vec![hero_hand, villain_hand],
+            [0, 0].map(Chips::new).to_vec(),
+            [100, 100].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_all_in_chip_ev_is_equity_times_pot() {
+        let board = cards("Ks9s4d2c");
+        let game_state = all_in_on_turn(&board, "AsQs", "KdKh");
+
+        // Work out hero's exact equity over every river.
+        let mut known = board.clone();
+        known.extend(cards("AsQsKdKh"));
+        let mut hero_share = 0.0;
+        let mut rivers = 0.0;
+        for river in board_completions(&known, 1) {
+            let mut hero = game_state.hands[0];
+            hero.extend(river.iter().cloned());
+            let mut villain = game_state.hands[1];
+            villain.extend(river.iter().cloned());
+            hero_share += match hero.rank().cmp(&villain.rank()) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            };
+            rivers += 1.0;
+        }
+        let equity = hero_share / rivers;
+
+        let agents: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<CallingAgentGenerator>::default(),
+            Box::<CallingAgentGenerator>::default(),
+        ];
+        let mut rng = StdRng::seed_from_u64(222);
+        let ev = expected_chips(&game_state, &agents, 10_000, &mut rng).unwrap();
+
+        let pot = 200.0;
+        assert!((ev[0] - equity * pot).abs() < 3.0, "{ev:?} vs {equity}");
+        // Every chip in the pot goes to someone.
+        assert!((ev[0] + ev[1] - pot).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_folding_loses_nothing_more() {
+        // With chips behind, a folding agent never adds to the pot.
+        let board = cards("Ks9s4d");
+        let hero = {
+            let mut h = Hand::new_from_str("2c3d").unwrap();
+            h.extend(board.iter().cloned());
+            h
+        };
+        let villain = {
+            let mut h = Hand::new_from_str("AhAd").unwrap();
+            h.extend(board.iter().cloned());
+            h
+        };
+        let game_state = GameState::new(
+            Round::Flop,
+            RoundData::new(2, Chips::new(10), PlayerBitSet::new(2), 1),
+            board,
+            vec![hero, villain],
+            [100, 100].map(Chips::new).to_vec(),
+            [10, 10].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let agents: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<FoldingAgentGenerator>::default(),
+            Box::<FoldingAgentGenerator>::default(),
+        ];
+        let ev =
+            expected_chips(&game_state, &agents, 100, &mut StdRng::seed_from_u64(222)).unwrap();
+        assert!(ev[0] >= 0.0 && ev[0] <= 20.0);
+        assert!((ev[0] + ev[1] - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mismatched_agents() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let agents: Vec<Box<dyn AgentGenerator>> = vec![Box::<CallingAgentGenerator>::default()];
+        assert_eq!(
+            Err(HoldemSimulationError::AgentCount(3)),
+            expected_chips(&game_state, &agents, 10, &mut StdRng::seed_from_u64(222))
+        );
+    }
+
+    #[test]
+    fn test_build_error_returned() {
+        // A straddle under the big blind can't be built.
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.straddle = Chips::new(5);
+        let agents: Vec<Box<dyn AgentGenerator>> = (0..3)
+            .map(|_| Box::<CallingAgentGenerator>::default() as Box<dyn AgentGenerator>)
+            .collect();
+        assert_eq!(
+            Err(HoldemSimulationError::StraddleTooSmall),
+            expected_chips(&game_state, &agents, 10, &mut StdRng::seed_from_u64(222))
+        );
+    }
+}
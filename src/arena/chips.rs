@@ -0,0 +1,215 @@
+//! Whole numbers of chips.
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
+
+/// A whole number of chips.
+///
+/// Every amount of money in the arena is `Chips`: stacks, bets, pots,
+/// winnings, blinds and the amounts in actions. Adding, taking away and
+/// splitting them never rounds, so every chip at the start of a hand is
+/// still there at the end. Ratios like pot odds are worked out from the
+/// float views.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::arena::Chips;
+///
+/// let pot = Chips::new(100);
+/// let shares = pot.split(3);
+/// assert_eq!(vec![Chips::new(34), Chips::new(33), Chips::new(33)], shares);
+/// assert_eq!(pot, shares.into_iter().sum());
+/// assert_eq!(100.0, pot.as_f32());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Chips(i64);
+
+impl Chips {
+    pub const ZERO: Chips = Chips(0);
+
+    pub const fn new(chips: i64) -> Self {
+        Chips(chips)
+    }
+
+    /// The number of chips.
+    pub fn get(self) -> i64 {
+        self.0
+    }
+
+    /// The chips times `factor`, rounded down to a whole chip. This is how
+    /// agents size bets as a fraction of the pot.
+    ///
+    /// ```
+    /// use rs_poker::arena::Chips;
+    ///
+    /// assert_eq!(Chips::new(33), Chips::new(100).scale(0.33));
+    /// assert_eq!(Chips::new(150), Chips::new(100).scale(1.5));
+    /// ```
+    pub fn scale(self, factor: f32) -> Chips {
+        Chips((self.0 as f64 * f64::from(factor)).floor() as i64)
+    }
+
+    /// The chips as an `f32`, for ratios and for display.
+    pub fn as_f32(self) -> f32 {
+        self.0 as f32
+    }
+
+    /// The chips as an `f64`.
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64
+    }
+
+    /// Divide the chips into `ways` shares that differ by at most one chip
+    /// and add up to the whole. The shares with the extra chips come first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ways` is zero.
+    pub fn split(self, ways: usize) -> Vec<Chips> {
+        assert!(ways > 0, "Can't split chips zero ways");
+        let ways = ways as i64;
+        let share = self.0.div_euclid(ways);
+        let odd = self.0.rem_euclid(ways);
+        (0..ways)
+            .map(|i| Chips(share + i64::from(i < odd)))
+            .collect()
+    }
+}
+
+impl From<i64> for Chips {
+    fn from(chips: i64) -> Self {
+        Chips(chips)
+    }
+}
+
+impl From<Chips> for i64 {
+    fn from(chips: Chips) -> Self {
+        chips.0
+    }
+}
+
+impl From<Chips> for f32 {
+    fn from(chips: Chips) -> Self {
+        chips.as_f32()
+    }
+}
+
+impl From<Chips> for f64 {
+    fn from(chips: Chips) -> Self {
+        chips.as_f64()
+    }
+}
+
+impl fmt::Display for Chips {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Add for Chips {
+    type Output = Chips;
+    fn add(self, rhs: Chips) -> Chips {
+        Chips(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Chips {
+    fn add_assign(&mut self, rhs: Chips) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Chips {
+    type Output = Chips;
+    fn sub(self, rhs: Chips) -> Chips {
+        Chips(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Chips {
+    fn sub_assign(&mut self, rhs: Chips) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Chips {
+    type Output = Chips;
+    fn neg(self) -> Chips {
+        Chips(-self.0)
+    }
+}
+
+impl Mul<i64> for Chips {
+    type Output = Chips;
+    fn mul(self, rhs: i64) -> Chips {
+        Chips(self.0 * rhs)
+    }
+}
+
+impl Div<i64> for Chips {
+    type Output = Chips;
+    fn div(self, rhs: i64) -> Chips {
+        Chips(self.0 / rhs)
+    }
+}
+
+impl Rem<i64> for Chips {
+    type Output = Chips;
+    fn rem(self, rhs: i64) -> Chips {
+        Chips(self.0 % rhs)
+    }
+}
+
+impl Sum for Chips {
+    fn sum<I: Iterator<Item = Chips>>(iter: I) -> Chips {
+        iter.fold(Chips::ZERO, |a, b| a + b)
+    }
+}
+
+impl<'a> Sum<&'a Chips> for Chips {
+    fn sum<I: Iterator<Item = &'a Chips>>(iter: I) -> Chips {
+        iter.copied().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_keeps_every_chip() {
+        for chips in 0..50 {
+            for ways in 1..10 {
+                let shares = Chips::new(chips).split(ways);
+                assert_eq!(ways, shares.len());
+                assert_eq!(Chips::new(chips), shares.iter().copied().sum());
+                let most = shares.iter().max().unwrap().get();
+                let least = shares.iter().min().unwrap().get();
+                assert!(most - least <= 1);
+            }
+        }
+        assert_eq!(
+            vec![Chips::new(51), Chips::new(50)],
+            Chips::new(101).split(2)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut chips = Chips::new(10) + Chips::new(5) - Chips::new(3);
+        assert_eq!(Chips::new(12), chips);
+        chips += Chips::new(8);
+        chips -= Chips::new(1);
+        assert_eq!(Chips::new(19), chips);
+        assert_eq!(Chips::new(38), chips * 2);
+        assert_eq!(Chips::new(6), chips / 3);
+        assert_eq!(Chips::new(1), chips % 3);
+        assert_eq!(Chips::new(-19), -chips);
+        assert_eq!(Chips::new(9), chips.scale(0.5));
+        assert_eq!("19", chips.to_string());
+    }
+}
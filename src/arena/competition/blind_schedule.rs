@@ -0,0 +1,89 @@
+use crate::arena::Chips;
+
+/// One level of a `BlindSchedule`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindLevel {
+    /// How many hands are played at this level before moving to the next.
+    pub hands: usize,
+    pub small_blind: Chips,
+    pub big_blind: Chips,
+    pub ante: Chips,
+}
+
+impl BlindLevel {
+    pub fn new(hands: usize, small_blind: Chips, big_blind: Chips, ante: Chips) -> Self {
+        Self {
+            hands,
+            small_blind,
+            big_blind,
+            ante,
+        }
+    }
+}
+
+/// Blinds that go up as a tournament goes on.
+///
+/// Levels last a number of hands rather than an amount of time, since
+/// simulated hands take no time at all. Once the last level is reached
+/// it's played for the rest of the tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlindSchedule {
+    levels: Vec<BlindLevel>,
+}
+
+impl BlindSchedule {
+    /// Create a schedule from its levels in the order they are played.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is empty.
+    pub fn new(levels: Vec<BlindLevel>) -> Self {
+        assert!(
+            !levels.is_empty(),
+            "A blind schedule needs at least one level"
+        );
+        Self { levels }
+    }
+
+    pub fn levels(&self) -> &[BlindLevel] {
+        &self.levels
+    }
+
+    /// The level being played after `hands_played` hands.
+    pub fn level(&self, hands_played: usize) -> &BlindLevel {
+        let mut hands_left = hands_played;
+        for level in &self.levels {
+            if hands_left < level.hands {
+                return level;
+            }
+            hands_left -= level.hands;
+        }
+        self.levels.last().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_advances_by_hands() {
+        let schedule = BlindSchedule::new(vec![
+            BlindLevel::new(2, Chips::new(5), Chips::new(10), Chips::ZERO),
+            BlindLevel::new(3, Chips::new(10), Chips::new(20), Chips::new(1)),
+            BlindLevel::new(1, Chips::new(25), Chips::new(50), Chips::new(5)),
+        ]);
+        let big_blinds: Vec<Chips> = (0..8).map(|h| schedule.level(h).big_blind).collect();
+        assert_eq!(
+            [10, 10, 20, 20, 20, 50, 50, 50].map(Chips::new).to_vec(),
+            big_blinds
+        );
+        assert_eq!(Chips::new(1), schedule.level(2).ante);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_schedule() {
+        BlindSchedule::new(vec![]);
+    }
+}
@@ -0,0 +1,428 @@
+use rand::Rng;
+use tracing::{event, trace_span};
+
+use crate::arena::{
+    Chips, GameState, HoldemSimulationBuilder, agent::AgentGenerator,
+    errors::HoldemSimulationError, historian::HistorianGenerator,
+};
+
+/// What happens to the players' stacks between hands of a
+/// `CashGameSession`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StackPolicy {
+    /// Stacks carry over from hand to hand. A player that busts sits out
+    /// the rest of the session.
+    #[default]
+    Keep,
+    /// Any stack below the target is topped back up to it between hands,
+    /// like a player rebuying at a cash table.
+    TopUpTo(Chips),
+}
+
+/// A session of cash game hands at a single table.
+///
+/// The same agents play hand after hand with the button moving each
+/// time. Stacks carry over between hands or are topped up depending on
+/// the `StackPolicy`. Agent `i` always sits in seat `i`.
+///
+/// This builder is used to create a `CashGameSession`.
+pub struct CashGameSessionBuilder {
+    agent_generators: Option<Vec<Box<dyn AgentGenerator>>>,
+    historian_generators: Option<Vec<Box<dyn HistorianGenerator>>>,
+    starting_game_state: Option<GameState>,
+    stack_policy: StackPolicy,
+    num_hands: usize,
+    panic_on_historian_error: bool,
+}
+
+pub struct CashGameSession {
+    agent_generators: Vec<Box<dyn AgentGenerator>>,
+    historian_generators: Vec<Box<dyn HistorianGenerator>>,
+    starting_game_state: GameState,
+    stack_policy: StackPolicy,
+    num_hands: usize,
+    panic_on_historian_error: bool,
+}
+
+/// The results of a cash game session.
+///
+/// Winnings are in chips. A player's net winnings don't count the chips
+/// they added by topping up.
+#[derive(Debug, Clone)]
+pub struct CashGameResults {
+    hands_played: usize,
+    net_winnings: Vec<Chips>,
+    /// Running mean of each agent's winnings per hand, for Welford's
+    /// variance.
+    mean: Vec<f64>,
+    /// Running sum of squared differences from the mean.
+    squared_diffs: Vec<f64>,
+    top_ups: Vec<Chips>,
+    final_stacks: Vec<Chips>,
+}
+
+impl CashGameResults {
+    fn new(num_players: usize) -> Self {
+        CashGameResults {
+            hands_played: 0,
+            net_winnings: vec![Chips::ZERO; num_players],
+            mean: vec![0.0; num_players],
+            squared_diffs: vec![0.0; num_players],
+            top_ups: vec![Chips::ZERO; num_players],
+            final_stacks: vec![Chips::ZERO; num_players],
+        }
+    }
+
+    fn record_hand(&mut self, game_state: &GameState) {
+        self.hands_played += 1;
+        let hands = self.hands_played as f64;
+        for idx in 0..game_state.num_players {
+            let change = game_state.player_reward(idx);
+            self.net_winnings[idx] += change;
+
+            let change = change.as_f64();
+            let delta = change - self.mean[idx];
+            self.mean[idx] += delta / hands;
+            self.squared_diffs[idx] += delta * (change - self.mean[idx]);
+        }
+        self.final_stacks.clone_from(&game_state.stacks);
+    }
+
+    /// How many hands were played. This can be fewer than asked for if
+    /// everyone but one player busted.
+    pub fn hands_played(&self) -> usize {
+        self.hands_played
+    }
+
+    /// The chips each agent won or lost over the whole session.
+    pub fn net_winnings(&self) -> &[Chips] {
+        &self.net_winnings
+    }
+
+    /// The average chips each agent won or lost per hand.
+    pub fn mean_winnings(&self) -> Vec<f32> {
+        let hands = self.hands_played.max(1) as f32;
+        self.net_winnings
+            .iter()
+            .map(|w| w.as_f32() / hands)
+            .collect()
+    }
+
+    /// The sample variance of each agent's winnings per hand.
+    pub fn variance(&self) -> Vec<f32> {
+        if self.hands_played < 2 {
+            return vec![0.0; self.net_winnings.len()];
+        }
+        let hands = self.hands_played as f64;
+        self.squared_diffs
+            .iter()
+            .map(|diffs| (diffs / (hands - 1.0)) as f32)
+            .collect()
+    }
+
+    /// The chips each agent added to their stack by topping up.
+    pub fn top_ups(&self) -> &[Chips] {
+        &self.top_ups
+    }
+
+    /// Every agent's stack after the last hand.
+    pub fn final_stacks(&self) -> &[Chips] {
+        &self.final_stacks
+    }
+}
+
+impl Default for CashGameSessionBuilder {
+    fn default() -> Self {
+        Self {
+            agent_generators: None,
+            historian_generators: None,
+            starting_game_state: None,
+            stack_policy: StackPolicy::default(),
+            num_hands: 0,
+            panic_on_historian_error: true,
+        }
+    }
+}
+
+impl CashGameSessionBuilder {
+    /// Sets the agent generators for the session. Each generator will be
+    /// called before every hand, and agent `i` sits in seat `i`.
+    pub fn agent_generators(mut self, agent_generators: Vec<Box<dyn AgentGenerator>>) -> Self {
+        self.agent_generators = Some(agent_generators);
+        self
+    }
+
+    /// Sets the historian generators for the session.
+    pub fn historian_generators(
+        mut self,
+        historian_generators: Vec<Box<dyn HistorianGenerator>>,
+    ) -> Self {
+        self.historian_generators = Some(historian_generators);
+        self
+    }
+
+    /// Sets the starting game state for the first hand. The blinds and
+    /// betting rules stay the same for every hand.
+    pub fn starting_game_state(mut self, starting_game_state: GameState) -> Self {
+        self.starting_game_state = Some(starting_game_state);
+        self
+    }
+
+    /// Sets what happens to stacks between hands. The default keeps them.
+    pub fn stack_policy(mut self, stack_policy: StackPolicy) -> Self {
+        self.stack_policy = stack_policy;
+        self
+    }
+
+    /// Sets how many hands to play.
+    pub fn num_hands(mut self, num_hands: usize) -> Self {
+        self.num_hands = num_hands;
+        self
+    }
+
+    /// Sets whether the underlying `HoldemSimulation` should panic if a
+    /// historian errors. Defaults to true, like `HoldemSimulationBuilder`.
+    pub fn panic_on_historian_error(mut self, panic_on_historian_error: bool) -> Self {
+        self.panic_on_historian_error = panic_on_historian_error;
+        self
+    }
+
+    /// Builds the `CashGameSession` from the builder.
+    pub fn build(self) -> Result<CashGameSession, HoldemSimulationError> {
+        let agent_generators = self
+            .agent_generators
+            .ok_or(HoldemSimulationError::NeedAgents)?;
+        let starting_game_state = self
+            .starting_game_state
+            .ok_or(HoldemSimulationError::NeedGameState)?;
+        Ok(CashGameSession {
+            agent_generators,
+            historian_generators: self.historian_generators.unwrap_or_default(),
+            starting_game_state,
+            stack_policy: self.stack_policy,
+            num_hands: self.num_hands,
+            panic_on_historian_error: self.panic_on_historian_error,
+        })
+    }
+}
+
+impl CashGameSession {
+    /// Play the session's hands with `rand` dealing every hand. Stops early
+    /// if fewer than two players have chips.
+    pub fn run<R: Rng>(self, rand: &mut R) -> Result<CashGameResults, HoldemSimulationError> {
+        let span = trace_span!("CashGameSession::run");
+        let _enter = span.enter();
+
+        let mut results = CashGameResults::new(self.starting_game_state.num_players);
+        results
+            .final_stacks
+            .clone_from(&self.starting_game_state.stacks);
+        let mut game_state = self.starting_game_state;
+
+        for hand in 0..self.num_hands {
+            if game_state
+                .stacks
+                .iter()
+                .filter(|s| **s > Chips::ZERO)
+                .count()
+                < 2
+            {
+                event!(
+                    tracing::Level::INFO,
+                    "Stopping after {} hands, not enough players with chips",
+                    hand
+                );
+                break;
+            }
+
+            let agents = self
+                .agent_generators
+                .iter()
+                .map(|builder| builder.generate(&game_state))
+                .collect::<Vec<_>>();
+            let historians = self
+                .historian_generators
+                .iter()
+                .map(|builder| builder.generate(&game_state))
+                .collect::<Vec<_>>();
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(game_state.clone())
+                .agents(agents)
+                .historians(historians)
+                .panic_on_historian_error(self.panic_on_historian_error)
+                .build()?;
+            sim.run(rand);
+            results.record_hand(&sim.game_state);
+
+            let mut stacks = sim.game_state.stacks.clone();
+            let is_last_hand = hand + 1 == self.num_hands;
+            if let (StackPolicy::TopUpTo(target), false) = (self.stack_policy, is_last_hand) {
+                for (idx, stack) in stacks.iter_mut().enumerate() {
+                    if *stack < target {
+                        results.top_ups[idx] += target - *stack;
+                        *stack = target;
+                    }
+                }
+            }
+            game_state = sim.game_state.next_hand(stacks);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::agent::{AllInAgentGenerator, CallingAgentGenerator, RandomAgentGenerator};
+
+    use super::*;
+
+    fn random_gens(n: usize) -> Vec<Box<dyn AgentGenerator>> {
+        (0..n)
+            .map(|_| Box::<RandomAgentGenerator>::default() as Box<dyn AgentGenerator>)
+            .collect()
+    }
+
+    #[test]
+    fn test_chips_conserved_keeping_stacks() {
+        let stacks = [100, 200, 150, 50].map(Chips::new).to_vec();
+        let session = CashGameSessionBuilder::default()
+            .agent_generators(random_gens(4))
+            .starting_game_state(GameState::new_starting(
+                stacks.clone(),
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .num_hands(50)
+            .build()
+            .unwrap();
+        let results = session.run(&mut StdRng::seed_from_u64(278)).unwrap();
+
+        assert!(results.hands_played() > 0);
+        assert_eq!(Chips::ZERO, results.net_winnings().iter().sum::<Chips>());
+        assert_eq!(
+            Chips::new(500),
+            results.final_stacks().iter().sum::<Chips>()
+        );
+        for (idx, stack) in stacks.iter().enumerate() {
+            assert_eq!(
+                *stack + results.net_winnings()[idx],
+                results.final_stacks()[idx]
+            );
+        }
+        assert!(results.top_ups().iter().all(|t| *t == Chips::ZERO));
+    }
+
+    #[test]
+    fn test_chips_conserved_with_top_ups() {
+        let session = CashGameSessionBuilder::default()
+            .agent_generators(random_gens(3))
+            .starting_game_state(GameState::new_starting(
+                vec![Chips::new(100); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::new(1),
+                0,
+            ))
+            .stack_policy(StackPolicy::TopUpTo(Chips::new(100)))
+            .num_hands(50)
+            .build()
+            .unwrap();
+        let results = session.run(&mut StdRng::seed_from_u64(278)).unwrap();
+
+        // Topping up means no one can bust out early.
+        assert_eq!(50, results.hands_played());
+        assert_eq!(Chips::ZERO, results.net_winnings().iter().sum::<Chips>());
+        let added: Chips = results.top_ups().iter().sum();
+        assert_eq!(
+            Chips::new(300) + added,
+            results.final_stacks().iter().sum::<Chips>()
+        );
+        assert!(results.variance().iter().all(|v| *v >= 0.0));
+    }
+
+    #[test]
+    fn test_seeded_session_repeats() {
+        let run = || {
+            let mut rng = StdRng::seed_from_u64(291);
+            let gens: Vec<Box<dyn AgentGenerator>> = (0..3)
+                .map(|_| -> Box<dyn AgentGenerator> {
+                    Box::new(RandomAgentGenerator::new_with_rng(
+                        vec![0.25, 0.30, 0.50],
+                        vec![0.5, 0.6, 0.45],
+                        &mut rng,
+                    ))
+                })
+                .collect();
+            let session = CashGameSessionBuilder::default()
+                .agent_generators(gens)
+                .starting_game_state(GameState::new_starting(
+                    vec![Chips::new(100); 3],
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    0,
+                ))
+                .stack_policy(StackPolicy::TopUpTo(Chips::new(100)))
+                .num_hands(20)
+                .build()
+                .unwrap();
+            session.run(&mut rng).unwrap()
+        };
+        let first = run();
+        let second = run();
+        assert_eq!(first.net_winnings(), second.net_winnings());
+        assert_eq!(first.top_ups(), second.top_ups());
+    }
+
+    #[test]
+    fn test_stops_when_one_player_left() {
+        let gens: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<AllInAgentGenerator>::default(),
+            Box::<CallingAgentGenerator>::default(),
+        ];
+        let session = CashGameSessionBuilder::default()
+            .agent_generators(gens)
+            .starting_game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .num_hands(1_000)
+            .build()
+            .unwrap();
+        let results = session.run(&mut StdRng::seed_from_u64(278)).unwrap();
+
+        // Every hand is all in, so someone busts long before 1,000 hands.
+        assert!(results.hands_played() < 1_000);
+        assert!(results.final_stacks().contains(&Chips::new(200)));
+        assert_eq!(-results.net_winnings()[0], results.net_winnings()[1]);
+        assert_eq!(
+            Chips::new(100),
+            results.net_winnings()[0].max(results.net_winnings()[1])
+        );
+    }
+
+    #[test]
+    fn test_variance() {
+        let mut results = CashGameResults::new(2);
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for change in [10, -10, 30].map(Chips::new) {
+            game_state.stacks = vec![Chips::new(100) + change, Chips::new(100) - change];
+            results.record_hand(&game_state);
+        }
+        assert_eq!(vec![10.0, -10.0], results.mean_winnings());
+        assert_eq!(vec![400.0, 400.0], results.variance());
+    }
+}
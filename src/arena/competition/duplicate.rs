@@ -0,0 +1,242 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use tracing::{event, trace_span};
+
+use crate::arena::{
+    AgentGenerator, GameState, HoldemSimulation, HoldemSimulationBuilder,
+    errors::HoldemSimulationError, historian::HistorianGenerator,
+};
+
+/// Duplicate poker competition.
+///
+/// Like duplicate bridge every deal is played more than once. Each hand the
+/// same shuffle is replayed at a number of independent tables, with the agents
+/// sat in a different seat at each table. Since every agent gets to play
+/// every seat's cards the luck of the deal cancels out, making it much
+/// cheaper to tell two agent populations apart.
+///
+/// By default there's one table per seat with the agents rotated one seat
+/// around the table each time.
+pub struct DuplicateCompetition<G: Iterator<Item = GameState>> {
+    agent_generators: Vec<Box<dyn AgentGenerator>>,
+    historian_generators: Vec<Box<dyn HistorianGenerator>>,
+    game_state_iterator: G,
+    /// For every table the agent index that sits in each seat.
+    seatings: Vec<Vec<usize>>,
+    /// The number of deals that have been played.
+    pub num_rounds: usize,
+    /// Stack size change for each agent normalized in big blinds.
+    pub total_change: Vec<f32>,
+}
+
+impl<G: Iterator<Item = GameState>> DuplicateCompetition<G> {
+    /// Create a new duplicate competition where the agents are rotated
+    /// around the table so that each agent plays every seat for each deal.
+    pub fn new(
+        agent_generators: Vec<Box<dyn AgentGenerator>>,
+        historian_generators: Vec<Box<dyn HistorianGenerator>>,
+        game_state_iterator: G,
+    ) -> Self {
+        let num_agents = agent_generators.len();
+        let seatings = (0..num_agents)
+            .map(|shift| {
+                (0..num_agents)
+                    .map(|seat| (seat + shift) % num_agents)
+                    .collect()
+            })
+            .collect();
+        DuplicateCompetition {
+            agent_generators,
+            historian_generators,
+            game_state_iterator,
+            seatings,
+            num_rounds: 0,
+            total_change: vec![0.0; num_agents],
+        }
+    }
+
+    /// Use custom seatings rather than rotating the agents. Each seating
+    /// gives the index of the agent that sits in each seat at one table.
+    ///
+    /// Returns `InvalidSeating` with the index of the first seating that
+    /// isn't a permutation of the agents.
+    pub fn seatings(mut self, seatings: Vec<Vec<usize>>) -> Result<Self, HoldemSimulationError> {
+        let num_agents = self.agent_generators.len();
+        for (idx, seating) in seatings.iter().enumerate() {
+            let mut sorted = seating.clone();
+            sorted.sort_unstable();
+            if !sorted.into_iter().eq(0..num_agents) {
+                return Err(HoldemSimulationError::InvalidSeating(idx));
+            }
+        }
+        self.seatings = seatings;
+        Ok(self)
+    }
+
+    /// Play `num_rounds` deals at every table.
+    ///
+    /// `rand` is only used to pick the seed for each deal, every table then
+    /// gets the same seeded shuffle. Returns the simulations from every table
+    /// for every deal, grouped by deal.
+    pub fn run<R: Rng>(
+        &mut self,
+        num_rounds: usize,
+        rand: &mut R,
+    ) -> Result<Vec<Vec<HoldemSimulation>>, HoldemSimulationError> {
+        let span = trace_span!("DuplicateCompetition::run");
+        let _enter = span.enter();
+
+        let mut results = Vec::with_capacity(num_rounds);
+        for _round in 0..num_rounds {
+            let game_state = self
+                .game_state_iterator
+                .next()
+                .ok_or(HoldemSimulationError::NeedGameState)?;
+            let seed: u64 = rand.random();
+            event!(tracing::Level::TRACE, seed, "duplicate_deal");
+
+            let mut tables = Vec::with_capacity(self.seatings.len());
+            for seating in &self.seatings {
+                let mut sim = self.build_table(seating, game_state.clone())?;
+                sim.run(&mut StdRng::seed_from_u64(seed));
+
+                for (seat, agent_idx) in seating.iter().enumerate() {
+                    self.total_change[*agent_idx] += sim.game_state.player_reward(seat).as_f32()
+                        / sim.game_state.big_blind.as_f32();
+                }
+                tables.push(sim);
+            }
+            self.num_rounds += 1;
+            results.push(tables);
+        }
+        Ok(results)
+    }
+
+    fn build_table(
+        &self,
+        seating: &[usize],
+        game_state: GameState,
+    ) -> Result<HoldemSimulation, HoldemSimulationError> {
+        let agents = seating
+            .iter()
+            .map(|agent_idx| self.agent_generators[*agent_idx].generate(&game_state))
+            .collect();
+        let historians = self
+            .historian_generators
+            .iter()
+            .map(|g| g.generate(&game_state))
+            .collect();
+
+        HoldemSimulationBuilder::default()
+            .agents(agents)
+            .historians(historians)
+            .game_state(game_state)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{
+        Chips,
+        agent::{CallingAgentGenerator, FoldingAgentGenerator},
+        game_state::CloneGameStateGenerator,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_tables_see_identical_cards() {
+        let generators: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<CallingAgentGenerator>::default(),
+            Box::<CallingAgentGenerator>::default(),
+            Box::<FoldingAgentGenerator>::default(),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut competition =
+            DuplicateCompetition::new(generators, vec![], CloneGameStateGenerator::new(game_state));
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let results = competition.run(5, &mut rng).unwrap();
+        assert_eq!(5, results.len());
+        assert_eq!(5, competition.num_rounds);
+
+        for tables in &results {
+            assert_eq!(3, tables.len());
+            let first = &tables[0].game_state;
+            // Two calling agents means every table goes to showdown.
+            assert_eq!(5, first.board.len());
+            for table in &tables[1..] {
+                assert_eq!(first.board, table.game_state.board);
+                assert_eq!(first.hands, table.game_state.hands);
+            }
+        }
+
+        // Different deals should get different cards.
+        assert_ne!(
+            results[0][0].game_state.board,
+            results[1][0].game_state.board
+        );
+    }
+
+    #[test]
+    fn test_custom_seatings() {
+        let generators: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<FoldingAgentGenerator>::default(),
+            Box::<FoldingAgentGenerator>::default(),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut competition =
+            DuplicateCompetition::new(generators, vec![], CloneGameStateGenerator::new(game_state))
+                .seatings(vec![vec![0, 1], vec![1, 0]])
+                .unwrap();
+
+        let results = competition.run(2, &mut rand::rng()).unwrap();
+        assert_eq!(2, results[0].len());
+
+        // Folding agents just trade blinds so swapping seats evens it out.
+        assert_eq!(0.0, competition.total_change.iter().sum::<f32>());
+        assert_eq!(competition.total_change[0], competition.total_change[1]);
+    }
+
+    #[test]
+    fn test_invalid_seatings() {
+        let competition = || {
+            let generators: Vec<Box<dyn AgentGenerator>> = vec![
+                Box::<FoldingAgentGenerator>::default(),
+                Box::<FoldingAgentGenerator>::default(),
+            ];
+            let game_state = GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            );
+            DuplicateCompetition::new(generators, vec![], CloneGameStateGenerator::new(game_state))
+        };
+        for (seatings, bad) in [
+            (vec![vec![0, 1], vec![1, 1]], 1),
+            (vec![vec![0, 2]], 0),
+            (vec![vec![1, 0], vec![0]], 1),
+        ] {
+            assert!(matches!(
+                competition().seatings(seatings),
+                Err(HoldemSimulationError::InvalidSeating(idx)) if idx == bad
+            ));
+        }
+    }
+}
@@ -100,11 +100,13 @@ impl<T: Iterator<Item = HoldemSimulation>> HoldemCompetition<T> {
             .map(|(idx, (starting, ending))| {
                 (
                     idx,
-                    (*ending - *starting) / running_sim.game_state.big_blind,
+                    (*ending - *starting).as_f32() / running_sim.game_state.big_blind.as_f32(),
                 )
             });
 
-        for (idx, norm_change) in changes {
+        // Results belong to the agent, whichever seat it was in.
+        for (seat, norm_change) in changes {
+            let idx = running_sim.seating.get(seat).copied().unwrap_or(seat);
             // Running total
             self.total_change[idx] += norm_change;
             // What's the most we lose
@@ -148,8 +150,10 @@ impl<T: Iterator<Item = HoldemSimulation>> Debug for HoldemCompetition<T> {
 #[cfg(test)]
 mod tests {
     use crate::arena::{
-        AgentGenerator, CloneGameStateGenerator, GameState,
-        agent::{CallingAgentGenerator, RandomAgentGenerator},
+        AgentGenerator, Chips, CloneGameStateGenerator, GameState,
+        agent::{
+            AllInAgentGenerator, CallingAgentGenerator, FoldingAgentGenerator, RandomAgentGenerator,
+        },
         competition::StandardSimulationIterator,
     };
 
@@ -162,8 +166,9 @@ mod tests {
             Box::<CallingAgentGenerator>::default(),
         ];
 
-        let stacks = vec![100.0; 2];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 2];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let sim_gen = StandardSimulationIterator::new(
             agent_gens,
             vec![], // no historians
@@ -173,4 +178,32 @@ mod tests {
 
         let _first_results = competition.run(100).unwrap();
     }
+
+    #[test]
+    fn test_results_follow_agent_across_seats() {
+        let agent_gens: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<AllInAgentGenerator>::default(),
+            Box::<FoldingAgentGenerator>::default(),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let sim_gen = StandardSimulationIterator::new(
+            agent_gens,
+            vec![],
+            CloneGameStateGenerator::new(game_state),
+        )
+        .randomize_seats(true);
+        let mut competition = HoldemCompetition::new(sim_gen);
+        competition.run(200).unwrap();
+
+        // The folding agent never wins a pot from the all in agent no
+        // matter which seat it was given.
+        assert_eq!(0, competition.win_count[1]);
+        assert_eq!(200, competition.win_count[0]);
+    }
 }
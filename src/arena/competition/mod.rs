@@ -1,7 +1,13 @@
+mod blind_schedule;
+mod cash_game;
+mod duplicate;
 mod holdem_competition;
 mod sim_iterator;
 mod tournament;
 
+pub use blind_schedule::{BlindLevel, BlindSchedule};
+pub use cash_game::{CashGameResults, CashGameSession, CashGameSessionBuilder, StackPolicy};
+pub use duplicate::DuplicateCompetition;
 pub use holdem_competition::HoldemCompetition;
 pub use sim_iterator::StandardSimulationIterator;
-pub use tournament::{SingleTableTournament, SingleTableTournamentBuilder};
+pub use tournament::{SingleTableTournament, SingleTableTournamentBuilder, TournamentResults};
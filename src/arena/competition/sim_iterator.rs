@@ -1,3 +1,5 @@
+use rand::seq::SliceRandom;
+
 use crate::arena::{
     AgentGenerator, GameState, HoldemSimulation, HoldemSimulationBuilder,
     historian::HistorianGenerator,
@@ -10,6 +12,7 @@ where
     agent_generators: Vec<Box<dyn AgentGenerator>>,
     historian_generators: Vec<Box<dyn HistorianGenerator>>,
     game_state_iterator: G,
+    randomize_seats: bool,
 }
 
 impl<G> StandardSimulationIterator<G>
@@ -25,8 +28,20 @@ where
             agent_generators,
             historian_generators,
             game_state_iterator,
+            randomize_seats: false,
         }
     }
+
+    /// Shuffle which agent sits in which seat for every hand.
+    ///
+    /// Seats aren't equal, the button makes money while the blinds lose it.
+    /// Moving agents around removes that bias from comparisons. Each
+    /// simulation's `seating` says which agent generator is in each seat so
+    /// results can be added up per agent.
+    pub fn randomize_seats(mut self, randomize_seats: bool) -> Self {
+        self.randomize_seats = randomize_seats;
+        self
+    }
 }
 
 impl<G> StandardSimulationIterator<G>
@@ -34,10 +49,13 @@ where
     G: Iterator<Item = GameState>,
 {
     fn generate(&mut self, game_state: GameState) -> Option<HoldemSimulation> {
-        let agents = self
-            .agent_generators
+        let mut seating: Vec<usize> = (0..self.agent_generators.len()).collect();
+        if self.randomize_seats {
+            seating.shuffle(&mut rand::rng());
+        }
+        let agents = seating
             .iter()
-            .map(|g| g.generate(&game_state))
+            .map(|idx| self.agent_generators[*idx].generate(&game_state))
             .collect();
         let historians = self
             .historian_generators
@@ -49,6 +67,7 @@ where
             .agents(agents)
             .historians(historians)
             .game_state(game_state)
+            .seating(seating)
             .build()
             .ok()
     }
@@ -72,7 +91,7 @@ where
 #[cfg(test)]
 mod tests {
     use crate::arena::{
-        GameState, agent::FoldingAgentGenerator, game_state::CloneGameStateGenerator,
+        Chips, GameState, agent::FoldingAgentGenerator, game_state::CloneGameStateGenerator,
     };
 
     use super::*;
@@ -84,8 +103,9 @@ mod tests {
             Box::<FoldingAgentGenerator>::default(),
             Box::<FoldingAgentGenerator>::default(),
         ];
-        let stacks = vec![100.0; 3];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 3];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let mut sim_gen = StandardSimulationIterator::new(
             generators,
             vec![],
@@ -96,4 +116,42 @@ mod tests {
             .next()
             .expect("There should always be a first simulation");
     }
+
+    #[test]
+    fn test_randomize_seats() {
+        let generators: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<FoldingAgentGenerator>::default(),
+            Box::<FoldingAgentGenerator>::default(),
+            Box::<FoldingAgentGenerator>::default(),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let sim_gen = StandardSimulationIterator::new(
+            generators,
+            vec![],
+            CloneGameStateGenerator::new(game_state),
+        )
+        .randomize_seats(true);
+
+        let num_hands = 3000;
+        // counts[agent][seat]
+        let mut counts = [[0; 3]; 3];
+        for sim in sim_gen.take(num_hands) {
+            for (seat, agent) in sim.seating.iter().enumerate() {
+                counts[*agent][seat] += 1;
+            }
+        }
+
+        for agent_counts in &counts {
+            for count in agent_counts {
+                let freq = f64::from(*count) / num_hands as f64;
+                assert!((freq - 1.0 / 3.0).abs() < 0.05, "{counts:?}");
+            }
+        }
+    }
 }
@@ -1,9 +1,12 @@
 use tracing::{event, trace_span};
 
 use crate::arena::{
-    GameState, agent::AgentGenerator, errors::HoldemSimulationError, historian::HistorianGenerator,
+    Chips, GameState, agent::AgentGenerator, errors::HoldemSimulationError,
+    historian::HistorianGenerator,
 };
 
+use super::BlindSchedule;
+
 /// A `SingleTableTournament` is a tournament that has multiple agents
 /// playing holdem poker at a single table. The tournament is played
 /// until a single agent has all the money.
@@ -14,6 +17,7 @@ pub struct SingleTableTournamentBuilder {
     agent_generators: Option<Vec<Box<dyn AgentGenerator>>>,
     historian_generators: Option<Vec<Box<dyn HistorianGenerator>>>,
     starting_game_state: Option<GameState>,
+    blind_schedule: Option<BlindSchedule>,
     panic_on_historian_error: bool,
 }
 
@@ -24,18 +28,19 @@ pub struct SingleTableTournamentBuilder {
 #[derive(Debug, Clone)]
 pub struct TournamentResults {
     places: Vec<usize>,
-    max_stacks: Vec<f32>,
+    max_stacks: Vec<Chips>,
     rounds: usize,
 }
 pub struct SingleTableTournament {
     agent_generators: Vec<Box<dyn AgentGenerator>>,
     historian_generators: Vec<Box<dyn HistorianGenerator>>,
     starting_game_state: GameState,
+    blind_schedule: Option<BlindSchedule>,
     panic_on_historian_error: bool,
     // TODO should this include payouts?
 }
 impl TournamentResults {
-    pub fn new(starting_stacks: &[f32]) -> Self {
+    pub fn new(starting_stacks: &[Chips]) -> Self {
         TournamentResults {
             places: vec![0; starting_stacks.len()],
             max_stacks: starting_stacks.to_vec(),
@@ -44,7 +49,7 @@ impl TournamentResults {
     }
 
     /// Update the max stacks for each player
-    pub fn update_max(&mut self, stacks: &[f32]) {
+    pub fn update_max(&mut self, stacks: &[Chips]) {
         self.rounds += 1;
         for (idx, stack) in stacks.iter().enumerate() {
             if *stack > self.max_stacks[idx] {
@@ -68,9 +73,17 @@ impl TournamentResults {
         self.rounds
     }
 
-    pub fn max_stacks(&self) -> &[f32] {
+    pub fn max_stacks(&self) -> &[Chips] {
         &self.max_stacks
     }
+
+    /// The agents in the order they finished, from the winner to the first
+    /// agent to bust out.
+    pub fn finishing_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.places.len()).collect();
+        order.sort_by_key(|idx| self.places[*idx]);
+        order
+    }
 }
 
 impl SingleTableTournamentBuilder {
@@ -97,6 +110,14 @@ impl SingleTableTournamentBuilder {
         self
     }
 
+    /// Sets the blinds to play at as the tournament goes on. Without a
+    /// schedule the blinds from the starting game state are used for every
+    /// hand.
+    pub fn blind_schedule(mut self, blind_schedule: BlindSchedule) -> Self {
+        self.blind_schedule = Some(blind_schedule);
+        self
+    }
+
     /// Sets whether the underlying `HoldemSimulation` should panic if a
     /// historian errors.
     pub fn panic_on_historian_error(mut self, panic_on_historian_error: bool) -> Self {
@@ -110,9 +131,19 @@ impl SingleTableTournamentBuilder {
         let agent_builders = self
             .agent_generators
             .ok_or(HoldemSimulationError::NeedAgents)?;
-        let starting_game_state = self
+        let mut starting_game_state = self
             .starting_game_state
             .ok_or(HoldemSimulationError::NeedGameState)?;
+        if let Some(schedule) = &self.blind_schedule {
+            let level = schedule.level(0);
+            starting_game_state = GameState::new_starting(
+                starting_game_state.stacks,
+                level.big_blind,
+                level.small_blind,
+                level.ante,
+                starting_game_state.dealer_idx,
+            );
+        }
         // Historians we default to the empty list
         let historian_builders = self.historian_generators.unwrap_or_default();
         // Return everything
@@ -120,6 +151,7 @@ impl SingleTableTournamentBuilder {
             agent_generators: agent_builders,
             historian_generators: historian_builders,
             starting_game_state,
+            blind_schedule: self.blind_schedule,
             panic_on_historian_error: self.panic_on_historian_error,
         })
     }
@@ -175,8 +207,8 @@ impl SingleTableTournament {
                 .stacks
                 .iter()
                 .enumerate()
-                .filter(|(_, stack)| **stack == 0.0)
-                .filter(|(idx, _)| sim.game_state.starting_stacks[*idx] != 0.0)
+                .filter(|(_, stack)| **stack == Chips::ZERO)
+                .filter(|(idx, _)| sim.game_state.starting_stacks[*idx] != Chips::ZERO)
                 .map(|(idx, _)| idx)
                 .collect::<Vec<_>>();
 
@@ -199,20 +231,16 @@ impl SingleTableTournament {
                 results.set_place(idx, place);
                 place -= 1;
             }
-            // Move the dealer button
-            // Find the next player with a stack
-            let mut dealer_idx = (sim.game_state.dealer_idx + 1) % sim.game_state.stacks.len();
-            while sim.game_state.stacks[dealer_idx] == 0.0 {
-                dealer_idx = (dealer_idx + 1) % sim.game_state.stacks.len();
+            // Move the button and the blinds on to the next hand.
+            game_state = sim.game_state.next_hand(sim.game_state.stacks.clone());
+
+            // Blinds for the next hand come from the schedule if there is one.
+            if let Some(schedule) = &self.blind_schedule {
+                let level = schedule.level(results.rounds());
+                game_state.big_blind = level.big_blind;
+                game_state.small_blind = level.small_blind;
+                game_state.ante = level.ante;
             }
-
-            game_state = GameState::new_starting(
-                sim.game_state.stacks,
-                sim.game_state.big_blind,
-                sim.game_state.small_blind,
-                sim.game_state.ante,
-                dealer_idx,
-            );
         }
 
         // Assign the winner
@@ -221,7 +249,7 @@ impl SingleTableTournament {
                 .stacks
                 .iter()
                 .enumerate()
-                .filter(|(_, stack)| **stack > 0.0)
+                .filter(|(_, stack)| **stack > Chips::ZERO)
                 .map(|(idx, _)| idx)
                 .collect();
 
@@ -240,20 +268,42 @@ impl SingleTableTournament {
 
 #[cfg(test)]
 mod tests {
-    use crate::arena::agent::{AllInAgentGenerator, FoldingAgentGenerator};
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::arena::{
+        Historian,
+        agent::{AllInAgentGenerator, CallingAgentGenerator, FoldingAgentGenerator},
+        competition::BlindLevel,
+        historian::NullHistorian,
+    };
 
     use super::*;
 
+    /// Remembers the blinds of every hand that's played.
+    struct BlindsSeen(Rc<RefCell<Vec<(Chips, Chips, Chips)>>>);
+
+    impl HistorianGenerator for BlindsSeen {
+        fn generate(&self, game_state: &GameState) -> Box<dyn Historian> {
+            self.0.borrow_mut().push((
+                game_state.small_blind,
+                game_state.big_blind,
+                game_state.ante,
+            ));
+            Box::new(NullHistorian)
+        }
+    }
+
     #[test]
     fn test_all_in() {
-        let stacks = vec![50.0; 4];
+        let stacks = vec![Chips::new(50); 4];
         let gens: Vec<Box<dyn AgentGenerator>> = vec![
             Box::<AllInAgentGenerator>::default(),
             Box::<AllInAgentGenerator>::default(),
             Box::<AllInAgentGenerator>::default(),
             Box::<AllInAgentGenerator>::default(),
         ];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 1.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::new(1), 0);
         let tournament = SingleTableTournamentBuilder::default()
             .agent_generators(gens)
             .starting_game_state(game_state)
@@ -270,7 +320,7 @@ mod tests {
 
     #[test]
     fn test_headsup_tournament_folding_never_wins() {
-        let stacks = vec![50.0; 4];
+        let stacks = vec![Chips::new(50); 4];
 
         // The all in agent always raises all in on preflop betting.
         // The Folding Agents will then fold to the bet.
@@ -286,7 +336,8 @@ mod tests {
             Box::<FoldingAgentGenerator>::default(),
         ];
 
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 1.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::new(1), 0);
 
         let tournament = SingleTableTournamentBuilder::default()
             .agent_generators(agent_gens)
@@ -303,4 +354,48 @@ mod tests {
         assert!(results.places()[1] > 1);
         assert!(results.places()[2] > 1);
     }
+
+    #[test]
+    fn test_blinds_increase_with_schedule() {
+        let seen = Rc::new(RefCell::new(vec![]));
+        let schedule = BlindSchedule::new(vec![
+            BlindLevel::new(3, Chips::new(1), Chips::new(2), Chips::ZERO),
+            BlindLevel::new(3, Chips::new(2), Chips::new(4), Chips::new(1)),
+            BlindLevel::new(1, Chips::new(50), Chips::new(100), Chips::new(10)),
+        ]);
+        // Calling agents check it down every hand, so the tournament lasts
+        // until the blinds get big enough.
+        let agent_gens: Vec<Box<dyn AgentGenerator>> = vec![
+            Box::<CallingAgentGenerator>::default(),
+            Box::<CallingAgentGenerator>::default(),
+            Box::<CallingAgentGenerator>::default(),
+        ];
+        let tournament = SingleTableTournamentBuilder::default()
+            .agent_generators(agent_gens)
+            .historian_generators(vec![Box::new(BlindsSeen(seen.clone()))])
+            .starting_game_state(GameState::new_starting(
+                vec![Chips::new(200); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .blind_schedule(schedule)
+            .build()
+            .unwrap();
+        let results = tournament.run().unwrap();
+
+        let seen = seen.borrow();
+        assert_eq!(results.rounds(), seen.len());
+        assert!(seen.len() > 6);
+        let blinds = |sb, bb, ante| (Chips::new(sb), Chips::new(bb), Chips::new(ante));
+        assert_eq!(&[blinds(1, 2, 0); 3], &seen[..3]);
+        assert_eq!(&[blinds(2, 4, 1); 3], &seen[3..6]);
+        assert!(seen[6..].iter().all(|b| *b == blinds(50, 100, 10)));
+
+        let order = results.finishing_order();
+        assert_eq!(3, order.len());
+        assert_eq!(1, results.places()[order[0]]);
+        assert_eq!(3, results.places()[order[2]]);
+    }
 }
@@ -1,6 +1,12 @@
 use thiserror::Error;
 
+use crate::core::Card;
+
+use super::game_state::MAX_PLAYERS;
+use super::stud::MAX_STUD_PLAYERS;
+
 #[derive(Error, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameStateError {
     #[error("Invalid number for a bet")]
     BetInvalidSize,
@@ -10,11 +16,18 @@ pub enum GameStateError {
     BetSizeDoesntCallSelf,
     #[error("The raise is below the minimum raise size")]
     RaiseSizeTooSmall,
+    #[error("A short all in didn't reopen the betting for this player to raise")]
+    RaiseNotReopened,
     #[error("Can't advance after showdown")]
     CantAdvanceRound,
+    #[error("A stack or bet is negative, or there are too many chips at the table")]
+    ChipsOutOfRange,
+    #[error("A table needs from 2 to {max} players, not {0}", max = MAX_PLAYERS)]
+    PlayerCount(usize),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HoldemSimulationError {
     #[error("Builder needs a game state")]
     NeedGameState,
@@ -22,8 +35,49 @@ pub enum HoldemSimulationError {
     #[error("Builder needs agents")]
     NeedAgents,
 
+    #[error("Builder needs starting stacks")]
+    NeedStacks,
+
+    #[error("Builder needs the betting limits")]
+    NeedLimits,
+
     #[error("Expected GameState to contain a winner (agent with all the money)")]
     NoWinner,
+
+    #[error(transparent)]
+    GameState(#[from] GameStateError),
+
+    #[error("Stacked card {0} is stacked twice or was already dealt")]
+    StackedCardUnavailable(Card),
+
+    #[error("There are more players than the deck can deal to")]
+    TooManyPlayers,
+
+    #[error("Stud needs from 2 to {max} players, not {0}", max = MAX_STUD_PLAYERS)]
+    StudPlayerCount(usize),
+
+    #[error("The simulation reached its limit on agent actions")]
+    StepLimit,
+
+    #[error("Blind position {0} isn't a seated player")]
+    InvalidBlindPosition(usize),
+
+    #[error("The straddle has to be at least the big blind")]
+    StraddleTooSmall,
+
+    #[error("The deck can't deal the board {0} times")]
+    TooManyRunouts(u8),
+
+    #[error("Expected {0} agents, one for each seat")]
+    AgentCount(usize),
+
+    #[error("Seating {0} doesn't seat every agent exactly once")]
+    InvalidSeating(usize),
+
+    /// The deck dealt a card that's already in play, or ran out of cards.
+    /// `None` means it ran out.
+    #[error("The deck dealt {0:?}, which is already in play or missing")]
+    DeckIntegrity(Option<Card>),
 }
 
 #[derive(Error, Debug)]
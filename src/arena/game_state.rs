@@ -3,9 +3,48 @@ use std::fmt::Display;
 
 use rand::{Rng, rng};
 
-use crate::core::{Card, Hand, PlayerBitSet};
+use crate::core::{Card, Hand, PlayerBitSet, Rank, Rankable};
+use crate::holdem::MonteCarloGame;
 
+use super::Chips;
+use super::action::ForcedBetPayload;
+use super::betting::{AnteStyle, BettingStructure};
 use super::errors::GameStateError;
+use super::rake::Rake;
+
+/// The largest number of chips a table can hold while every chip amount
+/// still has an exact `f32` view.
+///
+/// The chips themselves are always exact. Agents and ratios like
+/// `pot_odds` work with `f32`, and above 2^24 not every whole number can
+/// be represented as one.
+pub const MAX_SAFE_CHIPS: Chips = Chips::new(16_777_216);
+
+/// The most players at a table, a full ring game. A deck could deal hold'em
+/// to as many as 23, but tables don't seat that many.
+pub const MAX_PLAYERS: usize = 10;
+
+/// Which game is being dealt. The betting is the same for all of them,
+/// they differ in the hole cards and how hands are ranked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    /// Two hole cards, the best five of the seven cards play.
+    #[default]
+    Holdem,
+    /// Four hole cards, exactly two of them and three from the board play.
+    Omaha,
+}
+
+impl Variant {
+    /// How many hole cards each player is dealt.
+    pub fn hole_cards(&self) -> usize {
+        match self {
+            Variant::Holdem => 2,
+            Variant::Omaha => 4,
+        }
+    }
+}
 
 /// The round of the game.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
@@ -77,35 +116,40 @@ impl Round {
 }
 
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RoundData {
     // Which players were active starting this round.
     pub starting_player_active: PlayerBitSet,
     pub needs_action: PlayerBitSet,
     // The minimum allowed raise.
-    pub min_raise: f32,
+    pub min_raise: Chips,
     // The value to be called.
-    pub bet: f32,
+    pub bet: Chips,
     // How much each player has put in so far.
-    pub player_bet: Vec<f32>,
+    pub player_bet: Vec<Chips>,
     // The number of times anyone has put in money
     pub total_bet_count: u8,
     // The number of times anyone has increased the bet non-forced.
     pub total_raise_count: u8,
     // The index of the next player to act.
     pub to_act_idx: usize,
+    // Which players are allowed to raise when it's their turn. A short all
+    // in doesn't reopen the betting for players that have already acted.
+    pub can_raise: PlayerBitSet,
 }
 
 impl RoundData {
-    pub fn new(num_players: usize, min_raise: f32, active: PlayerBitSet, to_act: usize) -> Self {
+    pub fn new(num_players: usize, min_raise: Chips, active: PlayerBitSet, to_act: usize) -> Self {
         RoundData {
             needs_action: active,
             starting_player_active: active,
             min_raise,
-            bet: 0.0,
-            player_bet: vec![0.0; num_players],
+            bet: Chips::ZERO,
+            player_bet: vec![Chips::ZERO; num_players],
             total_bet_count: 0,
             total_raise_count: 0,
             to_act_idx: to_act,
+            can_raise: active,
         }
     }
 
@@ -130,33 +174,34 @@ impl RoundData {
     /// # Example
     ///
     /// ```
+    /// use rs_poker::arena::Chips;
     /// use rs_poker::arena::game_state::RoundData;
     /// use rs_poker::core::PlayerBitSet;
     ///
     /// let num_players = 3;
-    /// let min_raise = 10.0;
+    /// let min_raise = Chips::new(10);
     /// let active = PlayerBitSet::new(num_players);
     ///
-    /// let player_bet = vec![0.0, 10.0, 20.0];
+    /// let player_bet = vec![Chips::new(0), Chips::new(10), Chips::new(20)];
     /// let to_act = 0;
     ///
     /// let round_data = RoundData::new_with_bets(min_raise, active, to_act, player_bet);
     ///
-    /// assert_eq!(round_data.bet, 20.0);
+    /// assert_eq!(round_data.bet, Chips::new(20));
     ///
     /// assert_eq!(round_data.total_bet_count, 2);
     ///
     /// assert_eq!(round_data.total_raise_count, 2);
     /// ```
     pub fn new_with_bets(
-        min_raise: f32,
+        min_raise: Chips,
         active: PlayerBitSet,
         to_act: usize,
-        player_bet: Vec<f32>,
+        player_bet: Vec<Chips>,
     ) -> Self {
-        let bet: f32 = player_bet.iter().fold(0.0, |acc, &x| acc.max(x));
+        let bet = player_bet.iter().copied().max().unwrap_or_default();
 
-        let total_raise_count = player_bet.iter().filter(|&&x| x > 0.0).count() as u8;
+        let total_raise_count = player_bet.iter().filter(|&&x| x > Chips::ZERO).count() as u8;
 
         RoundData {
             needs_action: active,
@@ -169,6 +214,7 @@ impl RoundData {
             // raise_count,
             total_raise_count,
             to_act_idx: to_act,
+            can_raise: active,
         }
     }
 
@@ -184,7 +230,7 @@ impl RoundData {
         }
     }
 
-    pub fn do_bet(&mut self, extra_amount: f32, is_forced: bool) {
+    pub fn do_bet(&mut self, extra_amount: Chips, is_forced: bool) {
         self.player_bet[self.to_act_idx] += extra_amount;
         self.total_bet_count += 1;
 
@@ -206,12 +252,13 @@ impl RoundData {
         self.needs_action.count()
     }
 
-    pub fn current_player_bet(&self) -> f32 {
+    pub fn current_player_bet(&self) -> Chips {
         self.player_bet[self.to_act_idx]
     }
 }
 
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameState {
     /// The number of players that started
     pub num_players: usize,
@@ -219,24 +266,29 @@ pub struct GameState {
     pub player_active: PlayerBitSet,
     pub player_all_in: PlayerBitSet,
     /// The total amount in all pots
-    pub total_pot: f32,
+    pub total_pot: Chips,
     /// How much is left in each player's stack
-    pub stacks: Vec<f32>,
+    pub stacks: Vec<Chips>,
     // The amount at the start of the game (or creation of the gamestate).
-    pub starting_stacks: Vec<f32>,
-    pub player_bet: Vec<f32>,
-    pub player_winnings: Vec<f32>,
+    pub starting_stacks: Vec<Chips>,
+    pub player_bet: Vec<Chips>,
+    pub player_winnings: Vec<Chips>,
     /// The big blind size
-    pub big_blind: f32,
+    pub big_blind: Chips,
     /// The small blind size
-    pub small_blind: f32,
+    pub small_blind: Chips,
     /// The ante size
-    pub ante: f32,
+    pub ante: Chips,
     /// The hands for each player. We keep hands
     /// even if the player is not currently active.
     pub hands: Vec<Hand>,
     /// The index of the player who's the dealer
     pub dealer_idx: usize,
+    /// The seat that posts the small blind. If no one with chips is in the
+    /// seat the small blind is dead and isn't posted.
+    pub small_blind_idx: usize,
+    /// The seat that posts the big blind.
+    pub big_blind_idx: usize,
     // What round this is currently
     pub round: Round,
     /// This is the round before we completed the game.
@@ -252,6 +304,79 @@ pub struct GameState {
     // on sim restarts.
     pub bb_posted: bool,
     pub sb_posted: bool,
+    /// The forced bets (antes and blinds) posted this hand in the order
+    /// they were posted.
+    pub forced_bets: Vec<ForcedBetPayload>,
+    /// The limits on how much can be bet.
+    pub betting_structure: BettingStructure,
+    /// Who posts the antes.
+    pub ante_style: AnteStyle,
+    /// The game being dealt.
+    pub variant: Variant,
+    /// How much the house takes from the pot.
+    pub rake: Rake,
+    /// The rake taken from this hand's pots so far.
+    pub total_rake: Chips,
+    /// The straddle posted by the player after the big blind, or zero for
+    /// no straddle.
+    pub straddle: Chips,
+    /// The last player to bet or raise in the current betting round, or
+    /// the river once the hand reaches showdown.
+    pub last_aggressor: Option<usize>,
+    /// Which betting round this is, counting from 0 for the first. In
+    /// Hold'em it goes with the round, but seven card stud bets on sixth
+    /// and seventh street in `Round::River` and only this tells them apart.
+    pub street: usize,
+}
+
+/// One of the pots at showdown. The main pot comes first, then each side
+/// pot created by a player being all in for less.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SidePot {
+    /// All the chips in this pot.
+    pub amount: Chips,
+    /// The players that can win this pot. That's every player still in the
+    /// hand that put in at least this pot's level.
+    pub eligible: PlayerBitSet,
+}
+
+/// A consolidated view of the forced bets posted at the start of a hand.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ForcedBetsSummary {
+    /// Every forced bet in the order it was posted.
+    pub forced_bets: Vec<ForcedBetPayload>,
+    /// How much each player actually posted. This can be less than the
+    /// forced amount if it put the player all in.
+    pub player_posted: Vec<Chips>,
+    /// The size of the pot once all forced bets are in.
+    pub pot: Chips,
+    /// The first player that gets to act voluntarily. `None` if every
+    /// player was put all in by the forced bets.
+    pub first_to_act_idx: Option<usize>,
+}
+
+/// What changed from one game state to a later one. See
+/// [`GameState::diff`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GameStateDiff {
+    /// Each player whose stack moved, with how much it moved by. Bets are
+    /// negative and winnings positive.
+    pub stack_changes: Vec<(usize, Chips)>,
+    /// Community cards on the later board that weren't on the earlier one.
+    pub new_board_cards: Vec<Card>,
+    /// The rounds before and after, if the round changed.
+    pub round_change: Option<(Round, Round)>,
+    /// How much the pot grew by.
+    pub pot_change: Chips,
+    /// The player to act in the later state, if that's someone new.
+    pub to_act_change: Option<usize>,
+}
+
+impl GameStateDiff {
+    /// Is there nothing to show.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl GameState {
@@ -261,11 +386,11 @@ impl GameState {
         round_data: RoundData,
         board: Vec<Card>,
         hands: Vec<Hand>,
-        stacks: Vec<f32>,
-        player_bet: Vec<f32>,
-        big_blind: f32,
-        small_blind: f32,
-        ante: f32,
+        stacks: Vec<Chips>,
+        player_bet: Vec<Chips>,
+        big_blind: Chips,
+        small_blind: Chips,
+        ante: Chips,
         dealer_idx: usize,
     ) -> Self {
         let num_players = stacks.len();
@@ -273,7 +398,7 @@ impl GameState {
         let mut player_active = PlayerBitSet::new(num_players);
         // No one is all in by default.
         let mut player_all_in = PlayerBitSet::default();
-        let mut total_pot = 0.0;
+        let mut total_pot = Chips::ZERO;
 
         stacks
             .iter()
@@ -284,8 +409,8 @@ impl GameState {
                 total_pot += *bet;
 
                 // FlatHandle the case that they have no money left
-                if *stack <= 0.0 {
-                    if *bet > 0.0 && round != Round::Starting {
+                if *stack <= Chips::ZERO {
+                    if *bet > Chips::ZERO && round != Round::Starting {
                         // If the player is out of money and they've put money in
                         // then they're all in.
                         player_all_in.enable(idx);
@@ -297,6 +422,9 @@ impl GameState {
                 }
             });
 
+        let (small_blind_idx, big_blind_idx) =
+            default_blind_positions(player_active, num_players, dealer_idx);
+
         GameState {
             num_players,
             starting_stacks: stacks.clone(),
@@ -307,8 +435,10 @@ impl GameState {
             player_active,
             player_all_in,
             player_bet,
-            player_winnings: vec![0.0; num_players],
+            player_winnings: vec![Chips::ZERO; num_players],
             dealer_idx,
+            small_blind_idx,
+            big_blind_idx,
             total_pot,
             hands,
             round,
@@ -319,14 +449,34 @@ impl GameState {
             // if the game is just starting.
             bb_posted: round != Round::Starting,
             sb_posted: round != Round::Starting,
+            forced_bets: vec![],
+            betting_structure: BettingStructure::NoLimit,
+            ante_style: AnteStyle::PerPlayer,
+            variant: Variant::Holdem,
+            rake: Rake::default(),
+            total_rake: Chips::ZERO,
+            straddle: Chips::ZERO,
+            last_aggressor: None,
+            street: match round {
+                Round::Flop | Round::DealTurn => 1,
+                Round::Turn | Round::DealRiver => 2,
+                Round::River | Round::Showdown | Round::Complete => 3,
+                _ => 0,
+            },
         }
     }
 
+    /// A game at the start of a hand, before the blinds are posted. There's
+    /// a seat for each of `stacks`.
+    ///
+    /// Tables have from two to [`MAX_PLAYERS`] seats. That isn't checked
+    /// here, building a simulation checks it. Use `try_new_starting` to
+    /// check it up front.
     pub fn new_starting(
-        stacks: Vec<f32>,
-        big_blind: f32,
-        small_blind: f32,
-        ante: f32,
+        stacks: Vec<Chips>,
+        big_blind: Chips,
+        small_blind: Chips,
+        ante: Chips,
         dealer_idx: usize,
     ) -> Self {
         let num_players = stacks.len();
@@ -348,7 +498,7 @@ impl GameState {
             // Current stacks
             stacks,
             // No one has bet yet. That will be handled by ante and blinds
-            vec![0.0; num_players],
+            vec![Chips::ZERO; num_players],
             big_blind,
             small_blind,
             ante,
@@ -356,6 +506,95 @@ impl GameState {
         )
     }
 
+    /// The same as `new_starting`, but returns an error unless there are
+    /// from two to [`MAX_PLAYERS`] stacks.
+    pub fn try_new_starting(
+        stacks: Vec<Chips>,
+        big_blind: Chips,
+        small_blind: Chips,
+        ante: Chips,
+        dealer_idx: usize,
+    ) -> Result<Self, GameStateError> {
+        let game_state = Self::new_starting(stacks, big_blind, small_blind, ante, dealer_idx);
+        game_state.validate_player_count()?;
+        Ok(game_state)
+    }
+
+    /// The starting state of the hand after this one, with `stacks` as the
+    /// players' stacks.
+    ///
+    /// The blinds, betting rules and rake stay the same. Positions move with a
+    /// dead button: the big blind always moves to the next player with
+    /// chips, the small blind to the seat that was the big blind, and the
+    /// button to the seat that was the small blind. That way no one skips
+    /// or pays the big blind twice when players bust, at the cost of a
+    /// dead small blind or a button on an empty seat. Once only two players
+    /// are left the button posts the small blind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, GameState};
+    ///
+    /// let game_state = GameState::new_starting(
+    ///     vec![Chips::new(100); 4],
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// assert_eq!(
+    ///     (0, 1, 2),
+    ///     (
+    ///         game_state.dealer_idx,
+    ///         game_state.small_blind_idx,
+    ///         game_state.big_blind_idx
+    ///     )
+    /// );
+    ///
+    /// // The big blind busts, so next hand the small blind is dead.
+    /// let stacks = [100, 110, 0, 190].map(Chips::new).to_vec();
+    /// let next = game_state.next_hand(stacks);
+    /// assert_eq!(
+    ///     (1, 2, 3),
+    ///     (next.dealer_idx, next.small_blind_idx, next.big_blind_idx)
+    /// );
+    /// ```
+    pub fn next_hand(&self, stacks: Vec<Chips>) -> GameState {
+        let num_players = stacks.len();
+        let next_seated = |idx: usize| {
+            (1..=num_players)
+                .map(|offset| (idx + offset) % num_players)
+                .find(|i| stacks[*i] > Chips::ZERO)
+                .unwrap_or(idx)
+        };
+        let num_seated = stacks.iter().filter(|s| **s > Chips::ZERO).count();
+
+        let big_blind_idx = next_seated(self.big_blind_idx);
+        let (dealer_idx, small_blind_idx) = if num_seated == 2 {
+            let button = next_seated(big_blind_idx);
+            (button, button)
+        } else {
+            (self.small_blind_idx, self.big_blind_idx)
+        };
+
+        let mut game_state = GameState::new_starting(
+            stacks,
+            self.big_blind,
+            self.small_blind,
+            self.ante,
+            dealer_idx,
+        );
+        game_state.small_blind_idx = small_blind_idx;
+        game_state.big_blind_idx = big_blind_idx;
+        game_state.betting_structure = self.betting_structure;
+        game_state.ante_style = self.ante_style;
+        game_state.variant = self.variant;
+        game_state.rake = self.rake;
+        game_state.straddle = self.straddle;
+        game_state
+    }
+
     pub fn num_active_players(&self) -> usize {
         self.player_active.count()
     }
@@ -368,39 +607,85 @@ impl GameState {
         self.num_active_players() == 1 || self.round == Round::Complete
     }
 
+    /// Is the hand going to showdown with no more decisions to make. That's
+    /// when at least two players are still in the hand and all of them are
+    /// all in, except at most one player that has already matched the bet.
+    /// That player has no one left to bet against, so what's left of the
+    /// board can be run out.
+    pub fn is_all_in_showdown(&self) -> bool {
+        let in_hand = self.player_active | self.player_all_in;
+        if self.round == Round::Complete || in_hand.count() < 2 {
+            return false;
+        }
+        let mut active = self.player_active.ones();
+        match (active.next(), active.next()) {
+            (None, _) => true,
+            (Some(idx), None) => self.round_data.player_bet[idx] >= self.round_data.bet,
+            _ => false,
+        }
+    }
+
     pub fn to_act_idx(&self) -> usize {
         self.round_data.to_act_idx
     }
 
-    pub fn current_player_stack(&self) -> f32 {
-        *self.stacks.get(self.to_act_idx()).unwrap_or(&0.0)
+    pub fn current_player_stack(&self) -> Chips {
+        self.stacks
+            .get(self.to_act_idx())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Can the player to act raise, or has the betting only been reopened
+    /// for them by a short all in so that they can only call or fold.
+    pub fn current_player_can_raise(&self) -> bool {
+        self.round_data.can_raise.get(self.to_act_idx())
     }
 
-    pub fn current_player_starting_stack(&self) -> f32 {
-        *self.starting_stacks.get(self.to_act_idx()).unwrap_or(&0.0)
+    pub fn current_player_starting_stack(&self) -> Chips {
+        self.starting_stacks
+            .get(self.to_act_idx())
+            .copied()
+            .unwrap_or_default()
     }
 
-    pub fn current_round_current_player_bet(&self) -> f32 {
-        *self
-            .round_data
+    pub fn current_round_current_player_bet(&self) -> Chips {
+        self.round_data
             .player_bet
             .get(self.to_act_idx())
-            .unwrap_or(&0.0)
+            .copied()
+            .unwrap_or_default()
     }
 
-    pub fn current_round_bet(&self) -> f32 {
+    pub fn current_round_bet(&self) -> Chips {
         self.round_data.bet
     }
 
-    pub fn current_round_player_bet(&self, idx: usize) -> f32 {
-        self.round_data.player_bet.get(idx).copied().unwrap_or(0.0)
+    pub fn current_round_player_bet(&self, idx: usize) -> Chips {
+        self.round_data
+            .player_bet
+            .get(idx)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// How much player `idx` has put in on this betting round. Starts from
+    /// zero on every street.
+    pub fn committed_this_round(&self, idx: usize) -> Chips {
+        self.current_round_player_bet(idx)
+    }
+
+    /// How much player `idx` has put in over the whole hand, antes and
+    /// blinds included.
+    pub fn total_committed(&self, idx: usize) -> Chips {
+        self.player_bet.get(idx).copied().unwrap_or_default()
     }
 
     pub fn current_round_num_active_players(&self) -> usize {
         self.round_data.num_players_need_action()
     }
 
-    pub fn current_round_min_raise(&self) -> f32 {
+    pub fn current_round_min_raise(&self) -> Chips {
         self.round_data.min_raise
     }
 
@@ -417,6 +702,10 @@ impl GameState {
         self.round_before = self.round;
 
         self.round = self.round.advance();
+        if matches!(self.round, Round::Flop | Round::Turn | Round::River) {
+            self.last_aggressor = None;
+            self.street += 1;
+        }
 
         let mut round_data = RoundData::new(
             self.num_players,
@@ -459,7 +748,7 @@ impl GameState {
         self.round_data.advance_action();
     }
 
-    pub fn do_bet(&mut self, amount: f32, is_forced: bool) -> Result<f32, GameStateError> {
+    pub fn do_bet(&mut self, amount: Chips, is_forced: bool) -> Result<Chips, GameStateError> {
         // Which player is next to act
         let idx = self.to_act_idx();
 
@@ -480,6 +769,8 @@ impl GameState {
         };
 
         let prev_bet = self.round_data.bet;
+        let prev_min_raise = self.round_data.min_raise;
+        let prev_needs_action = self.round_data.needs_action;
         // At this point we start making changes.
         // Take the money out.
         self.stacks[idx] -= extra_amount;
@@ -491,8 +782,20 @@ impl GameState {
         self.total_pot += extra_amount;
 
         let is_betting_reopened = prev_bet < self.round_data.bet;
+        if is_betting_reopened && !is_forced {
+            self.last_aggressor = Some(idx);
+        }
 
         if is_betting_reopened {
+            // A full raise lets everyone raise again. An all in for less than
+            // the minimum raise only has to be called by the players that
+            // already acted, they can't raise on top of it. Each short all in
+            // is judged on its own.
+            if is_forced || self.round_data.bet - prev_bet >= prev_min_raise {
+                self.round_data.can_raise = self.player_active;
+            } else {
+                self.round_data.can_raise = self.round_data.can_raise & prev_needs_action;
+            }
             // This is a new max bet. We need to reset who can act in the round
             self.round_data.needs_action = self.player_active;
         }
@@ -503,7 +806,7 @@ impl GameState {
         }
 
         // We're out and can't continue
-        if self.stacks[idx] <= 0.0 {
+        if self.stacks[idx] <= Chips::ZERO {
             // Keep track of who's still active.
             self.player_active.disable(idx);
             // Keep track of going all in. We'll use that later on
@@ -520,7 +823,7 @@ impl GameState {
         Ok(extra_amount)
     }
 
-    pub fn award(&mut self, player_idx: usize, amount: f32) {
+    pub fn award(&mut self, player_idx: usize, amount: Chips) {
         self.stacks[player_idx] += amount;
         self.player_winnings[player_idx] += amount;
     }
@@ -531,26 +834,495 @@ impl GameState {
     ///
     /// # Arguments
     /// * `player_idx` - The index of the player to get the reward for.
-    pub fn player_reward(&self, player_idx: usize) -> f32 {
+    pub fn player_reward(&self, player_idx: usize) -> Chips {
         // The reward is the change in stack from the start of the game
         // to the end of the game.
         self.stacks[player_idx] - self.starting_stacks[player_idx]
     }
 
-    fn validate_forced_bet_amount(&self, amount: f32) -> f32 {
+    /// The rank of a player's hand with the cards dealt so far, using the
+    /// rules of `variant`. An Omaha hand before the flop is ranked on its
+    /// hole cards alone.
+    pub fn rank_hand(&self, player_idx: usize) -> Rank {
+        let hand = &self.hands[player_idx];
+        match self.variant {
+            Variant::Omaha if self.board.len() >= 3 => {
+                let mut hole = *hand;
+                for card in &self.board {
+                    hole.remove(card);
+                }
+                hole.rank_omaha(&self.board)
+            }
+            _ => hand.partial_rank(),
+        }
+    }
+
+    /// Split everything that's been bet this hand into the main pot and side
+    /// pots, ordered from the main pot out.
+    ///
+    /// Each distinct amount committed by a player still in the hand, either
+    /// active or all in, starts a new pot. Chips from players that folded
+    /// go into the pots they reached but they aren't eligible for any of
+    /// them. If the biggest bet wasn't called the last pot has just that
+    /// player eligible, it's their chips being returned. If only players
+    /// that folded put chips in, it's all one pot for everyone still in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, GameState};
+    ///
+    /// let mut game_state = GameState::new_starting(
+    ///     [50, 150, 200].map(Chips::new).to_vec(),
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     2,
+    /// );
+    /// game_state.player_bet = [50, 150, 150].map(Chips::new).to_vec();
+    /// game_state.player_all_in.enable(0);
+    /// game_state.player_all_in.enable(1);
+    /// game_state.player_active.disable(0);
+    /// game_state.player_active.disable(1);
+    ///
+    /// let pots = game_state.side_pots();
+    /// assert_eq!(2, pots.len());
+    /// assert_eq!(Chips::new(150), pots[0].amount);
+    /// assert_eq!(3, pots[0].eligible.count());
+    /// assert_eq!(Chips::new(200), pots[1].amount);
+    /// assert!(!pots[1].eligible.get(0));
+    /// ```
+    pub fn side_pots(&self) -> Vec<SidePot> {
+        let contenders = self.player_active | self.player_all_in;
+
+        let mut levels: Vec<Chips> = contenders
+            .ones()
+            .map(|idx| self.player_bet[idx])
+            .filter(|bet| *bet > Chips::ZERO)
+            .collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut pots: Vec<SidePot> = Vec::with_capacity(levels.len());
+        let mut prev_level = Chips::ZERO;
+        for level in levels {
+            let amount: Chips = self
+                .player_bet
+                .iter()
+                .map(|bet| (*bet).min(level) - (*bet).min(prev_level))
+                .sum();
+            let mut eligible = PlayerBitSet::new(0);
+            for idx in contenders.ones() {
+                if self.player_bet[idx] >= level {
+                    eligible.enable(idx);
+                }
+            }
+            pots.push(SidePot { amount, eligible });
+            prev_level = level;
+        }
+
+        // Folded players can't have put in more than the players still in
+        // the hand, but if they somehow did keep those chips in the last pot.
+        let leftover: Chips = self
+            .player_bet
+            .iter()
+            .map(|bet| (*bet - prev_level).max(Chips::ZERO))
+            .sum();
+        match pots.last_mut() {
+            Some(last) => last.amount += leftover,
+            None if leftover > Chips::ZERO => pots.push(SidePot {
+                amount: leftover,
+                eligible: contenders,
+            }),
+            None => {}
+        }
+
+        pots
+    }
+
+    /// Every chip at the table, in stacks and in the pot.
+    pub fn total_chips(&self) -> Chips {
+        self.stacks
+            .iter()
+            .chain(self.player_bet.iter())
+            .copied()
+            .sum()
+    }
+
+    /// Check that no stack or bet is negative and that the chips at the
+    /// table are few enough for their `f32` views to stay exact. See
+    /// [`MAX_SAFE_CHIPS`].
+    pub fn validate_chip_range(&self) -> Result<(), GameStateError> {
+        let any_negative = self
+            .stacks
+            .iter()
+            .chain(self.player_bet.iter())
+            .any(|c| *c < Chips::ZERO);
+        if any_negative || self.total_chips() > MAX_SAFE_CHIPS {
+            Err(GameStateError::ChipsOutOfRange)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that there are between two and [`MAX_PLAYERS`] seats.
+    pub fn validate_player_count(&self) -> Result<(), GameStateError> {
+        if (2..=MAX_PLAYERS).contains(&self.num_players) {
+            Ok(())
+        } else {
+            Err(GameStateError::PlayerCount(self.num_players))
+        }
+    }
+
+    /// How many seats after the button `idx` sits. The button is `0`, the
+    /// seat after it `1` and so on, so at a full table the cutoff is
+    /// `num_players - 1`. Seats are counted whether or not a player is in
+    /// them.
+    pub fn position_relative_to_button(&self, idx: usize) -> usize {
+        (idx + self.num_players - self.dealer_idx) % self.num_players
+    }
+
+    /// Where a player stands in line for odd chips. When a pot doesn't split
+    /// evenly the extra chips go to the worst position first, starting with
+    /// the first seat left of the button, so the button is last.
+    pub fn odd_chip_order(&self, idx: usize) -> usize {
+        (self.position_relative_to_button(idx) + self.num_players - 1) % self.num_players
+    }
+
+    /// The players that still have to act in the current round.
+    pub fn players_to_act(&self) -> PlayerBitSet {
+        self.round_data.needs_action & self.player_active
+    }
+
+    /// Does `idx` act last after the flop among the players still in the
+    /// hand. Players that are all in count since they'd act if they could.
+    pub fn is_in_position(&self, idx: usize) -> bool {
+        // After the flop the seat after the button acts first and the
+        // button acts last.
+        let acts_after = |idx: usize| {
+            (self.position_relative_to_button(idx) + self.num_players - 1) % self.num_players
+        };
+        let in_hand = self.player_active | self.player_all_in;
+        in_hand.get(idx)
+            && in_hand
+                .ones()
+                .all(|other| acts_after(other) <= acts_after(idx))
+    }
+
+    /// What changed going from this game state to `other`, usually a later
+    /// state of the same hand. Players are matched up by seat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, GameState};
+    ///
+    /// let before = GameState::new_starting(
+    ///     vec![Chips::new(100); 2],
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// let mut after = before.clone();
+    /// after.advance_round();
+    /// let diff = before.diff(&after);
+    /// assert!(diff.round_change.is_some());
+    /// assert!(diff.stack_changes.is_empty());
+    /// ```
+    pub fn diff(&self, other: &GameState) -> GameStateDiff {
+        let stack_changes = self
+            .stacks
+            .iter()
+            .zip(other.stacks.iter())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(idx, (before, after))| (idx, *after - *before))
+            .collect();
+        let new_board_cards = other
+            .board
+            .iter()
+            .filter(|card| !self.board.contains(card))
+            .copied()
+            .collect();
+        let round_change = (self.round != other.round).then_some((self.round, other.round));
+        let to_act_change = (self.to_act_idx() != other.to_act_idx() || round_change.is_some())
+            .then(|| other.to_act_idx());
+
+        GameStateDiff {
+            stack_changes,
+            new_board_cards,
+            round_change,
+            pot_change: other.total_pot - self.total_pot,
+            to_act_change,
+        }
+    }
+
+    /// How much more the player has to put in to call the current bet,
+    /// capped at what's left in their stack. Zero when they've already
+    /// matched the bet, for example as the aggressor.
+    pub fn amount_to_call(&self, idx: usize) -> Chips {
+        (self.current_round_bet() - self.current_round_player_bet(idx))
+            .clamp(Chips::ZERO, self.stacks[idx])
+    }
+
+    /// The share of the final pot a call would be, `call / (pot + call)`.
+    /// A call is profitable when the chance of winning is higher than this.
+    /// Zero when there's nothing to call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, GameState};
+    ///
+    /// let mut game_state = GameState::new_starting(
+    ///     vec![Chips::new(100); 2],
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// game_state.advance_round();
+    /// game_state.advance_round();
+    /// game_state.advance_round();
+    /// game_state.do_bet(Chips::new(5), true).unwrap();
+    /// game_state.do_bet(Chips::new(10), true).unwrap();
+    ///
+    /// // Heads up the button posts the small blind, and has 5 to call
+    /// // into a pot of 15.
+    /// assert_eq!(Chips::new(5), game_state.amount_to_call(0));
+    /// assert_eq!(0.25, game_state.pot_odds(0));
+    /// ```
+    pub fn pot_odds(&self, idx: usize) -> f32 {
+        let call = self.amount_to_call(idx);
+        if call <= Chips::ZERO {
+            return 0.0;
+        }
+        call.as_f32() / (self.total_pot + call).as_f32()
+    }
+
+    /// The community cards dealt so far, in the order they were dealt.
+    pub fn board(&self) -> &[Card] {
+        &self.board
+    }
+
+    /// The cards player `idx` can see, their hole cards followed by the
+    /// board in the order it was dealt. These are the cards an agent can
+    /// take out of the deck when working out what others might hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, GameState};
+    /// use rs_poker::core::Hand;
+    ///
+    /// let mut game_state = GameState::new_starting(
+    ///     vec![Chips::new(100); 2],
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// game_state.board = Hand::new_from_str("Kh7c2d").unwrap().iter().collect();
+    /// game_state.hands[0] = Hand::new_from_str("AsAdKh7c2d").unwrap();
+    ///
+    /// let known = game_state.known_cards(0);
+    /// assert_eq!(5, known.len());
+    /// assert_eq!(game_state.board(), &known[2..]);
+    /// ```
+    pub fn known_cards(&self, idx: usize) -> Vec<Card> {
+        // Every hand includes the board.
+        let mut hole = self.hands[idx];
+        for card in &self.board {
+            hole.remove(card);
+        }
+        hole.iter().chain(self.board.iter().copied()).collect()
+    }
+
+    /// The share of the pot player `idx` is expected to win against
+    /// `opponents` random hands, dealing out the rest of the board `sims`
+    /// times. The player's hole cards and the board so far come from the
+    /// game state.
+    ///
+    /// This simulates hold'em hands. Returns 0.0 if the player's cards
+    /// can't be simulated, for example if the hand has too many cards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use rs_poker::arena::{Chips, GameState};
+    /// use rs_poker::core::Hand;
+    ///
+    /// let mut game_state = GameState::new_starting(
+    ///     vec![Chips::new(100); 2],
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// game_state.hands[0] = Hand::new_from_str("AsAd").unwrap();
+    ///
+    /// let mut rng = StdRng::seed_from_u64(318);
+    /// let equity = game_state.hero_equity(0, 1, 2_000, &mut rng);
+    /// assert!(equity > 0.8 && equity < 0.9);
+    /// ```
+    pub fn hero_equity<R: Rng>(
+        &self,
+        idx: usize,
+        opponents: usize,
+        sims: usize,
+        rng: &mut R,
+    ) -> f32 {
+        // Every hand includes the board.
+        let mut board = Hand::new();
+        board.extend(self.board.iter().cloned());
+
+        let mut hands = vec![self.hands[idx]];
+        hands.extend(std::iter::repeat_n(board, opponents));
+        match MonteCarloGame::new_with_rng(hands, rng) {
+            Ok(mut monte) => monte.estimate_equity(sims)[0],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// The order players still in the hand show down in. The last player to
+    /// bet or raise on the final betting round shows first. If everyone
+    /// checked it's the first player left of the button. The rest follow
+    /// around the table.
+    pub fn showdown_order(&self) -> Vec<usize> {
+        let in_hand = self.player_active | self.player_all_in;
+        let first = self
+            .last_aggressor
+            .filter(|idx| in_hand.get(*idx))
+            .unwrap_or(self.dealer_idx + 1);
+        (0..self.num_players)
+            .map(|offset| (first + offset) % self.num_players)
+            .filter(|idx| in_hand.get(*idx))
+            .collect()
+    }
+
+    /// The most that players `a` and `b` can still win or lose from each
+    /// other, the smaller of their stacks behind. A player that's all in
+    /// has nothing behind, so it's zero.
+    pub fn effective_stack(&self, a: usize, b: usize) -> Chips {
+        self.stacks[a].min(self.stacks[b])
+    }
+
+    /// The stack to pot ratio for player `idx`. That's the player's
+    /// effective stack against the deepest other player who can still bet,
+    /// divided by the total pot.
+    ///
+    /// Players who are all in or have folded can't bet any more, so if no
+    /// one else can the ratio is zero. It's infinite before anything is in
+    /// the pot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, GameState};
+    ///
+    /// let mut game_state = GameState::new_starting(
+    ///     [100, 60, 200].map(Chips::new).to_vec(),
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// game_state.total_pot = Chips::new(20);
+    /// // The 100 chip stack is covered by the 200 chip stack.
+    /// assert_eq!(5.0, game_state.spr(0));
+    /// assert_eq!(Chips::new(60), game_state.effective_stack(1, 2));
+    /// ```
+    pub fn spr(&self, idx: usize) -> f32 {
+        let effective = self
+            .player_active
+            .ones()
+            .filter(|other| *other != idx)
+            .map(|other| self.effective_stack(idx, other))
+            .max()
+            .unwrap_or_default();
+        if self.total_pot <= Chips::ZERO {
+            return if effective > Chips::ZERO {
+                f32::INFINITY
+            } else {
+                0.0
+            };
+        }
+        effective.as_f32() / self.total_pot.as_f32()
+    }
+
+    /// The player's current stack measured in big blinds.
+    pub fn stack_in_bb(&self, idx: usize) -> f32 {
+        self.stacks[idx].as_f32() / self.big_blind.as_f32()
+    }
+
+    /// Harrington's M-ratio for a player. That's how many orbits the
+    /// player's stack would last if they posted blinds and antes without
+    /// playing a hand.
+    ///
+    /// Antes are counted for every player that was dealt into the hand, or
+    /// once with a big blind ante.
+    pub fn m_ratio(&self, idx: usize) -> f32 {
+        let antes = match self.ante_style {
+            AnteStyle::PerPlayer => self
+                .starting_stacks
+                .iter()
+                .filter(|s| **s > Chips::ZERO)
+                .count(),
+            AnteStyle::BigBlindAnte => 1,
+        };
+        let orbit_cost = self.small_blind + self.big_blind + self.ante * antes as i64;
+        self.stacks[idx].as_f32() / orbit_cost.as_f32()
+    }
+
+    /// Summarize the antes and blinds posted this hand.
+    ///
+    /// Returns `None` until the blinds have been posted, or if the game
+    /// state was created part way through a hand without the forced bets.
+    pub fn forced_bets_summary(&self) -> Option<ForcedBetsSummary> {
+        if !self.bb_posted || self.forced_bets.is_empty() {
+            return None;
+        }
+
+        let mut player_posted = vec![Chips::ZERO; self.num_players];
+        for forced_bet in &self.forced_bets {
+            // The payload holds the forced amount, so work out what was
+            // really posted from the stack left after posting.
+            let idx = forced_bet.idx;
+            let before = self.starting_stacks[idx] - player_posted[idx];
+            player_posted[idx] += before - forced_bet.player_stack;
+        }
+        let pot = player_posted.iter().sum();
+
+        // Action starts with the next player after the last forced bet who
+        // still has chips behind.
+        let last_idx = self.forced_bets.last().map(|f| f.idx).unwrap_or(0);
+        let first_to_act_idx = (1..=self.num_players)
+            .map(|offset| (last_idx + offset) % self.num_players)
+            .find(|idx| self.starting_stacks[*idx] - player_posted[*idx] > Chips::ZERO);
+
+        Some(ForcedBetsSummary {
+            forced_bets: self.forced_bets.clone(),
+            player_posted,
+            pot,
+            first_to_act_idx,
+        })
+    }
+
+    fn validate_forced_bet_amount(&self, amount: Chips) -> Chips {
         // Which player is next to act. Map the optional into the to_act_index or 0.
         let idx = self.to_act_idx();
 
         self.stacks[idx].min(amount)
     }
 
-    fn validate_bet_amount(&self, amount: f32) -> Result<f32, GameStateError> {
+    fn validate_bet_amount(&self, amount: Chips) -> Result<Chips, GameStateError> {
         // Which player is next to act
         let idx = self.to_act_idx();
 
-        if amount.is_sign_negative() || amount.is_nan() {
+        if amount < Chips::ZERO {
             // You can't bet negative numbers.
-            // You can't be a NaN.
             Err(GameStateError::BetInvalidSize)
         } else if self.round_data.player_bet[idx] > amount {
             // We've already bet more than this. No takes backs.
@@ -565,12 +1337,15 @@ impl GameState {
             let capped_new_player_bet = self.round_data.player_bet[idx] + capped_extra;
             let current_bet = self.round_data.bet;
             // How much this is a raise.
-            let raise = (capped_new_player_bet - current_bet).max(0.0);
+            let raise = (capped_new_player_bet - current_bet).max(Chips::ZERO);
             let is_all_in = capped_extra == self.stacks[idx];
-            let is_raise = raise > 0.0;
+            let is_raise = raise > Chips::ZERO;
             if capped_new_player_bet < self.round_data.bet && !is_all_in {
                 // If we're not even calling and it's not an all in.
                 Err(GameStateError::BetSizeDoesntCall)
+            } else if is_raise && !self.round_data.can_raise.get(idx) {
+                // Only a short all in has happened since we last acted.
+                Err(GameStateError::RaiseNotReopened)
             } else if is_raise && !is_all_in && raise < self.round_data.min_raise {
                 // There's a raise the raise is less than the min bet and it's not an all in
                 Err(GameStateError::RaiseSizeTooSmall)
@@ -610,21 +1385,21 @@ impl Iterator for CloneGameStateGenerator {
 /// and random stack sizes. The dealer button is also randomly placed.
 pub struct RandomGameStateGenerator {
     num_players: usize,
-    min_stack: f32,
-    max_stack: f32,
-    big_blind: f32,
-    small_blind: f32,
-    ante: f32,
+    min_stack: Chips,
+    max_stack: Chips,
+    big_blind: Chips,
+    small_blind: Chips,
+    ante: Chips,
 }
 
 impl RandomGameStateGenerator {
     pub fn new(
         num_players: usize,
-        min_stack: f32,
-        max_stack: f32,
-        big_blind: f32,
-        small_blind: f32,
-        ante: f32,
+        min_stack: Chips,
+        max_stack: Chips,
+        big_blind: Chips,
+        small_blind: Chips,
+        ante: Chips,
     ) -> RandomGameStateGenerator {
         RandomGameStateGenerator {
             num_players,
@@ -642,8 +1417,8 @@ impl Iterator for RandomGameStateGenerator {
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut rng = rng();
-        let stacks: Vec<f32> = (0..self.num_players)
-            .map(|_| rng.random_range(self.min_stack..self.max_stack))
+        let stacks: Vec<Chips> = (0..self.num_players)
+            .map(|_| Chips::new(rng.random_range(self.min_stack.get()..self.max_stack.get())))
             .collect();
 
         let num_players = stacks.len();
@@ -658,14 +1433,168 @@ impl Iterator for RandomGameStateGenerator {
     }
 }
 
+/// The small blind is the first player with chips after the button, and
+/// the big blind is the next one after that. Heads up the button is the
+/// small blind.
+fn default_blind_positions(
+    active: PlayerBitSet,
+    num_players: usize,
+    dealer_idx: usize,
+) -> (usize, usize) {
+    let next_active = |idx: usize| {
+        (1..=num_players)
+            .map(|offset| (idx + offset) % num_players)
+            .find(|i| active.get(*i))
+            .unwrap_or(idx)
+    };
+    let small_blind_idx = if active.count() == 2 && active.get(dealer_idx) {
+        dealer_idx
+    } else {
+        next_active(dealer_idx)
+    };
+    (small_blind_idx, next_active(small_blind_idx))
+}
+
 #[cfg(test)]
 mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
     use super::*;
 
+    #[test]
+    fn test_try_new_starting_checks_player_count() {
+        assert!(matches!(
+            GameState::try_new_starting(
+                vec![Chips::new(100); MAX_PLAYERS + 1],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0
+            ),
+            Err(GameStateError::PlayerCount(11))
+        ));
+        let game_state = GameState::try_new_starting(
+            vec![Chips::new(100); MAX_PLAYERS],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        )
+        .unwrap();
+        assert_eq!(MAX_PLAYERS, game_state.num_players);
+    }
+
+    /// Four handed with the short stack of 25 on the button, and the
+    /// blinds of 5 and 10 posted.
+    fn short_stack_preflop() -> GameState {
+        let stacks = [100, 100, 100, 25].map(Chips::new).to_vec();
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 3);
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+
+        // Under the gun raises to 20, and the button goes all in for 25. That
+        // raise of 5 is less than the full raise of 10.
+        assert_eq!(2, game_state.to_act_idx());
+        game_state.do_bet(Chips::new(20), false).unwrap();
+        game_state.do_bet(Chips::new(25), false).unwrap();
+        assert!(game_state.player_all_in.get(3));
+        game_state
+    }
+
+    #[test]
+    fn test_short_all_in_doesnt_reopen_betting() {
+        let mut game_state = short_stack_preflop();
+
+        // The blinds hadn't acted yet so they can still raise.
+        assert_eq!(0, game_state.to_act_idx());
+        assert!(game_state.current_player_can_raise());
+        game_state.fold();
+        assert!(game_state.current_player_can_raise());
+        game_state.fold();
+
+        // Under the gun already acted. They have to call the extra 5, but
+        // can't raise on top of it.
+        assert_eq!(2, game_state.to_act_idx());
+        assert!(!game_state.current_player_can_raise());
+        assert_eq!(
+            Err(GameStateError::RaiseNotReopened),
+            game_state.do_bet(Chips::new(60), false)
+        );
+        game_state.do_bet(Chips::new(25), false).unwrap();
+        assert_eq!(0, game_state.current_round_num_active_players());
+    }
+
+    #[test]
+    fn test_full_raise_reopens_betting() {
+        let mut game_state = short_stack_preflop();
+
+        // The small blind makes a full raise, so everyone can raise again.
+        game_state.do_bet(Chips::new(40), false).unwrap();
+        game_state.fold();
+        assert_eq!(2, game_state.to_act_idx());
+        assert!(game_state.current_player_can_raise());
+        game_state.do_bet(Chips::new(60), false).unwrap();
+    }
+
+    #[test]
+    fn test_side_pots_split_and_returned() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 4],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        // 0 folded after putting in 20, 1 is all in for 30, 2 and 3 are
+        // still in with 3's extra 40 uncalled.
+        game_state.player_bet = vec![
+            Chips::new(20),
+            Chips::new(30),
+            Chips::new(60),
+            Chips::new(100),
+        ];
+        game_state.player_active.disable(0);
+        game_state.player_active.disable(1);
+        game_state.player_all_in.enable(1);
+
+        let pots = game_state.side_pots();
+        let amounts: Vec<Chips> = pots.iter().map(|p| p.amount).collect();
+        assert_eq!([110, 60, 40].map(Chips::new).to_vec(), amounts);
+        assert_eq!(vec![1, 2, 3], pots[0].eligible.ones().collect::<Vec<_>>());
+        assert_eq!(vec![2, 3], pots[1].eligible.ones().collect::<Vec<_>>());
+        assert_eq!(vec![3], pots[2].eligible.ones().collect::<Vec<_>>());
+        let total: Chips = amounts.iter().sum();
+        assert_eq!(Chips::new(210), total);
+    }
+
+    #[test]
+    fn test_side_pots_only_folded_chips() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        // Only seat 0 put chips in, then folded.
+        game_state.player_bet = vec![Chips::new(10), Chips::ZERO, Chips::ZERO];
+        game_state.player_active.disable(0);
+
+        let pots = game_state.side_pots();
+        assert_eq!(1, pots.len());
+        assert_eq!(Chips::new(10), pots[0].amount);
+        assert_eq!(vec![1, 2], pots[0].eligible.ones().collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_fold_around_call() {
-        let stacks = vec![100.0; 4];
-        let mut game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 1);
+        let stacks = vec![Chips::new(100); 4];
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 1);
 
         // starting
         game_state.advance_round();
@@ -680,8 +1609,8 @@ mod tests {
         assert_eq!(2, game_state.to_act_idx());
 
         // Do the blinds now
-        game_state.do_bet(5.0, true).unwrap();
-        game_state.do_bet(10.0, true).unwrap();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
 
         // The blinds posting wraps around when needed
         assert_eq!(0, game_state.to_act_idx());
@@ -690,8 +1619,8 @@ mod tests {
         game_state.fold();
         game_state.fold();
 
-        game_state.do_bet(10.0, false).unwrap();
-        game_state.do_bet(10.0, false).unwrap();
+        game_state.do_bet(Chips::new(10), false).unwrap();
+        game_state.do_bet(Chips::new(10), false).unwrap();
         assert_eq!(0, game_state.current_round_num_active_players());
         assert_eq!(2, game_state.num_active_players());
 
@@ -701,9 +1630,9 @@ mod tests {
         // Flop
         game_state.advance_round();
         assert_eq!(2, game_state.to_act_idx());
-        game_state.do_bet(0.0, false).unwrap();
+        game_state.do_bet(Chips::ZERO, false).unwrap();
         assert_eq!(3, game_state.to_act_idx());
-        game_state.do_bet(0.0, false).unwrap();
+        game_state.do_bet(Chips::ZERO, false).unwrap();
         assert_eq!(0, game_state.current_round_num_active_players());
         assert_eq!(2, game_state.num_active_players());
 
@@ -714,8 +1643,8 @@ mod tests {
         game_state.advance_round();
         assert_eq!(2, game_state.to_act_idx());
         assert_eq!(2, game_state.current_round_num_active_players());
-        game_state.do_bet(0.0, false).unwrap();
-        game_state.do_bet(0.0, false).unwrap();
+        game_state.do_bet(Chips::ZERO, false).unwrap();
+        game_state.do_bet(Chips::ZERO, false).unwrap();
         assert_eq!(0, game_state.current_round_num_active_players());
         assert_eq!(2, game_state.num_active_players());
 
@@ -724,8 +1653,8 @@ mod tests {
 
         // River
         game_state.advance_round();
-        game_state.do_bet(0.0, false).unwrap();
-        game_state.do_bet(0.0, false).unwrap();
+        game_state.do_bet(Chips::ZERO, false).unwrap();
+        game_state.do_bet(Chips::ZERO, false).unwrap();
         assert_eq!(0, game_state.current_round_num_active_players());
         assert_eq!(2, game_state.num_active_players());
 
@@ -735,55 +1664,58 @@ mod tests {
 
     #[test]
     fn test_cant_bet_less_0() {
-        let stacks = vec![100.0; 5];
-        let mut game_state = GameState::new_starting(stacks, 2.0, 1.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 5];
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(2), Chips::new(1), Chips::ZERO, 0);
         game_state.advance_round();
         game_state.advance_round();
 
-        game_state.do_bet(33.0, false).unwrap();
+        game_state.do_bet(Chips::new(33), false).unwrap();
         game_state.fold();
-        let res = game_state.do_bet(20.0, false);
+        let res = game_state.do_bet(Chips::new(20), false);
 
         assert_eq!(res.err(), Some(GameStateError::BetSizeDoesntCall));
     }
 
     #[test]
     fn test_cant_bet_less_with_all_in() {
-        let stacks = vec![100.0, 50.0, 50.0, 100.0, 10.0];
-        let mut game_state = GameState::new_starting(stacks, 2.0, 1.0, 0.0, 0);
+        let stacks = [100, 50, 50, 100, 10].map(Chips::new).to_vec();
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(2), Chips::new(1), Chips::ZERO, 0);
         // Do the start and ante rounds and setup next to act
         game_state.advance_round();
         game_state.advance_round();
 
         // UTG raises to 10
-        game_state.do_bet(10.0, false).unwrap();
+        game_state.do_bet(Chips::new(10), false).unwrap();
 
         // UTG+1 has 10 remaining so betting 100 is overbetting
         // into an all in.
-        game_state.do_bet(100.0, false).unwrap();
+        game_state.do_bet(Chips::new(100), false).unwrap();
 
         // Dealer gets out of the way
         game_state.fold();
 
         // Small Blind raises to 20
-        game_state.do_bet(20.0, false).unwrap();
+        game_state.do_bet(Chips::new(20), false).unwrap();
 
         // Big Blind can't call the previous value.
-        let res = game_state.do_bet(10.0, false);
+        let res = game_state.do_bet(Chips::new(10), false);
         assert_eq!(res.err(), Some(GameStateError::BetSizeDoesntCall));
     }
 
     #[test]
     fn test_cant_under_minraise_bb() {
-        let stacks = vec![500.0; 5];
-        let mut game_state = GameState::new_starting(stacks, 20.0, 10.0, 0.0, 0);
+        let stacks = vec![Chips::new(500); 5];
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(20), Chips::new(10), Chips::ZERO, 0);
         // Do the start and ante rounds and setup next to act
         game_state.advance_round();
         game_state.advance_round();
         game_state.advance_round();
 
-        game_state.do_bet(10.0, true).unwrap();
-        game_state.do_bet(20.0, true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        game_state.do_bet(Chips::new(20), true).unwrap();
 
         // UTG raises to 33
         //
@@ -792,14 +1724,15 @@ mod tests {
         // we're not able to raise 13
         assert_eq!(
             Err(GameStateError::RaiseSizeTooSmall),
-            game_state.do_bet(33.0, false)
+            game_state.do_bet(Chips::new(33), false)
         );
     }
 
     #[test]
     fn test_gamestate_keeps_round_before_complete() {
-        let stacks = vec![100.0; 3];
-        let mut game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 3];
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         // Simulate a game where everyone folds and the big blind wins
         game_state.advance_round();
         game_state.advance_round();
@@ -814,12 +1747,12 @@ mod tests {
     #[test]
     fn test_can_create_starting_round_data() {
         let num_players = 3;
-        let min_raise = 10.0;
+        let min_raise = Chips::new(10);
         let active = PlayerBitSet::new(num_players);
 
         let round_data = RoundData::new(num_players, min_raise, active, 0);
 
-        assert_eq!(round_data.bet, 0.0);
+        assert_eq!(round_data.bet, Chips::ZERO);
 
         assert_eq!(round_data.total_bet_count, 0);
 
@@ -829,18 +1762,433 @@ mod tests {
     #[test]
     fn test_can_create_inprogress_round_data() {
         let num_players = 3;
-        let min_raise = 10.0;
+        let min_raise = Chips::new(10);
         let active = PlayerBitSet::new(num_players);
 
-        let player_bet = vec![0.0, 10.0, 20.0];
+        let player_bet = [0, 10, 20].map(Chips::new).to_vec();
         let to_act = 0;
 
         let round_data = RoundData::new_with_bets(min_raise, active, to_act, player_bet);
 
-        assert_eq!(round_data.bet, 20.0);
+        assert_eq!(round_data.bet, Chips::new(20));
 
         assert_eq!(round_data.total_bet_count, 2);
 
         assert_eq!(round_data.total_raise_count, 2);
     }
+
+    #[test]
+    fn test_m_ratio_and_stack_in_bb() {
+        // No antes, blinds of 5/10 cost 15 an orbit
+        let game_state = GameState::new_starting(
+            [300, 150, 45].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        assert_eq!(30.0, game_state.stack_in_bb(0));
+        assert_eq!(4.5, game_state.stack_in_bb(2));
+        assert_eq!(20.0, game_state.m_ratio(0));
+        assert_eq!(3.0, game_state.m_ratio(2));
+
+        // With antes 100/200 and a 25 ante with four players an orbit is 400
+        let game_state = GameState::new_starting(
+            vec![Chips::new(8000); 4],
+            Chips::new(200),
+            Chips::new(100),
+            Chips::new(25),
+            0,
+        );
+        assert_eq!(40.0, game_state.stack_in_bb(1));
+        assert_eq!(20.0, game_state.m_ratio(1));
+    }
+
+    #[test]
+    fn test_m_ratio_ignores_empty_seats() {
+        let game_state = GameState::new_starting(
+            [1000, 0, 1000].map(Chips::new).to_vec(),
+            Chips::new(20),
+            Chips::new(10),
+            Chips::new(10),
+            0,
+        );
+        // Only two players pay antes
+        assert_eq!(20.0, game_state.m_ratio(0));
+    }
+
+    #[test]
+    fn test_forced_bets_summary_not_posted() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::new(1),
+            0,
+        );
+        assert_eq!(None, game_state.forced_bets_summary());
+    }
+
+    #[test]
+    fn test_forced_bets_summary_matches_actions() {
+        use crate::arena::{
+            Agent, HoldemSimulationBuilder, action::Action, agent::FoldingAgent,
+            historian::VecHistorian,
+        };
+
+        let hist = Box::new(VecHistorian::default());
+        let records = hist.get_storage();
+        // The last player can only post part of the big blind.
+        let stacks = [100, 100, 100, 8].map(Chips::new).to_vec();
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::new(1), 1);
+        let agents: Vec<Box<dyn Agent>> = (0..4)
+            .map(|_| Box::<FoldingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![hist])
+            .build()
+            .unwrap();
+        sim.run(&mut rand::rng());
+
+        let emitted: Vec<ForcedBetPayload> = records
+            .borrow()
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::ForcedBet(payload) => Some(payload.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let summary = sim.game_state.forced_bets_summary().unwrap();
+        // Four antes and two blinds
+        assert_eq!(6, emitted.len());
+        assert_eq!(emitted, summary.forced_bets);
+
+        // Everyone antes 1, the small blind posts 5, and the big blind is
+        // all in for the 7 left after the ante.
+        assert_eq!([1, 1, 6, 8].map(Chips::new).to_vec(), summary.player_posted);
+        assert_eq!(Chips::new(16), summary.pot);
+        // The big blind is all in so action starts with the next seat.
+        assert_eq!(Some(0), summary.first_to_act_idx);
+    }
+
+    fn positions(game_state: &GameState) -> (usize, usize, usize) {
+        (
+            game_state.dealer_idx,
+            game_state.small_blind_idx,
+            game_state.big_blind_idx,
+        )
+    }
+
+    #[test]
+    fn test_next_hand_rotates() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 4],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::new(1),
+            3,
+        );
+        assert_eq!((3, 0, 1), positions(&game_state));
+        let next = game_state.next_hand(vec![Chips::new(100); 4]);
+        assert_eq!((0, 1, 2), positions(&next));
+        assert_eq!(Chips::new(1), next.ante);
+        assert_eq!(Round::Starting, next.round);
+    }
+
+    #[test]
+    fn test_next_hand_small_blind_busts() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 4],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        assert_eq!((0, 1, 2), positions(&game_state));
+
+        // The small blind busts, the button is dead on their empty seat.
+        let next = game_state.next_hand([150, 0, 150, 100].map(Chips::new).to_vec());
+        assert_eq!((1, 2, 3), positions(&next));
+        assert!(!next.player_active.get(1));
+
+        // Then the button moves on to a live seat again.
+        let next = next.next_hand([150, 0, 150, 100].map(Chips::new).to_vec());
+        assert_eq!((2, 3, 0), positions(&next));
+    }
+
+    #[test]
+    fn test_next_hand_big_blind_busts() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 4],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        // The small blind is dead, no one skips paying the big blind.
+        let next = game_state.next_hand([100, 150, 0, 150].map(Chips::new).to_vec());
+        assert_eq!((1, 2, 3), positions(&next));
+        let next = next.next_hand([100, 150, 0, 150].map(Chips::new).to_vec());
+        assert_eq!((2, 3, 0), positions(&next));
+        let next = next.next_hand([100, 150, 0, 150].map(Chips::new).to_vec());
+        assert_eq!((3, 0, 1), positions(&next));
+    }
+
+    #[test]
+    fn test_next_hand_down_to_heads_up() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        assert_eq!((0, 1, 2), positions(&game_state));
+        // Heads up the button is the small blind.
+        let next = game_state.next_hand([0, 150, 150].map(Chips::new).to_vec());
+        assert_eq!((2, 2, 1), positions(&next));
+        let next = next.next_hand([0, 150, 150].map(Chips::new).to_vec());
+        assert_eq!((1, 1, 2), positions(&next));
+    }
+
+    #[test]
+    fn test_six_max_positions() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 6],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for _ in 0..3 {
+            game_state.advance_round();
+        }
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+
+        // Button, small blind, big blind, then the cutoff right before the
+        // button.
+        assert_eq!(0, game_state.position_relative_to_button(0));
+        assert_eq!(1, game_state.position_relative_to_button(1));
+        assert_eq!(2, game_state.position_relative_to_button(2));
+        assert_eq!(5, game_state.position_relative_to_button(5));
+        assert_eq!(6, game_state.players_to_act().count());
+        assert!(game_state.is_in_position(0));
+        assert!(!game_state.is_in_position(5));
+
+        // Under the gun and the hijack fold.
+        assert_eq!(3, game_state.to_act_idx());
+        game_state.fold();
+        game_state.fold();
+        let to_act: Vec<usize> = game_state.players_to_act().ones().collect();
+        assert_eq!(vec![0, 1, 2, 5], to_act);
+
+        // The cutoff calls and the button folds, leaving the cutoff last to
+        // act after the flop.
+        game_state.do_bet(Chips::new(10), false).unwrap();
+        game_state.fold();
+        assert!(game_state.is_in_position(5));
+        assert!(!game_state.is_in_position(0));
+        assert!(!game_state.is_in_position(2));
+        let to_act: Vec<usize> = game_state.players_to_act().ones().collect();
+        assert_eq!(vec![1, 2], to_act);
+        assert_eq!(5, game_state.position_relative_to_button(5));
+    }
+
+    #[test]
+    fn test_diff_after_bet() {
+        let mut before = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        before.advance_round();
+        before.advance_round();
+        before.advance_round();
+        before.do_bet(Chips::new(5), true).unwrap();
+        before.do_bet(Chips::new(10), true).unwrap();
+
+        let mut after = before.clone();
+        after.do_bet(Chips::new(30), false).unwrap();
+
+        let diff = before.diff(&after);
+        assert_eq!(vec![(0, Chips::new(-30))], diff.stack_changes);
+        assert_eq!(Chips::new(30), diff.pot_change);
+        assert_eq!(Some(1), diff.to_act_change);
+        assert_eq!(None, diff.round_change);
+        assert!(diff.new_board_cards.is_empty());
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_diff_new_street() {
+        let mut before = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        before.board = Hand::new_from_str("Ah2c7d").unwrap().iter().collect();
+        let mut after = before.clone();
+        after.board.push(Card::try_from("Ks").unwrap());
+        after.advance_round();
+
+        let diff = before.diff(&after);
+        assert_eq!(vec![Card::try_from("Ks").unwrap()], diff.new_board_cards);
+        assert_eq!(Some((Round::Starting, Round::Ante)), diff.round_change);
+        assert_eq!(Chips::ZERO, diff.pot_change);
+    }
+
+    #[test]
+    fn test_pot_odds_after_bet() {
+        let mut game_state = GameState::new_starting(
+            [100, 100, 30].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        // The button raises to 40 making the pot 55.
+        game_state.do_bet(Chips::new(40), false).unwrap();
+
+        assert_eq!(Chips::ZERO, game_state.amount_to_call(0));
+        assert_eq!(0.0, game_state.pot_odds(0));
+        assert_eq!(Chips::new(35), game_state.amount_to_call(1));
+        assert_eq!(35.0 / 90.0, game_state.pot_odds(1));
+        // The big blind only has 20 behind.
+        assert_eq!(Chips::new(20), game_state.amount_to_call(2));
+        assert_eq!(20.0 / 75.0, game_state.pot_odds(2));
+
+        // Once the small blind calls the pot odds for the big blind improve.
+        game_state.do_bet(Chips::new(40), false).unwrap();
+        assert_eq!(Chips::ZERO, game_state.amount_to_call(1));
+        assert_eq!(20.0 / 110.0, game_state.pot_odds(2));
+    }
+
+    #[test]
+    fn test_hero_equity_with_the_nuts() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.board = Hand::new_from_str("QsJsTs2d3c").unwrap().iter().collect();
+        let mut hand = Hand::new_from_str("AsKs").unwrap();
+        hand.extend(game_state.board.iter().cloned());
+        game_state.hands[1] = hand;
+
+        let mut rng = StdRng::seed_from_u64(318);
+        let equity = game_state.hero_equity(1, 2, 500, &mut rng);
+        assert!((equity - 1.0).abs() < 1e-6, "{equity}");
+    }
+
+    #[test]
+    fn test_known_cards_on_turn() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.board = Hand::new_from_str("Kh7c2d9s").unwrap().iter().collect();
+        for (idx, hole) in ["AsAd", "QcJc", "8h8d"].iter().enumerate() {
+            let mut hand = Hand::new_from_str(hole).unwrap();
+            hand.extend(game_state.board.iter().cloned());
+            game_state.hands[idx] = hand;
+        }
+
+        let known = game_state.known_cards(1);
+        assert_eq!(6, known.len());
+        assert_eq!(Hand::new_from_str("QcJc").unwrap(), {
+            let mut hole = Hand::new();
+            hole.extend(known[..2].iter().cloned());
+            hole
+        });
+        assert_eq!(game_state.board(), &known[2..]);
+        // Nothing from the other players' hands.
+        assert!(!known.contains(&Card::try_from("As").unwrap()));
+        assert!(!known.contains(&Card::try_from("8h").unwrap()));
+    }
+
+    #[test]
+    fn test_effective_stack_and_spr() {
+        let mut game_state = GameState::new_starting(
+            [100, 60, 200].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.total_pot = Chips::new(40);
+        // The smaller stack either way round.
+        assert_eq!(Chips::new(60), game_state.effective_stack(0, 1));
+        assert_eq!(Chips::new(60), game_state.effective_stack(1, 0));
+        assert_eq!(Chips::new(100), game_state.effective_stack(0, 2));
+        assert_eq!(2.5, game_state.spr(0));
+        assert_eq!(1.5, game_state.spr(1));
+        // The big stack is only covered up to the next biggest stack.
+        assert_eq!(2.5, game_state.spr(2));
+
+        // Once the big stack folds, the 100 is only playing for 60 more.
+        game_state.player_active.disable(2);
+        assert_eq!(1.5, game_state.spr(0));
+
+        // All in leaves nothing behind.
+        game_state.stacks[1] = Chips::ZERO;
+        game_state.player_active.disable(1);
+        game_state.player_all_in.enable(1);
+        assert_eq!(Chips::ZERO, game_state.effective_stack(0, 1));
+        assert_eq!(0.0, game_state.spr(0));
+        assert_eq!(0.0, game_state.spr(1));
+    }
+
+    #[test]
+    fn test_committed_through_a_preflop_raise() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.advance_round();
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        // The button raises to 30 and the small blind calls.
+        game_state.do_bet(Chips::new(30), false).unwrap();
+        game_state.do_bet(Chips::new(30), false).unwrap();
+        assert_eq!(Chips::new(30), game_state.committed_this_round(0));
+        assert_eq!(Chips::new(30), game_state.committed_this_round(1));
+        assert_eq!(Chips::new(10), game_state.committed_this_round(2));
+        assert_eq!(Chips::new(10), game_state.total_committed(2));
+        // The big blind calls the extra 20.
+        game_state.do_bet(Chips::new(30), false).unwrap();
+        assert_eq!(Chips::new(30), game_state.committed_this_round(2));
+
+        game_state.advance_round();
+        assert_eq!(Round::DealFlop, game_state.round);
+        for idx in 0..3 {
+            assert_eq!(Chips::ZERO, game_state.committed_this_round(idx));
+            assert_eq!(Chips::new(30), game_state.total_committed(idx));
+        }
+        assert_eq!(Chips::ZERO, game_state.committed_this_round(7));
+        assert_eq!(Chips::ZERO, game_state.total_committed(7));
+    }
 }
@@ -19,7 +19,7 @@ impl Historian for FailingHistorian {
 
 #[cfg(test)]
 mod tests {
-    use crate::arena::{HoldemSimulationBuilder, agent::CallingAgent};
+    use crate::arena::{Chips, HoldemSimulationBuilder, agent::CallingAgent};
 
     use super::*;
 
@@ -28,8 +28,9 @@ mod tests {
     fn test_panic_fail_historian() {
         let historian = Box::new(FailingHistorian);
 
-        let stacks = vec![100.0; 3];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let stacks = vec![Chips::new(100); 3];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let mut rng = rand::rng();
 
         let mut sim = HoldemSimulationBuilder::default()
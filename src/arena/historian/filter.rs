@@ -0,0 +1,105 @@
+use crate::arena::{GameState, action::Action};
+
+use super::{Historian, HistorianError};
+
+/// A `Historian` that wraps another historian and only forwards the actions
+/// that match a predicate. This is useful for cutting down the volume of
+/// records on large runs, for example only keeping `Award` actions.
+#[derive(Debug, Clone)]
+pub struct FilterHistorian<H, F> {
+    inner: H,
+    predicate: F,
+}
+
+impl<H: Historian, F: Fn(&Action) -> bool> FilterHistorian<H, F> {
+    /// Create a new `FilterHistorian` forwarding to `inner` every action
+    /// where `predicate` returns true.
+    pub fn new(inner: H, predicate: F) -> Self {
+        Self { inner, predicate }
+    }
+
+    /// Get the wrapped historian back.
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: Historian, F: Fn(&Action) -> bool> Historian for FilterHistorian<H, F> {
+    fn record_action(
+        &mut self,
+        id: u128,
+        game_state: &GameState,
+        action: Action,
+    ) -> Result<(), HistorianError> {
+        if (self.predicate)(&action) {
+            self.inner.record_action(id, game_state, action)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arena::{
+        Agent, Chips, HoldemSimulationBuilder, agent::CallingAgent, historian::VecHistorian,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_filtered_actions_never_reach_inner() {
+        let inner = VecHistorian::default();
+        let records = inner.get_storage();
+        let historian = Box::new(FilterHistorian::new(inner, |action: &Action| {
+            matches!(action, Action::Award(_) | Action::PlayedAction(_))
+        }));
+
+        let agents: Vec<Box<dyn Agent>> = (0..3)
+            .map(|_| Box::<CallingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut sim = HoldemSimulationBuilder::default()
+            .agents(agents)
+            .game_state(game_state)
+            .historians(vec![historian])
+            .build()
+            .unwrap();
+        sim.run(&mut rand::rng());
+
+        let records = records.borrow();
+        assert!(!records.is_empty());
+        assert!(
+            records
+                .iter()
+                .all(|r| matches!(r.action, Action::Award(_) | Action::PlayedAction(_)))
+        );
+        assert!(records.iter().any(|r| matches!(r.action, Action::Award(_))));
+    }
+
+    #[test]
+    fn test_filter_nothing() {
+        let inner = VecHistorian::default();
+        let records = inner.get_storage();
+        let mut historian = FilterHistorian::new(inner, |_: &Action| false);
+
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        historian
+            .record_action(0, &game_state, Action::RoundAdvance(game_state.round))
+            .unwrap();
+
+        assert!(records.borrow().is_empty());
+    }
+}
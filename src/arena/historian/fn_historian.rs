@@ -37,7 +37,9 @@ impl<F: Clone + Fn(u128, &GameState, Action) -> Result<(), HistorianError>> Hist
 mod tests {
     use std::{cell::RefCell, rc::Rc};
 
-    use crate::arena::{Agent, HoldemSimulationBuilder, agent::RandomAgent, game_state::Round};
+    use crate::arena::{
+        Agent, Chips, HoldemSimulationBuilder, agent::RandomAgent, game_state::Round,
+    };
 
     use super::*;
 
@@ -49,7 +51,13 @@ mod tests {
         let agents: Vec<Box<dyn Agent>> = (0..2)
             .map(|_| Box::<RandomAgent>::default() as Box<dyn Agent>)
             .collect();
-        let game_state = GameState::new_starting(vec![100.0, 100.0], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            [100, 100].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
 
         let borrow_count = count.clone();
         let borrow_last_action = last_action.clone();
@@ -90,7 +98,13 @@ mod tests {
             .map(|_| Box::<RandomAgent>::default() as Box<dyn Agent>)
             .collect();
 
-        let game_state = GameState::new_starting(vec![100.0, 100.0], 10.0, 5.0, 0.0, 0);
+        let game_state = GameState::new_starting(
+            [100, 100].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
         let historian = Box::new(FnHistorian::new(|_, _, _| {
             Err(HistorianError::UnableToRecordAction)
         }));
@@ -0,0 +1,89 @@
+use std::io::Write;
+
+use crate::arena::{GameState, action::Action, game_state::Round};
+
+use super::{Historian, HistorianError};
+
+/// A historian that writes every action as one line of JSON.
+///
+/// The output is newline delimited JSON, one serialized `Action` with its
+/// payload per line, so a finished game can be read back a line at a time.
+/// The writer is flushed once the game is complete.
+#[derive(Debug)]
+pub struct JsonHistorian<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonHistorian<W> {
+    /// Create a new `JsonHistorian` that writes to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: Write> Historian for JsonHistorian<W> {
+    fn record_action(
+        &mut self,
+        _id: u128,
+        _game_state: &GameState,
+        action: Action,
+    ) -> Result<(), HistorianError> {
+        let is_complete = action == Action::RoundAdvance(Round::Complete);
+
+        serde_json::to_writer(&mut self.writer, &action)?;
+        self.writer.write_all(b"\n")?;
+
+        if is_complete {
+            self.writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+
+    use tempfile::NamedTempFile;
+
+    use crate::arena::{Agent, Chips, HoldemSimulationBuilder, agent::CallingAgent};
+
+    use super::*;
+
+    #[test]
+    fn test_json_lines_parse_back() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let historian = JsonHistorian::new(temp_file.reopen().unwrap());
+
+        let agents: Vec<Box<dyn Agent>> = (0..3)
+            .map(|_| Box::<CallingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut rand::rng());
+
+        let actions: Vec<Action> = BufReader::new(temp_file.reopen().unwrap())
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect();
+
+        // Everyone calls down so the whole board is dealt.
+        let community = actions
+            .iter()
+            .filter(|a| matches!(a, Action::DealCommunity(_)))
+            .count();
+        assert_eq!(5, community);
+        assert_eq!(Some(&Action::RoundAdvance(Round::Complete)), actions.last());
+    }
+}
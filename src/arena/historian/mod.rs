@@ -1,5 +1,6 @@
 use super::{GameState, action::Action};
 use thiserror::Error;
+use tracing::{Level, event};
 
 /// HistorianError is the error type for historian implementations.
 #[derive(Error, Debug)]
@@ -12,7 +13,7 @@ pub enum HistorianError {
     BorrowMutError(#[from] std::cell::BorrowMutError),
     #[error("Borrow Error: {0}")]
     BorrowError(#[from] std::cell::BorrowError),
-    #[cfg(any(test, feature = "serde"))]
+    #[cfg(feature = "serde")]
     #[error("JSON Error: {0}")]
     JSONError(#[from] serde_json::Error),
     #[error("Unexpected CFR Node: {0}")]
@@ -48,6 +49,34 @@ pub trait Historian {
     ) -> Result<(), HistorianError>;
 }
 
+/// Record `action` with each of `historians`. A historian that errors is
+/// dropped, or if `panic_on_error` is set the error panics.
+pub(crate) fn record_with_all(
+    historians: &mut Vec<Box<dyn Historian>>,
+    id: u128,
+    game_state: &GameState,
+    action: Action,
+    panic_on_error: bool,
+) {
+    historians.retain_mut(|historian| {
+        match historian.record_action(id, game_state, action.clone()) {
+            Ok(_) => true,
+            Err(error) => {
+                event!(Level::ERROR, ?error, "historian_error");
+
+                // Some user might never error.
+                // For them it's a panic.
+                if panic_on_error {
+                    panic!(
+                        "Historian error {error}\naction={action:?}\ngame_state = {game_state:?}"
+                    );
+                }
+                false
+            }
+        }
+    });
+}
+
 /// `HistorianGenerator` is a trait that is used to build historians
 /// for tournaments where each simulation needs a new historian.
 pub trait HistorianGenerator {
@@ -91,21 +120,31 @@ where
 }
 
 mod failing;
+mod filter;
 mod fn_historian;
 mod null;
+mod poker_stars;
+mod stats;
 mod stats_tracking;
 mod vec;
 
-#[cfg(any(test, feature = "serde"))]
+#[cfg(feature = "serde")]
 mod directory_historian;
+#[cfg(feature = "serde")]
+mod json;
 
 pub use failing::FailingHistorian;
+pub use filter::FilterHistorian;
 pub use fn_historian::FnHistorian;
 pub use null::NullHistorian;
+pub use poker_stars::PokerStarsHistorian;
+pub use stats::{PlayerStats, StatsHistorian};
 pub use vec::HistoryRecord;
 pub use vec::VecHistorian;
 
-#[cfg(any(test, feature = "serde"))]
+#[cfg(feature = "serde")]
 pub use directory_historian::DirectoryHistorian;
+#[cfg(feature = "serde")]
+pub use json::JsonHistorian;
 
 pub use stats_tracking::StatsTrackingHistorian;
@@ -0,0 +1,448 @@
+use std::{cell::RefCell, fmt::Write, rc::Rc};
+
+use crate::{
+    arena::{
+        BettingStructure, Chips, GameState,
+        action::{Action, AgentAction, ForcedBetType, PlayedActionPayload, ShowdownPayload},
+        game_state::Round,
+    },
+    core::{Card, Rank},
+};
+
+use super::{Historian, HistorianError};
+
+/// A historian that writes each game as a PokerStars style text hand
+/// history, the format most hand tracking tools can import.
+///
+/// Lines are buffered as the actions come in and the full hand is rendered
+/// after the last pot is awarded, when the game completes. Every finished
+/// hand is pushed onto the storage from `get_storage`. Seats are numbered
+/// from one, and players are named `Player <seat>`. Every player's hole
+/// cards are shown since the simulation knows all of them, except at
+/// showdown where players show in the order they had to and a mucked hand
+/// stays hidden.
+pub struct PokerStarsHistorian {
+    hands: Rc<RefCell<Vec<String>>>,
+    lines: Vec<String>,
+    hole_cards: Vec<Vec<Card>>,
+    shown_hole_cards: bool,
+    small_blind_idx: Option<usize>,
+    big_blind_idx: Option<usize>,
+    winnings: Vec<Chips>,
+    mucked: Vec<bool>,
+}
+
+impl PokerStarsHistorian {
+    pub fn new() -> Self {
+        Self::new_with_storage(Rc::new(RefCell::new(vec![])))
+    }
+
+    /// Create a new `PokerStarsHistorian` that pushes finished hands onto
+    /// `hands`.
+    pub fn new_with_storage(hands: Rc<RefCell<Vec<String>>>) -> Self {
+        Self {
+            hands,
+            lines: vec![],
+            hole_cards: vec![],
+            shown_hole_cards: false,
+            small_blind_idx: None,
+            big_blind_idx: None,
+            winnings: vec![],
+            mucked: vec![],
+        }
+    }
+
+    /// The text of every finished hand.
+    pub fn get_storage(&self) -> Rc<RefCell<Vec<String>>> {
+        self.hands.clone()
+    }
+
+    fn start(&mut self, id: u128, game_state: &GameState) {
+        let limit = match game_state.betting_structure {
+            BettingStructure::NoLimit => "No Limit",
+            BettingStructure::PotLimit => "Pot Limit",
+            BettingStructure::FixedLimit { .. } => "Limit",
+        };
+        self.lines = vec![
+            format!(
+                "PokerStars Hand #{id}: Hold'em {limit} ({}/{})",
+                game_state.small_blind, game_state.big_blind
+            ),
+            format!(
+                "Table 'rs_poker' {}-max Seat #{} is the button",
+                game_state.num_players,
+                game_state.dealer_idx + 1
+            ),
+        ];
+        for (idx, stack) in game_state.starting_stacks.iter().enumerate() {
+            self.lines.push(format!(
+                "Seat {}: {} ({stack} in chips)",
+                idx + 1,
+                name(idx)
+            ));
+        }
+        self.hole_cards = vec![vec![]; game_state.num_players];
+        self.shown_hole_cards = false;
+        self.small_blind_idx = None;
+        self.big_blind_idx = None;
+        self.winnings = vec![Chips::ZERO; game_state.num_players];
+        self.mucked = vec![false; game_state.num_players];
+    }
+
+    /// The hole cards are shown after the blinds, right before the first
+    /// voluntary action.
+    fn show_hole_cards(&mut self) {
+        if self.shown_hole_cards {
+            return;
+        }
+        self.shown_hole_cards = true;
+        self.lines.push("*** HOLE CARDS ***".to_string());
+        for (idx, cards) in self.hole_cards.iter().enumerate() {
+            if !cards.is_empty() {
+                self.lines
+                    .push(format!("Dealt to {} {}", name(idx), cards_str(cards)));
+            }
+        }
+    }
+
+    fn played(&mut self, payload: &PlayedActionPayload) {
+        self.show_hole_cards();
+        let player = name(payload.idx);
+        let added = payload.final_player_bet - payload.starting_player_bet;
+        let mut line = if payload.action == AgentAction::Fold {
+            format!("{player}: folds")
+        } else if added <= Chips::ZERO {
+            format!("{player}: checks")
+        } else if payload.final_bet <= payload.starting_bet {
+            format!("{player}: calls {added}")
+        } else if payload.starting_bet <= Chips::ZERO {
+            format!("{player}: bets {}", payload.final_bet)
+        } else {
+            format!(
+                "{player}: raises {} to {}",
+                payload.raise_amount(),
+                payload.final_bet
+            )
+        };
+        if payload.action != AgentAction::Fold && payload.player_stack <= Chips::ZERO {
+            line.push_str(" and is all-in");
+        }
+        self.lines.push(line);
+    }
+
+    fn round_advance(&mut self, round: Round, game_state: &GameState) {
+        let board = &game_state.board;
+        match round {
+            Round::Flop => {
+                self.show_hole_cards();
+                self.lines
+                    .push(format!("*** FLOP *** {}", cards_str(&board[..3])));
+            }
+            Round::Turn => self.lines.push(format!(
+                "*** TURN *** {} {}",
+                cards_str(&board[..3]),
+                cards_str(&board[3..4])
+            )),
+            Round::River => self.lines.push(format!(
+                "*** RIVER *** {} {}",
+                cards_str(&board[..4]),
+                cards_str(&board[4..5])
+            )),
+            Round::Showdown => {
+                self.show_hole_cards();
+                self.lines.push("*** SHOW DOWN ***".to_string());
+            }
+            Round::Complete => self.finish(game_state),
+            _ => {}
+        }
+    }
+
+    fn showdown(&mut self, payload: &ShowdownPayload) {
+        let line = match payload.rank {
+            Some(rank) => format!(
+                "{}: shows {} ({})",
+                name(payload.idx),
+                cards_str(&self.hole_cards[payload.idx]),
+                rank_name(&rank)
+            ),
+            None => {
+                self.mucked[payload.idx] = true;
+                format!("{}: mucks hand", name(payload.idx))
+            }
+        };
+        self.lines.push(line);
+    }
+
+    fn finish(&mut self, game_state: &GameState) {
+        self.lines.push("*** SUMMARY ***".to_string());
+        self.lines.push(format!(
+            "Total pot {} | Rake {}",
+            game_state.total_pot, game_state.total_rake
+        ));
+        if !game_state.board.is_empty() {
+            self.lines
+                .push(format!("Board {}", cards_str(&game_state.board)));
+        }
+
+        let in_hand = game_state.player_active | game_state.player_all_in;
+        let showdown = game_state.round_before == Round::Showdown;
+        for idx in 0..game_state.num_players {
+            let mut line = String::new();
+            write!(line, "Seat {}: {}", idx + 1, name(idx)).unwrap();
+            if idx == game_state.dealer_idx {
+                line.push_str(" (button)");
+            }
+            if Some(idx) == self.small_blind_idx {
+                line.push_str(" (small blind)");
+            }
+            if Some(idx) == self.big_blind_idx {
+                line.push_str(" (big blind)");
+            }
+
+            let won = self.winnings[idx];
+            if !in_hand.get(idx) {
+                line.push_str(" folded");
+            } else if showdown && self.mucked[idx] {
+                line.push_str(" mucked");
+            } else if showdown {
+                let rank = rank_name(&game_state.rank_hand(idx));
+                let cards = cards_str(&self.hole_cards[idx]);
+                if won > Chips::ZERO {
+                    write!(line, " showed {cards} and won ({won}) with {rank}").unwrap();
+                } else {
+                    write!(line, " showed {cards} and lost with {rank}").unwrap();
+                }
+            } else if won > Chips::ZERO {
+                write!(line, " collected ({won})").unwrap();
+            }
+            self.lines.push(line);
+        }
+
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        self.hands.borrow_mut().push(text);
+        self.lines.clear();
+    }
+}
+
+impl Default for PokerStarsHistorian {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Historian for PokerStarsHistorian {
+    fn record_action(
+        &mut self,
+        id: u128,
+        game_state: &GameState,
+        action: Action,
+    ) -> Result<(), HistorianError> {
+        match action {
+            Action::GameStart(_) => self.start(id, game_state),
+            Action::PlayerSit(_)
+            | Action::DealCommunity(_)
+            | Action::BurnCard(_)
+            | Action::DealRunout(_)
+            | Action::PotUpdate(_) => {}
+            Action::Showdown(payload) => self.showdown(&payload),
+            Action::DealStartingHand(payload) => {
+                self.hole_cards[payload.idx].push(payload.card);
+            }
+            Action::ForcedBet(payload) => {
+                let what = match payload.forced_bet_type {
+                    ForcedBetType::Ante => "the ante",
+                    ForcedBetType::SmallBlind => {
+                        self.small_blind_idx = Some(payload.idx);
+                        "small blind"
+                    }
+                    ForcedBetType::BigBlind => {
+                        self.big_blind_idx = Some(payload.idx);
+                        "big blind"
+                    }
+                    ForcedBetType::BringIn => "the bring-in",
+                    ForcedBetType::Straddle => "a straddle",
+                };
+                self.lines.push(format!(
+                    "{}: posts {what} {}",
+                    name(payload.idx),
+                    payload.bet
+                ));
+            }
+            Action::PlayedAction(payload) => self.played(&payload),
+            Action::FailedAction(payload) => self.played(&payload.result),
+            Action::RoundAdvance(round) => self.round_advance(round, game_state),
+            Action::Award(payload) => {
+                self.show_hole_cards();
+                self.winnings[payload.idx] += payload.award_amount;
+                self.lines.push(format!(
+                    "{} collected {} from pot",
+                    name(payload.idx),
+                    payload.award_amount
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn name(idx: usize) -> String {
+    format!("Player {}", idx + 1)
+}
+
+fn cards_str(cards: &[Card]) -> String {
+    let cards: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
+    format!("[{}]", cards.join(" "))
+}
+
+fn rank_name(rank: &Rank) -> &'static str {
+    match rank {
+        Rank::HighCard(_) => "high card",
+        Rank::OnePair(_) => "a pair",
+        Rank::TwoPair(_) => "two pair",
+        Rank::ThreeOfAKind(_) => "three of a kind",
+        Rank::Straight(_) => "a straight",
+        Rank::Flush(_) => "a flush",
+        Rank::FullHouse(_) => "a full house",
+        Rank::FourOfAKind(_) => "four of a kind",
+        Rank::StraightFlush(_) => "a straight flush",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{Agent, HoldemSimulationBuilder, StackedDeck, agent::VecReplayAgent};
+    use crate::core::cards;
+
+    use super::*;
+
+    #[test]
+    fn test_heads_up_snapshot() {
+        let historian = PokerStarsHistorian::new();
+        let hands = historian.get_storage();
+
+        // The button raises preflop and bets the flop, then it's checked
+        // down to showdown.
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(VecReplayAgent::new_with_default(
+                vec![
+                    AgentAction::Bet(Chips::new(20)),
+                    AgentAction::Bet(Chips::new(20)),
+                ],
+                AgentAction::Call,
+            )),
+            Box::new(VecReplayAgent::new_with_default(vec![], AgentAction::Call)),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.id = 272;
+        sim.run(&mut StdRng::seed_from_u64(272));
+
+        let hands = hands.borrow();
+        assert_eq!(1, hands.len());
+        assert_eq!(EXPECTED, hands[0]);
+    }
+
+    #[test]
+    fn test_losing_hand_mucked() {
+        let historian = PokerStarsHistorian::new();
+        let hands = historian.get_storage();
+
+        // The button bets the river with aces and gets called by seven
+        // high, which is mucked.
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("AsAd"))
+            .hole_cards(1, cards("7c2d"))
+            .board(cards("Kh8h3s4c9d"));
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(VecReplayAgent::new_with_default(
+                vec![
+                    AgentAction::Call,
+                    AgentAction::Call,
+                    AgentAction::Call,
+                    AgentAction::Bet(Chips::new(20)),
+                ],
+                AgentAction::Call,
+            )),
+            Box::new(VecReplayAgent::new_with_default(vec![], AgentAction::Call)),
+        ];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .muck_losing_hands(true)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(306));
+
+        let hands = hands.borrow();
+        let showdown: Vec<&str> = hands[0]
+            .lines()
+            .skip_while(|line| *line != "*** SHOW DOWN ***")
+            .skip(1)
+            .take(2)
+            .collect();
+        assert_eq!(
+            vec!["Player 1: shows [As Ad] (a pair)", "Player 2: mucks hand"],
+            showdown
+        );
+        assert!(
+            hands[0]
+                .lines()
+                .any(|line| line == "Seat 2: Player 2 (big blind) mucked")
+        );
+        assert!(!hands[0].contains("[7c 2d] and lost"));
+    }
+
+    const EXPECTED: &str = "PokerStars Hand #272: Hold'em No Limit (5/10)
+Table 'rs_poker' 2-max Seat #1 is the button
+Seat 1: Player 1 (100 in chips)
+Seat 2: Player 2 (100 in chips)
+Player 1: posts small blind 5
+Player 2: posts big blind 10
+*** HOLE CARDS ***
+Dealt to Player 1 [3d 5c]
+Dealt to Player 2 [2d 4h]
+Player 1: raises 10 to 20
+Player 2: calls 10
+*** FLOP *** [4c 5h 8d]
+Player 2: checks
+Player 1: bets 20
+Player 2: calls 20
+*** TURN *** [4c 5h 8d] [6c]
+Player 2: checks
+Player 1: checks
+*** RIVER *** [4c 5h 8d 6c] [2s]
+Player 2: checks
+Player 1: checks
+*** SHOW DOWN ***
+Player 2: shows [2d 4h] (two pair)
+Player 1: shows [3d 5c] (a straight)
+Player 1 collected 80 from pot
+*** SUMMARY ***
+Total pot 80 | Rake 0
+Board [4c 5h 8d 6c 2s]
+Seat 1: Player 1 (button) (small blind) showed [3d 5c] and won (80) with a straight
+Seat 2: Player 2 (big blind) showed [2d 4h] and lost with two pair
+";
+}
@@ -0,0 +1,298 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::arena::{
+    Chips, GameState,
+    action::{Action, AgentAction, PlayedActionPayload},
+    game_state::Round,
+};
+
+use super::{Historian, HistorianError};
+
+/// The counters kept for one seat over every hand a [`StatsHistorian`]
+/// has seen. The usual hand tracker stats are worked out from them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    /// Hands the player was dealt into.
+    pub hands: usize,
+    /// Hands where the player put chips in preflop without being forced.
+    pub vpip_hands: usize,
+    /// Hands where the player bet or raised preflop.
+    pub pfr_hands: usize,
+    /// Bets and raises on any street.
+    pub bets: usize,
+    /// Calls of a bet on any street. Checks aren't counted.
+    pub calls: usize,
+    /// Hands where the player was still in when the flop was dealt.
+    pub saw_flop: usize,
+    /// Hands where the player reached showdown.
+    pub went_to_showdown: usize,
+    /// Hands where the player won some of the pot at showdown.
+    pub won_at_showdown: usize,
+}
+
+impl PlayerStats {
+    /// The fraction of hands the player voluntarily put chips in preflop.
+    pub fn vpip(&self) -> f64 {
+        ratio(self.vpip_hands, self.hands)
+    }
+
+    /// The fraction of hands the player raised preflop.
+    pub fn pfr(&self) -> f64 {
+        ratio(self.pfr_hands, self.hands)
+    }
+
+    /// Bets and raises for each call. `None` if the player never called.
+    pub fn aggression_factor(&self) -> Option<f64> {
+        (self.calls > 0).then(|| self.bets as f64 / self.calls as f64)
+    }
+
+    /// The fraction of the hands where the player saw the flop that they
+    /// went on to showdown.
+    pub fn went_to_showdown_rate(&self) -> f64 {
+        ratio(self.went_to_showdown, self.saw_flop)
+    }
+
+    /// The fraction of showdowns the player won some of the pot in.
+    pub fn won_at_showdown_rate(&self) -> f64 {
+        ratio(self.won_at_showdown, self.went_to_showdown)
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+/// What each player did in the hand being played. It's only added to the
+/// totals once the hand is complete.
+#[derive(Debug, Clone, Copy, Default)]
+struct HandFlags {
+    dealt: bool,
+    vpip: bool,
+    pfr: bool,
+    saw_flop: bool,
+    showdown: bool,
+    won_at_showdown: bool,
+}
+
+/// A historian that only keeps running counts for each seat, the VPIP,
+/// PFR, aggression, and showdown stats, rather than every action. That
+/// makes it cheap enough to leave on for millions of hands.
+///
+/// Cloning the historian shares the storage, so a
+/// `CloneHistorianGenerator` adds up the stats across every game it
+/// builds a historian for.
+///
+/// # Example
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use rs_poker::arena::agent::CallingAgent;
+/// use rs_poker::arena::historian::StatsHistorian;
+/// use rs_poker::arena::{Agent, Chips, GameState, HoldemSimulationBuilder};
+///
+/// let historian = StatsHistorian::new();
+/// let stats = historian.get_storage();
+/// let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+/// let mut sim = HoldemSimulationBuilder::default()
+///     .game_state(GameState::new_starting(
+///         vec![Chips::new(100); 2],
+///         Chips::new(10),
+///         Chips::new(5),
+///         Chips::ZERO,
+///         0,
+///     ))
+///     .agents(agents)
+///     .historians(vec![Box::new(historian)])
+///     .build()
+///     .unwrap();
+/// sim.run(&mut StdRng::seed_from_u64(311));
+///
+/// // The small blind called, the big blind only checked.
+/// let stats = stats.borrow();
+/// assert_eq!(1.0, stats[0].vpip());
+/// assert_eq!(0.0, stats[1].vpip());
+/// assert_eq!(1, stats[1].went_to_showdown);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatsHistorian {
+    stats: Rc<RefCell<Vec<PlayerStats>>>,
+    hand: Vec<HandFlags>,
+}
+
+impl StatsHistorian {
+    pub fn new() -> Self {
+        Self::new_with_storage(Rc::new(RefCell::new(vec![])))
+    }
+
+    /// Create a new `StatsHistorian` that adds into `stats`. The storage
+    /// grows to fit the largest table seen.
+    pub fn new_with_storage(stats: Rc<RefCell<Vec<PlayerStats>>>) -> Self {
+        Self {
+            stats,
+            hand: vec![],
+        }
+    }
+
+    /// The stats for each seat.
+    pub fn get_storage(&self) -> Rc<RefCell<Vec<PlayerStats>>> {
+        self.stats.clone()
+    }
+
+    fn played(&mut self, payload: &PlayedActionPayload) -> Result<(), HistorianError> {
+        if payload.action == AgentAction::Fold {
+            return Ok(());
+        }
+        let added = payload.final_player_bet - payload.starting_player_bet;
+        let raised = payload.final_bet > payload.starting_bet;
+        let preflop = payload.round == Round::Preflop;
+
+        let flags = &mut self.hand[payload.idx];
+        if preflop && added > Chips::ZERO {
+            flags.vpip = true;
+        }
+        if preflop && raised {
+            flags.pfr = true;
+        }
+
+        let mut stats = self.stats.try_borrow_mut()?;
+        if raised {
+            stats[payload.idx].bets += 1;
+        } else if added > Chips::ZERO {
+            stats[payload.idx].calls += 1;
+        }
+        Ok(())
+    }
+
+    fn complete(&mut self) -> Result<(), HistorianError> {
+        let mut stats = self.stats.try_borrow_mut()?;
+        for (player, flags) in stats.iter_mut().zip(&self.hand) {
+            if !flags.dealt {
+                continue;
+            }
+            player.hands += 1;
+            player.vpip_hands += usize::from(flags.vpip);
+            player.pfr_hands += usize::from(flags.pfr);
+            player.saw_flop += usize::from(flags.saw_flop);
+            player.went_to_showdown += usize::from(flags.showdown);
+            player.won_at_showdown += usize::from(flags.won_at_showdown);
+        }
+        Ok(())
+    }
+}
+
+impl Default for StatsHistorian {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Historian for StatsHistorian {
+    fn record_action(
+        &mut self,
+        _id: u128,
+        game_state: &GameState,
+        action: Action,
+    ) -> Result<(), HistorianError> {
+        match action {
+            Action::GameStart(_) => {
+                self.hand = vec![HandFlags::default(); game_state.num_players];
+                let mut stats = self.stats.try_borrow_mut()?;
+                if stats.len() < game_state.num_players {
+                    stats.resize(game_state.num_players, PlayerStats::default());
+                }
+            }
+            Action::DealStartingHand(payload) => self.hand[payload.idx].dealt = true,
+            Action::PlayedAction(payload) => self.played(&payload)?,
+            Action::FailedAction(payload) => self.played(&payload.result)?,
+            Action::RoundAdvance(Round::Flop) => {
+                let in_hand = game_state.player_active | game_state.player_all_in;
+                for idx in in_hand.ones() {
+                    self.hand[idx].saw_flop = true;
+                }
+            }
+            Action::Showdown(payload) => self.hand[payload.idx].showdown = true,
+            // Only pots won at showdown come with a rank.
+            Action::Award(payload) if payload.rank.is_some() => {
+                self.hand[payload.idx].won_at_showdown = true;
+            }
+            Action::RoundAdvance(Round::Complete) => self.complete()?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{
+        Agent, HoldemSimulationBuilder,
+        agent::{CallingAgent, FoldingAgent, VecReplayAgent},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_folder_has_no_vpip() {
+        let historian = StatsHistorian::new();
+        let stats = historian.get_storage();
+
+        // The button folds every hand, the small blind raises, and the big
+        // blind calls it down.
+        for seed in 0..5 {
+            let agents: Vec<Box<dyn Agent>> = vec![
+                Box::new(FoldingAgent),
+                Box::new(VecReplayAgent::new_with_default(
+                    vec![AgentAction::Bet(Chips::new(30))],
+                    AgentAction::Call,
+                )),
+                Box::new(CallingAgent),
+            ];
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(GameState::new_starting(
+                    vec![Chips::new(100); 3],
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    0,
+                ))
+                .agents(agents)
+                .historians(vec![Box::new(historian.clone())])
+                .build()
+                .unwrap();
+            sim.run(&mut StdRng::seed_from_u64(seed));
+        }
+
+        let stats = stats.borrow();
+        assert_eq!(3, stats.len());
+        assert!(stats.iter().all(|s| s.hands == 5));
+
+        assert_eq!(0, stats[0].vpip_hands);
+        assert_eq!(0.0, stats[0].vpip());
+        assert_eq!(0.0, stats[0].pfr());
+        assert_eq!(0, stats[0].saw_flop);
+        assert_eq!(None, stats[0].aggression_factor());
+
+        assert_eq!(1.0, stats[1].vpip());
+        assert_eq!(1.0, stats[1].pfr());
+        // Raising and then checking it down never calls anything.
+        assert_eq!(5, stats[1].bets);
+        assert_eq!(None, stats[1].aggression_factor());
+
+        assert_eq!(1.0, stats[2].vpip());
+        assert_eq!(0.0, stats[2].pfr());
+        assert_eq!(5, stats[2].calls);
+        assert_eq!(Some(0.0), stats[2].aggression_factor());
+
+        for player in &stats[1..] {
+            assert_eq!(5, player.saw_flop);
+            assert_eq!(1.0, player.went_to_showdown_rate());
+        }
+        assert!(stats[1].won_at_showdown + stats[2].won_at_showdown >= 5);
+    }
+}
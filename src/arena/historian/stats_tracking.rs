@@ -2,8 +2,8 @@ use std::{cell::RefCell, rc::Rc};
 
 use super::Historian;
 
-use crate::arena::GameState;
 use crate::arena::action::{Action, AgentAction, PlayedActionPayload};
+use crate::arena::{Chips, GameState};
 use crate::core::Rankable;
 
 /// Storage for tracking various poker player statistics
@@ -30,12 +30,12 @@ pub struct StatsStorage {
     // How many times each player has voluntarily put money in the pot
     pub vpip_count: Vec<usize>,
     // The total amount of money each player has voluntarily put in the pot
-    pub vpip_total: Vec<f32>,
+    pub vpip_total: Vec<Chips>,
 
     // How many times they were ahead
     pub vpip_ahead_count: Vec<usize>,
     // They ammount they were ahead and bet
-    pub vpip_ahead_total: Vec<f32>,
+    pub vpip_ahead_total: Vec<Chips>,
 
     // How many times they raised
     pub raise_count: Vec<usize>,
@@ -49,10 +49,10 @@ impl StatsStorage {
             actions_count: vec![0; num_players],
 
             vpip_count: vec![0; num_players],
-            vpip_total: vec![0.0; num_players],
+            vpip_total: vec![Chips::ZERO; num_players],
 
             vpip_ahead_count: vec![0; num_players],
-            vpip_ahead_total: vec![0.0; num_players],
+            vpip_ahead_total: vec![Chips::ZERO; num_players],
 
             raise_count: vec![0; num_players],
 
@@ -100,7 +100,7 @@ impl StatsTrackingHistorian {
 
         if let AgentAction::Bet(bet_ammount) = payload.action {
             let put_into_pot = bet_ammount - payload.starting_player_bet;
-            if put_into_pot > 0.0 {
+            if put_into_pot > Chips::ZERO {
                 // Played Action Payloads can't come from a forced bet
                 // so if there's a bet amount, it's a voluntary action
                 storage.vpip_count[payload.idx] += 1;
@@ -174,13 +174,14 @@ mod tests {
         let hist = Box::new(StatsTrackingHistorian::new_with_num_players(2));
         let storage = hist.get_storage();
 
-        let stacks = vec![100.0; 2];
+        let stacks = vec![Chips::new(100); 2];
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::<AllInAgent>::default() as Box<dyn Agent>,
             Box::<AllInAgent>::default() as Box<dyn Agent>,
         ];
 
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
         let mut rng = rand::rng();
 
         let mut sim = HoldemSimulationBuilder::default()
@@ -206,13 +207,14 @@ mod tests {
         let hist = Box::new(StatsTrackingHistorian::new_with_num_players(2));
         let storage = hist.get_storage();
 
-        let stacks = vec![100.0; 2];
+        let stacks = vec![Chips::new(100); 2];
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::<CallingAgent>::default() as Box<dyn Agent>,
             Box::<CallingAgent>::default() as Box<dyn Agent>,
         ];
 
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
 
         let mut rng = rand::rng();
 
@@ -239,13 +241,14 @@ mod tests {
         let hist = Box::new(StatsTrackingHistorian::new_with_num_players(2));
         let storage = hist.get_storage();
 
-        let stacks = vec![100.0; 2];
+        let stacks = vec![Chips::new(100); 2];
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::<FoldingAgent>::default() as Box<dyn Agent>,
             Box::<FoldingAgent>::default() as Box<dyn Agent>,
         ];
 
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
 
         let mut rng = rand::rng();
 
@@ -269,23 +272,27 @@ mod tests {
     fn test_replay_agents_had_raises_counted() {
         let hist = Box::new(StatsTrackingHistorian::new_with_num_players(2));
         let storage = hist.get_storage();
-        let stacks = vec![100.0; 2];
+        let stacks = vec![Chips::new(100); 2];
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::<VecReplayAgent>::new(VecReplayAgent::new_with_default(
-                vec![AgentAction::Bet(10.0), AgentAction::Bet(40.0)],
-                AgentAction::Bet(0.0),
+                vec![
+                    AgentAction::Bet(Chips::new(10)),
+                    AgentAction::Bet(Chips::new(40)),
+                ],
+                AgentAction::Bet(Chips::ZERO),
             )) as Box<dyn Agent>,
             Box::<VecReplayAgent>::new(VecReplayAgent::new_with_default(
                 vec![
-                    AgentAction::Bet(10.0),
-                    AgentAction::Bet(20.0),
-                    AgentAction::Bet(40.0),
+                    AgentAction::Bet(Chips::new(10)),
+                    AgentAction::Bet(Chips::new(20)),
+                    AgentAction::Bet(Chips::new(40)),
                 ],
-                AgentAction::Bet(0.0),
+                AgentAction::Bet(Chips::ZERO),
             )) as Box<dyn Agent>,
         ];
 
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
 
         let mut rng = rand::rng();
 
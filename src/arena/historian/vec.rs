@@ -70,7 +70,7 @@ impl Historian for VecHistorian {
 #[cfg(test)]
 mod tests {
     use crate::arena::{
-        Agent, HoldemSimulationBuilder,
+        Agent, Chips, HoldemSimulationBuilder,
         agent::{CallingAgent, RandomAgent},
     };
 
@@ -82,7 +82,7 @@ mod tests {
         let records = hist.get_storage();
         let mut rng = rand::rng();
 
-        let stacks = vec![100.0; 5];
+        let stacks = vec![Chips::new(100); 5];
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::<RandomAgent>::default(),
             Box::<RandomAgent>::default(),
@@ -90,7 +90,8 @@ mod tests {
             Box::<RandomAgent>::default(),
             Box::<RandomAgent>::default(),
         ];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
 
         let mut sim = HoldemSimulationBuilder::default()
             .game_state(game_state)
@@ -104,6 +105,44 @@ mod tests {
         assert!(records.borrow().len() > 10);
     }
 
+    #[test]
+    fn test_holdem_hole_cards_hidden() {
+        let hist = Box::new(VecHistorian::default());
+        let records = hist.get_storage();
+        let mut rng = rand::rng();
+
+        let stacks = vec![Chips::new(100); 3];
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::<CallingAgent>::default(),
+            Box::<CallingAgent>::default(),
+            Box::<CallingAgent>::default(),
+        ];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
+
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![hist])
+            .build()
+            .unwrap();
+
+        sim.run(&mut rng);
+
+        let deals: Vec<_> = records
+            .borrow()
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::DealStartingHand(payload) => Some(payload.clone()),
+                _ => None,
+            })
+            .collect();
+
+        // Two hole cards for each of the three players
+        assert_eq!(6, deals.len());
+        assert!(deals.iter().all(|p| !p.is_visible()));
+    }
+
     #[test]
     fn test_restarting_simulations() {
         // The first records.
@@ -111,13 +150,14 @@ mod tests {
         let records = hist.get_storage();
         let mut rng = rand::rng();
 
-        let stacks = vec![100.0; 2];
+        let stacks = vec![Chips::new(100); 2];
         let agents: Vec<Box<dyn Agent>> = vec![
             Box::<CallingAgent>::default(),
             Box::<CallingAgent>::default(),
         ];
 
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
 
         let mut sim = HoldemSimulationBuilder::default()
             .game_state(game_state)
@@ -9,19 +9,19 @@
 //!
 //! ```
 //! use rand::{SeedableRng, rngs::StdRng};
-//! use rs_poker::arena::HoldemSimulationBuilder;
 //! use rs_poker::arena::agent::CallingAgent;
 //! use rs_poker::arena::agent::RandomAgent;
 //! use rs_poker::arena::game_state::GameState;
+//! use rs_poker::arena::{Chips, HoldemSimulationBuilder};
 //!
-//! let stacks = vec![100.0, 100.0];
+//! let stacks = [100, 100].map(Chips::new).to_vec();
 //! let agents: Vec<Box<dyn rs_poker::arena::Agent>> = vec![
 //!     Box::<CallingAgent>::default(),
 //!     Box::<RandomAgent>::default(),
 //! ];
 //! let mut rng = StdRng::seed_from_u64(420);
 //!
-//! let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+//! let game_state = GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
 //! let mut sim = HoldemSimulationBuilder::default()
 //!     .game_state(game_state)
 //!     .agents(agents)
@@ -40,13 +40,13 @@
 //! with tabulated results
 //!
 //! ```
-//! use rs_poker::arena::AgentGenerator;
 //! use rs_poker::arena::agent::CallingAgentGenerator;
 //! use rs_poker::arena::agent::FoldingAgentGenerator;
 //! use rs_poker::arena::agent::RandomAgentGenerator;
 //! use rs_poker::arena::competition::HoldemCompetition;
 //! use rs_poker::arena::competition::StandardSimulationIterator;
 //! use rs_poker::arena::game_state::RandomGameStateGenerator;
+//! use rs_poker::arena::{AgentGenerator, Chips};
 //!
 //! // We are not limited to just heads up. We can have up to full ring of 9 agents.
 //! let agent_gens: Vec<Box<dyn AgentGenerator>> = vec![
@@ -55,7 +55,14 @@
 //!     Box::<RandomAgentGenerator>::default(),
 //! ];
 //!
-//! let game_state_gen = RandomGameStateGenerator::new(3, 100.0, 500.0, 10.0, 5.0, 0.0);
+//! let game_state_gen = RandomGameStateGenerator::new(
+//!     3,
+//!     Chips::new(100),
+//!     Chips::new(500),
+//!     Chips::new(10),
+//!     Chips::new(5),
+//!     Chips::ZERO,
+//! );
 //! let sim_gen = StandardSimulationIterator::new(agent_gens, vec![], game_state_gen);
 //!
 //! let mut competition = HoldemCompetition::new(sim_gen);
@@ -73,10 +80,10 @@
 //! game state continues on until one player has all the money.
 //!
 //! ```
-//! use rs_poker::arena::AgentGenerator;
 //! use rs_poker::arena::agent::RandomAgentGenerator;
 //! use rs_poker::arena::competition::SingleTableTournamentBuilder;
 //! use rs_poker::arena::game_state::GameState;
+//! use rs_poker::arena::{AgentGenerator, Chips};
 //!
 //! // We are not limited to just heads up. We can have up to full ring of 9 agents.
 //! let agent_gens: Vec<Box<dyn AgentGenerator>> = vec![
@@ -85,10 +92,11 @@
 //!     Box::<RandomAgentGenerator>::default(),
 //!     Box::<RandomAgentGenerator>::default(),
 //! ];
-//! let stacks = vec![100.0; 4];
+//! let stacks = vec![Chips::new(100); 4];
 //!
 //! // This is the starting game state.
-//! let game_state = GameState::new_starting(stacks, 10.0, 5.0, 1.0, 0);
+//! let game_state =
+//!     GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::new(1), 0);
 //!
 //! let tournament = SingleTableTournamentBuilder::default()
 //!     .agent_generators(agent_gens)
@@ -99,6 +107,43 @@
 //! let results = tournament.run().unwrap();
 //! ```
 //!
+//! ## `CashGameSession` Example
+//!
+//! A cash game session plays a number of hands with the same agents,
+//! keeping or topping up stacks between hands, and reports each agent's
+//! net winnings.
+//!
+//! ```
+//! use rand::{SeedableRng, rngs::StdRng};
+//! use rs_poker::arena::agent::{CallingAgentGenerator, RandomAgentGenerator};
+//! use rs_poker::arena::competition::{CashGameSessionBuilder, StackPolicy};
+//! use rs_poker::arena::game_state::GameState;
+//! use rs_poker::arena::{AgentGenerator, Chips};
+//!
+//! let agent_gens: Vec<Box<dyn AgentGenerator>> = vec![
+//!     Box::<CallingAgentGenerator>::default(),
+//!     Box::<RandomAgentGenerator>::default(),
+//! ];
+//! let game_state = GameState::new_starting(
+//!     vec![Chips::new(100); 2],
+//!     Chips::new(2),
+//!     Chips::new(1),
+//!     Chips::ZERO,
+//!     0,
+//! );
+//!
+//! let session = CashGameSessionBuilder::default()
+//!     .agent_generators(agent_gens)
+//!     .starting_game_state(game_state)
+//!     .stack_policy(StackPolicy::TopUpTo(Chips::new(100)))
+//!     .num_hands(20)
+//!     .build()
+//!     .unwrap();
+//!
+//! let results = session.run(&mut StdRng::seed_from_u64(20)).unwrap();
+//! println!("{:?} {:?}", results.net_winnings(), results.variance());
+//! ```
+//!
 //! ##  Counter Factual Regret Minimization (CFR) Example
 //!
 //! rs-poker has an implementation of CFR that can be used to implement agents
@@ -122,19 +167,35 @@
 //! ```
 pub mod action;
 pub mod agent;
+pub mod betting;
 pub mod cfr;
+pub mod chip_ev;
+pub mod chips;
 pub mod competition;
 pub mod errors;
 pub mod game_state;
 pub mod historian;
+pub mod rake;
+pub mod realization;
+pub mod replay;
 pub mod sim_builder;
 pub mod simulation;
+pub mod stacked_deck;
+pub mod stud;
 
 #[cfg(any(test, feature = "arena-test-util"))]
 pub mod test_util;
 
 pub use agent::{Agent, AgentGenerator, CloneAgentGenerator};
-pub use game_state::{CloneGameStateGenerator, GameState, GameStateGenerator};
+pub use betting::{AnteStyle, BettingStructure, IllegalRaisePolicy, LegalActions};
+pub use chips::Chips;
+pub use game_state::{
+    CloneGameStateGenerator, GameState, GameStateDiff, GameStateGenerator, Variant,
+};
 pub use historian::{CloneHistorianGenerator, Historian, HistorianError, HistorianGenerator};
+pub use rake::Rake;
+pub use replay::replay;
 pub use sim_builder::HoldemSimulationBuilder;
-pub use simulation::HoldemSimulation;
+pub use simulation::{HandResult, HoldemSimulation, SimulationSnapshot};
+pub use stacked_deck::StackedDeck;
+pub use stud::{StudSimulation, StudSimulationBuilder};
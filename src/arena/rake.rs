@@ -0,0 +1,259 @@
+use super::Chips;
+
+/// How much the house takes from each pot.
+///
+/// The rake is a percentage of the pot, optionally capped at a maximum
+/// amount. The rake is rounded down to a whole chip. With
+/// `no_flop_no_drop` set, hands that end before the flop is dealt aren't
+/// raked at all.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rake {
+    /// Fraction of the pot taken. `0.05` is 5%.
+    pub percent: f32,
+    /// The most that will be taken from a single hand.
+    pub cap: Option<Chips>,
+    /// Don't rake hands that end before the flop.
+    pub no_flop_no_drop: bool,
+}
+
+impl Rake {
+    /// Create a new rake taking `percent` of the pot up to `cap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::arena::{Chips, Rake};
+    ///
+    /// // 5% up to 3 big blinds with a 10 chip big blind.
+    /// let rake = Rake::new(0.05, Some(Chips::new(30)));
+    /// assert_eq!(Chips::new(5), rake.amount(Chips::new(100)));
+    /// assert_eq!(Chips::new(30), rake.amount(Chips::new(1000)));
+    /// // 5% of 50 is 2.5, which rounds down.
+    /// assert_eq!(Chips::new(2), rake.amount(Chips::new(50)));
+    /// ```
+    pub fn new(percent: f32, cap: Option<Chips>) -> Self {
+        Rake {
+            percent,
+            cap,
+            no_flop_no_drop: false,
+        }
+    }
+
+    /// Set whether hands that end before the flop are raked.
+    pub fn no_flop_no_drop(mut self, no_flop_no_drop: bool) -> Self {
+        self.no_flop_no_drop = no_flop_no_drop;
+        self
+    }
+
+    /// The amount of rake taken from a pot of `pot` chips, assuming the pot
+    /// is eligible to be raked.
+    pub fn amount(&self, pot: Chips) -> Chips {
+        let rake = pot.scale(self.percent).max(Chips::ZERO);
+        match self.cap {
+            Some(cap) => rake.min(cap),
+            None => rake,
+        }
+    }
+}
+
+/// Expected chips won from an all-in pot after the rake is taken.
+///
+/// `equity` is the share of the pot the player expects to win, between 0.0
+/// and 1.0. Since an all-in pot is always run out to showdown the flop is
+/// seen and `no_flop_no_drop` doesn't apply. This is an expectation, so
+/// unlike [`Rake::amount`] the rake isn't rounded to whole chips.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::arena::{Rake, rake::raked_equity_chips};
+///
+/// let rake = Rake::new(0.05, None);
+/// // 60% of a 200 chip pot with 10 chips raked
+/// assert!((114.0 - raked_equity_chips(0.6, 200.0, &rake)).abs() < 1e-6);
+/// ```
+pub fn raked_equity_chips(equity: f64, pot: f64, rake: &Rake) -> f64 {
+    let raked = (pot * f64::from(rake.percent)).max(0.0);
+    let raked = match rake.cap {
+        Some(cap) => raked.min(f64::from(cap)),
+        None => raked,
+    };
+    equity * (pot - raked)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{
+        Agent, GameState, HoldemSimulationBuilder,
+        action::Action,
+        agent::{AllInAgent, FoldingAgent, RandomAgent},
+        historian::{HistoryRecord, VecHistorian},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_uncapped_rake() {
+        let rake = Rake::new(0.05, None);
+        assert_eq!(Chips::new(50), rake.amount(Chips::new(1000)));
+        // 0.05 as an f32 is a hair over 0.05.
+        assert_relative_eq!(
+            475.0,
+            raked_equity_chips(0.5, 1000.0, &rake),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_capped_rake() {
+        let rake = Rake::new(0.05, Some(Chips::new(3)));
+        assert_eq!(Chips::new(3), rake.amount(Chips::new(1000)));
+        assert_relative_eq!(997.0, raked_equity_chips(1.0, 1000.0, &rake));
+        assert_relative_eq!(498.5, raked_equity_chips(0.5, 1000.0, &rake));
+
+        // Under the cap the percentage applies.
+        assert_eq!(Chips::new(1), rake.amount(Chips::new(20)));
+        // 5% of 30 is 1.5 chips, and the half chip isn't taken.
+        assert_eq!(Chips::new(1), rake.amount(Chips::new(30)));
+    }
+
+    #[test]
+    fn test_no_rake() {
+        let rake = Rake::default();
+        assert_eq!(Chips::ZERO, rake.amount(Chips::new(1000)));
+        assert_relative_eq!(250.0, raked_equity_chips(0.25, 1000.0, &rake));
+    }
+
+    #[test]
+    fn test_zero_equity() {
+        let rake = Rake::new(0.1, Some(Chips::new(5)));
+        assert_relative_eq!(0.0, raked_equity_chips(0.0, 1000.0, &rake));
+    }
+
+    #[test]
+    fn test_raked_equity_keeps_precision() {
+        // 2^24 + 1 can't be held exactly in an f32.
+        let rake = Rake::new(0.5, None);
+        assert_eq!(8_388_608.5, raked_equity_chips(1.0, 16_777_217.0, &rake));
+    }
+
+    fn run_raked(
+        agents: Vec<Box<dyn Agent>>,
+        stacks: Vec<Chips>,
+        rake: Rake,
+        seed: u64,
+    ) -> Vec<HistoryRecord> {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .rake(rake)
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(seed));
+
+        // Every chip bet is either awarded or raked.
+        let game_state = &sim.game_state;
+        let awarded: Chips = game_state.player_winnings.iter().copied().sum();
+        assert_eq!(game_state.total_pot, awarded + game_state.total_rake);
+        let start: Chips = game_state.starting_stacks.iter().copied().sum();
+        let end: Chips = game_state.stacks.iter().copied().sum();
+        assert_eq!(start, end + game_state.total_rake);
+
+        records.take()
+    }
+
+    fn awards(records: &[HistoryRecord]) -> Vec<Chips> {
+        records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::Award(award) => Some(award.award_amount),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_simulation_rake_capped() {
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(AllInAgent), Box::new(AllInAgent)];
+        let records = run_raked(
+            agents,
+            vec![Chips::new(100); 2],
+            Rake::new(0.05, Some(Chips::new(3))),
+            279,
+        );
+        let last = records.last().unwrap();
+        assert_eq!(Chips::new(3), last.after_game_state.total_rake);
+        assert_eq!(Chips::new(197), awards(&records).into_iter().sum());
+    }
+
+    #[test]
+    fn test_simulation_no_flop_no_drop() {
+        let agents =
+            || -> Vec<Box<dyn Agent>> { vec![Box::new(AllInAgent), Box::new(FoldingAgent)] };
+        let records = run_raked(
+            agents(),
+            vec![Chips::new(100); 2],
+            Rake::new(0.05, Some(Chips::new(3))).no_flop_no_drop(true),
+            279,
+        );
+        assert_eq!(
+            Chips::ZERO,
+            records.last().unwrap().after_game_state.total_rake
+        );
+        assert_eq!(vec![Chips::new(110)], awards(&records));
+
+        // Without no flop no drop the same hand is raked, but only on the
+        // called 20. The other 90 of the all in goes back untouched.
+        let records = run_raked(
+            agents(),
+            vec![Chips::new(100); 2],
+            Rake::new(0.05, Some(Chips::new(3))),
+            279,
+        );
+        assert_eq!(
+            Chips::new(1),
+            records.last().unwrap().after_game_state.total_rake
+        );
+        assert_eq!(vec![Chips::new(109)], awards(&records));
+    }
+
+    #[test]
+    fn test_simulation_rake_conserves_chips() {
+        for seed in 0..50 {
+            let agents: Vec<Box<dyn Agent>> = (0..4)
+                .map(|_| Box::<RandomAgent>::default() as Box<dyn Agent>)
+                .collect();
+            run_raked(
+                agents,
+                [100, 50, 200, 75].map(Chips::new).to_vec(),
+                Rake::new(0.05, Some(Chips::new(30))),
+                seed,
+            );
+        }
+    }
+
+    #[test]
+    fn test_uncalled_bet_not_raked() {
+        // The big stack's extra 100 is returned without rake.
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(AllInAgent), Box::new(AllInAgent)];
+        let records = run_raked(
+            agents,
+            [200, 100].map(Chips::new).to_vec(),
+            Rake::new(0.05, None),
+            279,
+        );
+        assert_eq!(
+            Chips::new(10),
+            records.last().unwrap().after_game_state.total_rake
+        );
+    }
+}
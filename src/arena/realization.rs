@@ -0,0 +1,272 @@
+//! Estimate how much of its raw equity a hand actually realizes once there
+//! is betting after the flop.
+//!
+//! Raw equity assumes both players get to see every card for free. Real
+//! hands get bet off of their equity, or get value from worse hands, so the
+//! share of the pot they end up with differs. The ratio between the two is
+//! the equity realization factor, `R = realized equity / raw equity`.
+use rand::Rng;
+
+use crate::{
+    core::{Card, FlatHand, Hand, PlayerBitSet},
+    holdem::hand_vs_range_equity,
+};
+
+use super::{
+    AgentGenerator, Chips, GameState, HoldemSimulationBuilder,
+    game_state::{Round, RoundData},
+};
+
+/// The result of estimating equity realization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquityRealizationResult {
+    /// Hero's all in equity against the range.
+    pub raw_equity: f64,
+    /// The share of the flop pot hero won on average after playing out
+    /// the hand.
+    pub realized_equity: f64,
+    /// `realized_equity / raw_equity`
+    pub realization: f64,
+}
+
+/// Estimate the equity realization of a hand against a range by simulating
+/// heads up play from the flop with the supplied agents.
+///
+/// Hero always sits in seat 0 and villain in seat 1. Each simulated hand
+/// villain is dealt a random combo from their range that doesn't conflict
+/// with hero's cards or the board.
+pub struct EquityRealizationEstimator {
+    hero_hand: Hand,
+    villain_range: Vec<FlatHand>,
+    board: Vec<Card>,
+    hero_agent: Box<dyn AgentGenerator>,
+    villain_agent: Box<dyn AgentGenerator>,
+    pot: Chips,
+    stack: Chips,
+    big_blind: Chips,
+    hero_in_position: bool,
+    equity_sims: usize,
+}
+
+impl EquityRealizationEstimator {
+    /// Create a new estimator. By default the pot on the flop is 10 big
+    /// blinds with 95 big blinds behind, and hero is in position.
+    pub fn new(
+        hero_hand: Hand,
+        villain_range: Vec<FlatHand>,
+        board: Vec<Card>,
+        hero_agent: Box<dyn AgentGenerator>,
+        villain_agent: Box<dyn AgentGenerator>,
+    ) -> Self {
+        Self {
+            hero_hand,
+            villain_range,
+            board,
+            hero_agent,
+            villain_agent,
+            pot: Chips::new(20),
+            stack: Chips::new(190),
+            big_blind: Chips::new(2),
+            hero_in_position: true,
+            equity_sims: 1_000,
+        }
+    }
+
+    /// Set the size of the pot when the flop is dealt.
+    pub fn pot(mut self, pot: Chips) -> Self {
+        self.pot = pot;
+        self
+    }
+
+    /// Set how much each player has behind on the flop.
+    pub fn stack(mut self, stack: Chips) -> Self {
+        self.stack = stack;
+        self
+    }
+
+    /// Set the big blind, this is the minimum bet postflop.
+    pub fn big_blind(mut self, big_blind: Chips) -> Self {
+        self.big_blind = big_blind;
+        self
+    }
+
+    /// Set whether hero acts last after the flop.
+    pub fn hero_in_position(mut self, hero_in_position: bool) -> Self {
+        self.hero_in_position = hero_in_position;
+        self
+    }
+
+    /// Set how many simulations are used per villain combo for the raw
+    /// equity.
+    pub fn equity_sims(mut self, equity_sims: usize) -> Self {
+        self.equity_sims = equity_sims;
+        self
+    }
+
+    /// Play `num_hands` hands and estimate the realization factor.
+    ///
+    /// The raw equity is simulated with `rand` too, so a seeded rng gives
+    /// the same result every time.
+    ///
+    /// Returns `None` if hero's hand shares a card with the board or no
+    /// villain combo can be dealt.
+    pub fn estimate<R: Rng>(
+        &self,
+        num_hands: usize,
+        rand: &mut R,
+    ) -> Option<EquityRealizationResult> {
+        let raw = hand_vs_range_equity(
+            self.hero_hand,
+            &self.villain_range,
+            &self.board,
+            self.equity_sims,
+            rand,
+        )
+        .ok()?;
+        if raw.combos.is_empty() {
+            return None;
+        }
+
+        let mut total_share = 0.0;
+        for _ in 0..num_hands {
+            let combo = &raw.combos[rand.random_range(0..raw.combos.len())].0;
+            let game_state = self.flop_game_state(combo);
+
+            let agents = vec![
+                self.hero_agent.generate(&game_state),
+                self.villain_agent.generate(&game_state),
+            ];
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(game_state)
+                .agents(agents)
+                .build()
+                .ok()?;
+            sim.run(rand);
+
+            // Chips won back from the pot, relative to the pot on the flop.
+            total_share += sim.game_state.player_reward(0).as_f64() / self.pot.as_f64();
+        }
+
+        let realized_equity = total_share / num_hands as f64;
+        Some(EquityRealizationResult {
+            raw_equity: raw.equity,
+            realized_equity,
+            realization: realized_equity / raw.equity,
+        })
+    }
+
+    fn flop_game_state(&self, villain: &FlatHand) -> GameState {
+        let mut hero_hand = self.hero_hand;
+        hero_hand.extend(self.board.iter().cloned());
+        let mut villain_hand = Hand::new_with_cards(self.board.clone());
+        villain_hand.extend(villain.iter().cloned());
+
+        // Heads up the dealer acts last after the flop.
+        let dealer_idx = if self.hero_in_position { 0 } else { 1 };
+        let round_data = RoundData::new(2, self.big_blind, PlayerBitSet::new(2), 1 - dealer_idx);
+
+        GameState::new(
+            Round::Flop,
+            round_data,
+            self.board.clone(),
+            vec![hero_hand, villain_hand],
+            vec![self.stack; 2],
+            self.pot.split(2),
+            self.big_blind,
+            self.big_blind / 2,
+            Chips::ZERO,
+            dealer_idx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::{
+        arena::{Agent, action::AgentAction},
+        holdem::RangeParser,
+    };
+
+    use super::*;
+
+    /// Checks when it can and folds to any bet.
+    #[derive(Clone)]
+    struct CheckFoldAgent;
+
+    impl Agent for CheckFoldAgent {
+        fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+            if game_state.current_round_bet() > game_state.current_round_current_player_bet() {
+                AgentAction::Fold
+            } else {
+                AgentAction::Bet(game_state.current_round_bet())
+            }
+        }
+    }
+
+    /// Bets half pot when checked to in position, otherwise checks or
+    /// calls.
+    #[derive(Clone)]
+    struct StabAgent;
+
+    impl Agent for StabAgent {
+        fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+            let in_position = game_state.to_act_idx() == game_state.dealer_idx;
+            if game_state.current_round_bet() == Chips::ZERO && in_position {
+                AgentAction::Bet(game_state.total_pot / 2)
+            } else {
+                AgentAction::Bet(game_state.current_round_bet())
+            }
+        }
+    }
+
+    fn estimator(hero_in_position: bool) -> EquityRealizationEstimator {
+        let hero = Hand::new_from_str("9s8s").unwrap();
+        let board: Vec<Card> = Hand::new_from_str("Th7d2c").unwrap().iter().collect();
+        let range = RangeParser::parse_many("AK,QQ,JTs").unwrap();
+
+        EquityRealizationEstimator::new(
+            hero,
+            range,
+            board,
+            Box::new(crate::arena::CloneAgentGenerator::new(CheckFoldAgent)),
+            Box::new(crate::arena::CloneAgentGenerator::new(StabAgent)),
+        )
+        .hero_in_position(hero_in_position)
+        .equity_sims(200)
+    }
+
+    #[test]
+    fn test_out_of_position_realizes_less() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let oop = estimator(false).estimate(200, &mut rng).unwrap();
+        let ip = estimator(true).estimate(200, &mut rng).unwrap();
+
+        assert!(oop.raw_equity > 0.0);
+        assert!(oop.realization < 1.0);
+        assert!(oop.realization < ip.realization);
+    }
+
+    #[test]
+    fn test_seeded_estimate_repeats() {
+        let estimator = estimator(true);
+        let first = estimator.estimate(50, &mut StdRng::seed_from_u64(210));
+        let second = estimator.estimate(50, &mut StdRng::seed_from_u64(210));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_no_valid_combos() {
+        let hero = Hand::new_from_str("AsAh").unwrap();
+        let range = RangeParser::parse_one("AsAh").unwrap();
+        let estimator = EquityRealizationEstimator::new(
+            hero,
+            range,
+            vec![],
+            Box::new(crate::arena::CloneAgentGenerator::new(CheckFoldAgent)),
+            Box::new(crate::arena::CloneAgentGenerator::new(StabAgent)),
+        );
+        assert_eq!(None, estimator.estimate(10, &mut rand::rng()));
+    }
+}
@@ -0,0 +1,283 @@
+//! Rebuild the game states of a simulation from its recorded actions.
+//!
+//! A [`super::historian::VecHistorian`] keeps every action a simulation
+//! took. Applying those actions again one at a time to the starting game
+//! state gives every state in between, which is what a step through debugger
+//! needs.
+use super::{
+    GameState,
+    action::{Action, AgentAction, ForcedBetType, PlayedActionPayload},
+    game_state::Round,
+    historian::HistoryRecord,
+};
+
+/// Apply each recorded action to `initial` in order and return the game
+/// state after every one of them. The last state is the same as the
+/// simulation's final `game_state`.
+///
+/// `initial` should be a clone of the game state the simulation was built
+/// with.
+///
+/// # Panics
+///
+/// Panics if a recorded bet can't be applied, meaning the records didn't
+/// come from a simulation started with `initial`.
+///
+/// # Examples
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use rs_poker::arena::agent::{CallingAgent, RandomAgent};
+/// use rs_poker::arena::historian::VecHistorian;
+/// use rs_poker::arena::{Agent, Chips, GameState, HoldemSimulationBuilder, replay};
+///
+/// let initial = GameState::new_starting(
+///     vec![Chips::new(100); 3],
+///     Chips::new(10),
+///     Chips::new(5),
+///     Chips::ZERO,
+///     0,
+/// );
+/// let agents: Vec<Box<dyn Agent>> = vec![
+///     Box::<CallingAgent>::default(),
+///     Box::<RandomAgent>::default(),
+///     Box::<RandomAgent>::default(),
+/// ];
+/// let historian = VecHistorian::new();
+/// let records = historian.get_storage();
+/// let mut sim = HoldemSimulationBuilder::default()
+///     .game_state(initial.clone())
+///     .agents(agents)
+///     .historians(vec![Box::new(historian)])
+///     .build()
+///     .unwrap();
+/// sim.run(&mut StdRng::seed_from_u64(270));
+///
+/// let states = replay(initial, &records.borrow());
+/// assert_eq!(records.borrow().len(), states.len());
+/// assert_eq!(&sim.game_state, states.last().unwrap());
+/// ```
+pub fn replay(initial: GameState, records: &[HistoryRecord]) -> Vec<GameState> {
+    let mut game_state = initial;
+    records
+        .iter()
+        .map(|record| {
+            apply_action(&mut game_state, &record.action);
+            game_state.clone()
+        })
+        .collect()
+}
+
+/// Make the same change to `game_state` that the simulation made when it
+/// recorded `action`.
+fn apply_action(game_state: &mut GameState, action: &Action) {
+    match action {
+        // The starting state already has the blinds and the stacks. Burned
+        // cards only come out of the deck, which isn't in the game state, the
+        // game state only has the first board, and showing a hand or the pot
+        // size doesn't change anything.
+        Action::GameStart(_)
+        | Action::PlayerSit(_)
+        | Action::BurnCard(_)
+        | Action::DealRunout(_)
+        | Action::Showdown(_)
+        | Action::PotUpdate(_) => {}
+        Action::DealStartingHand(payload) => {
+            game_state.hands[payload.idx].insert(payload.card);
+        }
+        Action::RoundAdvance(round) => {
+            if *round == Round::Complete {
+                game_state.complete();
+            } else {
+                game_state.advance_round();
+            }
+        }
+        Action::ForcedBet(payload) => {
+            game_state.round_data.to_act_idx = payload.idx;
+            game_state
+                .do_bet(payload.bet, true)
+                .expect("Forced bets can always be made");
+            match payload.forced_bet_type {
+                ForcedBetType::Ante => game_state.round_data.needs_action.disable(payload.idx),
+                ForcedBetType::SmallBlind => game_state.sb_posted = true,
+                // A dead small blind is never posted.
+                ForcedBetType::BigBlind => {
+                    game_state.sb_posted = true;
+                    game_state.bb_posted = true;
+                }
+                ForcedBetType::BringIn | ForcedBetType::Straddle => {}
+            }
+            game_state.forced_bets.push(payload.clone());
+        }
+        Action::PlayedAction(payload) => apply_played(game_state, payload),
+        // Only the result of a failed action changed the game.
+        Action::FailedAction(payload) => apply_played(game_state, &payload.result),
+        Action::DealCommunity(card) => {
+            for hand in &mut game_state.hands {
+                hand.insert(*card);
+            }
+            game_state.board.push(*card);
+        }
+        Action::Award(payload) => {
+            game_state.award(payload.idx, payload.award_amount);
+            game_state.total_rake += payload.rake;
+        }
+    }
+}
+
+fn apply_played(game_state: &mut GameState, payload: &PlayedActionPayload) {
+    game_state.round_data.to_act_idx = payload.idx;
+    if payload.action == AgentAction::Fold {
+        game_state.fold();
+    } else {
+        game_state
+            .do_bet(payload.final_player_bet, false)
+            .expect("Recorded bets should replay");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{
+        Agent, Chips, HoldemSimulationBuilder, Rake,
+        agent::{AllInAgent, CallingAgent, RandomAgent},
+        historian::VecHistorian,
+    };
+
+    use super::*;
+
+    fn run_and_replay(initial: GameState, agents: Vec<Box<dyn Agent>>, seed: u64) {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(initial.clone())
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(seed));
+
+        let records = records.borrow();
+        let states = replay(initial, &records);
+        for (record, state) in records.iter().zip(states.iter()) {
+            assert_eq!(record.after_game_state.stacks, state.stacks);
+            assert_eq!(record.after_game_state.round, state.round);
+        }
+        let last = states.last().unwrap();
+        assert_eq!(sim.game_state.stacks, last.stacks);
+        assert_eq!(sim.game_state.player_winnings, last.player_winnings);
+        assert_eq!(&sim.game_state, last);
+    }
+
+    #[test]
+    fn test_replay_random_games() {
+        for seed in 0..50 {
+            let initial = GameState::new_starting(
+                [100, 50, 200, 75].map(Chips::new).to_vec(),
+                Chips::new(10),
+                Chips::new(5),
+                Chips::new(1),
+                1,
+            );
+            let agents: Vec<Box<dyn Agent>> = (0..4)
+                .map(|_| Box::<RandomAgent>::default() as Box<dyn Agent>)
+                .collect();
+            run_and_replay(initial, agents, seed);
+        }
+    }
+
+    #[test]
+    fn test_replay_all_in_showdown() {
+        let initial = GameState::new_starting(
+            [50, 150, 150].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(AllInAgent),
+            Box::new(AllInAgent),
+            Box::new(CallingAgent),
+        ];
+        run_and_replay(initial, agents, 7);
+    }
+
+    #[test]
+    fn test_replay_runouts() {
+        for seed in 0..20 {
+            let initial = GameState::new_starting(
+                [100, 150, 150].map(Chips::new).to_vec(),
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            );
+            let historian = VecHistorian::new();
+            let records = historian.get_storage();
+            let agents: Vec<Box<dyn Agent>> = vec![
+                Box::new(AllInAgent),
+                Box::new(AllInAgent),
+                Box::new(AllInAgent),
+            ];
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(initial.clone())
+                .agents(agents)
+                .runouts(3)
+                .historians(vec![Box::new(historian)])
+                .build()
+                .unwrap();
+            sim.run(&mut StdRng::seed_from_u64(seed));
+
+            let records = records.borrow();
+            let states = replay(initial, &records);
+            assert_eq!(&sim.game_state, states.last().unwrap());
+
+            // Everyone is all in preflop, so each extra board is five cards.
+            let mut boards = vec![sim.game_state.board.clone()];
+            for record in records.iter() {
+                if let Action::DealRunout(payload) = &record.action {
+                    if boards.len() == payload.runout {
+                        boards.push(vec![]);
+                    }
+                    boards[payload.runout].push(payload.card);
+                }
+            }
+            assert_eq!(3, boards.len());
+            assert!(boards.iter().all(|board| board.len() == 5));
+
+            // Every award is for a hand on one of the recorded boards.
+            for record in records.iter() {
+                if let Action::Award(payload) = &record.action {
+                    let hand = payload.hand.unwrap();
+                    assert!(
+                        boards
+                            .iter()
+                            .any(|board| board.iter().all(|card| hand.contains(card))),
+                        "seed {seed}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_replay_rake() {
+        for seed in 0..20 {
+            let mut initial = GameState::new_starting(
+                [100, 50, 200].map(Chips::new).to_vec(),
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            );
+            initial.rake = Rake::new(0.05, Some(Chips::new(3)));
+            let agents: Vec<Box<dyn Agent>> = (0..3)
+                .map(|_| Box::<RandomAgent>::default() as Box<dyn Agent>)
+                .collect();
+            run_and_replay(initial, agents, seed);
+        }
+    }
+}
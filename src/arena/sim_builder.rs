@@ -1,9 +1,12 @@
+use std::collections::HashMap;
+
 use rand::Rng;
 
-use crate::core::{CardBitSet, Deck};
+use crate::core::{Card, CardBitSet, Deck};
 
 use super::{
-    Agent, GameState, HoldemSimulation, agent::FoldingAgent, errors::HoldemSimulationError,
+    Agent, AnteStyle, BettingStructure, Chips, GameState, HoldemSimulation, IllegalRaisePolicy,
+    Rake, StackedDeck, Variant, agent::FoldingAgent, errors::HoldemSimulationError,
     historian::Historian,
 };
 
@@ -24,6 +27,27 @@ fn build_deck(game_state: &GameState) -> Deck {
     d.into() // convert the bitset into a deck
 }
 
+/// How many more cards the deck has to deal to finish the hand. That's the
+/// hole cards still to come for every player, the rest of the board, and a
+/// burn for each street left if burning.
+fn cards_needed(game_state: &GameState, burn_cards: bool) -> usize {
+    let board = game_state.board.len();
+    let hole_cards: usize = game_state
+        .hands
+        .iter()
+        .map(|hand| {
+            let held = hand.count().saturating_sub(board);
+            game_state.variant.hole_cards().saturating_sub(held)
+        })
+        .sum();
+    let burns = if burn_cards {
+        [0, 3, 4].iter().filter(|dealt| **dealt >= board).count()
+    } else {
+        0
+    };
+    hole_cards + 5usize.saturating_sub(board) + burns
+}
+
 fn build_agents(num_agents: usize) -> Vec<Box<dyn Agent>> {
     (0..num_agents)
         .map(|_| -> Box<dyn Agent> { Box::<FoldingAgent>::default() })
@@ -50,9 +74,15 @@ fn build_agents(num_agents: usize) -> Vec<Box<dyn Agent>> {
 /// ## Examples
 ///
 /// ```
-/// use rs_poker::arena::{GameState, HoldemSimulationBuilder};
+/// use rs_poker::arena::{Chips, GameState, HoldemSimulationBuilder};
 ///
-/// let game_state = GameState::new_starting(vec![100.0; 5], 2.0, 1.0, 0.0, 3);
+/// let game_state = GameState::new_starting(
+///     vec![Chips::new(100); 5],
+///     Chips::new(2),
+///     Chips::new(1),
+///     Chips::ZERO,
+///     3,
+/// );
 /// let sim = HoldemSimulationBuilder::default()
 ///     .game_state(game_state)
 ///     .build()
@@ -63,9 +93,15 @@ fn build_agents(num_agents: usize) -> Vec<Box<dyn Agent>> {
 ///
 /// ```
 /// use rand::{SeedableRng, rngs::StdRng};
-/// use rs_poker::arena::{GameState, HoldemSimulationBuilder};
+/// use rs_poker::arena::{Chips, GameState, HoldemSimulationBuilder};
 ///
-/// let game_state = GameState::new_starting(vec![100.0; 5], 2.0, 1.0, 0.0, 3);
+/// let game_state = GameState::new_starting(
+///     vec![Chips::new(100); 5],
+///     Chips::new(2),
+///     Chips::new(1),
+///     Chips::ZERO,
+///     3,
+/// );
 /// let sim = HoldemSimulationBuilder::default()
 ///     .game_state(game_state)
 ///     .build()
@@ -76,16 +112,39 @@ pub struct HoldemSimulationBuilder {
     historians: Vec<Box<dyn Historian>>,
     game_state: Option<GameState>,
     deck: Option<Deck>,
+    stacked_deck: Option<StackedDeck>,
+    hole_cards: HashMap<usize, [Card; 2]>,
+    seating: Option<Vec<usize>>,
+    betting_structure: Option<BettingStructure>,
+    ante_style: Option<AnteStyle>,
+    variant: Option<Variant>,
+    rake: Option<Rake>,
+    straddle: Option<Chips>,
+    small_blind_position: Option<usize>,
+    big_blind_position: Option<usize>,
+    clamp_over_limit_bets: bool,
+    check_chip_range: bool,
+    illegal_raise_policy: IllegalRaisePolicy,
+    runouts: u8,
+    max_actions: Option<usize>,
+    burn_cards: bool,
+    muck_losing_hands: bool,
     panic_on_historian_error: bool,
 }
 
 /// # Examples
 /// ```
 /// use rand::{SeedableRng, rngs::StdRng};
-/// use rs_poker::arena::{Agent, agent::FoldingAgent};
+/// use rs_poker::arena::{Agent, Chips, agent::FoldingAgent};
 /// use rs_poker::arena::{GameState, HoldemSimulationBuilder};
 ///
-/// let game_state = GameState::new_starting(vec![100.0; 5], 2.0, 1.0, 0.0, 3);
+/// let game_state = GameState::new_starting(
+///     vec![Chips::new(100); 5],
+///     Chips::new(2),
+///     Chips::new(1),
+///     Chips::ZERO,
+///     3,
+/// );
 /// let agents: Vec<Box<dyn Agent>> = (0..5)
 ///     .map(|_| Box::<FoldingAgent>::default() as Box<dyn Agent>)
 ///     .collect();
@@ -114,12 +173,168 @@ impl HoldemSimulationBuilder {
         self
     }
 
+    /// Set cards to deal before any random ones. The stacked cards are taken
+    /// out of the deck, and building fails if one of them isn't in it.
+    pub fn stacked_deck(mut self, stacked_deck: StackedDeck) -> Self {
+        self.stacked_deck = Some(stacked_deck);
+        self
+    }
+
+    /// Deal these hole cards to the players in the given seats, and deal
+    /// everyone else at random from what's left of the deck. The cards are
+    /// added to the stacked deck, replacing any stacked hole cards for the
+    /// same seats, and building fails if a card is given out twice.
+    pub fn hole_cards(mut self, hole_cards: HashMap<usize, [Card; 2]>) -> Self {
+        self.hole_cards = hole_cards;
+        self
+    }
+
     /// Set the historians for the simulation created by this builder.
     pub fn historians(mut self, historians: Vec<Box<dyn Historian>>) -> Self {
         self.historians = historians;
         self
     }
 
+    /// Record which agent sits in each seat. `seating[seat]` is an id for
+    /// the agent, such as the index of its generator in a competition. This
+    /// lets results be added up per agent when agents move seats between
+    /// hands. If not set every agent's id is its seat.
+    pub fn seating(mut self, seating: Vec<usize>) -> Self {
+        self.seating = Some(seating);
+        self
+    }
+
+    /// Set the betting structure for the game. If not set the one on the
+    /// game state is used, which is no limit unless changed.
+    pub fn betting_structure(mut self, betting_structure: BettingStructure) -> Self {
+        self.betting_structure = Some(betting_structure);
+        self
+    }
+
+    /// Set who posts the antes. If not set the style on the game state is
+    /// used, which is an ante from every player unless changed.
+    pub fn ante_style(mut self, ante_style: AnteStyle) -> Self {
+        self.ante_style = Some(ante_style);
+        self
+    }
+
+    /// Set the game being dealt. If not set the variant on the game state
+    /// is used, which is Hold'em unless changed.
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+
+    /// Have the player after the big blind straddle for `straddle`, usually
+    /// twice the big blind. They act last preflop. If not set the straddle
+    /// on the game state is used, which is no straddle unless changed.
+    ///
+    /// The straddle has to be at least the big blind. Heads up there's no
+    /// one after the big blind but the small blind, so nobody straddles.
+    pub fn straddle(mut self, straddle: Chips) -> Self {
+        self.straddle = Some(straddle);
+        self
+    }
+
+    /// Set the rake taken from each hand. If not set the rake on the game
+    /// state is used, which is no rake unless changed.
+    pub fn rake(mut self, rake: Rake) -> Self {
+        self.rake = Some(rake);
+        self
+    }
+
+    /// What to do with bets that are bigger than the betting structure
+    /// allows. When true, the default, they are lowered to the biggest
+    /// legal bet. When false they are recorded as a failed action and the
+    /// player folds.
+    pub fn clamp_over_limit_bets(mut self, clamp_over_limit_bets: bool) -> Self {
+        self.clamp_over_limit_bets = clamp_over_limit_bets;
+        self
+    }
+
+    /// Refuse game states with a negative stack or bet, or holding more
+    /// chips than their `f32` views can show exactly, see
+    /// [`MAX_SAFE_CHIPS`](super::game_state::MAX_SAFE_CHIPS). Off by
+    /// default. The chips are counted exactly either way, only ratios like
+    /// pot odds lose precision on bigger tables.
+    pub fn check_chip_range(mut self, check_chip_range: bool) -> Self {
+        self.check_chip_range = check_chip_range;
+        self
+    }
+
+    /// What to do with raises that are too small, or that come from a
+    /// player a short all in didn't reopen the betting for. Either way a
+    /// failed action is recorded. The default is to fold the player.
+    pub fn illegal_raise_policy(mut self, illegal_raise_policy: IllegalRaisePolicy) -> Self {
+        self.illegal_raise_policy = illegal_raise_policy;
+        self
+    }
+
+    /// Deal the rest of the board twice when every player is all in before
+    /// the river. This is the same as `runouts(2)`, or `runouts(1)` when
+    /// false. Default is false.
+    pub fn run_it_twice(mut self, run_it_twice: bool) -> Self {
+        self.runouts = if run_it_twice { 2 } else { 1 };
+        self
+    }
+
+    /// Deal the rest of the board this many times when every player is all
+    /// in before the river. Each pot is split into equal shares, and each
+    /// share goes to the best hand on one of the boards. Chips that don't
+    /// divide evenly go to the first of the pot's winners left of the
+    /// button. Only the first board's cards are recorded as community
+    /// cards, the other boards' cards are recorded as
+    /// [`Action::DealRunout`](super::action::Action::DealRunout).
+    ///
+    /// Zero is treated as one. Building fails if the deck is too small to
+    /// run out every board. Default is 1.
+    pub fn runouts(mut self, runouts: u8) -> Self {
+        self.runouts = runouts.max(1);
+        self
+    }
+
+    /// Stop the hand after agents have made this many decisions, so that
+    /// misbehaving agents can't keep a batch of simulations running
+    /// forever. `HoldemSimulation::try_run` then returns
+    /// `HoldemSimulationError::StepLimit`. Default is no limit.
+    pub fn max_actions(mut self, max_actions: usize) -> Self {
+        self.max_actions = Some(max_actions);
+        self
+    }
+
+    /// Burn a card before dealing the flop, turn, and river the way a
+    /// dealer does. Each burn is recorded as an `Action::BurnCard`, and
+    /// stacked burn cards are used first, see `StackedDeck::burn_cards`.
+    /// Default is false.
+    pub fn burn_cards(mut self, burn_cards: bool) -> Self {
+        self.burn_cards = burn_cards;
+        self
+    }
+
+    /// Seat the small blind somewhere other than where the game state put
+    /// it. By default it's the player after the button, or the button
+    /// itself heads up.
+    pub fn small_blind_position(mut self, idx: usize) -> Self {
+        self.small_blind_position = Some(idx);
+        self
+    }
+
+    /// Seat the big blind somewhere other than where the game state put it.
+    /// The first player to act preflop is the one after the big blind.
+    pub fn big_blind_position(mut self, idx: usize) -> Self {
+        self.big_blind_position = Some(idx);
+        self
+    }
+
+    /// Let players muck at showdown. Players show in
+    /// `GameState::showdown_order`, and with this set a player after the
+    /// first that isn't all in and doesn't win any of the pot mucks. Their
+    /// `Action::Showdown` has no hand. Default is false, everyone shows.
+    pub fn muck_losing_hands(mut self, muck_losing_hands: bool) -> Self {
+        self.muck_losing_hands = muck_losing_hands;
+        self
+    }
+
     /// Should the simulation panic if a historian errors.
     /// Default is false and allows the simulation to continue if a historian
     /// errors. It will be removed from the simulation and recorded in the logs.
@@ -131,11 +346,58 @@ impl HoldemSimulationBuilder {
     /// Given the fields already specified build any that are not specified and
     /// create a new HoldemSimulation.
     ///
-    /// @returns HoldemSimulationError if no game_state was given.
+    /// @returns HoldemSimulationError if no game_state was given, if
+    /// `check_chip_range` is set and the game state has a negative stack or
+    /// more chips than [`MAX_SAFE_CHIPS`](super::game_state::MAX_SAFE_CHIPS),
+    /// if
+    /// a blind position isn't a seated player, if the straddle is less than
+    /// the big blind, if there aren't from 2 to
+    /// [`MAX_PLAYERS`](super::game_state::MAX_PLAYERS) players, or if the deck
+    /// doesn't have enough cards to deal the hand.
     pub fn build(self) -> Result<HoldemSimulation, HoldemSimulationError> {
-        let game_state = self
+        let mut game_state = self
             .game_state
             .ok_or(HoldemSimulationError::NeedGameState)?;
+        if let Some(betting_structure) = self.betting_structure {
+            game_state.betting_structure = betting_structure;
+        }
+        if let Some(ante_style) = self.ante_style {
+            game_state.ante_style = ante_style;
+        }
+        if let Some(variant) = self.variant {
+            game_state.variant = variant;
+        }
+        if let Some(rake) = self.rake {
+            game_state.rake = rake;
+        }
+        if let Some(straddle) = self.straddle {
+            game_state.straddle = straddle;
+        }
+        if game_state.straddle > Chips::ZERO && game_state.straddle < game_state.big_blind {
+            return Err(HoldemSimulationError::StraddleTooSmall);
+        }
+        if self.check_chip_range {
+            game_state.validate_chip_range()?;
+        }
+        game_state.validate_player_count()?;
+        if let Some(idx) = self.small_blind_position {
+            game_state.small_blind_idx = idx;
+        }
+        if let Some(idx) = self.big_blind_position {
+            game_state.big_blind_idx = idx;
+        }
+        if self.small_blind_position.is_some() || self.big_blind_position.is_some() {
+            for idx in [game_state.small_blind_idx, game_state.big_blind_idx] {
+                if !game_state.player_active.get(idx) {
+                    return Err(HoldemSimulationError::InvalidBlindPosition(idx));
+                }
+            }
+            if game_state.small_blind_idx == game_state.big_blind_idx {
+                return Err(HoldemSimulationError::InvalidBlindPosition(
+                    game_state.big_blind_idx,
+                ));
+            }
+        }
 
         let agents = self
             .agents
@@ -151,7 +413,34 @@ impl HoldemSimulationBuilder {
             .chain(agent_historians)
             .collect();
 
-        let deck = self.deck.unwrap_or_else(|| build_deck(&game_state));
+        let mut deck = self.deck.unwrap_or_else(|| build_deck(&game_state));
+        // A deck that still holds a card that's already dealt would deal it
+        // a second time.
+        if let Some(card) = game_state
+            .hands
+            .iter()
+            .flat_map(|hand| hand.iter())
+            .chain(game_state.board.iter().copied())
+            .find(|card| deck.contains(card))
+        {
+            return Err(HoldemSimulationError::DeckIntegrity(Some(card)));
+        }
+        let needed = cards_needed(&game_state, self.burn_cards);
+        if needed > deck.len() {
+            return Err(HoldemSimulationError::TooManyPlayers);
+        }
+        // Each extra runout deals the rest of the board again.
+        let extra_runouts =
+            usize::from(self.runouts - 1) * 5usize.saturating_sub(game_state.board.len());
+        if needed + extra_runouts > deck.len() {
+            return Err(HoldemSimulationError::TooManyRunouts(self.runouts));
+        }
+        let stacked_deck = self.hole_cards.into_iter().fold(
+            self.stacked_deck.unwrap_or_default(),
+            |stacked, (idx, cards)| stacked.hole_cards(idx, cards),
+        );
+        stacked_deck.remove_from(&mut deck)?;
+        let seating = self.seating.unwrap_or_else(|| (0..agents.len()).collect());
 
         // Create a new simulation id.
         // This will be used to track
@@ -163,9 +452,22 @@ impl HoldemSimulationBuilder {
             agents,
             game_state,
             deck,
+            stacked_deck,
             id,
             historians,
+            seating,
+            clamp_over_limit_bets: self.clamp_over_limit_bets,
+            illegal_raise_policy: self.illegal_raise_policy,
+            runouts: self.runouts,
+            max_actions: self.max_actions,
+            actions_taken: 0,
+            burn_cards: self.burn_cards,
+            burned_cards: vec![],
+            muck_losing_hands: self.muck_losing_hands,
             panic_on_historian_error: self.panic_on_historian_error,
+            runouts_from: None,
+            deck_error: None,
+            contested_awards: vec![],
         })
     }
 }
@@ -177,6 +479,23 @@ impl Default for HoldemSimulationBuilder {
             historians: vec![],
             game_state: None,
             deck: None,
+            stacked_deck: None,
+            hole_cards: HashMap::new(),
+            seating: None,
+            betting_structure: None,
+            ante_style: None,
+            variant: None,
+            rake: None,
+            straddle: None,
+            small_blind_position: None,
+            big_blind_position: None,
+            clamp_over_limit_bets: true,
+            check_chip_range: false,
+            illegal_raise_policy: IllegalRaisePolicy::default(),
+            runouts: 1,
+            max_actions: None,
+            burn_cards: false,
+            muck_losing_hands: false,
             panic_on_historian_error: true,
         }
     }
@@ -184,33 +503,48 @@ impl Default for HoldemSimulationBuilder {
 
 #[cfg(test)]
 mod tests {
+    use crate::core::cards;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
     use rand::{SeedableRng, rngs::StdRng};
 
-    use crate::{arena::game_state::Round, core::Card};
+    use crate::{
+        arena::{
+            LegalActions,
+            action::{Action, AgentAction, ForcedBetType},
+            agent::{AllInAgent, CallingAgent, RandomAgent, SliceReplayAgent, VecReplayAgent},
+            errors::GameStateError,
+            game_state::Round,
+            historian::{HistoryRecord, VecHistorian},
+        },
+        core::{Card, Hand, Rank, Value},
+    };
 
     use super::*;
 
     #[test_log::test]
     fn test_single_step_agent() {
         let mut rng = StdRng::seed_from_u64(420);
-        let stacks = vec![100.0; 9];
-        let game_state = GameState::new_starting(stacks, 10.0, 5.0, 1.0, 0);
+        let stacks = vec![Chips::new(100); 9];
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::new(1), 0);
         let mut sim = HoldemSimulationBuilder::default()
             .game_state(game_state)
             .build()
             .unwrap();
 
-        assert_eq!(100.0, sim.game_state.stacks[1]);
-        assert_eq!(100.0, sim.game_state.stacks[2]);
+        assert_eq!(Chips::new(100), sim.game_state.stacks[1]);
+        assert_eq!(Chips::new(100), sim.game_state.stacks[2]);
         // We are starting out.
         sim.run_round(&mut rng);
-        assert_eq!(100.0, sim.game_state.stacks[1]);
-        assert_eq!(100.0, sim.game_state.stacks[2]);
+        assert_eq!(Chips::new(100), sim.game_state.stacks[1]);
+        assert_eq!(Chips::new(100), sim.game_state.stacks[2]);
 
         // Post the ante and check the results.
         sim.run_round(&mut rng);
         for i in 0..9 {
-            assert_eq!(99.0, sim.game_state.stacks[i]);
+            assert_eq!(Chips::new(99), sim.game_state.stacks[i]);
         }
 
         // Deal Pre-Flop
@@ -218,14 +552,183 @@ mod tests {
 
         // Post the blinds and check the results.
         sim.run_round(&mut rng);
-        assert_eq!(6.0, sim.game_state.player_bet[1]);
-        assert_eq!(11.0, sim.game_state.player_bet[2]);
+        assert_eq!(Chips::new(6), sim.game_state.player_bet[1]);
+        assert_eq!(Chips::new(11), sim.game_state.player_bet[2]);
+    }
+
+    /// Play up to the first voluntary action with a big blind ante of 10.
+    /// The big blind sits in seat 2 and seat 3 is first to act.
+    fn big_blind_ante_sim(stacks: Vec<Chips>) -> HoldemSimulation {
+        let mut rng = StdRng::seed_from_u64(276);
+        let game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::new(10), 0);
+        let agents: Vec<Box<dyn Agent>> = (0..4)
+            .map(|_| Box::<CallingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .ante_style(AnteStyle::BigBlindAnte)
+            .build()
+            .unwrap();
+        for _ in 0..3 {
+            sim.run_round(&mut rng);
+        }
+        // Posts the blinds and has seat 3 call.
+        assert!(sim.step(&mut rng));
+        sim
+    }
+
+    #[test]
+    fn test_big_blind_ante() {
+        let sim = big_blind_ante_sim(vec![Chips::new(100); 4]);
+        assert_eq!(
+            [100, 95, 80, 90].map(Chips::new).to_vec(),
+            sim.game_state.stacks
+        );
+        assert_eq!(Chips::new(35), sim.game_state.total_pot);
+
+        let summary = sim.game_state.forced_bets_summary().unwrap();
+        assert_eq!(3, summary.forced_bets.len());
+        assert_eq!(
+            [0, 5, 20, 0].map(Chips::new).to_vec(),
+            summary.player_posted
+        );
+        assert_eq!(Chips::new(25), summary.pot);
+        assert_eq!(Some(3), summary.first_to_act_idx);
+    }
+
+    #[test]
+    fn test_big_blind_ante_capped_by_stack() {
+        // Only 5 of the ante is left after posting the big blind.
+        let sim = big_blind_ante_sim([100, 100, 15, 100].map(Chips::new).to_vec());
+        assert_eq!(Chips::ZERO, sim.game_state.stacks[2]);
+        assert_eq!(Chips::new(15), sim.game_state.player_bet[2]);
+        assert_eq!(Chips::new(30), sim.game_state.total_pot);
+        assert!(sim.game_state.player_all_in.get(2));
+
+        // Short of the big blind there's no ante at all.
+        let sim = big_blind_ante_sim([100, 100, 8, 100].map(Chips::new).to_vec());
+        assert_eq!(Chips::new(8), sim.game_state.player_bet[2]);
+        assert_eq!(Chips::new(21), sim.game_state.total_pot);
+        assert_eq!(2, sim.game_state.forced_bets.len());
+    }
+
+    #[test]
+    fn test_dead_small_blind() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 4],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        // The big blind busted, so there's no small blind this hand.
+        let game_state = game_state.next_hand([100, 150, 0, 150].map(Chips::new).to_vec());
+        let agents: Vec<Box<dyn Agent>> = (0..4)
+            .map(|_| Box::<CallingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(277);
+        for _ in 0..3 {
+            sim.run_round(&mut rng);
+        }
+        assert!(sim.step(&mut rng));
+
+        let forced: Vec<usize> = sim.game_state.forced_bets.iter().map(|f| f.idx).collect();
+        assert_eq!(vec![3], forced);
+        // Seat 0 acted first and called, the button is next.
+        assert_eq!(
+            [90, 150, 0, 140].map(Chips::new).to_vec(),
+            sim.game_state.stacks
+        );
+        assert_eq!(1, sim.game_state.to_act_idx());
+    }
+
+    #[test]
+    fn test_rejects_too_many_chips() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(10000000); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        // Without the check the game is played.
+        assert!(
+            HoldemSimulationBuilder::default()
+                .game_state(game_state.clone())
+                .build()
+                .is_ok()
+        );
+        let result = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .check_chip_range(true)
+            .build();
+        assert_eq!(
+            HoldemSimulationError::GameState(GameStateError::ChipsOutOfRange),
+            result.err().unwrap()
+        );
+
+        let game_state = GameState::new_starting(
+            [-1, 100].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let result = HoldemSimulationBuilder::default()
+            .check_chip_range(true)
+            .game_state(game_state)
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_large_stacks_award_exactly() {
+        // Just under the limit every chip still counts.
+        let stacks = vec![Chips::new(5592405); 3];
+        let total: Chips = stacks.iter().sum();
+        let mut rng = StdRng::seed_from_u64(219);
+        for _ in 0..10 {
+            let game_state = GameState::new_starting(
+                stacks.clone(),
+                Chips::new(3),
+                Chips::new(1),
+                Chips::new(1),
+                0,
+            );
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(game_state)
+                .agents(vec![
+                    Box::new(AllInAgent),
+                    Box::new(CallingAgent),
+                    Box::new(CallingAgent),
+                ])
+                .build()
+                .unwrap();
+            sim.run(&mut rng);
+
+            assert_eq!(total, sim.game_state.stacks.iter().sum::<Chips>());
+            let won: Chips = sim
+                .game_state
+                .player_winnings
+                .iter()
+                .filter(|w| **w > Chips::ZERO)
+                .sum();
+            assert_eq!(sim.game_state.total_pot, won);
+        }
     }
 
     // #[test_log::test]
     // fn test_flatdeck_order() {
-    //     let stacks = vec![100.0; 2];
-    //     let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+    //     let stacks = vec![Chips::new(100); 2];
+    //     let game_state = GameState::new_starting(stacks, Chips::new(10),
+    // Chips::new(5), Chips::ZERO, 0);
 
     //     let rng_one = StdRng::seed_from_u64(420);
     //     let sim_one = RngHoldemSimulationBuilder::default()
@@ -246,8 +749,9 @@ mod tests {
 
     #[test_log::test]
     fn test_simulation_complex_showdown() {
-        let stacks = vec![102.0, 7.0, 12.0, 102.0, 202.0];
-        let mut game_state = GameState::new_starting(stacks, 10.0, 5.0, 2.0, 0);
+        let stacks = [102, 7, 12, 102, 202].map(Chips::new).to_vec();
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::new(2), 0);
         let mut deck = CardBitSet::default();
         let mut rng = rand::rng();
 
@@ -255,11 +759,11 @@ mod tests {
         game_state.advance_round();
 
         // Ante
-        game_state.do_bet(2.0, true).unwrap(); // ante@idx 1
-        game_state.do_bet(2.0, true).unwrap(); // ante@idx 2
-        game_state.do_bet(2.0, true).unwrap(); // ante@idx 3
-        game_state.do_bet(2.0, true).unwrap(); // ante@idx 4
-        game_state.do_bet(2.0, true).unwrap(); // ante@idx 0
+        game_state.do_bet(Chips::new(2), true).unwrap(); // ante@idx 1
+        game_state.do_bet(Chips::new(2), true).unwrap(); // ante@idx 2
+        game_state.do_bet(Chips::new(2), true).unwrap(); // ante@idx 3
+        game_state.do_bet(Chips::new(2), true).unwrap(); // ante@idx 4
+        game_state.do_bet(Chips::new(2), true).unwrap(); // ante@idx 0
         game_state.advance_round();
 
         // Deal Preflop
@@ -280,11 +784,11 @@ mod tests {
         game_state.advance_round();
 
         // Preflop
-        game_state.do_bet(5.0, true).unwrap(); // blinds@idx 1
-        game_state.do_bet(10.0, true).unwrap(); // blinds@idx 2
+        game_state.do_bet(Chips::new(5), true).unwrap(); // blinds@idx 1
+        game_state.do_bet(Chips::new(10), true).unwrap(); // blinds@idx 2
         game_state.fold(); // idx 3
-        game_state.do_bet(10.0, false).unwrap(); // idx 4
-        game_state.do_bet(10.0, false).unwrap(); // idx 0
+        game_state.do_bet(Chips::new(10), false).unwrap(); // idx 4
+        game_state.do_bet(Chips::new(10), false).unwrap(); // idx 0
         game_state.advance_round();
 
         // Deal Flop
@@ -295,8 +799,8 @@ mod tests {
 
         // Flop
         assert_eq!(game_state.num_active_players(), 2);
-        game_state.do_bet(90.0, false).unwrap(); // idx 4
-        game_state.do_bet(90.0, false).unwrap(); // idx 0
+        game_state.do_bet(Chips::new(90), false).unwrap(); // idx 4
+        game_state.do_bet(Chips::new(90), false).unwrap(); // idx 0
         game_state.advance_round();
         assert_eq!(game_state.num_active_players(), 1);
 
@@ -305,7 +809,7 @@ mod tests {
         game_state.advance_round();
 
         // Turn
-        game_state.do_bet(0.0, false).unwrap(); // idx 4
+        game_state.do_bet(Chips::ZERO, false).unwrap(); // idx 4
         game_state.advance_round();
         assert_eq!(game_state.num_active_players(), 1);
 
@@ -314,7 +818,7 @@ mod tests {
         game_state.advance_round();
 
         // River
-        game_state.do_bet(100.0, false).unwrap(); // idx 4
+        game_state.do_bet(Chips::new(100), false).unwrap(); // idx 4
         game_state.advance_round();
         assert_eq!(game_state.num_active_players(), 0);
 
@@ -326,17 +830,1319 @@ mod tests {
 
         assert_eq!(Round::Complete, sim.game_state.round);
 
-        assert_eq!(180.0, sim.game_state.player_winnings[0]);
-        assert_eq!(15.0, sim.game_state.player_winnings[1]);
-        assert_eq!(30.0, sim.game_state.player_winnings[2]);
-        assert_eq!(0.0, sim.game_state.player_winnings[3]);
-        assert_eq!(100.0, sim.game_state.player_winnings[4]);
-
-        assert_eq!(180.0, sim.game_state.stacks[0]);
-        assert_eq!(15.0, sim.game_state.stacks[1]);
-        assert_eq!(30.0, sim.game_state.stacks[2]);
-        assert_eq!(100.0, sim.game_state.stacks[3]);
-        assert_eq!(100.0, sim.game_state.stacks[4]);
+        assert_eq!(Chips::new(180), sim.game_state.player_winnings[0]);
+        assert_eq!(Chips::new(15), sim.game_state.player_winnings[1]);
+        assert_eq!(Chips::new(30), sim.game_state.player_winnings[2]);
+        assert_eq!(Chips::ZERO, sim.game_state.player_winnings[3]);
+        assert_eq!(Chips::new(100), sim.game_state.player_winnings[4]);
+
+        assert_eq!(Chips::new(180), sim.game_state.stacks[0]);
+        assert_eq!(Chips::new(15), sim.game_state.stacks[1]);
+        assert_eq!(Chips::new(30), sim.game_state.stacks[2]);
+        assert_eq!(Chips::new(100), sim.game_state.stacks[3]);
+        assert_eq!(Chips::new(100), sim.game_state.stacks[4]);
+    }
+
+    #[test]
+    fn test_simulation_three_way_side_pots() {
+        let stacks = [50, 150, 200].map(Chips::new).to_vec();
+        let mut game_state =
+            GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 2);
+        let mut deck = CardBitSet::default();
+
+        // Skip ahead to the river with nothing bet.
+        for _ in 0..3 {
+            game_state.advance_round();
+        }
+        deal_hand_card(0, "As", &mut deck, &mut game_state);
+        deal_hand_card(0, "Ad", &mut deck, &mut game_state);
+        deal_hand_card(1, "Ks", &mut deck, &mut game_state);
+        deal_hand_card(1, "Kd", &mut deck, &mut game_state);
+        deal_hand_card(2, "Qs", &mut deck, &mut game_state);
+        deal_hand_card(2, "Qd", &mut deck, &mut game_state);
+        for card in ["2c", "5h", "8c", "9h", "Jc"] {
+            deal_community_card(card, &mut deck, &mut game_state);
+        }
+        for _ in 0..6 {
+            game_state.advance_round();
+        }
+        assert_eq!(Round::River, game_state.round);
+
+        // P0 all in for 50, P1 all in for 150, and P2 calls.
+        game_state.do_bet(Chips::new(50), false).unwrap();
+        game_state.do_bet(Chips::new(150), false).unwrap();
+        game_state.do_bet(Chips::new(150), false).unwrap();
+        game_state.advance_round();
+
+        let pots = game_state.side_pots();
+        assert_eq!(2, pots.len());
+        assert_eq!(Chips::new(150), pots[0].amount);
+        assert_eq!(vec![0, 1, 2], pots[0].eligible.ones().collect::<Vec<_>>());
+        assert_eq!(Chips::new(200), pots[1].amount);
+        assert_eq!(vec![1, 2], pots[1].eligible.ones().collect::<Vec<_>>());
+
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(268));
+
+        let awards: Vec<(usize, Chips, Chips)> = records
+            .borrow()
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::Award(award) => Some((award.idx, award.total_pot, award.award_amount)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (0, Chips::new(150), Chips::new(150)),
+                (1, Chips::new(200), Chips::new(200))
+            ],
+            awards
+        );
+        assert_eq!(
+            [150, 200, 50].map(Chips::new).to_vec(),
+            sim.game_state.stacks
+        );
+    }
+
+    #[test]
+    fn test_step_runs_one_decision() {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = (0..3)
+            .map(|_| Box::<CallingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(273);
+
+        let played = |records: &[HistoryRecord]| {
+            records
+                .iter()
+                .filter(|r| matches!(r.action, Action::PlayedAction(_)))
+                .count()
+        };
+        let mut steps = 0;
+        while sim.step(&mut rng) {
+            steps += 1;
+            assert_eq!(steps, played(&records.borrow()));
+        }
+        // Everyone calls or checks on every street.
+        assert_eq!(3 + 3 * 3, steps);
+        assert_eq!(Round::Complete, sim.game_state.round);
+        assert!(!sim.step(&mut rng));
+    }
+
+    #[test]
+    fn test_clone_forks_at_decision() {
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::<CallingAgent>::default(),
+            Box::<CallingAgent>::default(),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .historians(vec![Box::new(VecHistorian::new())])
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(273);
+
+        // The small blind calls, then fork at the big blind's option.
+        assert!(sim.step(&mut rng));
+        let mut check = sim.try_clone().unwrap();
+        let mut raise = sim.try_clone().unwrap();
+        assert!(check.historians.is_empty());
+        assert_eq!(sim.game_state, check.game_state);
+
+        let bb_idx = sim.game_state.to_act_idx();
+        check.run_agent_action(AgentAction::Call);
+        raise.run_agent_action(AgentAction::Bet(Chips::new(30)));
+        assert_eq!(
+            Chips::new(10),
+            check.game_state.current_round_player_bet(bb_idx)
+        );
+        assert_eq!(
+            Chips::new(30),
+            raise.game_state.current_round_player_bet(bb_idx)
+        );
+
+        check.run(&mut rng);
+        raise.run(&mut rng);
+        assert_eq!(Round::Complete, check.game_state.round);
+        assert_eq!(Round::Complete, raise.game_state.round);
+
+        // The original hasn't moved.
+        assert_eq!(Round::Preflop, sim.game_state.round);
+        assert_eq!(Chips::new(20), sim.game_state.total_pot);
+        assert_eq!(bb_idx, sim.game_state.to_act_idx());
+    }
+
+    #[test]
+    fn test_try_clone_needs_cloneable_agents() {
+        static ACTIONS: [AgentAction; 1] = [AgentAction::Call];
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(SliceReplayAgent::new(&ACTIONS)),
+            Box::<CallingAgent>::default(),
+        ];
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .build()
+            .unwrap();
+        assert!(sim.try_clone().is_none());
+    }
+
+    /// Heads up to showdown with the nut flush for player 0 against
+    /// `hole_cards` for player 1 on `board`.
+    fn nut_flush_showdown(
+        variant: Variant,
+        hole_cards: &str,
+        board: &str,
+    ) -> (HoldemSimulation, Vec<HistoryRecord>) {
+        let flush = if variant == Variant::Omaha {
+            "AhQh9s9c"
+        } else {
+            "AhQh"
+        };
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards(flush))
+            .hole_cards(1, cards(hole_cards))
+            .board(cards(board));
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::<CallingAgent>::default(),
+            Box::<CallingAgent>::default(),
+        ];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .variant(variant)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(283));
+        (sim, records.take())
+    }
+
+    #[test]
+    fn test_omaha_nut_flush_scoops() {
+        // Kings and deuces only make a full house with three hole cards.
+        let (sim, records) = nut_flush_showdown(Variant::Omaha, "KsKc2s2d", "Kh8h3h2c7d");
+        for idx in 0..2 {
+            let dealt = records
+                .iter()
+                .filter(|r| matches!(&r.action, Action::DealStartingHand(p) if p.idx == idx))
+                .count();
+            assert_eq!(4, dealt);
+            assert_eq!(9, sim.game_state.hands[idx].count());
+        }
+
+        let awards: Vec<_> = records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::Award(payload) => Some(payload.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(1, awards.len());
+        assert_eq!(0, awards[0].idx);
+        assert_eq!(Chips::new(20), awards[0].award_amount);
+        assert!(matches!(awards[0].rank, Some(Rank::Flush(_))));
+        assert_eq!([110, 90].map(Chips::new).to_vec(), sim.game_state.stacks);
+    }
+
+    #[test]
+    fn test_holdem_ranks_all_hole_cards() {
+        // In Hold'em both kings play with the board's king and deuces for a
+        // full house.
+        let (sim, records) = nut_flush_showdown(Variant::Holdem, "KsKc", "Kh8h3h2c2d");
+        let awards: Vec<_> = records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::Award(payload) => Some(payload.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(1, awards.len());
+        assert_eq!(1, awards[0].idx);
+        assert!(matches!(awards[0].rank, Some(Rank::FullHouse(_))));
+        assert_eq!([90, 110].map(Chips::new).to_vec(), sim.game_state.stacks);
+    }
+
+    /// Aces against kings all in preflop, with the kings hitting a set on
+    /// the stacked board.
+    fn all_in_aces_kings(run_it_twice: bool, seed: u64) -> (HoldemSimulation, Vec<HistoryRecord>) {
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("AsAd"))
+            .hole_cards(1, cards("KsKd"))
+            .board(cards("Kh8c3d2s7h"));
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(AllInAgent), Box::new(AllInAgent)];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .run_it_twice(run_it_twice)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(seed));
+        (sim, records.take())
+    }
+
+    fn awards(records: &[HistoryRecord]) -> Vec<(usize, Chips)> {
+        records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::Award(payload) => Some((payload.idx, payload.award_amount)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_all_in_skips_decisions() {
+        let (sim, records) = all_in_aces_kings(false, 292);
+        let decisions = records
+            .iter()
+            .filter(|r| matches!(r.action, Action::PlayedAction(_)))
+            .count();
+        assert_eq!(2, decisions);
+        assert_eq!(vec![(1, Chips::new(200))], awards(&records));
+        assert_eq!(5, sim.game_state.board.len());
+    }
+
+    #[test]
+    fn test_run_it_twice_splits_pot() {
+        // Find a seed where the aces win the second board.
+        let (sim, records) = (0..20)
+            .map(|seed| all_in_aces_kings(true, seed))
+            .find(|(_, records)| awards(records).iter().any(|(idx, _)| *idx == 0))
+            .unwrap();
+        assert_eq!(
+            vec![(1, Chips::new(100)), (0, Chips::new(100))],
+            awards(&records)
+        );
+        assert_eq!([100, 100].map(Chips::new).to_vec(), sim.game_state.stacks);
+
+        // The second board is a different runout of all five cards.
+        let second_hand = records
+            .iter()
+            .find_map(|r| match &r.action {
+                Action::Award(payload) if payload.idx == 0 => payload.hand,
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(7, second_hand.count());
+        assert!(!second_hand.contains(&Card::try_from("Kh").unwrap()));
+        assert_eq!(Hand::new_from_str("Kh8c3d2s7h").unwrap(), {
+            let mut board = Hand::new();
+            board.extend(sim.game_state.board.iter().cloned());
+            board
+        });
+    }
+
+    /// Aces against seven deuce all in preflop, with every board random.
+    fn all_in_favorite(runouts: u8, seed: u64) -> (HoldemSimulation, Vec<HistoryRecord>) {
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("AsAd"))
+            .hole_cards(1, cards("7c2h"));
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(AllInAgent), Box::new(AllInAgent)];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .runouts(runouts)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(seed));
+        (sim, records.take())
+    }
+
+    #[test]
+    fn test_more_runouts_less_variance() {
+        let variance = |runouts: u8| {
+            let stacks: Vec<f64> = (0..300)
+                .map(|seed| f64::from(all_in_favorite(runouts, seed).0.game_state.stacks[0]))
+                .collect();
+            let mean = stacks.iter().sum::<f64>() / stacks.len() as f64;
+            stacks.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / stacks.len() as f64
+        };
+        let once = variance(1);
+        let twice = variance(2);
+        assert!(twice < 0.75 * once, "{twice} vs {once}");
+    }
+
+    #[test]
+    fn test_runout_leftover_chips() {
+        // Three runouts of a 200 chip pot are 66 chips each, with 2 left
+        // over. Find a hand where the seven deuce wins a board.
+        let (sim, records) = (0..100)
+            .map(|seed| all_in_favorite(3, seed))
+            .find(|(_, records)| awards(records).iter().any(|(idx, _)| *idx == 1))
+            .unwrap();
+        let awards = awards(&records);
+        assert_eq!(3, awards.len());
+        assert_eq!(Chips::new(200), sim.game_state.stacks.iter().sum::<Chips>());
+
+        // Heads up the big blind is left of the button, so they get the
+        // leftover chips on the first board they win.
+        let boards_won = awards.iter().filter(|(idx, _)| *idx == 1).count() as i64;
+        assert_eq!(Chips::new(66 * boards_won + 2), sim.game_state.stacks[1]);
+    }
+
+    #[test]
+    fn test_too_many_runouts() {
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(AllInAgent), Box::new(AllInAgent)];
+        let result = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .runouts(10)
+            .build();
+        assert!(matches!(
+            result,
+            Err(HoldemSimulationError::TooManyRunouts(10))
+        ));
+    }
+
+    #[test]
+    fn test_deck_with_dealt_card() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.hands[0] = Hand::new_from_str("AsAd").unwrap();
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+        // A full deck still has both of the aces already dealt.
+        let result = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(agents)
+            .deck(Deck::default())
+            .build();
+        assert!(matches!(
+            result,
+            Err(HoldemSimulationError::DeckIntegrity(Some(card))) if card.value == Value::Ace
+        ));
+    }
+
+    #[test]
+    fn test_odd_chip_left_of_button() {
+        // A royal flush on the board so the two players left chop.
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("4c4d"))
+            .hole_cards(1, cards("2c3d"))
+            .hole_cards(2, cards("2h3c"))
+            .board(cards("AsKsQsJsTs"));
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        // Seat 1 has the button. The big blind in seat 0 antes 1, posts 10,
+        // and folds. The button and small blind both go all in for 45,
+        // making a 101 chip pot.
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(FoldingAgent),
+            Box::new(AllInAgent),
+            Box::new(AllInAgent),
+        ];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                [100, 45, 45].map(Chips::new).to_vec(),
+                Chips::new(10),
+                Chips::new(5),
+                Chips::new(1),
+                1,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(323));
+
+        let records = records.take();
+        let total: Chips = awards(&records).iter().map(|(_, amount)| amount).sum();
+        assert_eq!(Chips::new(101), total);
+        // The small blind is the first seat left of the button, so gets the
+        // odd chip.
+        assert_eq!([89, 50, 51].map(Chips::new).to_vec(), sim.game_state.stacks);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_round_trip() {
+        let agents = || -> Vec<Box<dyn Agent>> {
+            vec![
+                Box::new(CallingAgent),
+                Box::new(VecReplayAgent::new_with_default(
+                    vec![AgentAction::Bet(Chips::new(20))],
+                    AgentAction::Call,
+                )),
+                Box::new(CallingAgent),
+            ]
+        };
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents())
+            .burn_cards(true)
+            .build()
+            .unwrap();
+        // Play up to the flop, then save the hand.
+        let mut rng = StdRng::seed_from_u64(326);
+        while sim.game_state.round != Round::Flop {
+            sim.run_round(&mut rng);
+        }
+        let json = serde_json::to_string(&sim.snapshot()).unwrap();
+        let snapshot: crate::arena::SimulationSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(sim.snapshot(), snapshot);
+
+        // The replay agent already raised, so a fresh one only calls.
+        let restored_agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(CallingAgent),
+            Box::new(CallingAgent),
+            Box::new(CallingAgent),
+        ];
+        let mut restored = snapshot.restore(restored_agents, vec![]).unwrap();
+        sim.run(&mut StdRng::seed_from_u64(3260));
+        restored.run(&mut StdRng::seed_from_u64(3260));
+        assert_eq!(sim.game_state, restored.game_state);
+        assert_eq!(sim.burned_cards, restored.burned_cards);
+
+        let too_few: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+        assert_eq!(
+            Err(HoldemSimulationError::AgentCount(3)),
+            sim.snapshot().restore(too_few, vec![]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_snapshot_keeps_deck_error() {
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .build()
+            .unwrap();
+        sim.deck_error = Some(HoldemSimulationError::DeckIntegrity(None));
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+        let mut restored = sim.snapshot().restore(agents, vec![]).unwrap();
+        assert!(!restored.more_rounds());
+        assert_eq!(
+            Err(HoldemSimulationError::DeckIntegrity(None)),
+            restored.try_run(&mut StdRng::seed_from_u64(326))
+        );
+    }
+
+    #[test]
+    fn test_is_all_in_showdown() {
+        let mut game_state = GameState::new_starting(
+            [100, 50, 100].map(Chips::new).to_vec(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for _ in 0..3 {
+            game_state.advance_round();
+        }
+        assert!(!game_state.is_all_in_showdown());
+        game_state.do_bet(Chips::new(5), true).unwrap();
+        game_state.do_bet(Chips::new(10), true).unwrap();
+        // The dealer folds and the short stacked small blind is all in.
+        game_state.fold();
+        game_state.do_bet(Chips::new(100), false).unwrap();
+        assert!(!game_state.is_all_in_showdown());
+        // Once the big blind calls they have no one left to bet against.
+        game_state.do_bet(Chips::new(50), false).unwrap();
+        assert!(game_state.is_all_in_showdown());
+    }
+
+    #[test]
+    fn test_chips_conserved_over_many_hands() {
+        use rand::Rng;
+
+        use crate::arena::agent::FoldingAgent;
+
+        let starting = vec![Chips::new(100); 4];
+        let mut total: Chips = starting.iter().sum();
+        let mut rng = StdRng::seed_from_u64(304);
+        let mut game_state = GameState::new_starting(
+            starting.clone(),
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        for _ in 0..10_000 {
+            let agents: Vec<Box<dyn Agent>> = (0..4)
+                .map(|_| -> Box<dyn Agent> {
+                    match rng.random_range(0..3) {
+                        0 => Box::new(CallingAgent),
+                        1 => Box::new(AllInAgent),
+                        _ => Box::new(FoldingAgent),
+                    }
+                })
+                .collect();
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(game_state.clone())
+                .agents(agents)
+                .rake(Rake::new(0.05, Some(Chips::new(3))))
+                .build()
+                .unwrap();
+            sim.run(&mut rng);
+
+            // Every chip is either still on the table or in the rake.
+            let rake = sim.result().rake;
+            let stacks = sim.game_state.stacks.clone();
+            assert_eq!(total, stacks.iter().sum::<Chips>() + rake);
+            total -= rake;
+            game_state = if stacks.iter().filter(|s| **s > Chips::ZERO).count() < 2 {
+                total = starting.iter().sum();
+                GameState::new_starting(
+                    starting.clone(),
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    0,
+                )
+            } else {
+                sim.game_state.next_hand(stacks)
+            };
+        }
+    }
+
+    #[test]
+    fn test_pinned_hole_cards() {
+        let aces = [Card::try_from("As").unwrap(), Card::try_from("Ad").unwrap()];
+        for seed in 0..10 {
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(GameState::new_starting(
+                    vec![Chips::new(100); 3],
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    0,
+                ))
+                .hole_cards(HashMap::from([(0, aces)]))
+                .build()
+                .unwrap();
+            sim.run(&mut StdRng::seed_from_u64(seed));
+
+            let hands = &sim.game_state.hands;
+            assert!(aces.iter().all(|c| hands[0].contains(c)));
+            // No one else can have the pinned cards.
+            assert!(
+                hands[1..]
+                    .iter()
+                    .all(|h| aces.iter().all(|c| !h.contains(c)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_pinned_hole_cards_duplicate_rejected() {
+        let as_ = Card::try_from("As").unwrap();
+        let result = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .hole_cards(HashMap::from([
+                (0, [as_, Card::try_from("Ad").unwrap()]),
+                (2, [as_, Card::try_from("Kd").unwrap()]),
+            ]))
+            .build();
+        assert_eq!(
+            HoldemSimulationError::StackedCardUnavailable(as_),
+            result.err().unwrap()
+        );
+    }
+
+    /// Raises the first time it's given the option, then calls. Keeps what
+    /// it was allowed to do each time it acted.
+    struct OptionRaiser {
+        seen: Rc<RefCell<Vec<(Round, LegalActions)>>>,
+    }
+
+    impl Agent for OptionRaiser {
+        fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+            AgentAction::Bet(game_state.current_round_bet())
+        }
+
+        fn act_with_legal_actions(
+            &mut self,
+            _id: u128,
+            game_state: &GameState,
+            legal_actions: &LegalActions,
+        ) -> AgentAction {
+            let first = self.seen.borrow().is_empty();
+            self.seen
+                .borrow_mut()
+                .push((game_state.round, *legal_actions));
+            if first && legal_actions.can_check && legal_actions.can_raise {
+                AgentAction::Bet(Chips::new(60))
+            } else {
+                AgentAction::Bet(game_state.current_round_bet())
+            }
+        }
+    }
+
+    #[test]
+    fn test_straddler_acts_last_preflop() {
+        let seen = Rc::new(RefCell::new(vec![]));
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(CallingAgent),
+            Box::new(CallingAgent),
+            Box::new(CallingAgent),
+            Box::new(OptionRaiser { seen: seen.clone() }),
+        ];
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 4],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .straddle(Chips::new(20))
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(302));
+
+        let preflop: Vec<(usize, Option<ForcedBetType>)> = records
+            .borrow()
+            .iter()
+            .filter(|r| r.before_game_state.as_ref().map(|g| g.round) == Some(Round::Preflop))
+            .filter_map(|r| match &r.action {
+                Action::ForcedBet(p) => Some((p.idx, Some(p.forced_bet_type.clone()))),
+                Action::PlayedAction(p) => Some((p.idx, None)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (1, Some(ForcedBetType::SmallBlind)),
+                (2, Some(ForcedBetType::BigBlind)),
+                (3, Some(ForcedBetType::Straddle)),
+                // Everyone calls the straddle and then the straddler raises.
+                (0, None),
+                (1, None),
+                (2, None),
+                (3, None),
+                (0, None),
+                (1, None),
+                (2, None),
+            ],
+            preflop
+        );
+
+        let (round, legal) = seen.borrow()[0];
+        assert_eq!(Round::Preflop, round);
+        assert!(legal.can_check);
+        assert!(legal.can_raise);
+        assert_eq!(
+            Chips::new(240),
+            sim.game_state.player_winnings.iter().sum::<Chips>()
+        );
+    }
+
+    #[test]
+    fn test_straddle_smaller_than_big_blind() {
+        let result = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 4],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .straddle(Chips::new(5))
+            .build();
+        assert_eq!(
+            HoldemSimulationError::StraddleTooSmall,
+            result.err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_straddle_heads_up() {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .historians(vec![Box::new(historian)])
+            .straddle(Chips::new(20))
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(302));
+
+        let forced: Vec<ForcedBetType> = records
+            .borrow()
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::ForcedBet(p) => Some(p.forced_bet_type.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            vec![ForcedBetType::SmallBlind, ForcedBetType::BigBlind],
+            forced
+        );
+    }
+
+    /// Always raises by a single chip, which is never a legal raise.
+    struct MinChipRaiser;
+
+    impl Agent for MinChipRaiser {
+        fn act(&mut self, _id: u128, game_state: &GameState) -> AgentAction {
+            AgentAction::Bet(game_state.current_round_bet() + Chips::new(1))
+        }
+    }
+
+    #[test]
+    fn test_max_actions_stops_illegal_agents() {
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(MinChipRaiser), Box::new(MinChipRaiser)];
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .illegal_raise_policy(IllegalRaisePolicy::Call)
+            .historians(vec![Box::new(historian)])
+            .max_actions(3)
+            .build()
+            .unwrap();
+
+        let result = sim.try_run(&mut StdRng::seed_from_u64(293));
+        assert_eq!(Err(HoldemSimulationError::StepLimit), result);
+        assert_eq!(3, sim.actions_taken);
+        assert_ne!(Round::Complete, sim.game_state.round);
+        let failed = records
+            .borrow()
+            .iter()
+            .filter(|r| matches!(r.action, Action::FailedAction(_)))
+            .count();
+        assert_eq!(3, failed);
+
+        // Running again doesn't get any further.
+        sim.run(&mut StdRng::seed_from_u64(293));
+        assert_eq!(3, sim.actions_taken);
+    }
+
+    #[test]
+    fn test_max_actions_allows_finishing() {
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .max_actions(100)
+            .build()
+            .unwrap();
+        assert_eq!(Ok(()), sim.try_run(&mut StdRng::seed_from_u64(293)));
+        assert_eq!(Round::Complete, sim.game_state.round);
+    }
+
+    /// Heads up to showdown where the button bets the river with aces and
+    /// the big blind calls with seven high.
+    fn river_bet_showdown(muck_losing_hands: bool) -> Vec<(usize, Option<Hand>)> {
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("AsAd"))
+            .hole_cards(1, cards("7c2d"))
+            .board(cards("Kh8h3s4c9d"));
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(VecReplayAgent::new_with_default(
+                vec![
+                    AgentAction::Call,
+                    AgentAction::Call,
+                    AgentAction::Call,
+                    AgentAction::Bet(Chips::new(20)),
+                ],
+                AgentAction::Call,
+            )),
+            Box::new(CallingAgent),
+        ];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .muck_losing_hands(muck_losing_hands)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(306));
+        assert_eq!(vec![0, 1], sim.game_state.showdown_order());
+
+        records
+            .borrow()
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::Showdown(payload) => Some((payload.idx, payload.hand)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_river_aggressor_shows_first() {
+        let shown = river_bet_showdown(false);
+        let order: Vec<usize> = shown.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(vec![0, 1], order);
+        assert!(shown.iter().all(|(_, hand)| hand.is_some()));
+    }
+
+    #[test]
+    fn test_losing_hand_mucked() {
+        let shown = river_bet_showdown(true);
+        assert_eq!(2, shown.len());
+        assert_eq!(0, shown[0].0);
+        assert!(shown[0].1.is_some());
+        assert_eq!((1, None), shown[1]);
+    }
+
+    #[test]
+    fn test_pot_recorded_each_street() {
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(VecReplayAgent::new_with_default(
+                vec![
+                    AgentAction::Bet(Chips::new(30)),
+                    AgentAction::Bet(Chips::new(15)),
+                ],
+                AgentAction::Call,
+            )),
+            Box::new(CallingAgent),
+            Box::new(CallingAgent),
+        ];
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(308));
+
+        let records = records.borrow();
+        let preflop: Chips = records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::ForcedBet(p) => Some(p.bet),
+                Action::PlayedAction(p) if p.round == Round::Preflop => {
+                    Some(p.final_player_bet - p.starting_player_bet)
+                }
+                _ => None,
+            })
+            .sum();
+        let pots: Vec<(Round, Chips)> = records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::PotUpdate(p) => Some((p.round, p.total_pot)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(Chips::new(90), preflop);
+        assert_eq!(
+            vec![
+                (Round::Preflop, preflop),
+                (Round::Flop, Chips::new(135)),
+                (Round::Turn, Chips::new(135)),
+                (Round::River, Chips::new(135)),
+            ],
+            pots
+        );
+    }
+
+    #[test]
+    fn test_full_ring() {
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 10],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(
+                (0..10)
+                    .map(|_| Box::new(CallingAgent) as Box<dyn Agent>)
+                    .collect(),
+            )
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(310));
+        assert_eq!(Round::Complete, sim.game_state.round);
+        assert_eq!(
+            Chips::new(1000),
+            sim.game_state.stacks.iter().sum::<Chips>()
+        );
+    }
+
+    #[test]
+    fn test_player_count_checked() {
+        for players in [1, 11] {
+            let game_state = GameState::new_starting(
+                vec![Chips::new(100); players],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            );
+            let result = HoldemSimulationBuilder::default()
+                .game_state(game_state)
+                .build();
+            assert_eq!(
+                HoldemSimulationError::GameState(GameStateError::PlayerCount(players)),
+                result.err().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_deck_too_small_for_table() {
+        // Ten hands of Omaha and a board is 45 cards, more than a short deck.
+        let result = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 10],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .variant(Variant::Omaha)
+            .deck(Deck::new_short_deck())
+            .build();
+        assert_eq!(HoldemSimulationError::TooManyPlayers, result.err().unwrap());
+
+        // Ten handed holdem needs 25 cards, and three more to burn.
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 10],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        assert_eq!(25, cards_needed(&game_state, false));
+        assert_eq!(28, cards_needed(&game_state, true));
+    }
+
+    #[test]
+    fn test_result_matches_showdown() {
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("7c2d"))
+            .hole_cards(1, cards("AsAd"))
+            .hole_cards(2, cards("KsKd"))
+            .board(cards("Kh8h3s4c9d"));
+        let agents: Vec<Box<dyn Agent>> = (0..3)
+            .map(|_| Box::new(CallingAgent) as Box<dyn Agent>)
+            .collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .rake(Rake::new(0.05, None))
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(312));
+
+        let result = sim.result();
+        assert_eq!(vec![2], result.winners);
+        assert_eq!(vec![(2, sim.game_state.rank_hand(2))], result.winning_ranks);
+        assert!(matches!(result.winning_ranks[0].1, Rank::ThreeOfAKind(_)));
+        assert_eq!(1, result.pots.len());
+        assert_eq!(Chips::new(30), result.pots[0].amount);
+        // Five percent of 30 rounds down to a single chip.
+        assert_eq!(Chips::new(1), result.rake);
+        assert_eq!(-result.rake, result.net.iter().sum::<Chips>());
+        assert_eq!(sim.game_state.stacks, result.stacks);
+    }
+
+    #[test]
+    fn test_result_winners_from_every_runout() {
+        // Find a seed where the kings win the first board and the aces the
+        // second.
+        let (sim, records) = (0..20)
+            .map(|seed| all_in_aces_kings(true, seed))
+            .find(|(_, records)| awards(records).iter().any(|(idx, _)| *idx == 0))
+            .unwrap();
+        let ranks: Vec<(usize, Rank)> = records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::Award(payload) => Some((payload.idx, payload.rank.unwrap())),
+                _ => None,
+            })
+            .collect();
+
+        let result = sim.result();
+        assert_eq!(vec![0, 1], result.winners);
+        assert_eq!(2, result.winning_ranks.len());
+        // The aces only won the second board, so their winning rank is from
+        // that one.
+        assert_eq!(
+            vec![1, 0],
+            ranks.iter().map(|(idx, _)| *idx).collect::<Vec<_>>()
+        );
+        for winning in &ranks {
+            assert!(result.winning_ranks.contains(winning));
+        }
+    }
+
+    #[test]
+    fn test_result_nets_to_rake() {
+        for seed in 0..50 {
+            let agents: Vec<Box<dyn Agent>> = (0..4)
+                .map(|_| Box::<RandomAgent>::default() as Box<dyn Agent>)
+                .collect();
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(GameState::new_starting(
+                    vec![Chips::new(100); 4],
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    1,
+                ))
+                .agents(agents)
+                .rake(Rake::new(0.05, Some(Chips::new(3))))
+                .build()
+                .unwrap();
+            sim.run(&mut StdRng::seed_from_u64(seed));
+
+            let result = sim.result();
+            let net: Chips = result.net.iter().sum();
+            assert_eq!(Chips::ZERO, net + result.rake, "seed {seed}");
+            assert!(!result.winners.is_empty());
+            assert!(
+                result
+                    .net
+                    .iter()
+                    .enumerate()
+                    .all(|(idx, net)| *net <= Chips::ZERO || result.winners.contains(&idx))
+            );
+        }
+    }
+
+    /// The seats that acted preflop, in order, with the forced bets first.
+    fn preflop_order(sim: &mut HoldemSimulation) -> Vec<(usize, Option<ForcedBetType>)> {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        sim.historians.push(Box::new(historian));
+        sim.run(&mut StdRng::seed_from_u64(313));
+        records
+            .borrow()
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::ForcedBet(p) => Some((p.idx, Some(p.forced_bet_type.clone()))),
+                Action::PlayedAction(p) if p.round == Round::Preflop => Some((p.idx, None)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_heads_up_button_acts_first() {
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                1,
+            ))
+            .agents(agents)
+            .small_blind_position(1)
+            .big_blind_position(0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            vec![
+                (1, Some(ForcedBetType::SmallBlind)),
+                (0, Some(ForcedBetType::BigBlind)),
+                (1, None),
+                (0, None),
+            ],
+            preflop_order(&mut sim)
+        );
+    }
+
+    #[test]
+    fn test_custom_blind_positions() {
+        let agents: Vec<Box<dyn Agent>> = (0..3)
+            .map(|_| Box::new(CallingAgent) as Box<dyn Agent>)
+            .collect();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 3],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .small_blind_position(2)
+            .big_blind_position(0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            vec![
+                (2, Some(ForcedBetType::SmallBlind)),
+                (0, Some(ForcedBetType::BigBlind)),
+                (1, None),
+                (2, None),
+                (0, None),
+            ],
+            preflop_order(&mut sim)
+        );
+    }
+
+    #[test]
+    fn test_blind_position_must_be_seated() {
+        let build = |sb: usize, bb: usize| {
+            HoldemSimulationBuilder::default()
+                .game_state(GameState::new_starting(
+                    [100, 0, 100].map(Chips::new).to_vec(),
+                    Chips::new(10),
+                    Chips::new(5),
+                    Chips::ZERO,
+                    0,
+                ))
+                .small_blind_position(sb)
+                .big_blind_position(bb)
+                .build()
+                .err()
+        };
+        assert_eq!(
+            Some(HoldemSimulationError::InvalidBlindPosition(1)),
+            build(1, 2)
+        );
+        assert_eq!(
+            Some(HoldemSimulationError::InvalidBlindPosition(3)),
+            build(0, 3)
+        );
+        assert_eq!(
+            Some(HoldemSimulationError::InvalidBlindPosition(2)),
+            build(2, 2)
+        );
+        assert!(build(2, 0).is_none());
     }
 
     fn deal_hand_card(
@@ -6,16 +6,19 @@ use tracing::{Level, debug_span, event, instrument, trace_span};
 
 use crate::arena::action::{FailedActionPayload, PlayedActionPayload};
 use crate::arena::game_state::Round;
-use crate::core::{Card, Deck, Rank, Rankable};
+use crate::core::{Card, Deck, Hand, Rank};
 
 use super::action::{
-    Action, AgentAction, AwardPayload, DealStartingHandPayload, ForcedBetPayload, GameStartPayload,
-    PlayerSitPayload,
+    Action, AgentAction, AwardPayload, CardVisibility, DealRunoutPayload, DealStartingHandPayload,
+    ForcedBetPayload, GameStartPayload, PlayerSitPayload, PotUpdatePayload, ShowdownPayload,
 };
 
 use super::Agent;
 use super::GameState;
-use super::historian::Historian;
+use super::errors::{GameStateError, HoldemSimulationError};
+use super::game_state::SidePot;
+use super::historian::{Historian, record_with_all};
+use super::{AnteStyle, BettingStructure, Chips, IllegalRaisePolicy, LegalActions, StackedDeck};
 
 /// # Description
 ///
@@ -44,9 +47,16 @@ use super::historian::Historian;
 /// - Any agent bet that is an over bet will silently turn into an all in. That
 ///   is to say if an agent has 100 in their stack and bet `100_000_000` that
 ///   will be accepted and will be equivilant to bet `100`
+/// - Bets bigger than the game state's `BettingStructure` allows are lowered to
+///   the limit, or fail and fold the player if `clamp_over_limit_bets` is
+///   false. In fixed limit every raise is snapped to the street's bet size, and
+///   raises past the cap turn into calls.
 /// - Any bet that `GameState` rules as being impossible, those that turn into
 ///   [`rs-poker::arena::errors::GameStateError`] will instead be turned into
 ///   fold.
+/// - Raises smaller than the minimum raise, and raises from players that a
+///   short all in didn't reopen the betting for, are folds or calls depending
+///   on `illegal_raise_policy`.
 /// - It's expected that you have the same number of agents as you have chip
 ///   stacks in the game state. If players are not active, you can use the
 ///   `FoldingAgent` as a stand in and set the active bit to false.
@@ -56,13 +66,256 @@ pub struct HoldemSimulation {
     pub agents: Vec<Box<dyn Agent>>,
     pub game_state: GameState,
     pub deck: Deck,
+    /// Cards dealt before any from `deck`. See
+    /// [`super::HoldemSimulationBuilder::stacked_deck`].
+    pub stacked_deck: StackedDeck,
     pub historians: Vec<Box<dyn Historian>>,
+    /// The id of the agent in each seat. See
+    /// [`super::HoldemSimulationBuilder::seating`].
+    pub seating: Vec<usize>,
+    /// Lower bets that are over the betting structure's limit rather than
+    /// folding the player. See
+    /// [`super::HoldemSimulationBuilder::clamp_over_limit_bets`].
+    pub clamp_over_limit_bets: bool,
+    /// What to do with a raise that isn't legal. See
+    /// [`super::HoldemSimulationBuilder::illegal_raise_policy`].
+    pub illegal_raise_policy: IllegalRaisePolicy,
+    /// How many times to deal the rest of the board once everyone is all
+    /// in. See [`super::HoldemSimulationBuilder::runouts`].
+    pub runouts: u8,
+    /// Stop the hand after this many agent decisions. See
+    /// [`super::HoldemSimulationBuilder::max_actions`].
+    pub max_actions: Option<usize>,
+    /// How many decisions agents have made so far.
+    pub actions_taken: usize,
+    /// Burn a card before dealing each street. See
+    /// [`super::HoldemSimulationBuilder::burn_cards`].
+    pub burn_cards: bool,
+    /// The cards burned so far, in the order they were burned.
+    pub burned_cards: Vec<Card>,
+    /// Let players that lose at showdown muck. See
+    /// [`super::HoldemSimulationBuilder::muck_losing_hands`].
+    pub muck_losing_hands: bool,
     pub panic_on_historian_error: bool,
+    /// How many board cards were out when the players got all in, if the
+    /// board is going to be run more than once.
+    pub(crate) runouts_from: Option<usize>,
+    /// Set when the deck deals a card that's already in play, which stops
+    /// the hand. Only reachable in release builds, debug builds panic.
+    pub(crate) deck_error: Option<HoldemSimulationError>,
+    /// The awards of pots more than one player was in, for every board
+    /// that was run out. Empty until the showdown.
+    pub(crate) contested_awards: Vec<AwardPayload>,
+}
+
+/// How a finished hand turned out, worked out from the game state alone so
+/// it doesn't need a historian. See [`HoldemSimulation::result`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandResult {
+    /// Each player's stack after the pots are awarded.
+    pub stacks: Vec<Chips>,
+    /// How much each player's stack changed over the hand. These add up to
+    /// minus the rake.
+    pub net: Vec<Chips>,
+    /// The players that won a pot someone else was in, or the last player
+    /// left if everyone else folded.
+    pub winners: Vec<usize>,
+    /// The rank of each winning hand, when the hand went to showdown. A
+    /// player that won on boards where their hand ranked differently has
+    /// an entry for each.
+    pub winning_ranks: Vec<(usize, Rank)>,
+    pub board: Vec<Card>,
+    /// The main pot and then the side pots.
+    pub pots: Vec<SidePot>,
+    pub rake: Chips,
+}
+
+/// Build `$target` from every field of `$source` that a
+/// [`SimulationSnapshot`] keeps, plus the `$extra` fields. Snapshots,
+/// restoring them, and `try_clone` all copy the same fields, so they're
+/// listed once here.
+macro_rules! with_snapshot_fields {
+    ($target:ident { $($extra:tt)* } from $source:ident) => {
+        $target {
+            id: $source.id,
+            game_state: $source.game_state.clone(),
+            deck: $source.deck,
+            stacked_deck: $source.stacked_deck.clone(),
+            seating: $source.seating.clone(),
+            clamp_over_limit_bets: $source.clamp_over_limit_bets,
+            illegal_raise_policy: $source.illegal_raise_policy,
+            runouts: $source.runouts,
+            max_actions: $source.max_actions,
+            actions_taken: $source.actions_taken,
+            burn_cards: $source.burn_cards,
+            burned_cards: $source.burned_cards.clone(),
+            muck_losing_hands: $source.muck_losing_hands,
+            panic_on_historian_error: $source.panic_on_historian_error,
+            runouts_from: $source.runouts_from,
+            deck_error: $source.deck_error,
+            contested_awards: $source.contested_awards.clone(),
+            $($extra)*
+        }
+    };
+}
+
+/// Everything about a [`HoldemSimulation`] except its agents and
+/// historians, which are trait objects that can't be saved. With the
+/// `serde` feature it can be written out and read back to pick a hand up
+/// where it left off, for example after a crash.
+///
+/// Restoring the snapshot takes new agents and historians. Agents that
+/// keep their own state, like a seeded rng, need it restored too for the
+/// hand to play out the same way. What a historian has recorded lives with
+/// whoever reads it, like the storage of a `VecHistorian`, so keeping it is
+/// up to them. Historians passed to `restore` only see the actions from
+/// the snapshot on.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationSnapshot {
+    pub id: u128,
+    pub game_state: GameState,
+    pub deck: Deck,
+    pub stacked_deck: StackedDeck,
+    /// The id of the agent in each seat.
+    pub seating: Vec<usize>,
+    pub clamp_over_limit_bets: bool,
+    pub illegal_raise_policy: IllegalRaisePolicy,
+    pub runouts: u8,
+    pub max_actions: Option<usize>,
+    pub actions_taken: usize,
+    pub burn_cards: bool,
+    pub burned_cards: Vec<Card>,
+    pub muck_losing_hands: bool,
+    pub panic_on_historian_error: bool,
+    pub runouts_from: Option<usize>,
+    /// The deck error that stopped the hand, if there was one.
+    pub deck_error: Option<HoldemSimulationError>,
+    pub contested_awards: Vec<AwardPayload>,
+}
+
+impl SimulationSnapshot {
+    /// Turn the snapshot back into a simulation, seating `agents` in the
+    /// same order as the original. Returns an error unless there's one
+    /// agent for each seat.
+    pub fn restore(
+        self,
+        agents: Vec<Box<dyn Agent>>,
+        historians: Vec<Box<dyn Historian>>,
+    ) -> Result<HoldemSimulation, HoldemSimulationError> {
+        if agents.len() != self.game_state.num_players {
+            return Err(HoldemSimulationError::AgentCount(
+                self.game_state.num_players,
+            ));
+        }
+        Ok(with_snapshot_fields!(HoldemSimulation {
+            agents,
+            historians,
+        } from self))
+    }
 }
 
 impl HoldemSimulation {
+    /// Clone the simulation, or `None` if one of the agents can't be
+    /// copied. The game state, the deck, and every agent through
+    /// [`Agent::clone_agent`] are copied so that the clone can be driven on
+    /// its own, for example to explore different actions from the same
+    /// decision.
+    ///
+    /// - Historians are not cloned. The clone starts with none, add new ones to
+    ///   `historians` if the clone's actions should be recorded.
+    /// - There's no random state to copy, the rng is passed into `run` and
+    ///   `step`. Give each clone its own rng to get different runouts.
+    pub fn try_clone(&self) -> Option<Self> {
+        let agents = self
+            .agents
+            .iter()
+            .map(|agent| agent.clone_agent())
+            .collect::<Option<Vec<_>>>()?;
+        Some(with_snapshot_fields!(Self {
+            agents,
+            historians: vec![],
+        } from self))
+    }
+
+    /// Save everything but the agents and historians. See
+    /// [`SimulationSnapshot`].
+    pub fn snapshot(&self) -> SimulationSnapshot {
+        with_snapshot_fields!(SimulationSnapshot {} from self)
+    }
+
+    /// Summarize the hand once it's complete. For a hand that's still
+    /// being played the stacks and pots are as they are so far.
+    ///
+    /// When the board was run more than once a player that won on any
+    /// board is a winner, with the rank for each board they won.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use rs_poker::arena::agent::CallingAgent;
+    /// use rs_poker::arena::{Agent, Chips, GameState, HoldemSimulationBuilder};
+    ///
+    /// let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+    /// let mut sim = HoldemSimulationBuilder::default()
+    ///     .game_state(GameState::new_starting(
+    ///         vec![Chips::new(100); 2],
+    ///         Chips::new(10),
+    ///         Chips::new(5),
+    ///         Chips::ZERO,
+    ///         0,
+    ///     ))
+    ///     .agents(agents)
+    ///     .build()
+    ///     .unwrap();
+    /// sim.run(&mut StdRng::seed_from_u64(312));
+    ///
+    /// let result = sim.result();
+    /// assert_eq!(5, result.board.len());
+    /// assert_eq!(Chips::ZERO, result.net.iter().sum::<Chips>());
+    /// assert!(!result.winners.is_empty());
+    /// ```
+    pub fn result(&self) -> HandResult {
+        let game_state = &self.game_state;
+        let pots = game_state.side_pots();
+        let showdown = game_state.round_before == Round::Showdown;
+
+        let mut winners: Vec<usize> = vec![];
+        let mut winning_ranks: Vec<(usize, Rank)> = vec![];
+        if showdown {
+            for award in &self.contested_awards {
+                if !winners.contains(&award.idx) {
+                    winners.push(award.idx);
+                }
+                if let Some(rank) = award.rank
+                    && !winning_ranks.contains(&(award.idx, rank))
+                {
+                    winning_ranks.push((award.idx, rank));
+                }
+            }
+        } else {
+            let in_hand = game_state.player_active | game_state.player_all_in;
+            winners.extend(in_hand.ones());
+        }
+        winners.sort_unstable();
+        winning_ranks.sort_unstable_by_key(|(idx, _)| *idx);
+
+        HandResult {
+            stacks: game_state.stacks.clone(),
+            net: (0..game_state.num_players)
+                .map(|idx| game_state.stacks[idx] - game_state.starting_stacks[idx])
+                .collect(),
+            winners,
+            winning_ranks,
+            board: game_state.board.clone(),
+            pots,
+            rake: game_state.total_rake,
+        }
+    }
+
     pub fn more_rounds(&self) -> bool {
-        !matches!(self.game_state.round, Round::Complete)
+        self.deck_error.is_none() && !matches!(self.game_state.round, Round::Complete)
     }
     /// Returns the number of poker agents participating in this simulation.
     pub fn num_agents(&self) -> usize {
@@ -71,15 +324,181 @@ impl HoldemSimulation {
 
     /// Run the simulation all the way to completion. This will mutate the
     /// current state.
+    ///
+    /// If the simulation hits `max_actions` the hand is left unfinished and
+    /// the error is logged. Use `try_run` to get the error back.
     pub fn run<R: Rng>(&mut self, rand: &mut R) {
+        if let Err(error) = self.try_run(rand) {
+            event!(
+                Level::ERROR,
+                ?error,
+                actions_taken = self.actions_taken,
+                "run_stopped"
+            );
+        }
+    }
+
+    /// Run the simulation to completion, or until the agents have made
+    /// `max_actions` decisions without finishing the hand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use rs_poker::arena::agent::CallingAgent;
+    /// use rs_poker::arena::errors::HoldemSimulationError;
+    /// use rs_poker::arena::{Agent, Chips, GameState, HoldemSimulationBuilder};
+    ///
+    /// let agents: Vec<Box<dyn Agent>> = vec![Box::new(CallingAgent), Box::new(CallingAgent)];
+    /// let mut sim = HoldemSimulationBuilder::default()
+    ///     .game_state(GameState::new_starting(
+    ///         vec![Chips::new(100); 2],
+    ///         Chips::new(10),
+    ///         Chips::new(5),
+    ///         Chips::ZERO,
+    ///         0,
+    ///     ))
+    ///     .agents(agents)
+    ///     .max_actions(2)
+    ///     .build()
+    ///     .unwrap();
+    /// let result = sim.try_run(&mut StdRng::seed_from_u64(293));
+    /// assert_eq!(Err(HoldemSimulationError::StepLimit), result);
+    /// assert!(sim.more_rounds());
+    /// ```
+    pub fn try_run<R: Rng>(&mut self, rand: &mut R) -> Result<(), HoldemSimulationError> {
         let span = debug_span!("run",
             game_state = ?self.game_state,
             deck = ?self.deck);
         let _enter = span.enter();
 
+        if self.max_actions.is_none() {
+            while self.more_rounds() {
+                self.run_round(rand);
+            }
+            return self.deck_error.map_or(Ok(()), Err);
+        }
+
+        // Go one decision at a time so the limit is checked before each.
+        while self.next_decision(rand) {
+            self.check_step_limit()?;
+            self.run_single_agent();
+        }
+        self.deck_error.map_or(Ok(()), Err)
+    }
+
+    /// Returns `StepLimit` if the agents have already made `max_actions`
+    /// decisions.
+    fn check_step_limit(&self) -> Result<(), HoldemSimulationError> {
+        match self.max_actions {
+            Some(max_actions) if self.actions_taken >= max_actions => {
+                Err(HoldemSimulationError::StepLimit)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Run the simulation until exactly one agent has made a decision.
+    /// Dealing, forced bets, and finished betting rounds in between are all
+    /// run. Returns false without doing anything if the game is already
+    /// complete, and false if the game completed before any agent had to
+    /// act.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use rs_poker::arena::action::AgentAction;
+    /// use rs_poker::arena::{Chips, GameState, HoldemSimulationBuilder};
+    ///
+    /// let game_state = GameState::new_starting(
+    ///     vec![Chips::new(100); 2],
+    ///     Chips::new(10),
+    ///     Chips::new(5),
+    ///     Chips::ZERO,
+    ///     0,
+    /// );
+    /// let mut sim = HoldemSimulationBuilder::default()
+    ///     .game_state(game_state)
+    ///     .build()
+    ///     .unwrap();
+    /// let mut rng = StdRng::seed_from_u64(273);
+    ///
+    /// // Stop at the first decision, the small blind.
+    /// assert!(sim.step(&mut rng));
+    ///
+    /// // Explore calling instead of what the agent did.
+    /// let mut fork = sim.try_clone().unwrap();
+    /// fork.run_agent_action(AgentAction::Call);
+    /// fork.run(&mut rng);
+    /// ```
+    pub fn step<R: Rng>(&mut self, rand: &mut R) -> bool {
+        if self.next_decision(rand) {
+            self.run_single_agent();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Deal, post forced bets and finish betting rounds until an agent has
+    /// to decide. Returns false if the game ends first.
+    fn next_decision<R: Rng>(&mut self, rand: &mut R) -> bool {
         while self.more_rounds() {
-            self.run_round(rand);
+            if self.is_betting_round() {
+                self.post_blinds();
+                if self.needs_action() {
+                    return true;
+                }
+                self.advance_round();
+            } else {
+                self.run_round(rand);
+            }
         }
+        false
+    }
+
+    /// Run the simulation to completion with `agents` making the decisions
+    /// in place of the simulation's own agents. `agents` is indexed by seat
+    /// like the simulation's agents. Historians are told about every action
+    /// in order as it happens, the same as with `run`.
+    ///
+    /// This doesn't depend on any runtime. Await it from whatever executor
+    /// the agents need.
+    ///
+    /// Returns an error if there isn't one agent for each seat, and the
+    /// same errors as `try_run` if the hand stops early.
+    #[cfg(feature = "async")]
+    pub async fn run_async<R: Rng>(
+        &mut self,
+        agents: &mut [Box<dyn super::agent::AsyncAgent>],
+        rand: &mut R,
+    ) -> Result<(), HoldemSimulationError> {
+        if agents.len() != self.game_state.num_players {
+            return Err(HoldemSimulationError::AgentCount(
+                self.game_state.num_players,
+            ));
+        }
+        while self.next_decision(rand) {
+            self.check_step_limit()?;
+            let idx = self.game_state.to_act_idx();
+            let legal_actions = LegalActions::new(&self.game_state);
+            let action = agents[idx]
+                .act(self.id, &self.game_state, &legal_actions)
+                .await;
+            self.actions_taken += 1;
+            event!(Level::TRACE, ?action, idx, "run_async_agent");
+            self.run_agent_action(action);
+        }
+        self.deck_error.map_or(Ok(()), Err)
+    }
+
+    /// Is the simulation in a round where agents can bet.
+    fn is_betting_round(&self) -> bool {
+        matches!(
+            self.game_state.round,
+            Round::Preflop | Round::Flop | Round::Turn | Round::River
+        )
     }
 
     pub fn run_round<R: Rng>(&mut self, rand: &mut R) {
@@ -96,6 +515,16 @@ impl HoldemSimulation {
             Round::DealPreflop => self.deal_preflop(rand),
             Round::Preflop => self.preflop(),
 
+            Round::DealFlop | Round::DealTurn | Round::DealRiver
+                if self.runouts > 1
+                    && self.runouts_from.is_none()
+                    && self.game_state.is_all_in_showdown() =>
+            {
+                // Remember where the other boards start, then deal the
+                // first one as usual.
+                self.runouts_from = Some(self.game_state.board.len());
+                self.run_round(rand);
+            }
             Round::DealFlop => self.deal_flop(rand),
             Round::Flop => self.flop(),
 
@@ -105,7 +534,7 @@ impl HoldemSimulation {
             Round::DealRiver => self.deal_river(rand),
             Round::River => self.river(),
 
-            Round::Showdown => self.showdown(),
+            Round::Showdown => self.showdown(rand),
 
             // There's nothing left to do to this.
             Round::Complete => (),
@@ -154,18 +583,20 @@ impl HoldemSimulation {
         let _enter = span.enter();
 
         let ante = self.game_state.ante;
-        if ante > 0.0 {
+        if ante > Chips::ZERO && self.game_state.ante_style == AnteStyle::BigBlindAnte {
+            self.post_big_blind_ante();
+        } else if ante > Chips::ZERO {
             // Force the ante from each active player.
             while self.game_state.current_round_num_active_players() > 0 {
                 let idx = self.game_state.to_act_idx();
 
                 self.game_state.do_bet(ante, true).unwrap();
-                self.record_action(Action::ForcedBet(ForcedBetPayload {
+                self.record_forced_bet(ForcedBetPayload {
                     bet: ante,
                     idx,
                     player_stack: self.game_state.stacks[idx],
                     forced_bet_type: super::action::ForcedBetType::Ante,
-                }));
+                });
 
                 self.game_state.round_data.needs_action.disable(idx);
             }
@@ -173,6 +604,30 @@ impl HoldemSimulation {
         self.advance_round();
     }
 
+    /// Force the ante for the whole table from the big blind. The blind is
+    /// posted in full before the ante, so the ante is capped at what the
+    /// player has after the blind.
+    fn post_big_blind_ante(&mut self) {
+        let idx = self.game_state.big_blind_idx;
+        self.game_state.round_data.to_act_idx = idx;
+        let ante = self
+            .game_state
+            .ante
+            .min(self.game_state.stacks[idx] - self.game_state.big_blind)
+            .max(Chips::ZERO);
+        if ante <= Chips::ZERO {
+            return;
+        }
+
+        self.game_state.do_bet(ante, true).unwrap();
+        self.record_forced_bet(ForcedBetPayload {
+            bet: ante,
+            idx,
+            player_stack: self.game_state.stacks[idx],
+            forced_bet_type: super::action::ForcedBetType::Ante,
+        });
+    }
+
     fn deal_preflop<R: Rng>(&mut self, rand: &mut R) {
         let span = trace_span!("deal_preflop");
         let _enter = span.enter();
@@ -181,7 +636,7 @@ impl HoldemSimulation {
         while self.game_state.current_round_num_active_players() > 0 {
             let idx = self.game_state.to_act_idx();
 
-            self.deal_player_cards(2, rand);
+            self.deal_player_cards(self.game_state.variant.hole_cards(), rand);
 
             // This allows us to not deal to players that
             // are sitting out, while also going in the same
@@ -197,36 +652,80 @@ impl HoldemSimulation {
         let span = trace_span!("preflop");
         let _enter = span.enter();
 
-        // Force the small blind and the big blind.
+        self.post_blinds();
+        self.run_betting_round();
+        self.advance_round();
+    }
+
+    /// Force the small blind and the big blind, unless they are already
+    /// posted.
+    fn post_blinds(&mut self) {
+        if self.game_state.round != Round::Preflop {
+            return;
+        }
+
         if !self.game_state.sb_posted {
-            let sb = self.game_state.small_blind;
-            let sb_idx = self.game_state.to_act_idx();
-            self.game_state.do_bet(sb, true).unwrap();
+            // A small blind seat without a player is a dead small blind.
+            let sb_idx = self.game_state.small_blind_idx;
             self.game_state.sb_posted = true;
-
-            self.record_action(Action::ForcedBet(ForcedBetPayload {
-                bet: sb,
-                idx: sb_idx,
-                forced_bet_type: super::action::ForcedBetType::SmallBlind,
-                player_stack: self.game_state.stacks[sb_idx],
-            }));
+            if self.game_state.player_active.get(sb_idx) {
+                let sb = self.game_state.small_blind;
+                self.game_state.round_data.to_act_idx = sb_idx;
+                self.game_state.do_bet(sb, true).unwrap();
+
+                self.record_forced_bet(ForcedBetPayload {
+                    bet: sb,
+                    idx: sb_idx,
+                    forced_bet_type: super::action::ForcedBetType::SmallBlind,
+                    player_stack: self.game_state.stacks[sb_idx],
+                });
+            }
         }
 
         if !self.game_state.bb_posted {
-            let bb = self.game_state.big_blind;
-            let bb_idx = self.game_state.to_act_idx();
-            self.game_state.do_bet(bb, true).unwrap();
-            self.game_state.bb_posted = true;
-            self.record_action(Action::ForcedBet(ForcedBetPayload {
-                bet: bb,
-                idx: bb_idx,
-                forced_bet_type: super::action::ForcedBetType::BigBlind,
-                player_stack: self.game_state.stacks[bb_idx],
-            }));
+            let bb_idx = self.game_state.big_blind_idx;
+            self.game_state.round_data.to_act_idx = bb_idx;
+            if self.game_state.player_active.get(bb_idx) {
+                let bb = self.game_state.big_blind;
+                self.game_state.do_bet(bb, true).unwrap();
+                self.game_state.bb_posted = true;
+                self.record_forced_bet(ForcedBetPayload {
+                    bet: bb,
+                    idx: bb_idx,
+                    forced_bet_type: super::action::ForcedBetType::BigBlind,
+                    player_stack: self.game_state.stacks[bb_idx],
+                });
+            } else {
+                // The big blind was put all in by the ante.
+                self.game_state.bb_posted = true;
+                self.game_state.round_data.advance_action();
+            }
+            self.post_straddle();
         }
+    }
 
-        self.run_betting_round();
-        self.advance_round();
+    /// Post the straddle for the next player to act after the big blind.
+    /// Forced bets keep the player that posted them in the action, so
+    /// everyone else acts first and the straddler has the last say.
+    ///
+    /// Heads up the next player is the small blind, who has already posted,
+    /// so there's no straddle.
+    fn post_straddle(&mut self) {
+        let straddle = self.game_state.straddle;
+        let idx = self.game_state.to_act_idx();
+        if straddle <= Chips::ZERO
+            || idx == self.game_state.small_blind_idx
+            || !self.game_state.round_data.needs_action.get(idx)
+        {
+            return;
+        }
+        self.game_state.do_bet(straddle, true).unwrap();
+        self.record_forced_bet(ForcedBetPayload {
+            bet: straddle,
+            idx,
+            forced_bet_type: super::action::ForcedBetType::Straddle,
+            player_stack: self.game_state.stacks[idx],
+        });
     }
 
     fn deal_flop<R: Rng>(&mut self, rand: &mut R) {
@@ -276,123 +775,181 @@ impl HoldemSimulation {
         self.advance_round();
     }
 
-    fn showdown(&mut self) {
+    fn showdown<R: Rng>(&mut self, rand: &mut R) {
         let span = trace_span!("showdown");
         let _enter = span.enter();
 
-        // Rank each player that still has a chance.
+        // Rank each player that still has a chance, on every board that was
+        // run out.
         let active = self.game_state.player_active | self.game_state.player_all_in;
+        let runouts: Vec<(BTreeMap<usize, Rank>, Vec<Hand>)> = self
+            .runout_game_states(rand)
+            .into_iter()
+            .map(|game_state| {
+                let ranks = active
+                    .ones()
+                    .map(|idx| (idx, game_state.rank_hand(idx)))
+                    .collect();
+                (ranks, game_state.hands)
+            })
+            .collect();
 
-        let mut bets = self.game_state.player_bet.clone();
-
-        // Create a map where the keys are the ranks of hands and
-        // the values are vectors of player index, for players that had that hand
-        let ranks = active
-            .ones()
-            .map(|idx| (idx, self.game_state.hands[idx].rank()))
-            .fold(
-                BTreeMap::new(),
-                |mut map: BTreeMap<Rank, Vec<usize>>, (idx, rank)| {
-                    map.entry(rank)
-                        .and_modify(|m| {
-                            m.push(idx);
-                            m.sort_by(|a, b| bets[*a].partial_cmp(&bets[*b]).unwrap());
-                        })
-                        .or_insert_with(|| vec![idx]);
-
-                    map
-                },
-            );
-        // There can be bets that players made but didn't take to showdown they should
-        // be added to the main pot. Keep them here and then split them up
-        // between the winners of the first rank pot. resetting the ammount to
-        // zero.
-        let mut folded_pot = bets
+        // Only pots that more than one player can win are raked. A pot with
+        // a single player in it is a bet no one called being returned.
+        let pots = self.game_state.side_pots();
+        let contested: Chips = pots
             .iter()
-            .enumerate()
-            .filter(|(idx, _)| !active.get(*idx))
-            .map(|(_, bet)| *bet)
-            .sum::<f32>();
-        bets = bets
+            .filter(|pot| pot.eligible.count() > 1)
+            .map(|pot| pot.amount)
+            .sum();
+        let rake = self.take_rake(contested);
+        // The rake comes out of each contested pot in proportion to its
+        // size. The chips that don't divide come out of the first one.
+        let mut pot_rakes: Vec<Chips> = pots
             .iter()
-            .enumerate()
-            .map(|(idx, v)| if active.get(idx) { *v } else { 0.0 })
+            .map(|pot| {
+                if pot.eligible.count() > 1 && contested > Chips::ZERO {
+                    Chips::new(rake.get() * pot.amount.get() / contested.get())
+                } else {
+                    Chips::ZERO
+                }
+            })
             .collect();
+        if let Some(first) = pots.iter().position(|pot| pot.eligible.count() > 1) {
+            let divided: Chips = pot_rakes.iter().sum();
+            pot_rakes[first] += rake - divided;
+        }
 
-        // By default the map gives keys in assending order. We want them descending.
-        // The actual player vector is sorted in ascending order according to bet size.
-
-        for (rank, players) in ranks.into_iter().rev() {
-            let mut start_idx = 0;
-            let end_idx = players.len();
-
-            // We'll conitune until every player has been given the matching money
-            // up to their wager. However since some players might have gone allin
-            // earlier we keep removing from the pot and splitting it equally to all
-            // those players still left in the pot.
-            while start_idx < end_idx {
-                // Becasue our lists are ordered from smallest bets to largest
-                // we can just assume the first one is the smallest
-                //
-                // Here we use that property to find the max bet that this pot
-                // will give for this round of splitting ties.
-                let max_wager = bets[players[start_idx]];
-                let mut pot: f64 = f64::from(folded_pot);
-                folded_pot = 0.0;
-
-                // Most common is that ties will
-                // be for wagers that are all the same.
-                // So check if there's no more
-                // bets to award for this player.
-                if max_wager <= 0.0 {
-                    start_idx += 1;
+        // Work out every award first so the players that win nothing know
+        // they can muck.
+        let mut awards = vec![];
+        // Each pot goes to the best hand among the players eligible for it.
+        // Ties split the pot equally, in whole chips so none go missing.
+        // When the board was run more than once each runout decides an equal
+        // share of every pot.
+        let num_runouts = runouts.len() as i64;
+        for (pot, pot_rake) in pots.into_iter().zip(pot_rakes) {
+            let pot_chips = pot.amount - pot_rake;
+            let runout_chips = Chips::new(pot_chips.get() / num_runouts);
+            let leftover = Chips::new(pot_chips.get() % num_runouts);
+            let pot_awards = awards.len();
+
+            for (ranks, hands) in &runouts {
+                let Some(best) = pot.eligible.ones().map(|idx| ranks[&idx]).max() else {
                     continue;
-                }
-
-                // Take all the wagers remaining into a
-                // side pot. However this side pot might
-                // be the only pot if there were no allins
-                for b in bets.iter_mut() {
-                    let w = (*b).min(max_wager);
-                    *b -= w;
-                    pot += w as f64;
-                }
-
-                // Now all the winning players get
-                // an equal share of the side pot
-                let num_players = (end_idx - start_idx) as f64;
-                let split = pot / num_players;
-
-                for idx in &players[start_idx..end_idx] {
-                    // Record that this player won something
-                    event!(parent: &span, Level::INFO, idx, split, pot, ?rank, "pot_awarded");
-                    self.game_state.award(*idx, split as f32);
-                    self.record_action(Action::Award(AwardPayload {
-                        idx: *idx,
-                        total_pot: pot as f32,
-                        award_amount: split as f32,
+                };
+                let mut winners: Vec<usize> = pot
+                    .eligible
+                    .ones()
+                    .filter(|idx| ranks[idx] == best)
+                    .collect();
+                // When the chips don't split evenly the extra ones go to the
+                // worst positions first.
+                winners.sort_by_key(|idx| self.game_state.odd_chip_order(*idx));
+                let splits = runout_chips.split(winners.len());
+
+                for (idx, award_amount) in winners.into_iter().zip(splits) {
+                    let award = AwardPayload {
+                        idx,
+                        total_pot: pot.amount,
+                        award_amount,
                         // Since we had a showdown we cen copy the hand
                         // and the resulting rank.
-                        rank: Some(rank),
-                        hand: Some(self.game_state.hands[*idx]),
-                    }));
+                        rank: Some(best),
+                        hand: Some(hands[idx]),
+                        rake: Chips::ZERO,
+                    };
+                    awards.push(award);
                 }
+            }
 
-                // Since the first player is bet size
-                // that we used. They have won everything that they're eligible for.
-                start_idx += 1;
+            // Chips that don't divide between the runouts go to the first of
+            // this pot's winners left of the button.
+            if let Some(award) = awards[pot_awards..]
+                .iter_mut()
+                .min_by_key(|award| self.game_state.odd_chip_order(award.idx))
+            {
+                award.award_amount += leftover;
+            }
+            if let Some(award) = awards.get_mut(pot_awards) {
+                award.rake = pot_rake;
+            }
+            if pot.eligible.count() > 1 {
+                self.contested_awards
+                    .extend(awards[pot_awards..].iter().cloned());
             }
         }
 
+        // The first player to show always has to. After that a player that
+        // isn't all in and wins nothing can throw their hand away.
+        let (first_ranks, first_hands) = &runouts[0];
+        for (i, idx) in self.game_state.showdown_order().into_iter().enumerate() {
+            let mucks = self.muck_losing_hands
+                && i > 0
+                && !self.game_state.player_all_in.get(idx)
+                && awards.iter().all(|award| award.idx != idx);
+            self.record_action(Action::Showdown(ShowdownPayload {
+                idx,
+                hand: (!mucks).then_some(first_hands[idx]),
+                rank: (!mucks).then_some(first_ranks[&idx]),
+            }));
+        }
+
+        for award in awards {
+            // Record that this player won something
+            event!(parent: &span, Level::INFO, idx = award.idx, split = award.award_amount.get(), pot = award.total_pot.get(), rank = ?award.rank, "pot_awarded");
+            self.award(award);
+        }
+
         self.end_game();
     }
 
+    /// The game state for each board that decides the showdown. That's just
+    /// the current game state, unless the board is being run more than
+    /// once. Then the other runouts are dealt here from what's left of the
+    /// deck, and each of their cards is recorded as a `DealRunout`.
+    fn runout_game_states<R: Rng>(&mut self, rand: &mut R) -> Vec<GameState> {
+        let first = self.game_state.clone();
+        let Some(from) = self.runouts_from.filter(|from| *from < first.board.len()) else {
+            return vec![first];
+        };
+
+        let first_runout = first.board[from..].to_vec();
+        let mut game_states = vec![first];
+        for runout_idx in 1..usize::from(self.runouts) {
+            let mut other = game_states[0].clone();
+            let runout = self.deal_cards(&[], first_runout.len(), rand);
+            for card in &runout {
+                self.record_action(Action::DealRunout(DealRunoutPayload {
+                    runout: runout_idx,
+                    card: *card,
+                }));
+            }
+            for hand in &mut other.hands {
+                for card in &first_runout {
+                    hand.remove(card);
+                }
+                hand.extend(runout.iter().cloned());
+            }
+            other.board.truncate(from);
+            other.board.extend(runout);
+            game_states.push(other);
+        }
+        game_states
+    }
+
     fn deal_player_cards<R: Rng>(&mut self, num_cards: usize, rand: &mut R) {
-        let new_hand: Vec<Card> = self.deal_cards(num_cards, rand);
+        let stacked = self
+            .stacked_deck
+            .player_cards(self.game_state.to_act_idx())
+            .to_vec();
+        let new_hand: Vec<Card> = self.deal_cards(&stacked, num_cards, rand);
         for c in &new_hand {
             self.record_action(Action::DealStartingHand(DealStartingHandPayload {
                 card: *c,
                 idx: self.game_state.to_act_idx(),
+                // Hold'em hole cards are always dealt face down.
+                visibility: CardVisibility::Hidden,
             }));
         }
 
@@ -401,7 +958,14 @@ impl HoldemSimulation {
     }
 
     fn deal_comunity_cards<R: Rng>(&mut self, num_cards: usize, rand: &mut R) {
-        let mut community_cards = self.deal_cards(num_cards, rand);
+        if self.burn_cards {
+            self.burn_card(rand);
+        }
+        let stacked = self
+            .stacked_deck
+            .board_cards(self.game_state.board.len())
+            .to_vec();
+        let mut community_cards = self.deal_cards(&stacked, num_cards, rand);
         for c in &community_cards {
             self.record_action(Action::DealCommunity(*c));
         }
@@ -413,11 +977,40 @@ impl HoldemSimulation {
         self.game_state.board.append(&mut community_cards);
     }
 
-    /// Pull num_cards from the deck and return them as a vector.
-    fn deal_cards<R: Rng>(&mut self, num_cards: usize, rand: &mut R) -> Vec<Card> {
-        let mut cards: Vec<Card> = (0..num_cards)
-            .map(|_| self.deck.deal(rand).unwrap())
-            .collect();
+    /// Take the top card off the deck, or the next stacked burn card, and
+    /// put it face down out of play.
+    fn burn_card<R: Rng>(&mut self, rand: &mut R) {
+        let card = match self.stacked_deck.burn_card(self.burned_cards.len()) {
+            Some(card) => Some(card),
+            None => self.deck.deal(rand),
+        };
+        let Some(card) = self.checked_card(card) else {
+            return;
+        };
+        self.burned_cards.push(card);
+        self.record_action(Action::BurnCard(card));
+    }
+
+    /// Take num_cards, first from `stacked` and then from the deck, and
+    /// return them as a vector.
+    fn deal_cards<R: Rng>(
+        &mut self,
+        stacked: &[Card],
+        num_cards: usize,
+        rand: &mut R,
+    ) -> Vec<Card> {
+        let mut cards: Vec<Card> = stacked.iter().copied().take(num_cards).collect();
+        while cards.len() < num_cards {
+            let dealt = self.deck.deal(rand);
+            match self.checked_card(dealt) {
+                Some(card) if !cards.contains(&card) => cards.push(card),
+                Some(card) => {
+                    self.deck_integrity_violated(Some(card));
+                    break;
+                }
+                None => break,
+            }
+        }
 
         // Keep the cards sorted in min to max order
         // this keeps the number of permutations down since
@@ -427,6 +1020,36 @@ impl HoldemSimulation {
         cards
     }
 
+    /// Check that a card coming off the deck isn't already in a hand, on
+    /// the board, or burned. Every `Card` is one of the 52, so that's all
+    /// that can go wrong besides the deck running out. `None` if either
+    /// happened.
+    fn checked_card(&mut self, card: Option<Card>) -> Option<Card> {
+        let in_play = |card: &Card| {
+            self.game_state.board.contains(card)
+                || self.burned_cards.contains(card)
+                || self.game_state.hands.iter().any(|hand| hand.contains(card))
+        };
+        match card {
+            Some(card) if !in_play(&card) => Some(card),
+            _ => {
+                self.deck_integrity_violated(card);
+                None
+            }
+        }
+    }
+
+    /// Debug builds panic so the bug is found where it happens. Release
+    /// builds stop the hand and `try_run` returns the error.
+    fn deck_integrity_violated(&mut self, card: Option<Card>) {
+        let error = HoldemSimulationError::DeckIntegrity(card);
+        if cfg!(debug_assertions) {
+            panic!("{error}");
+        }
+        event!(Level::ERROR, ?error, "deck_integrity");
+        self.deck_error = Some(error);
+    }
+
     /// This runs betting for the round to completion. It will run until
     /// everyone has acted or until the round has been completed because no one
     /// can act anymore.
@@ -443,7 +1066,9 @@ impl HoldemSimulation {
         let players_needing_action = self.game_state.round_data.needs_action;
 
         let active_players_needing_action = active_players & players_needing_action;
-        !active_players_needing_action.empty()
+        // Once everyone is all in there's nothing to decide, the board just
+        // runs out.
+        !active_players_needing_action.empty() && !self.game_state.is_all_in_showdown()
     }
 
     /// Run the next agent in the game state to act.
@@ -451,7 +1076,10 @@ impl HoldemSimulation {
         let idx = self.game_state.to_act_idx();
         let span = trace_span!("run_agent", idx);
         let _enter = span.enter();
-        let action = self.agents[idx].act(self.id, &self.game_state);
+        let legal_actions = LegalActions::new(&self.game_state);
+        let action =
+            self.agents[idx].act_with_legal_actions(self.id, &self.game_state, &legal_actions);
+        self.actions_taken += 1;
 
         event!(parent: &span, Level::TRACE, ?action, idx);
         self.run_agent_action(action);
@@ -463,6 +1091,15 @@ impl HoldemSimulation {
     pub fn run_agent_action(&mut self, agent_action: AgentAction) {
         event!(Level::TRACE, ?agent_action, "run_agent_action");
 
+        let agent_action = match self.limit_action(&agent_action) {
+            Some(action) => action,
+            None => {
+                event!(Level::WARN, ?agent_action, "over_limit_bet");
+                self.fail_to_fold(agent_action);
+                return;
+            }
+        };
+
         let idx = self.game_state.to_act_idx();
         let starting_bet = self.game_state.current_round_bet();
         let starting_player_bet = self.game_state.current_round_player_bet(idx);
@@ -525,6 +1162,10 @@ impl HoldemSimulation {
                 let bet_result = self.game_state.do_bet(bet_amount, false);
 
                 match bet_result {
+                    Err(error) if self.calls_instead(error) => {
+                        event!(Level::WARN, ?error, "illegal_raise");
+                        self.fail_to_call(agent_action);
+                    }
                     Err(error) => {
                         // If the agent failed to give us a good bet then they lose this round.
                         //
@@ -669,6 +1310,10 @@ impl HoldemSimulation {
                 let bet_result = self.game_state.do_bet(all_in_amount, false);
 
                 match bet_result {
+                    Err(error) if self.calls_instead(error) => {
+                        event!(Level::WARN, ?error, "illegal_raise");
+                        self.fail_to_call(agent_action);
+                    }
                     Err(error) => {
                         // If the agent failed to give us a good bet then they lose this round.
                         //
@@ -739,6 +1384,112 @@ impl HoldemSimulation {
         }
     }
 
+    /// Apply the betting structure to the action. Returns `None` if the
+    /// bet is over the limit and shouldn't be clamped.
+    fn limit_action(&self, agent_action: &AgentAction) -> Option<AgentAction> {
+        let betting_structure = self.game_state.betting_structure;
+        if betting_structure == BettingStructure::NoLimit {
+            return Some(agent_action.clone());
+        }
+
+        let max_bet = betting_structure.max_bet(&self.game_state);
+        let wanted = match agent_action {
+            AgentAction::Bet(amount) => *amount,
+            AgentAction::AllIn => {
+                self.game_state.current_round_current_player_bet()
+                    + self.game_state.current_player_stack()
+            }
+            AgentAction::Call | AgentAction::Fold => return Some(agent_action.clone()),
+        };
+
+        if betting_structure.is_fixed() {
+            // Any raise is snapped to the one legal raise size. Once the
+            // street is capped that's the same as calling.
+            if wanted > self.game_state.current_round_bet() {
+                Some(AgentAction::Bet(max_bet))
+            } else {
+                Some(agent_action.clone())
+            }
+        } else if wanted <= max_bet {
+            Some(agent_action.clone())
+        } else if self.clamp_over_limit_bets {
+            Some(AgentAction::Bet(max_bet))
+        } else {
+            None
+        }
+    }
+
+    /// Should a bet that failed with `error` be turned into a call rather
+    /// than a fold.
+    fn calls_instead(&self, error: GameStateError) -> bool {
+        self.illegal_raise_policy == IllegalRaisePolicy::Call
+            && matches!(
+                error,
+                GameStateError::RaiseSizeTooSmall | GameStateError::RaiseNotReopened
+            )
+    }
+
+    /// Record that `agent_action` couldn't be played and call for the
+    /// player instead.
+    fn fail_to_call(&mut self, agent_action: AgentAction) {
+        let idx = self.game_state.to_act_idx();
+        let starting_bet = self.game_state.current_round_bet();
+        let starting_player_bet = self.game_state.current_round_player_bet(idx);
+        let starting_min_raise = self.game_state.current_round_min_raise();
+        let starting_pot = self.game_state.total_pot;
+
+        // Calling is always legal, if it's more than the stack it's an all in.
+        self.game_state.do_bet(starting_bet, false).unwrap();
+        self.record_action(Action::FailedAction(FailedActionPayload {
+            action: agent_action,
+            result: PlayedActionPayload {
+                action: AgentAction::Call,
+                player_stack: self.game_state.stacks[idx],
+                idx,
+                round: self.game_state.round,
+                starting_bet,
+                final_bet: self.game_state.current_round_bet(),
+                starting_min_raise,
+                final_min_raise: self.game_state.current_round_min_raise(),
+                starting_player_bet,
+                final_player_bet: self.game_state.current_round_player_bet(idx),
+                players_active: self.game_state.player_active,
+                players_all_in: self.game_state.player_all_in,
+                starting_pot,
+                final_pot: self.game_state.total_pot,
+            },
+        }));
+    }
+
+    /// Record that `agent_action` couldn't be played and fold the player
+    /// instead.
+    fn fail_to_fold(&mut self, agent_action: AgentAction) {
+        let idx = self.game_state.to_act_idx();
+        let bet = self.game_state.current_round_bet();
+        let player_bet = self.game_state.current_round_player_bet(idx);
+        let min_raise = self.game_state.current_round_min_raise();
+        self.record_action(Action::FailedAction(FailedActionPayload {
+            action: agent_action,
+            result: PlayedActionPayload {
+                action: AgentAction::Fold,
+                player_stack: self.game_state.stacks[idx],
+                idx,
+                round: self.game_state.round,
+                starting_bet: bet,
+                final_bet: bet,
+                starting_min_raise: min_raise,
+                final_min_raise: min_raise,
+                starting_player_bet: player_bet,
+                final_player_bet: player_bet,
+                players_active: self.game_state.player_active,
+                players_all_in: self.game_state.player_all_in,
+                starting_pot: self.game_state.total_pot,
+                final_pot: self.game_state.total_pot,
+            },
+        }));
+        self.player_fold();
+    }
+
     #[instrument]
     fn player_fold(&mut self) {
         self.game_state.fold();
@@ -750,21 +1501,56 @@ impl HoldemSimulation {
         if left.count() <= 1 {
             if let Some(winning_idx) = left.ones().next() {
                 let total_pot = self.game_state.total_pot;
-                event!(Level::INFO, winning_idx, total_pot, "folded_to_winner");
-                self.game_state.award(winning_idx, total_pot);
-                self.record_action(Action::Award(AwardPayload {
+                // The part of the winner's bet no one called goes straight
+                // back, only the rest of the pot is raked.
+                let called = self
+                    .game_state
+                    .player_bet
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| *idx != winning_idx)
+                    .map(|(_, bet)| *bet)
+                    .max()
+                    .unwrap_or_default();
+                let uncalled = (self.game_state.player_bet[winning_idx] - called).max(Chips::ZERO);
+                let rake = self.take_rake(total_pot - uncalled);
+                event!(
+                    Level::INFO,
+                    winning_idx,
+                    total_pot = total_pot.get(),
+                    "folded_to_winner"
+                );
+                self.award(AwardPayload {
                     idx: winning_idx,
                     total_pot,
-                    award_amount: total_pot,
+                    award_amount: total_pot - rake,
                     rank: None,
                     hand: None,
-                }))
+                    rake,
+                });
             }
 
             self.end_game();
         }
     }
 
+    /// Work out the rake on `pot` chips. Nothing is taken if the hand ended
+    /// before the flop and the rake is no flop no drop.
+    fn take_rake(&self, pot: Chips) -> Chips {
+        let rake = self.game_state.rake;
+        if rake.no_flop_no_drop && self.game_state.board.is_empty() {
+            return Chips::ZERO;
+        }
+        rake.amount(pot)
+    }
+
+    /// Pay out an award and add its rake to the hand's total.
+    fn award(&mut self, award: AwardPayload) {
+        self.game_state.award(award.idx, award.award_amount);
+        self.game_state.total_rake += award.rake;
+        self.record_action(Action::Award(award));
+    }
+
     #[instrument]
     fn end_game(&mut self) {
         let current_round = self.game_state.round;
@@ -777,40 +1563,38 @@ impl HoldemSimulation {
     #[instrument]
     fn advance_round(&mut self) {
         let current_round = self.game_state.round;
+        if matches!(
+            current_round,
+            Round::Preflop | Round::Flop | Round::Turn | Round::River
+        ) {
+            self.record_action(Action::PotUpdate(PotUpdatePayload {
+                round: current_round,
+                total_pot: self.game_state.total_pot,
+            }));
+        }
         self.game_state.advance_round();
         if self.game_state.round != current_round {
             self.record_action(Action::RoundAdvance(self.game_state.round));
         }
     }
 
+    /// Keep track of the forced bet on the game state before recording it.
+    fn record_forced_bet(&mut self, payload: ForcedBetPayload) {
+        self.game_state.forced_bets.push(payload.clone());
+        self.record_action(Action::ForcedBet(payload));
+    }
+
     // Make sure that all modifications to game_state are complete before calling
     // `record_action`. This is critical for making sure replays are deterministic.
     fn record_action(&mut self, action: Action) {
         event!(Level::TRACE, action = ?action, game_state = ?self.game_state, "add_action");
-        // Iterate over the historians and record the action
-        // If there's an error, log it and remove the historian
-        self.historians = self
-            .historians
-            .drain(..)
-            .filter_map(|mut historian| {
-                match historian.record_action(self.id, &self.game_state, action.clone()) {
-                    Ok(_) => Some(historian),
-                    Err(error) => {
-                        event!(Level::ERROR, ?error, "historian_error");
-
-                        // Some user might never error.
-                        // For them it's a panic.
-                        if self.panic_on_historian_error {
-                            panic!(
-                                "Historian error {}\naction={:?}\ngame_state = {:?}",
-                                error, action, self.game_state
-                            );
-                        }
-                        None
-                    }
-                }
-            })
-            .collect();
+        record_with_all(
+            &mut self.historians,
+            self.id,
+            &self.game_state,
+            action,
+            self.panic_on_historian_error,
+        );
     }
 }
 
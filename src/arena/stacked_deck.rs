@@ -0,0 +1,321 @@
+//! Choose the cards a simulation deals.
+use crate::core::{Card, Deck};
+
+use super::errors::HoldemSimulationError;
+
+/// Cards to deal ahead of the shuffled deck.
+///
+/// Each player can be given their hole cards, and the board can be set
+/// from the flop on. Anything not stacked, for example the river when only
+/// the flop and turn are given, is dealt at random from the cards that are
+/// left using the simulation's rng. Pass it to
+/// [`super::HoldemSimulationBuilder::stacked_deck`].
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::arena::{Chips, GameState, HoldemSimulationBuilder, StackedDeck};
+/// use rs_poker::core::Hand;
+///
+/// let stacked = StackedDeck::new()
+///     .hole_cards(0, Hand::new_from_str("AsKs").unwrap().iter())
+///     .board(Hand::new_from_str("Ah2c7d").unwrap().iter());
+/// let game_state = GameState::new_starting(
+///     vec![Chips::new(100); 2],
+///     Chips::new(10),
+///     Chips::new(5),
+///     Chips::ZERO,
+///     0,
+/// );
+/// let sim = HoldemSimulationBuilder::default()
+///     .game_state(game_state)
+///     .stacked_deck(stacked)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StackedDeck {
+    hole_cards: Vec<Vec<Card>>,
+    board: Vec<Card>,
+    burns: Vec<Card>,
+}
+
+impl StackedDeck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deal `cards` to the player in seat `idx`.
+    pub fn hole_cards(mut self, idx: usize, cards: impl IntoIterator<Item = Card>) -> Self {
+        if self.hole_cards.len() <= idx {
+            self.hole_cards.resize(idx + 1, vec![]);
+        }
+        self.hole_cards[idx] = cards.into_iter().collect();
+        self
+    }
+
+    /// Deal `cards` as the board, in order from the first flop card.
+    pub fn board(mut self, cards: impl IntoIterator<Item = Card>) -> Self {
+        self.board = cards.into_iter().collect();
+        self
+    }
+
+    /// Burn `cards` in order, the first before the flop. Only used when the
+    /// simulation burns cards, see
+    /// [`super::HoldemSimulationBuilder::burn_cards`].
+    pub fn burn_cards(mut self, cards: impl IntoIterator<Item = Card>) -> Self {
+        self.burns = cards.into_iter().collect();
+        self
+    }
+
+    /// The stacked card for burn number `burned`, counting from zero.
+    pub fn burn_card(&self, burned: usize) -> Option<Card> {
+        self.burns.get(burned).copied()
+    }
+
+    /// The stacked hole cards for the player in seat `idx`.
+    pub fn player_cards(&self, idx: usize) -> &[Card] {
+        self.hole_cards.get(idx).map_or(&[], |c| c.as_slice())
+    }
+
+    /// The stacked board cards that come after the first `dealt` cards.
+    pub fn board_cards(&self, dealt: usize) -> &[Card] {
+        self.board.get(dealt..).unwrap_or(&[])
+    }
+
+    /// Every stacked card.
+    pub fn cards(&self) -> impl Iterator<Item = Card> + '_ {
+        self.hole_cards
+            .iter()
+            .flatten()
+            .chain(self.board.iter())
+            .chain(self.burns.iter())
+            .copied()
+    }
+
+    /// Take the stacked cards out of `deck` so that they can't be dealt
+    /// again at random.
+    ///
+    /// Returns an error for the first stacked card that isn't in the deck.
+    /// That's a card that's stacked twice, or one that's already been dealt.
+    pub(crate) fn remove_from(&self, deck: &mut Deck) -> Result<(), HoldemSimulationError> {
+        for card in self.cards() {
+            if !deck.remove(&card) {
+                return Err(HoldemSimulationError::StackedCardUnavailable(card));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::cards;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::{
+        arena::{
+            Agent, Chips, GameState, HoldemSimulationBuilder,
+            action::Action,
+            agent::CallingAgent,
+            historian::{HistoryRecord, VecHistorian},
+        },
+        core::Hand,
+    };
+
+    use super::*;
+
+    fn run_stacked(stacked: StackedDeck, seed: u64) -> (GameState, Vec<HistoryRecord>) {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::<CallingAgent>::default(),
+            Box::<CallingAgent>::default(),
+        ];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .stacked_deck(stacked)
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(seed));
+        (sim.game_state, records.take())
+    }
+
+    #[test]
+    fn test_stacked_hand_wins() {
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("KdKh"))
+            .hole_cards(1, cards("AsKs"))
+            .board(cards("AhKc2c7d9h"));
+        for seed in 0..5 {
+            let (game_state, records) = run_stacked(stacked.clone(), seed);
+            // The cards in each street are sorted as they're dealt.
+            assert_eq!(cards("2cKcAh7d9h"), game_state.board);
+            // Trip kings beat top two pair.
+            assert_eq!([20, 0].map(Chips::new).to_vec(), game_state.player_winnings);
+
+            let dealt: Vec<(usize, Card)> = records
+                .iter()
+                .filter_map(|r| match &r.action {
+                    Action::DealStartingHand(p) => Some((p.idx, p.card)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(4, dealt.len());
+            assert!(dealt.contains(&(1, Card::try_from("As").unwrap())));
+            assert!(dealt.contains(&(0, Card::try_from("Kd").unwrap())));
+        }
+    }
+
+    #[test]
+    fn test_rest_is_random() {
+        let stacked = StackedDeck::new()
+            .hole_cards(1, cards("AsKs"))
+            .board(cards("QsJs"));
+        let (game_state, _) = run_stacked(stacked, 281);
+        assert!(game_state.board[..3].contains(&Card::try_from("Qs").unwrap()));
+        assert!(game_state.board[..3].contains(&Card::try_from("Js").unwrap()));
+        assert_eq!(5, game_state.board.len());
+
+        // Nothing stacked is dealt a second time.
+        let mut all: Vec<Card> = game_state.board.clone();
+        all.extend(
+            game_state.hands[0]
+                .iter()
+                .filter(|c| !game_state.board.contains(c)),
+        );
+        all.extend(
+            game_state.hands[1]
+                .iter()
+                .filter(|c| !game_state.board.contains(c)),
+        );
+        assert_eq!(9, all.len());
+        all.sort();
+        all.dedup();
+        assert_eq!(9, all.len());
+    }
+
+    #[test]
+    fn test_duplicate_card_rejected() {
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("AsKs"))
+            .board(cards("As2c3d"));
+        let result = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .stacked_deck(stacked)
+            .build();
+        assert_eq!(
+            HoldemSimulationError::StackedCardUnavailable(Card::try_from("As").unwrap()),
+            result.err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_already_dealt_card_rejected() {
+        let mut game_state = GameState::new_starting(
+            vec![Chips::new(100); 2],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        game_state.hands[0] = Hand::new_from_str("AsKs").unwrap();
+        let result = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .stacked_deck(StackedDeck::new().board(cards("Ks2c3d")))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_flop_comes_after_burn() {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::<CallingAgent>::default(),
+            Box::<CallingAgent>::default(),
+        ];
+        let stacked = StackedDeck::new()
+            .burn_cards(cards("2h3h4h"))
+            .board(cards("AhKc2c7d9h"));
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .stacked_deck(stacked)
+            .burn_cards(true)
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(303));
+
+        assert_eq!(cards("2h3h4h"), sim.burned_cards);
+        // Each street is dealt right after its burn card.
+        let dealt: Vec<Action> = records
+            .borrow()
+            .iter()
+            .map(|r| r.action.clone())
+            .filter(|a| matches!(a, Action::BurnCard(_) | Action::DealCommunity(_)))
+            .collect();
+        let expected: Vec<Action> = cards("2h2cKcAh3h7d4h9h")
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| match i {
+                0 | 4 | 6 => Action::BurnCard(c),
+                _ => Action::DealCommunity(c),
+            })
+            .collect();
+        assert_eq!(expected, dealt);
+        for hand in &sim.game_state.hands {
+            assert!(sim.burned_cards.iter().all(|c| !hand.contains(c)));
+        }
+    }
+
+    #[test]
+    fn test_random_burns_leave_the_deck() {
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::<CallingAgent>::default(),
+            Box::<CallingAgent>::default(),
+        ];
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .burn_cards(true)
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(303));
+        assert_eq!(3, sim.burned_cards.len());
+        // Two hands and a board of five along with three burns.
+        assert_eq!(52 - 4 - 5 - 3, sim.deck.len());
+        for card in &sim.burned_cards {
+            assert!(!sim.game_state.board.contains(card));
+            assert!(!sim.deck.contains(card));
+        }
+    }
+}
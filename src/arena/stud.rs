@@ -0,0 +1,850 @@
+//! Seven card stud played by agents.
+//!
+//! Each player gets two cards face down and one face up, then a card on
+//! each of fourth, fifth and sixth street face up and a last card on
+//! seventh street face down. There's no board. Third street starts with a
+//! forced bring-in from the lowest card showing, and on every later street
+//! the best hand showing acts first.
+//!
+//! Betting is fixed limit. A `GameState` tracks the chips so agents and
+//! historians work the same as in Hold'em. Its `round` follows Hold'em's
+//! rounds: third street is `Preflop`, fourth is `Flop`, fifth is `Turn`,
+//! and sixth and seventh street are both `River`. Its `street` counts the
+//! streets from 0 for third street, so it's 3 on sixth street and 4 on
+//! seventh.
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use tracing::{Level, event, trace_span};
+
+use crate::core::{Card, Deck, Hand, Rank, Rankable, Suit};
+
+use super::{
+    Agent, BettingStructure, Chips, GameState, Historian, LegalActions,
+    action::{
+        Action, AgentAction, AwardPayload, CardVisibility, DealStartingHandPayload,
+        FailedActionPayload, ForcedBetPayload, ForcedBetType, GameStartPayload,
+        PlayedActionPayload, PlayerSitPayload,
+    },
+    betting::FIXED_LIMIT_RAISE_CAP,
+    errors::HoldemSimulationError,
+    game_state::{Round, RoundData},
+    historian::record_with_all,
+};
+
+/// Seven card stud deals seven cards to each player, so a full deck can't
+/// deal more than seven players.
+pub const MAX_STUD_PLAYERS: usize = 7;
+
+/// The betting rounds of seven card stud. Each is named for how many
+/// cards each player holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StudStreet {
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+}
+
+impl StudStreet {
+    /// The number of the street's betting round, from 0 for third street.
+    /// This is the game state's `street` while it's bet.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// The Hold'em round the game state is in during this street.
+    pub fn round(&self) -> Round {
+        match self {
+            StudStreet::Third => Round::Preflop,
+            StudStreet::Fourth => Round::Flop,
+            StudStreet::Fifth => Round::Turn,
+            StudStreet::Sixth | StudStreet::Seventh => Round::River,
+        }
+    }
+
+    fn next(&self) -> Option<StudStreet> {
+        match self {
+            StudStreet::Third => Some(StudStreet::Fourth),
+            StudStreet::Fourth => Some(StudStreet::Fifth),
+            StudStreet::Fifth => Some(StudStreet::Sixth),
+            StudStreet::Sixth => Some(StudStreet::Seventh),
+            StudStreet::Seventh => None,
+        }
+    }
+}
+
+/// The forced bets and bet sizes for a game of seven card stud.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StudLimits {
+    /// Posted by every player before the deal.
+    pub ante: Chips,
+    /// Posted on third street by the player with the lowest card showing.
+    pub bring_in: Chips,
+    /// The bet size on third and fourth street.
+    pub small_bet: Chips,
+    /// The bet size from fifth street on.
+    pub big_bet: Chips,
+}
+
+/// The position of the card's suit when breaking ties for the bring-in,
+/// clubs are lowest and spades highest.
+fn suit_order(suit: Suit) -> u8 {
+    match suit {
+        Suit::Club => 0,
+        Suit::Diamond => 1,
+        Suit::Heart => 2,
+        Suit::Spade => 3,
+    }
+}
+
+/// The player that has to bring in, the one with the lowest up card. Aces
+/// are high, and ties go to the lower suit. `up_cards` holds each player's
+/// first up card, or `None` for players not in the hand.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::arena::stud::bring_in_idx;
+/// use rs_poker::core::Card;
+///
+/// let up_cards = ["Ks", "2s", "2c"].map(|c| Some(Card::try_from(c).unwrap()));
+/// assert_eq!(Some(2), bring_in_idx(&up_cards));
+/// ```
+pub fn bring_in_idx(up_cards: &[Option<Card>]) -> Option<usize> {
+    up_cards
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, card)| card.map(|c| (idx, c)))
+        .min_by_key(|(_, card)| (card.value, suit_order(card.suit)))
+        .map(|(idx, _)| idx)
+}
+
+/// The player whose up cards make the best hand, who acts first from
+/// fourth street on. Ties go to the first player after `dealer_idx`.
+/// Players with no up cards are skipped.
+pub fn best_board_idx(up_cards: &[Vec<Card>], dealer_idx: usize) -> Option<usize> {
+    let num_players = up_cards.len();
+    let mut best: Option<(Rank, usize)> = None;
+    for offset in 1..=num_players {
+        let idx = (dealer_idx + offset) % num_players;
+        if up_cards[idx].is_empty() {
+            continue;
+        }
+        let rank = Hand::new_with_cards(up_cards[idx].clone()).rank();
+        if best.is_none_or(|(best_rank, _)| rank > best_rank) {
+            best = Some((rank, idx));
+        }
+    }
+    best.map(|(_, idx)| idx)
+}
+
+/// Builds a `StudSimulation`. Stacks and limits are required, agents
+/// default to folding agents.
+#[derive(Default)]
+pub struct StudSimulationBuilder {
+    stacks: Option<Vec<Chips>>,
+    limits: Option<StudLimits>,
+    agents: Option<Vec<Box<dyn Agent>>>,
+    historians: Vec<Box<dyn Historian>>,
+    panic_on_historian_error: bool,
+    check_chip_range: bool,
+}
+
+impl StudSimulationBuilder {
+    /// Set the starting stack of each player.
+    pub fn stacks(mut self, stacks: Vec<Chips>) -> Self {
+        self.stacks = Some(stacks);
+        self
+    }
+
+    /// Set the forced bets and bet sizes.
+    pub fn limits(mut self, limits: StudLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Set the agents, one for each stack.
+    pub fn agents(mut self, agents: Vec<Box<dyn Agent>>) -> Self {
+        self.agents = Some(agents);
+        self
+    }
+
+    /// Set the historians for the simulation.
+    pub fn historians(mut self, historians: Vec<Box<dyn Historian>>) -> Self {
+        self.historians = historians;
+        self
+    }
+
+    /// Should the simulation panic if a historian errors.
+    pub fn panic_on_historian_error(mut self, panic_on_historian_error: bool) -> Self {
+        self.panic_on_historian_error = panic_on_historian_error;
+        self
+    }
+
+    /// Refuse negative stacks, or stacks and limits holding more chips than
+    /// [`MAX_SAFE_CHIPS`](super::game_state::MAX_SAFE_CHIPS), the same as
+    /// [`HoldemSimulationBuilder::check_chip_range`](super::HoldemSimulationBuilder::check_chip_range).
+    /// Off by default.
+    pub fn check_chip_range(mut self, check_chip_range: bool) -> Self {
+        self.check_chip_range = check_chip_range;
+        self
+    }
+
+    /// Create the simulation.
+    ///
+    /// @returns HoldemSimulationError if the stacks or limits are missing,
+    /// if `check_chip_range` is set and a stack is negative or there are
+    /// too many chips, or if there are fewer than two or more than
+    /// [`MAX_STUD_PLAYERS`] players.
+    pub fn build(self) -> Result<StudSimulation, HoldemSimulationError> {
+        let stacks = self.stacks.ok_or(HoldemSimulationError::NeedStacks)?;
+        let limits = self.limits.ok_or(HoldemSimulationError::NeedLimits)?;
+        if !(2..=MAX_STUD_PLAYERS).contains(&stacks.len()) {
+            return Err(HoldemSimulationError::StudPlayerCount(stacks.len()));
+        }
+        let num_players = stacks.len();
+
+        // There's no button in stud. The nominal dealer sits in the last
+        // seat so that seat 0 is dealt to first.
+        let mut game_state = GameState::new_starting(
+            stacks,
+            limits.small_bet,
+            limits.bring_in,
+            limits.ante,
+            num_players.saturating_sub(1),
+        );
+        game_state.betting_structure = BettingStructure::FixedLimit {
+            small_bet: limits.small_bet,
+            big_bet: limits.big_bet,
+        };
+        if self.check_chip_range {
+            game_state.validate_chip_range()?;
+        }
+
+        let agents = self.agents.unwrap_or_else(|| {
+            (0..num_players)
+                .map(|_| -> Box<dyn Agent> { Box::<super::agent::FoldingAgent>::default() })
+                .collect()
+        });
+
+        Ok(StudSimulation {
+            id: rand::rng().random::<u128>(),
+            agents,
+            game_state,
+            deck: Deck::default(),
+            up_cards: vec![vec![]; num_players],
+            street: None,
+            historians: self.historians,
+            panic_on_historian_error: self.panic_on_historian_error,
+        })
+    }
+}
+
+/// A single hand of fixed limit seven card stud.
+pub struct StudSimulation {
+    /// A randomly generated ID to represent the simulation.
+    pub id: u128,
+    pub agents: Vec<Box<dyn Agent>>,
+    /// The chips and every player's cards. Each player's `hands` entry holds
+    /// all of their cards, up and down.
+    pub game_state: GameState,
+    pub deck: Deck,
+    /// The cards each player has face up, in the order they were dealt.
+    pub up_cards: Vec<Vec<Card>>,
+    /// The street being played, `None` before third street is dealt.
+    pub street: Option<StudStreet>,
+    pub historians: Vec<Box<dyn Historian>>,
+    pub panic_on_historian_error: bool,
+}
+
+impl StudSimulation {
+    /// Play the hand to completion.
+    pub fn run<R: Rng>(&mut self, rand: &mut R) {
+        let span = trace_span!("stud_run", id = self.id);
+        let _enter = span.enter();
+
+        self.record_action(Action::GameStart(GameStartPayload {
+            ante: self.game_state.ante,
+            small_blind: self.game_state.small_blind,
+            big_blind: self.game_state.big_blind,
+        }));
+        for idx in 0..self.game_state.num_players {
+            self.record_action(Action::PlayerSit(PlayerSitPayload {
+                idx,
+                player_stack: self.game_state.stacks[idx],
+            }));
+        }
+
+        // Ante
+        self.advance_round();
+        self.post_antes();
+
+        let mut street = StudStreet::Third;
+        loop {
+            self.street = Some(street);
+            // Deal, then bet.
+            if street != StudStreet::Seventh {
+                self.advance_round();
+            }
+            self.deal_street(street, rand);
+            if street != StudStreet::Seventh {
+                self.advance_round();
+            }
+            if street == StudStreet::Seventh {
+                // Seventh street is bet in the same round as sixth.
+                self.game_state.street = street.index();
+            }
+            self.run_street(street);
+
+            if self.game_state.round == Round::Complete {
+                return;
+            }
+            match street.next() {
+                Some(next) => street = next,
+                None => break,
+            }
+        }
+
+        self.showdown();
+    }
+
+    fn advance_round(&mut self) {
+        self.game_state.advance_round();
+        self.record_action(Action::RoundAdvance(self.game_state.round));
+    }
+
+    fn post_antes(&mut self) {
+        let ante = self.game_state.ante;
+        if ante <= Chips::ZERO {
+            return;
+        }
+        for idx in self.game_state.player_active.ones() {
+            self.game_state.round_data.to_act_idx = idx;
+            self.game_state.do_bet(ante, true).unwrap();
+            self.record_forced_bet(ForcedBetPayload {
+                bet: ante,
+                player_stack: self.game_state.stacks[idx],
+                idx,
+                forced_bet_type: ForcedBetType::Ante,
+            });
+            self.game_state.round_data.needs_action.disable(idx);
+        }
+    }
+
+    /// Deal the street's cards to every player still in the hand, starting
+    /// with seat 0.
+    fn deal_street<R: Rng>(&mut self, street: StudStreet, rand: &mut R) {
+        let in_hand = self.game_state.player_active | self.game_state.player_all_in;
+        let visibilities: &[CardVisibility] = match street {
+            StudStreet::Third => &[
+                CardVisibility::Hidden,
+                CardVisibility::Hidden,
+                CardVisibility::Visible,
+            ],
+            StudStreet::Seventh => &[CardVisibility::Hidden],
+            _ => &[CardVisibility::Visible],
+        };
+        for &visibility in visibilities {
+            for idx in in_hand.ones() {
+                let card = self.deck.deal(rand).expect("Stud never runs out of cards");
+                self.game_state.hands[idx].insert(card);
+                if visibility == CardVisibility::Visible {
+                    self.up_cards[idx].push(card);
+                }
+                self.record_action(Action::DealStartingHand(DealStartingHandPayload {
+                    card,
+                    idx,
+                    visibility,
+                }));
+            }
+        }
+    }
+
+    /// Run the betting for a street. Third street starts with the bring-in
+    /// and the player after them acts first, on later streets it's the best
+    /// hand showing.
+    fn run_street(&mut self, street: StudStreet) {
+        let num_players = self.game_state.num_players;
+        let active = self.game_state.player_active;
+        if street == StudStreet::Third {
+            let first_up: Vec<Option<Card>> = (0..num_players)
+                .map(|idx| {
+                    active
+                        .get(idx)
+                        .then(|| self.up_cards[idx].first().copied())
+                        .flatten()
+                })
+                .collect();
+            let Some(idx) = bring_in_idx(&first_up) else {
+                return;
+            };
+            self.game_state.round_data = RoundData::new(num_players, Chips::ZERO, active, idx);
+            let bring_in = self.game_state.small_blind;
+            self.game_state.do_bet(bring_in, true).unwrap();
+            self.record_forced_bet(ForcedBetPayload {
+                bet: bring_in,
+                player_stack: self.game_state.stacks[idx],
+                idx,
+                forced_bet_type: ForcedBetType::BringIn,
+            });
+            // Completing to the small bet is a full raise no matter how
+            // big the bring-in was.
+            self.game_state.round_data.min_raise = Chips::ZERO;
+        } else {
+            let showing: Vec<Vec<Card>> = (0..num_players)
+                .map(|idx| {
+                    if active.get(idx) {
+                        self.up_cards[idx].clone()
+                    } else {
+                        vec![]
+                    }
+                })
+                .collect();
+            let Some(idx) = best_board_idx(&showing, self.game_state.dealer_idx) else {
+                return;
+            };
+            let bet_size = self
+                .game_state
+                .betting_structure
+                .bet_increment(street.round());
+            self.game_state.round_data = RoundData::new(num_players, bet_size, active, idx);
+        }
+
+        // With at most one player that can bet, there's no one to bet
+        // against unless they face a bet.
+        while self.needs_action() {
+            self.run_single_agent();
+            if self.game_state.round == Round::Complete {
+                return;
+            }
+        }
+    }
+
+    fn needs_action(&self) -> bool {
+        let active = self.game_state.player_active;
+        let needs_action = active & self.game_state.round_data.needs_action;
+        if needs_action.empty() {
+            return false;
+        }
+        let facing_bet = self.game_state.current_round_bet()
+            > self.game_state.current_round_current_player_bet();
+        active.count() > 1 || facing_bet
+    }
+
+    /// The legal actions in fixed limit stud. Before anyone completes, the
+    /// only raise is to the small bet. After that raises are one bet more,
+    /// until the raise cap.
+    pub fn legal_actions(&self) -> LegalActions {
+        let game_state = &self.game_state;
+        let bet = game_state.current_round_bet();
+        let player_bet = game_state.current_round_current_player_bet();
+        let stack = game_state.current_player_stack();
+        let all_in = player_bet + stack;
+        let call_amount = (bet - player_bet).clamp(Chips::ZERO, stack);
+
+        let small_bet = game_state.big_blind;
+        let raise_to = if game_state.round == Round::Preflop && bet < small_bet {
+            small_bet
+        } else {
+            bet + game_state.betting_structure.bet_increment(game_state.round)
+        };
+        let can_raise = game_state.current_player_can_raise()
+            && game_state.round_data.total_raise_count < FIXED_LIMIT_RAISE_CAP
+            && all_in > bet;
+        let raise = if can_raise { all_in.min(raise_to) } else { bet };
+
+        LegalActions {
+            can_check: call_amount <= Chips::ZERO,
+            call_amount,
+            can_raise,
+            min_raise: raise,
+            max_raise: raise,
+        }
+    }
+
+    fn run_single_agent(&mut self) {
+        let idx = self.game_state.to_act_idx();
+        let legal_actions = self.legal_actions();
+        let action =
+            self.agents[idx].act_with_legal_actions(self.id, &self.game_state, &legal_actions);
+        event!(Level::TRACE, ?action, idx, "stud_action");
+
+        let starting_pot = self.game_state.total_pot;
+        let starting_bet = self.game_state.current_round_bet();
+        let starting_min_raise = self.game_state.current_round_min_raise();
+        let starting_player_bet = self.game_state.current_round_current_player_bet();
+
+        // Every raise is snapped to the one legal raise size, and raises
+        // that aren't allowed are calls. Folding when there's nothing to
+        // call is a check.
+        let played = match action {
+            AgentAction::Fold if !legal_actions.can_check => AgentAction::Fold,
+            AgentAction::Bet(amount) if amount > starting_bet && legal_actions.can_raise => {
+                AgentAction::Bet(legal_actions.max_raise)
+            }
+            AgentAction::AllIn if legal_actions.can_raise => {
+                AgentAction::Bet(legal_actions.max_raise)
+            }
+            _ => AgentAction::Bet(starting_bet),
+        };
+        // Anything but that is recorded as a failed action. Calling and
+        // going all in for no more than a call are always fine.
+        let all_in = starting_player_bet + self.game_state.current_player_stack();
+        let converted = match action {
+            AgentAction::Call => false,
+            AgentAction::AllIn => all_in > starting_bet && played != AgentAction::Bet(all_in),
+            _ => played != action,
+        };
+        let folded = played == AgentAction::Fold;
+        if let AgentAction::Bet(amount) = played {
+            self.game_state
+                .do_bet(amount, false)
+                .expect("Legal stud bets are always accepted");
+        } else {
+            self.game_state.fold();
+        }
+
+        let result = PlayedActionPayload {
+            action: played.clone(),
+            idx,
+            round: self.game_state.round,
+            player_stack: self.game_state.stacks[idx],
+            starting_pot,
+            final_pot: self.game_state.total_pot,
+            starting_bet,
+            final_bet: self.game_state.current_round_bet(),
+            starting_min_raise,
+            final_min_raise: self.game_state.current_round_min_raise(),
+            starting_player_bet,
+            final_player_bet: self.game_state.current_round_player_bet(idx),
+            players_active: self.game_state.player_active,
+            players_all_in: self.game_state.player_all_in,
+        };
+        if converted {
+            event!(Level::WARN, ?action, ?played, "stud_illegal_action");
+            self.record_action(Action::FailedAction(FailedActionPayload { action, result }));
+        } else {
+            self.record_action(Action::PlayedAction(result));
+        }
+
+        let left = self.game_state.player_active | self.game_state.player_all_in;
+        if folded && left.count() == 1 {
+            let winning_idx = left.ones().next().unwrap();
+            let total_pot = self.game_state.total_pot;
+            self.game_state.award(winning_idx, total_pot);
+            self.record_action(Action::Award(AwardPayload {
+                total_pot,
+                award_amount: total_pot,
+                rank: None,
+                hand: None,
+                idx: winning_idx,
+                rake: Chips::ZERO,
+            }));
+            self.end_game();
+        }
+    }
+
+    fn showdown(&mut self) {
+        self.game_state.round = Round::River;
+        self.advance_round();
+
+        let in_hand = self.game_state.player_active | self.game_state.player_all_in;
+        let ranks: BTreeMap<usize, Rank> = in_hand
+            .ones()
+            .map(|idx| (idx, self.game_state.hands[idx].rank()))
+            .collect();
+
+        for pot in self.game_state.side_pots() {
+            let Some(best) = pot.eligible.ones().map(|idx| ranks[&idx]).max() else {
+                continue;
+            };
+            let mut winners: Vec<usize> = pot
+                .eligible
+                .ones()
+                .filter(|idx| ranks[idx] == best)
+                .collect();
+            // Ties split the pot in whole chips, with the odd chips going
+            // to the first seats.
+            winners.sort_by_key(|idx| self.game_state.odd_chip_order(*idx));
+            let splits = pot.amount.split(winners.len());
+            for (idx, award_amount) in winners.into_iter().zip(splits) {
+                self.game_state.award(idx, award_amount);
+                self.record_action(Action::Award(AwardPayload {
+                    total_pot: pot.amount,
+                    award_amount,
+                    rank: Some(best),
+                    hand: Some(self.game_state.hands[idx]),
+                    idx,
+                    rake: Chips::ZERO,
+                }));
+            }
+        }
+        self.end_game();
+    }
+
+    fn end_game(&mut self) {
+        self.game_state.complete();
+        self.record_action(Action::RoundAdvance(Round::Complete));
+    }
+
+    fn record_forced_bet(&mut self, payload: ForcedBetPayload) {
+        self.game_state.forced_bets.push(payload.clone());
+        self.record_action(Action::ForcedBet(payload));
+    }
+
+    fn record_action(&mut self, action: Action) {
+        record_with_all(
+            &mut self.historians,
+            self.id,
+            &self.game_state,
+            action,
+            self.panic_on_historian_error,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::arena::{
+        agent::{AllInAgent, CallingAgent},
+        errors::GameStateError,
+        historian::{HistoryRecord, VecHistorian},
+    };
+
+    use super::*;
+
+    fn card(s: &str) -> Card {
+        Card::try_from(s).unwrap()
+    }
+
+    const LIMITS: StudLimits = StudLimits {
+        ante: Chips::new(1),
+        bring_in: Chips::new(1),
+        small_bet: Chips::new(4),
+        big_bet: Chips::new(8),
+    };
+
+    fn run_stud(
+        agents: Vec<Box<dyn Agent>>,
+        stacks: Vec<Chips>,
+        seed: u64,
+    ) -> (StudSimulation, Vec<HistoryRecord>) {
+        let historian = VecHistorian::new();
+        let records = historian.get_storage();
+        let mut sim = StudSimulationBuilder::default()
+            .stacks(stacks)
+            .limits(LIMITS)
+            .agents(agents)
+            .historians(vec![Box::new(historian)])
+            .panic_on_historian_error(true)
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(seed));
+        (sim, records.take())
+    }
+
+    #[test]
+    fn test_bring_in_lowest_card() {
+        let up = [Some(card("9h")), Some(card("3d")), None, Some(card("As"))];
+        assert_eq!(Some(1), bring_in_idx(&up));
+        // Aces are high, and clubs are the lowest suit.
+        let up = [Some(card("Ac")), Some(card("3d")), Some(card("3c"))];
+        assert_eq!(Some(2), bring_in_idx(&up));
+        assert_eq!(None, bring_in_idx(&[None, None]));
+    }
+
+    #[test]
+    fn test_best_board_acts_first() {
+        let up = vec![
+            vec![card("As"), card("Kd")],
+            vec![card("3d"), card("3c")],
+            vec![],
+            vec![card("Ah"), card("Kc")],
+        ];
+        assert_eq!(Some(1), best_board_idx(&up, 3));
+        // Equal boards go to the first seat after the dealer.
+        let up = vec![vec![card("As")], vec![card("Ad")], vec![card("Ac")]];
+        assert_eq!(Some(1), best_board_idx(&up, 0));
+        assert_eq!(Some(0), best_board_idx(&up, 2));
+    }
+
+    #[test]
+    fn test_calling_hand_to_showdown() {
+        for seed in 0..10 {
+            let agents: Vec<Box<dyn Agent>> = (0..4)
+                .map(|_| Box::<CallingAgent>::default() as Box<dyn Agent>)
+                .collect();
+            let (sim, records) = run_stud(agents, vec![Chips::new(100); 4], seed);
+            let game_state = &sim.game_state;
+            assert_eq!(Round::Complete, game_state.round);
+            assert_eq!(Round::Showdown, game_state.round_before);
+            assert!(game_state.board.is_empty());
+            for idx in 0..4 {
+                assert_eq!(7, game_state.hands[idx].count());
+                assert_eq!(4, sim.up_cards[idx].len());
+            }
+            let visible = records
+                .iter()
+                .filter(|r| matches!(&r.action, Action::DealStartingHand(p) if p.is_visible()))
+                .count();
+            assert_eq!(16, visible);
+
+            // Antes and the bring-in, then everyone calls the bring-in.
+            assert_eq!(Chips::new(4 + 4), game_state.total_pot);
+            let total: Chips = game_state.stacks.iter().sum();
+            assert_eq!(Chips::new(400), total);
+
+            let bring_in = game_state
+                .forced_bets
+                .iter()
+                .find(|f| f.forced_bet_type == ForcedBetType::BringIn)
+                .unwrap();
+            let first_up: Vec<Option<Card>> =
+                sim.up_cards.iter().map(|c| c.first().copied()).collect();
+            assert_eq!(bring_in_idx(&first_up), Some(bring_in.idx));
+        }
+    }
+
+    #[test]
+    fn test_fixed_limit_raises() {
+        let agents: Vec<Box<dyn Agent>> = (0..3)
+            .map(|_| Box::new(AllInAgent) as Box<dyn Agent>)
+            .collect();
+        let (_, records) = run_stud(agents, vec![Chips::new(1000); 3], 282);
+        // Going all in is snapped to the one legal raise, so every raise is
+        // a failed action.
+        let played: Vec<&PlayedActionPayload> = records
+            .iter()
+            .filter_map(|r| match &r.action {
+                Action::PlayedAction(p) => Some(p),
+                Action::FailedAction(f) => Some(&f.result),
+                _ => None,
+            })
+            .collect();
+        assert!(
+            records
+                .iter()
+                .any(|r| matches!(r.action, Action::FailedAction(_)))
+        );
+        let third_street: Vec<Chips> = played
+            .iter()
+            .filter(|p| p.round == Round::Preflop)
+            .map(|p| p.final_bet)
+            .collect();
+        // Complete to the small bet, then three raises hit the cap.
+        assert_eq!(
+            [4, 8, 12, 16, 16, 16].map(Chips::new).to_vec(),
+            third_street
+        );
+
+        // Fifth street raises are the big bet.
+        let fifth_street: Vec<Chips> = played
+            .iter()
+            .filter(|p| p.round == Round::Turn)
+            .map(|p| p.final_bet)
+            .collect();
+        assert_eq!(
+            [8, 16, 24, 32, 32, 32].map(Chips::new).to_vec(),
+            fifth_street
+        );
+    }
+
+    #[test]
+    fn test_sixth_and_seventh_street_differ() {
+        let agents: Vec<Box<dyn Agent>> = (0..3)
+            .map(|_| Box::<CallingAgent>::default() as Box<dyn Agent>)
+            .collect();
+        let (_, records) = run_stud(agents, vec![Chips::new(100); 3], 282);
+        let mut streets: Vec<(Round, usize)> = records
+            .iter()
+            .filter(|r| matches!(r.action, Action::PlayedAction(_)))
+            .map(|r| (r.after_game_state.round, r.after_game_state.street))
+            .collect();
+        streets.dedup();
+        assert_eq!(
+            vec![
+                (Round::Preflop, StudStreet::Third.index()),
+                (Round::Flop, StudStreet::Fourth.index()),
+                (Round::Turn, StudStreet::Fifth.index()),
+                (Round::River, StudStreet::Sixth.index()),
+                (Round::River, StudStreet::Seventh.index()),
+            ],
+            streets
+        );
+    }
+
+    #[test]
+    fn test_split_pot_odd_chip() {
+        let mut sim = StudSimulationBuilder::default()
+            .stacks(vec![Chips::new(10); 3])
+            .limits(LIMITS)
+            .build()
+            .unwrap();
+        // Two players put in 3 each with the same hand, the third folded
+        // after their ante.
+        let game_state = &mut sim.game_state;
+        for (idx, bet) in [3, 3, 1].map(Chips::new).into_iter().enumerate() {
+            game_state.stacks[idx] -= bet;
+            game_state.player_bet[idx] = bet;
+        }
+        game_state.total_pot = Chips::new(7);
+        game_state.player_active.disable(2);
+        game_state.hands[0] = Hand::new_from_str("AsKsQsJsTs2c3c").unwrap();
+        game_state.hands[1] = Hand::new_from_str("AhKhQhJhTh2d3d").unwrap();
+        sim.showdown();
+
+        // The odd chip goes to the first seat.
+        assert_eq!(
+            [4, 3, 0].map(Chips::new).to_vec(),
+            sim.game_state.player_winnings
+        );
+    }
+
+    #[test]
+    fn test_missing_limits() {
+        let result = StudSimulationBuilder::default()
+            .stacks(vec![Chips::new(100); 3])
+            .build();
+        assert_eq!(HoldemSimulationError::NeedLimits, result.err().unwrap());
+        let result = StudSimulationBuilder::default().limits(LIMITS).build();
+        assert_eq!(HoldemSimulationError::NeedStacks, result.err().unwrap());
+    }
+
+    #[test]
+    fn test_chip_range_checked_when_asked() {
+        let build = |check| {
+            StudSimulationBuilder::default()
+                .stacks(vec![Chips::new(10000000); 2])
+                .limits(LIMITS)
+                .check_chip_range(check)
+                .build()
+        };
+        assert!(build(false).is_ok());
+        assert_eq!(
+            HoldemSimulationError::GameState(GameStateError::ChipsOutOfRange),
+            build(true).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_player_count() {
+        for players in [1, MAX_STUD_PLAYERS + 1] {
+            let result = StudSimulationBuilder::default()
+                .stacks(vec![Chips::new(100); players])
+                .limits(LIMITS)
+                .build();
+            assert_eq!(
+                HoldemSimulationError::StudPlayerCount(players),
+                result.err().unwrap()
+            );
+        }
+        assert_eq!(
+            "Stud needs from 2 to 7 players, not 8",
+            HoldemSimulationError::StudPlayerCount(8).to_string()
+        );
+    }
+}
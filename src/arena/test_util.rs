@@ -1,11 +1,9 @@
-use std::assert_matches::assert_matches;
-
-use approx::assert_relative_eq;
+use std::assert_matches;
 
 use crate::arena::game_state::Round;
 
 use super::action::AgentAction;
-use super::{GameState, game_state::RoundData};
+use super::{Chips, GameState, game_state::RoundData};
 
 use crate::arena::action::Action;
 use crate::arena::historian::HistoryRecord;
@@ -15,7 +13,7 @@ pub fn assert_valid_round_data(round_data: &RoundData) {
     // for any round with bets they should have called.
     //
     // EG no one should call for less than the max and still be in.
-    let active_bets: Vec<f32> = round_data
+    let active_bets: Vec<Chips> = round_data
         .player_bet
         .iter()
         .enumerate()
@@ -23,7 +21,7 @@ pub fn assert_valid_round_data(round_data: &RoundData) {
         .map(|(_, bet)| *bet)
         .collect();
 
-    let max_active = active_bets.clone().into_iter().reduce(f32::max);
+    let max_active = active_bets.iter().copied().max();
 
     if let Some(max) = max_active {
         for bet in active_bets.into_iter() {
@@ -38,12 +36,13 @@ pub fn assert_valid_round_data(round_data: &RoundData) {
 pub fn assert_valid_game_state(game_state: &GameState) {
     assert_eq!(Round::Complete, game_state.round);
 
-    let should_have_bets = game_state.ante + game_state.small_blind + game_state.big_blind > 0.0;
+    let should_have_bets =
+        game_state.ante + game_state.small_blind + game_state.big_blind > Chips::ZERO;
 
-    let total_bet = game_state.player_bet.iter().copied().sum();
+    let total_bet: Chips = game_state.player_bet.iter().sum();
 
     if should_have_bets {
-        let any_above_zero = game_state.player_bet.iter().any(|bet| *bet > 0.0);
+        let any_above_zero = game_state.player_bet.iter().any(|bet| *bet > Chips::ZERO);
 
         assert!(
             any_above_zero,
@@ -51,16 +50,15 @@ pub fn assert_valid_game_state(game_state: &GameState) {
             game_state.player_bet
         );
 
-        assert_ne!(0.0, total_bet);
+        assert_ne!(Chips::ZERO, total_bet);
     }
 
-    let epsilon = total_bet / 100_000.0;
-    assert_relative_eq!(total_bet, game_state.total_pot, epsilon = epsilon);
+    assert_eq!(total_bet, game_state.total_pot);
 
-    let total_winning: f32 = game_state.player_winnings.iter().copied().sum();
+    let total_winning: Chips = game_state.player_winnings.iter().sum();
 
-    assert_relative_eq!(total_winning, total_bet, epsilon = epsilon);
-    assert_relative_eq!(total_winning, game_state.total_pot, epsilon = epsilon);
+    assert_eq!(total_winning, total_bet);
+    assert_eq!(total_winning, game_state.total_pot);
 
     // The dealer has to be well specified.
     assert!(game_state.dealer_idx < game_state.num_players);
@@ -74,7 +72,7 @@ pub fn assert_valid_game_state(game_state: &GameState) {
         // If they aren't active (folded)
         // and aren't all in then they shouldn't win anything
         if !game_state.player_active.get(idx) && !game_state.player_all_in.get(idx) {
-            assert_eq!(0.0, game_state.player_winnings[idx]);
+            assert_eq!(Chips::ZERO, game_state.player_winnings[idx]);
         }
     }
 }
@@ -110,6 +110,29 @@ impl Value {
         let max = cmp::max(self as u8, other as u8);
         max - min
     }
+
+    /// The gap between two values where the ace can also play low, so this
+    /// is the smaller of `gap` with the ace high and with it below the two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::Value;
+    ///
+    /// assert_eq!(12, Value::Ace.gap(Value::Two));
+    /// assert_eq!(1, Value::Ace.wheel_gap(Value::Two));
+    /// assert_eq!(2, Value::Ten.wheel_gap(Value::Eight));
+    /// ```
+    pub fn wheel_gap(self, other: Self) -> u8 {
+        let gap = self.gap(other);
+        if self == Self::Ace || other == Self::Ace {
+            // With the ace low it sits one below the two.
+            let low = cmp::min(self as u8, other as u8);
+            cmp::min(gap, low + 1)
+        } else {
+            gap
+        }
+    }
 }
 
 impl From<u8> for Value {
@@ -143,7 +166,7 @@ impl TryFrom<char> for Value {
             '4' => Ok(Self::Four),
             '3' => Ok(Self::Three),
             '2' => Ok(Self::Two),
-            _ => Err(RSPokerError::UnexpectedValueChar),
+            _ => Err(RSPokerError::InvalidValueChar(value)),
         }
     }
 }
@@ -291,7 +314,7 @@ impl TryFrom<char> for Suit {
             's' => Ok(Self::Spade),
             'h' => Ok(Self::Heart),
             'c' => Ok(Self::Club),
-            _ => Err(RSPokerError::UnexpectedSuitChar),
+            _ => Err(RSPokerError::InvalidSuitChar(value)),
         }
     }
 }
@@ -373,8 +396,9 @@ impl TryFrom<&str> for Card {
         let value_char = chars.next().ok_or(RSPokerError::TooFewChars)?;
         let suit_char = chars.next().ok_or(RSPokerError::TooFewChars)?;
         Ok(Self {
-            value: Value::try_from(value_char)?,
-            suit: Suit::try_from(suit_char)?,
+            value: Value::from_char(value_char)
+                .ok_or(RSPokerError::UnknownValueChar(value_char, 0))?,
+            suit: Suit::from_char(suit_char).ok_or(RSPokerError::UnknownSuitChar(suit_char, 1))?,
         })
     }
 }
@@ -450,6 +474,26 @@ mod tests {
         assert_eq!(expected, Card::try_from("Ks").unwrap())
     }
 
+    #[test]
+    fn test_bad_chars() {
+        assert!(matches!(
+            Value::try_from('x'),
+            Err(RSPokerError::InvalidValueChar('x'))
+        ));
+        assert!(matches!(
+            Suit::try_from('x'),
+            Err(RSPokerError::InvalidSuitChar('x'))
+        ));
+        assert!(matches!(
+            Card::try_from("1s"),
+            Err(RSPokerError::UnknownValueChar('1', 0))
+        ));
+        assert!(matches!(
+            Card::try_from("Kx"),
+            Err(RSPokerError::UnknownSuitChar('x', 1))
+        ));
+    }
+
     #[test]
     fn test_parse_all_cards() {
         for suit in SUITS {
@@ -531,6 +575,19 @@ mod tests {
         assert!(12 == Value::Two.gap(Value::Ace));
     }
 
+    #[test]
+    fn test_wheel_gap() {
+        assert_eq!(1, Value::Nine.wheel_gap(Value::Eight));
+        assert_eq!(1, Value::Ace.wheel_gap(Value::Two));
+        assert_eq!(1, Value::Two.wheel_gap(Value::Ace));
+        assert_eq!(2, Value::Ten.wheel_gap(Value::Eight));
+        assert_eq!(4, Value::Ace.wheel_gap(Value::Five));
+        assert_eq!(1, Value::Ace.wheel_gap(Value::King));
+        assert_eq!(0, Value::Ace.wheel_gap(Value::Ace));
+        // Even ace to seven is closer with the ace low than high.
+        assert_eq!(6, Value::Ace.wheel_gap(Value::Seven));
+    }
+
     #[test]
     fn test_suit_to_char() {
         let s = Suit::Spade;
@@ -570,4 +627,26 @@ mod tests {
             assert_eq!(char::from_digit(u32::from(i), 10).unwrap(), v.to_char());
         }
     }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_card_as_string() {
+        let card = Card::new(Value::Ace, Suit::Spade);
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!("\"As\"", json);
+        assert_eq!(card, serde_json::from_str::<Card>(&json).unwrap());
+        assert!(serde_json::from_str::<Card>("\"Zz\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_value_and_suit() {
+        for value in Value::values() {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(value, serde_json::from_str::<Value>(&json).unwrap());
+        }
+        for suit in Suit::suits() {
+            let json = serde_json::to_string(&suit).unwrap();
+            assert_eq!(suit, serde_json::from_str::<Suit>(&json).unwrap());
+        }
+    }
 }
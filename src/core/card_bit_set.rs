@@ -193,6 +193,22 @@ impl From<CardBitSet> for FlatDeck {
     }
 }
 
+impl From<CardBitSet> for u64 {
+    /// The raw bits, card `u8::from(card)` is bit `1 << u8::from(card)`.
+    fn from(value: CardBitSet) -> Self {
+        value.cards
+    }
+}
+
+impl From<u64> for CardBitSet {
+    /// The inverse of `u64::from`. Bits above the 52 cards are dropped.
+    fn from(value: u64) -> Self {
+        Self {
+            cards: value & FIFTY_TWO_ONES,
+        }
+    }
+}
+
 impl Debug for CardBitSet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_set().entries(*self).finish()
@@ -1,8 +1,8 @@
 use rand::Rng;
 
-use crate::core::card::Card;
+use crate::core::card::{Card, Suit, Value};
 
-use super::{CardBitSet, CardBitSetIter};
+use super::{CardBitSet, CardBitSetIter, Hand};
 
 /// Deck struct that can tell quickly if a card is in the deck
 ///
@@ -59,6 +59,32 @@ impl Deck {
     pub fn new() -> Self {
         Self(CardBitSet::new())
     }
+    /// Create a full 36 card short deck (6-plus) deck. The twos through
+    /// fives are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Card, Deck, Suit, Value};
+    ///
+    /// let deck = Deck::new_short_deck();
+    ///
+    /// assert_eq!(36, deck.len());
+    /// assert!(!deck.contains(&Card::new(Value::Five, Suit::Heart)));
+    /// assert!(deck.contains(&Card::new(Value::Six, Suit::Heart)));
+    /// ```
+    pub fn new_short_deck() -> Self {
+        let mut deck = Self::new();
+        for value in Value::values() {
+            if value >= Value::Six {
+                for suit in Suit::suits() {
+                    deck.insert(Card::new(value, suit));
+                }
+            }
+        }
+        deck
+    }
+
     /// Given a card, is it in the current deck?
     pub fn contains(&self, c: &Card) -> bool {
         self.0.contains(*c)
@@ -102,6 +128,29 @@ impl Deck {
             None
         }
     }
+
+    /// Deal `cards_each` random cards to each of `n_players` hands, one
+    /// card to every player in turn and then around again, like
+    /// [`FlatDeck::deal_hands`](super::FlatDeck::deal_hands).
+    ///
+    /// None if there aren't enough cards, and then none are dealt.
+    pub fn deal_hands<R: Rng>(
+        &mut self,
+        n_players: usize,
+        cards_each: usize,
+        rng: &mut R,
+    ) -> Option<Vec<Hand>> {
+        if self.len() < n_players * cards_each {
+            return None;
+        }
+        let mut hands = vec![Hand::default(); n_players];
+        for _ in 0..cards_each {
+            for hand in hands.iter_mut() {
+                hand.insert(self.deal(rng)?);
+            }
+        }
+        Some(hands)
+    }
 }
 
 /// Turn a deck into an iterator
@@ -159,8 +208,6 @@ impl From<CardBitSet> for Deck {
 mod tests {
     use rand::{SeedableRng, rngs::StdRng};
 
-    use crate::core::{Suit, Value};
-
     use super::*;
 
     #[test]
@@ -236,6 +283,21 @@ mod tests {
         assert!(d_two.is_empty());
     }
 
+    #[test]
+    fn test_deal_hands() {
+        let mut d = Deck::default();
+        let mut rng = StdRng::seed_from_u64(317);
+        let hands = d.deal_hands(3, 2, &mut rng).unwrap();
+
+        assert_eq!(46, d.len());
+        for hand in &hands {
+            assert_eq!(2, hand.count());
+            assert!(hand.iter().all(|c| !d.contains(&c)));
+        }
+        assert_eq!(None, d.deal_hands(10, 5, &mut rng));
+        assert_eq!(46, d.len());
+    }
+
     #[test]
     fn test_insert_returns_bool() {
         let mut d = Deck::new();
@@ -0,0 +1,178 @@
+use super::{Card, Hand, Suit, Value, rank::rank_straight};
+
+/// What kind of straight draw a hand has.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum StraightDraw {
+    /// No single card makes a straight.
+    None,
+    /// Only one value makes a straight, for example 5-6-8-9.
+    Gutshot,
+    /// Two or more values make a straight, for example 5-6-7-8. A double
+    /// gutshot like 5-7-8-9-J is just as good so it counts here too.
+    OpenEnded,
+}
+
+impl Hand {
+    /// The suit of a four card flush draw, if there is one.
+    ///
+    /// Hands that already have a flush don't have a flush draw.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, Suit};
+    ///
+    /// let hand = Hand::new_from_str("AhKh7h2hQd").unwrap();
+    /// assert_eq!(Some(Suit::Heart), hand.flush_draw_suit());
+    /// ```
+    pub fn flush_draw_suit(&self) -> Option<Suit> {
+        if self.suit_counts().iter().any(|c| *c >= 5) {
+            return None;
+        }
+        Suit::suits()
+            .into_iter()
+            .zip(self.suit_counts())
+            .find(|(_, count)| *count == 4)
+            .map(|(suit, _)| suit)
+    }
+
+    /// What kind of straight draw the hand has.
+    ///
+    /// Hands that already have a straight don't have a straight draw.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, StraightDraw};
+    ///
+    /// let open = Hand::new_from_str("5h6d7c8s").unwrap();
+    /// assert_eq!(StraightDraw::OpenEnded, open.straight_draw());
+    ///
+    /// let gutshot = Hand::new_from_str("5h6d8c9s").unwrap();
+    /// assert_eq!(StraightDraw::Gutshot, gutshot.straight_draw());
+    /// ```
+    pub fn straight_draw(&self) -> StraightDraw {
+        match self.straight_values().len() {
+            0 => StraightDraw::None,
+            1 => StraightDraw::Gutshot,
+            _ => StraightDraw::OpenEnded,
+        }
+    }
+
+    /// The number of cards not in the hand that would complete a flush or
+    /// a straight draw. Cards that complete both are only counted once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    ///
+    /// // Nine hearts and six more cards that make the straight.
+    /// let hand = Hand::new_from_str("9h8h2h3hTcJd").unwrap();
+    /// assert_eq!(15, hand.outs());
+    /// ```
+    pub fn outs(&self) -> u8 {
+        let flush_suit = self.flush_draw_suit();
+        let straight_values = self.straight_values();
+
+        let mut outs = 0;
+        for value in Value::values() {
+            for suit in Suit::suits() {
+                let card = Card::new(value, suit);
+                if !self.contains(&card)
+                    && (Some(suit) == flush_suit || straight_values.contains(&value))
+                {
+                    outs += 1;
+                }
+            }
+        }
+        outs
+    }
+
+    fn suit_counts(&self) -> [u8; 4] {
+        let mut counts = [0; 4];
+        for card in self.iter() {
+            counts[card.suit as usize] += 1;
+        }
+        counts
+    }
+
+    /// Every value that isn't in the hand but would make a straight.
+    fn straight_values(&self) -> Vec<Value> {
+        let value_set = self.iter().fold(0u32, |set, c| set | 1 << (c.value as u32));
+        if rank_straight(value_set).is_some() {
+            return vec![];
+        }
+        Value::values()
+            .into_iter()
+            .filter(|v| {
+                let bit = 1 << (*v as u32);
+                value_set & bit == 0 && rank_straight(value_set | bit).is_some()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_four_flush() {
+        let hand = Hand::new_from_str("AsKs7s2sQd3c").unwrap();
+        assert_eq!(Some(Suit::Spade), hand.flush_draw_suit());
+        assert_eq!(StraightDraw::None, hand.straight_draw());
+        assert_eq!(9, hand.outs());
+    }
+
+    #[test]
+    fn test_no_flush_draw() {
+        let three = Hand::new_from_str("AsKs7s2dQd").unwrap();
+        assert_eq!(None, three.flush_draw_suit());
+        let made = Hand::new_from_str("AsKs7s2sQs").unwrap();
+        assert_eq!(None, made.flush_draw_suit());
+        assert_eq!(0, made.outs());
+    }
+
+    #[test]
+    fn test_open_ender() {
+        let hand = Hand::new_from_str("5h6d7c8sKd").unwrap();
+        assert_eq!(StraightDraw::OpenEnded, hand.straight_draw());
+        assert_eq!(8, hand.outs());
+    }
+
+    #[test]
+    fn test_double_gutshot_is_open_ended() {
+        // A 6 or a T makes a straight.
+        let hand = Hand::new_from_str("5h7d8c9sJd").unwrap();
+        assert_eq!(StraightDraw::OpenEnded, hand.straight_draw());
+        assert_eq!(8, hand.outs());
+    }
+
+    #[test]
+    fn test_gutshots() {
+        let hand = Hand::new_from_str("5h6d8c9s").unwrap();
+        assert_eq!(StraightDraw::Gutshot, hand.straight_draw());
+        assert_eq!(4, hand.outs());
+
+        // Only a ten or a five finish these.
+        let broadway = Hand::new_from_str("AhKdQcJs").unwrap();
+        assert_eq!(StraightDraw::Gutshot, broadway.straight_draw());
+        let wheel = Hand::new_from_str("Ah2d3c4s").unwrap();
+        assert_eq!(StraightDraw::Gutshot, wheel.straight_draw());
+    }
+
+    #[test]
+    fn test_made_straight_has_no_draw() {
+        let hand = Hand::new_from_str("5h6d7c8s9d").unwrap();
+        assert_eq!(StraightDraw::None, hand.straight_draw());
+    }
+
+    #[test]
+    fn test_combo_draw_outs() {
+        let hand = Hand::new_from_str("9h8h2h3hTcJd").unwrap();
+        assert_eq!(Some(Suit::Heart), hand.flush_draw_suit());
+        assert_eq!(StraightDraw::OpenEnded, hand.straight_draw());
+        assert_eq!(15, hand.outs());
+    }
+}
@@ -7,16 +7,29 @@ use super::Card;
 /// readable error messages
 #[derive(Error, Debug, Hash)]
 pub enum RSPokerError {
+    #[deprecated(since = "4.0.0", note = "use `UnknownValueChar` or `InvalidValueChar`")]
     #[error("Unable to parse value")]
     UnexpectedValueChar,
+    #[deprecated(since = "4.0.0", note = "use `UnknownSuitChar` or `InvalidSuitChar`")]
     #[error("Unable to parse suit")]
     UnexpectedSuitChar,
+    #[error("Unknown card value '{0}' at position {1}")]
+    UnknownValueChar(char, usize),
+    #[error("Unknown card suit '{0}' at position {1}")]
+    UnknownSuitChar(char, usize),
+    #[error("Each card is a value and a suit, but {0} characters were given")]
+    OddNumberOfChars(usize),
+    #[error("'{0}' is not a card value")]
+    InvalidValueChar(char),
+    #[error("'{0}' is not a card suit")]
+    InvalidSuitChar(char),
     #[error("Error reading characters while parsing")]
     TooFewChars,
     #[error("Holdem hands should never have more than 7 cards in them.")]
     HoldemHandSize,
     #[error("Card already added to hand {0}")]
     DuplicateCardInHand(Card),
+    #[deprecated(since = "4.0.0", note = "use `OddNumberOfChars`")]
     #[error("Extra un-used characters found after parsing")]
     UnparsedCharsRemaining,
     #[error("Hand range can't be offsuit while cards are suiterd")]
@@ -29,4 +42,10 @@ pub enum RSPokerError {
     InvalidGap,
     #[error("Pairs can't be suited.")]
     InvalidSuitedPairs,
+    #[error("The ranges can't all be dealt at the same time.")]
+    ImpossibleRanges,
+    #[error("A range game needs at least one range.")]
+    NoRanges,
+    #[error("There aren't enough cards left in the deck to finish the hand.")]
+    NotEnoughCards,
 }
@@ -1,5 +1,6 @@
 use crate::core::card::Card;
 use crate::core::deck::Deck;
+use crate::core::hand::Hand;
 use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
 
 extern crate rand;
@@ -65,6 +66,46 @@ impl FlatDeck {
     pub fn deal(&mut self) -> Option<Card> {
         self.cards.pop()
     }
+
+    /// Deal `cards_each` cards to each of `n_players` hands the way a
+    /// dealer does, one card to every player in turn and then around
+    /// again. So the first card dealt goes to the first hand, the second
+    /// to the second hand, and so on.
+    ///
+    /// None if there aren't enough cards, and then none are dealt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Card, FlatDeck, Suit, Value};
+    ///
+    /// // The top of the deck is the end of the vec.
+    /// let mut deck: FlatDeck = vec![
+    ///     Card::new(Value::Four, Suit::Club),
+    ///     Card::new(Value::Three, Suit::Club),
+    ///     Card::new(Value::Two, Suit::Club),
+    ///     Card::new(Value::Ace, Suit::Club),
+    /// ]
+    /// .into();
+    /// let hands = deck.deal_hands(2, 2).unwrap();
+    ///
+    /// assert!(hands[0].contains(&Card::new(Value::Ace, Suit::Club)));
+    /// assert!(hands[0].contains(&Card::new(Value::Three, Suit::Club)));
+    /// assert!(deck.is_empty());
+    /// assert_eq!(None, deck.deal_hands(1, 1));
+    /// ```
+    pub fn deal_hands(&mut self, n_players: usize, cards_each: usize) -> Option<Vec<Hand>> {
+        if self.len() < n_players * cards_each {
+            return None;
+        }
+        let mut hands = vec![Hand::default(); n_players];
+        for _ in 0..cards_each {
+            for hand in hands.iter_mut() {
+                hand.insert(self.cards.pop()?);
+            }
+        }
+        Some(hands)
+    }
 }
 
 impl Index<usize> for FlatDeck {
@@ -193,6 +234,25 @@ mod tests {
         assert_eq!(c2, fd[1]);
     }
 
+    #[test]
+    fn test_deal_hands_round_robin() {
+        let cards: Vec<Card> = Deck::default().into_iter().take(10).collect();
+        // Deal from the top, the end of the vec, down.
+        let mut fd: FlatDeck = cards.iter().rev().copied().collect::<Vec<_>>().into();
+
+        let hands = fd.deal_hands(3, 2).unwrap();
+        assert_eq!(4, fd.len());
+        for (idx, hand) in hands.iter().enumerate() {
+            assert_eq!(2, hand.count());
+            assert!(hand.contains(&cards[idx]));
+            assert!(hand.contains(&cards[idx + 3]));
+        }
+
+        // Asking for too many leaves the deck alone.
+        assert_eq!(None, fd.deal_hands(3, 2));
+        assert_eq!(4, fd.len());
+    }
+
     #[test]
     fn test_is_empty() {
         let mut fd: FlatDeck = Deck::new().into();
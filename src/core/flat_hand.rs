@@ -35,43 +35,27 @@ impl FlatHand {
     /// assert!(hand.is_err());
     /// ```
     pub fn new_from_str(hand_string: &str) -> Result<Self, RSPokerError> {
-        // Get the chars iterator.
-        let mut chars = hand_string.chars();
+        let chars: Vec<char> = hand_string.chars().collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err(RSPokerError::OddNumberOfChars(chars.len()));
+        }
         // Where we will put the cards
         //
         // We make the assumption that the hands will have 2 plus five cards.
         let mut cards: Vec<Card> = Vec::with_capacity(7);
 
-        // Keep looping until we explicitly break
-        loop {
-            // Now try and get a char.
-            let vco = chars.next();
-            // If there was no char then we are done.
-            if vco.is_none() {
-                break;
-            } else {
-                // If we got a value char then we should get a
-                // suit.
-                let sco = chars.next();
-                // Now try and parse the two chars that we have.
-                let v = vco
-                    .and_then(Value::from_char)
-                    .ok_or(RSPokerError::UnexpectedValueChar)?;
-                let s = sco
-                    .and_then(Suit::from_char)
-                    .ok_or(RSPokerError::UnexpectedSuitChar)?;
-
-                let c = Card { value: v, suit: s };
-
-                match cards.binary_search(&c) {
-                    Ok(_) => return Err(RSPokerError::DuplicateCardInHand(c)),
-                    Err(i) => cards.insert(i, c),
-                };
-            }
-        }
+        for (pos, pair) in chars.chunks_exact(2).enumerate() {
+            let v = Value::from_char(pair[0])
+                .ok_or(RSPokerError::UnknownValueChar(pair[0], pos * 2))?;
+            let s = Suit::from_char(pair[1])
+                .ok_or(RSPokerError::UnknownSuitChar(pair[1], pos * 2 + 1))?;
+
+            let c = Card { value: v, suit: s };
 
-        if chars.next().is_some() {
-            return Err(RSPokerError::UnparsedCharsRemaining);
+            match cards.binary_search(&c) {
+                Ok(_) => return Err(RSPokerError::DuplicateCardInHand(c)),
+                Err(i) => cards.insert(i, c),
+            };
         }
 
         cards.reserve(7);
@@ -209,6 +193,22 @@ mod tests {
         assert!(FlatHand::new_from_str("AdAd").is_err());
     }
 
+    #[test]
+    fn test_parse_errors_name_the_problem() {
+        assert!(matches!(
+            FlatHand::new_from_str("Adx"),
+            Err(RSPokerError::OddNumberOfChars(3))
+        ));
+        assert!(matches!(
+            FlatHand::new_from_str("Ad1s"),
+            Err(RSPokerError::UnknownValueChar('1', 2))
+        ));
+        assert!(matches!(
+            FlatHand::new_from_str("AdKx"),
+            Err(RSPokerError::UnknownSuitChar('x', 3))
+        ));
+    }
+
     #[test]
     fn test_deterministic_new_from_str() {
         let h = FlatHand::new_from_str("AdKd").unwrap();
@@ -1,6 +1,8 @@
 use std::ops::{BitAnd, BitAndAssign};
 
-use super::{Card, CardBitSet, CardBitSetIter, RSPokerError, Suit, Value};
+use super::{
+    Card, CardBitSet, CardBitSetIter, CardIter, RSPokerError, Rank, Rankable, Suit, Value,
+};
 
 #[derive(Debug, Clone, PartialEq, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -96,39 +98,240 @@ impl Hand {
         self.0.clear();
     }
 
+    /// Parse a hand from pairs of value and suit characters, like `"AsKd"`.
+    /// Either case is accepted for both.
+    ///
+    /// Returns an error naming the problem if the string has an odd number
+    /// of characters, a character that isn't a value or suit, or the same
+    /// card twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, RSPokerError};
+    ///
+    /// assert_eq!(2, Hand::new_from_str("AsKd").unwrap().count());
+    /// assert!(matches!(
+    ///     Hand::new_from_str("AsXd"),
+    ///     Err(RSPokerError::UnknownValueChar('X', 2))
+    /// ));
+    /// ```
     pub fn new_from_str(hand_string: &str) -> Result<Self, RSPokerError> {
-        let mut chars = hand_string.chars();
+        let chars: Vec<char> = hand_string.chars().collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err(RSPokerError::OddNumberOfChars(chars.len()));
+        }
         let mut bitset = CardBitSet::new();
 
-        // Keep looping until we explicitly break
-        loop {
-            let vco = chars.next();
-            if vco.is_none() {
-                break;
+        for (pos, pair) in chars.chunks_exact(2).enumerate() {
+            let v = Value::from_char(pair[0])
+                .ok_or(RSPokerError::UnknownValueChar(pair[0], pos * 2))?;
+            let s = Suit::from_char(pair[1])
+                .ok_or(RSPokerError::UnknownSuitChar(pair[1], pos * 2 + 1))?;
+
+            let c = Card { value: v, suit: s };
+
+            if bitset.contains(c) {
+                return Err(RSPokerError::DuplicateCardInHand(c));
             } else {
-                let sco = chars.next();
-                let v = vco
-                    .and_then(Value::from_char)
-                    .ok_or(RSPokerError::UnexpectedValueChar)?;
-                let s = sco
-                    .and_then(Suit::from_char)
-                    .ok_or(RSPokerError::UnexpectedSuitChar)?;
-
-                let c = Card { value: v, suit: s };
-
-                if bitset.contains(c) {
-                    return Err(RSPokerError::DuplicateCardInHand(c));
-                } else {
-                    bitset.insert(c);
-                }
+                bitset.insert(c);
             }
         }
 
-        if chars.next().is_some() {
-            return Err(RSPokerError::UnparsedCharsRemaining);
+        Ok(Self(bitset))
+    }
+}
+
+#[cfg(feature = "lookup-table")]
+impl Hand {
+    /// Rank the hand using precomputed lookup tables.
+    ///
+    /// This is a lot faster than `Rankable::rank` for hands of up to seven
+    /// cards. The returned number isn't a `Rank` but it orders hands the
+    /// same way, so it can be compared between hands. The tables are built
+    /// the first time this is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hand has more than seven cards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    ///
+    /// let flush = Hand::new_from_str("AsKs9s5s2s3d4d").unwrap();
+    /// let straight = Hand::new_from_str("AsKdQdJcTs3d4d").unwrap();
+    /// assert!(flush.rank_fast() > straight.rank_fast());
+    /// ```
+    pub fn rank_fast(&self) -> u32 {
+        super::lookup::rank_fast(u64::from(self.0))
+    }
+}
+
+impl Hand {
+    /// Create a hand from a bitmask with one bit for each card.
+    ///
+    /// The layout is suit major. Each suit has 13 bits, from two in the
+    /// lowest bit up to ace, and the suits go spades, clubs, hearts, then
+    /// diamonds. So a card is bit `13 * suit + value`, the same number as
+    /// `u8::from(card)`. Bits 52 and up are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Card, Hand, Suit, Value};
+    ///
+    /// // The two of spades and the ace of diamonds.
+    /// let hand = Hand::from_bitmask(1 | 1 << 51);
+    /// assert!(hand.contains(&Card::new(Value::Two, Suit::Spade)));
+    /// assert!(hand.contains(&Card::new(Value::Ace, Suit::Diamond)));
+    /// assert_eq!(2, hand.count());
+    /// ```
+    pub fn from_bitmask(bits: u64) -> Self {
+        Self(CardBitSet::from(bits))
+    }
+
+    /// The cards in the hand as a bitmask, laid out as described in
+    /// [`Hand::from_bitmask`].
+    pub fn to_bitmask(&self) -> u64 {
+        u64::from(self.0)
+    }
+
+    /// The same hand with its suits relabeled into a canonical order, so
+    /// that every hand that only differs by swapping suits gives the same
+    /// result. Combined with `to_bitmask` it makes a key for caches and
+    /// lookup tables.
+    ///
+    /// Suits are ordered by their cards from the ace down, the suit holding
+    /// the highest card not shared by the others comes first. They're then
+    /// renamed spades, clubs, hearts and diamonds in that order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    ///
+    /// let hand = Hand::new_from_str("AsKh").unwrap();
+    /// let other = Hand::new_from_str("AhKs").unwrap();
+    /// assert_eq!(hand.canonical(), other.canonical());
+    /// assert_eq!(Hand::new_from_str("AsKc").unwrap(), hand.canonical());
+    /// ```
+    pub fn canonical(&self) -> Hand {
+        let bits = self.to_bitmask();
+        let mut suits: Vec<u64> = (0..4).map(|suit| (bits >> (13 * suit)) & 0x1FFF).collect();
+        suits.sort_unstable_by(|a, b| b.cmp(a));
+        Hand::from_bitmask(
+            suits
+                .iter()
+                .enumerate()
+                .map(|(suit, values)| values << (13 * suit))
+                .sum(),
+        )
+    }
+
+    /// Every five card subset of the hand along with its rank. Seven cards
+    /// give 21 of them, and the best of those ranks is what `rank` returns.
+    /// A hand with fewer than five cards has none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, Rankable};
+    ///
+    /// let hand = Hand::new_from_str("AsAdKsQs9s2c3s").unwrap();
+    /// assert_eq!(21, hand.five_card_combos().count());
+    ///
+    /// let best = hand.five_card_combos().map(|(_, rank)| rank).max();
+    /// assert_eq!(Some(hand.rank()), best);
+    /// ```
+    pub fn five_card_combos(&self) -> impl Iterator<Item = (Hand, Rank)> {
+        let cards: Vec<Card> = self.iter().collect();
+        let combos: Vec<(Hand, Rank)> = CardIter::new(&cards, 5)
+            .map(|five| {
+                let hand = Hand::new_with_cards(five);
+                (hand, hand.rank())
+            })
+            .collect();
+        combos.into_iter()
+    }
+
+    /// The five cards that make the best hand, along with their rank. For
+    /// a seven card flush that's the five highest cards of the flush suit.
+    /// When more than one set of five cards makes the same rank any of them
+    /// may be returned. Returns `None` for a hand with fewer than five
+    /// cards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, Rankable};
+    ///
+    /// let hand = Hand::new_from_str("AsKs9s5s2s3s4d").unwrap();
+    /// let (rank, cards) = hand.best_five().unwrap();
+    /// assert_eq!(hand.rank(), rank);
+    /// assert_eq!(
+    ///     Hand::new_from_str("AsKs9s5s3s").unwrap(),
+    ///     Hand::new_with_cards(cards.to_vec())
+    /// );
+    /// ```
+    pub fn best_five(&self) -> Option<(Rank, [Card; 5])> {
+        self.five_card_combos()
+            .max_by_key(|(_, rank)| *rank)
+            .map(|(hand, rank)| {
+                let cards: Vec<Card> = hand.iter().collect();
+                (rank, cards.try_into().expect("Every combo has five cards"))
+            })
+    }
+
+    /// Are all the cards the same suit. A hand needs at least two cards to
+    /// be suited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    ///
+    /// assert!(Hand::new_from_str("AsKs").unwrap().is_suited());
+    /// assert!(!Hand::new_from_str("AsKd").unwrap().is_suited());
+    /// ```
+    pub fn is_suited(&self) -> bool {
+        let mut cards = self.iter();
+        match cards.next() {
+            Some(first) => self.count() > 1 && cards.all(|c| c.suit == first.suit),
+            None => false,
         }
+    }
 
-        Ok(Self(bitset))
+    /// Do the cards' values run one after another with no pairs, like `98`
+    /// or `JT9`. The ace can play high or low, so `A2` is connected. A hand
+    /// needs at least two cards to be connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    ///
+    /// assert!(Hand::new_from_str("9s8d").unwrap().is_connected());
+    /// assert!(Hand::new_from_str("As2d").unwrap().is_connected());
+    /// assert!(!Hand::new_from_str("Ts8d").unwrap().is_connected());
+    /// ```
+    pub fn is_connected(&self) -> bool {
+        let count = self.count();
+        if count < 2 {
+            return false;
+        }
+        // One bit per value, with the ace copied below the two.
+        let values = (0..4).fold(0u16, |values, suit| {
+            values | ((self.to_bitmask() >> (13 * suit)) & 0x1FFF) as u16
+        });
+        let values = (values << 1) | (values >> 12);
+        if values.count_ones() as usize - usize::from(values & 1 == 1) != count {
+            // Some value is paired.
+            return false;
+        }
+        let run = (1u16 << count) - 1;
+        (0..=(14 - count)).any(|shift| values & (run << shift) == run << shift)
     }
 }
 
@@ -267,4 +470,165 @@ mod tests {
         assert!(hand.is_empty());
         assert_eq!(hand.count(), 0);
     }
+
+    #[test]
+    fn test_bitmask_round_trip() {
+        let hand = Hand::new_from_str("AsKd7c2h").unwrap();
+        let bits = hand.to_bitmask();
+        assert_eq!(hand.count(), bits.count_ones() as usize);
+        assert_eq!(hand, Hand::from_bitmask(bits));
+
+        for card in hand.iter() {
+            assert_ne!(0, bits & (1 << u8::from(card)));
+        }
+        // Spades come first, two is the lowest bit of each suit.
+        assert_eq!(1 << 12, Hand::new_from_str("As").unwrap().to_bitmask());
+        assert_eq!(1 << 13, Hand::new_from_str("2c").unwrap().to_bitmask());
+
+        let all = Hand::from_bitmask(u64::MAX);
+        assert_eq!(52, all.count());
+        assert_eq!((1 << 52) - 1, all.to_bitmask());
+    }
+
+    #[test]
+    fn test_canonical_preflop_hands() {
+        let deck: Vec<Card> = Hand::from_bitmask(u64::MAX).iter().collect();
+        let mut combos = 0;
+        let mut canonical = std::collections::HashSet::new();
+        for (i, first) in deck.iter().enumerate() {
+            for second in &deck[i + 1..] {
+                combos += 1;
+                let hand = Hand::new_with_cards(vec![*first, *second]);
+                canonical.insert(hand.canonical().to_bitmask());
+            }
+        }
+        assert_eq!(1326, combos);
+        assert_eq!(169, canonical.len());
+    }
+
+    #[test]
+    fn test_canonical_keeps_suit_structure() {
+        let flush_draw = Hand::new_from_str("AdKd7d2c").unwrap().canonical();
+        assert_eq!(Hand::new_from_str("AsKs7s2c").unwrap(), flush_draw);
+        assert_eq!(flush_draw, flush_draw.canonical());
+        assert_ne!(
+            Hand::new_from_str("AsKs").unwrap().canonical(),
+            Hand::new_from_str("AsKd").unwrap().canonical()
+        );
+    }
+
+    #[test]
+    fn test_five_card_combos_flush() {
+        let hand = Hand::new_from_str("AhJh9h4h2hAcAd").unwrap();
+        let combos: Vec<(Hand, Rank)> = hand.five_card_combos().collect();
+        assert_eq!(21, combos.len());
+        for (five, rank) in &combos {
+            assert_eq!(5, five.count());
+            assert_eq!(five.rank(), *rank);
+        }
+
+        // The flush beats the trip aces, and only the five hearts make it.
+        let (best, rank) = combos.iter().max_by_key(|(_, rank)| *rank).unwrap();
+        assert_eq!(hand.rank(), *rank);
+        assert!(matches!(rank, Rank::Flush(_)));
+        assert_eq!(Hand::new_from_str("AhJh9h4h2h").unwrap(), *best);
+
+        assert_eq!(
+            0,
+            Hand::new_from_str("AhJh9h4h")
+                .unwrap()
+                .five_card_combos()
+                .count()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let hand = Hand::new_from_str("AsKd7c2h").unwrap();
+        let json = serde_json::to_string(&hand).unwrap();
+        let parsed: Hand = serde_json::from_str(&json).unwrap();
+        assert_eq!(hand, parsed);
+        // The cards are stored as their strings.
+        assert!(json.contains("\"As\""));
+    }
+
+    #[test]
+    fn test_new_from_str_errors() {
+        assert!(matches!(
+            Hand::new_from_str("AsK"),
+            Err(RSPokerError::OddNumberOfChars(3))
+        ));
+        assert!(matches!(
+            Hand::new_from_str("As1d"),
+            Err(RSPokerError::UnknownValueChar('1', 2))
+        ));
+        assert!(matches!(
+            Hand::new_from_str("AsKx"),
+            Err(RSPokerError::UnknownSuitChar('x', 3))
+        ));
+        assert!(matches!(
+            Hand::new_from_str("AsAs"),
+            Err(RSPokerError::DuplicateCardInHand(c)) if c == Card::try_from("As").unwrap()
+        ));
+        assert!(matches!(
+            Hand::new_from_str("asAS"),
+            Err(RSPokerError::DuplicateCardInHand(_))
+        ));
+
+        let mixed = Hand::new_from_str("AsKd").unwrap();
+        assert_eq!(mixed, Hand::new_from_str("aSkD").unwrap());
+        assert!(mixed.contains(&Card::try_from("Kd").unwrap()));
+        assert!(Hand::new_from_str("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_connected_and_suited() {
+        let hand = |s: &str| Hand::new_from_str(s).unwrap();
+        assert!(hand("9s8s").is_connected());
+        assert!(hand("9s8s").is_suited());
+        assert!(hand("3cAd2h").is_connected());
+        assert!(hand("AsKd").is_connected());
+        assert!(hand("AsKdQh").is_connected());
+        assert!(!hand("KsAd2h").is_connected());
+        assert!(!hand("9s9d").is_connected());
+        assert!(!hand("9s9d8c").is_connected());
+        assert!(!hand("Th8h").is_connected());
+        assert!(hand("Th8h").is_suited());
+        assert!(!hand("Th8c").is_suited());
+        assert!(!hand("Th").is_suited());
+        assert!(!hand("Th").is_connected());
+        assert!(!Hand::default().is_suited());
+    }
+
+    #[test]
+    fn test_best_five_matches_rank() {
+        for s in [
+            "AsKs9s5s2s3s4d",
+            "AhAdKs9c4d3s2h",
+            "KsKdKh3c3dQs2h",
+            "As2d3h4c5dKsKd",
+            "9h8h7h6h5hTh2c",
+            "2s2d2h2cAdKdQd",
+            "AsKdQhJc8d",
+        ] {
+            let hand = Hand::new_from_str(s).unwrap();
+            let (rank, cards) = hand.best_five().unwrap();
+            assert_eq!(hand.rank(), rank, "{s}");
+            assert_eq!(rank, cards.to_vec().rank_five(), "{s}");
+            let best = Hand::new_with_cards(cards.to_vec());
+            assert_eq!(5, best.count());
+            assert!(cards.iter().all(|c| hand.contains(c)));
+        }
+        // The nine high straight flush plays over the ace high flush.
+        let (_, cards) = Hand::new_from_str("9h8h7h6h5hAh2c")
+            .unwrap()
+            .best_five()
+            .unwrap();
+        assert_eq!(
+            Hand::new_from_str("9h8h7h6h5h").unwrap(),
+            Hand::new_with_cards(cards.to_vec())
+        );
+        assert_eq!(None, Hand::new_from_str("AsKd").unwrap().best_five());
+    }
 }
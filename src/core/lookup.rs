@@ -0,0 +1,256 @@
+use std::sync::OnceLock;
+
+use super::rank::STRENGTH_CATEGORY_SHIFT as CATEGORY_SHIFT;
+use super::{Card, FlatHand, Rank, Rankable, Suit, Value};
+
+/// The most cards that the tables can rank.
+const MAX_CARDS: usize = 7;
+const VALUE_MASK: u32 = (1 << CATEGORY_SHIFT) - 1;
+/// Mask for the 13 value bits of a single suit.
+const SUIT_MASK: u64 = (1 << 13) - 1;
+
+/// Everything needed to rank a hand with a couple of table lookups.
+///
+/// Hands with a flush are ranked only by the values in the flush suit,
+/// that's `flush`. Every other hand is ranked by how many of each value
+/// it has. Those counts have a perfect hash into `counts`. With seven or
+/// fewer cards a flush is always better than anything the other suits
+/// could make, so these never overlap.
+struct Tables {
+    /// Rank for each bitset of values in the flush suit.
+    flush: Vec<u32>,
+    /// Spread the 13 bits of a suit out to 3 bits per value. Adding these
+    /// up for every suit gives the count of each value.
+    spread: Vec<u64>,
+    /// `offsets[value][cards_left][count]` is how far into the hash
+    /// having `count` cards of `value` moves.
+    offsets: [[[u32; 5]; MAX_CARDS + 1]; 13],
+    /// Where the hashes for each number of cards start in `counts`. Hands
+    /// with fewer cards come first.
+    starts: [u32; MAX_CARDS + 1],
+    /// Rank for each perfect hash of value counts.
+    counts: Vec<u32>,
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(Tables::build)
+}
+
+/// Turn a rank into a single number with the same ordering.
+fn encode(rank: Rank) -> u32 {
+    rank.strength_key()
+}
+
+/// Turn the output of `encode` back into a `Rank`.
+pub(crate) fn decode(fast: u32) -> Rank {
+    let value = fast & VALUE_MASK;
+    match fast >> CATEGORY_SHIFT {
+        0 => Rank::HighCard(value),
+        1 => Rank::OnePair(value),
+        2 => Rank::TwoPair(value),
+        3 => Rank::ThreeOfAKind(value),
+        4 => Rank::Straight(value),
+        5 => Rank::Flush(value),
+        6 => Rank::FullHouse(value),
+        7 => Rank::FourOfAKind(value),
+        _ => Rank::StraightFlush(value),
+    }
+}
+
+impl Tables {
+    fn build() -> Self {
+        // ways[value][cards] is the number of ways to pick `cards` cards
+        // from the values at or above `value`, with at most four of each.
+        let mut ways = [[0u32; MAX_CARDS + 1]; 14];
+        ways[13][0] = 1;
+        for value in (0..13).rev() {
+            for cards in 0..=MAX_CARDS {
+                ways[value][cards] = (0..=cards.min(4)).map(|c| ways[value + 1][cards - c]).sum();
+            }
+        }
+
+        let mut offsets = [[[0u32; 5]; MAX_CARDS + 1]; 13];
+        for (value, value_offsets) in offsets.iter_mut().enumerate() {
+            for (cards, card_offsets) in value_offsets.iter_mut().enumerate() {
+                for count in 1..=cards.min(4) {
+                    card_offsets[count] =
+                        card_offsets[count - 1] + ways[value + 1][cards - (count - 1)];
+                }
+            }
+        }
+
+        let spread = (0..(1u64 << 13))
+            .map(|mask| {
+                (0..13)
+                    .filter(|v| mask & (1 << v) != 0)
+                    .map(|v| 1u64 << (3 * v))
+                    .sum()
+            })
+            .collect();
+
+        let mut flush = vec![0; 1 << 13];
+        for (mask, rank) in flush.iter_mut().enumerate() {
+            let count = mask.count_ones() as usize;
+            if (5..=MAX_CARDS).contains(&count) {
+                let hand = FlatHand::new_with_cards(
+                    Value::values()
+                        .into_iter()
+                        .filter(|v| mask & (1 << *v as usize) != 0)
+                        .map(|v| Card::new(v, Suit::Spade))
+                        .collect(),
+                );
+                *rank = encode(hand.rank());
+            }
+        }
+
+        let mut starts = [0u32; MAX_CARDS + 1];
+        for cards in 1..=MAX_CARDS {
+            starts[cards] = starts[cards - 1] + ways[0][cards - 1];
+        }
+        let total = starts[MAX_CARDS] + ways[0][MAX_CARDS];
+
+        let mut tables = Self {
+            flush,
+            spread,
+            offsets,
+            starts,
+            counts: vec![0; total as usize],
+        };
+
+        let mut counts = [0u8; 13];
+        tables.fill_counts(0, 0, &mut counts);
+        tables
+    }
+
+    /// Walk every way of having up to seven cards, and rank a hand made
+    /// of those values.
+    fn fill_counts(&mut self, value: usize, cards: usize, counts: &mut [u8; 13]) {
+        if value == 13 {
+            let mut hand = Vec::with_capacity(cards);
+            // Deal the suits round robin so that no suit has five cards,
+            // and no value gets the same suit twice.
+            let mut suit = 0;
+            for (v, &count) in counts.iter().enumerate() {
+                for _ in 0..count {
+                    hand.push(Card::new(Value::from(v as u8), Suit::from(suit % 4)));
+                    suit += 1;
+                }
+            }
+            let packed = counts
+                .iter()
+                .enumerate()
+                .map(|(v, &c)| u64::from(c) << (3 * v))
+                .sum();
+            let idx = self.count_index(packed, cards);
+            self.counts[idx] = encode(FlatHand::new_with_cards(hand).rank());
+            return;
+        }
+
+        for count in 0..=4.min(MAX_CARDS - cards) {
+            counts[value] = count as u8;
+            self.fill_counts(value + 1, cards + count, counts);
+        }
+        counts[value] = 0;
+    }
+
+    /// The perfect hash of the value counts packed 3 bits per value.
+    fn count_index(&self, packed: u64, cards: usize) -> usize {
+        let mut idx = self.starts[cards];
+        let mut left = cards;
+        for (value, value_offsets) in self.offsets.iter().enumerate() {
+            if left == 0 {
+                break;
+            }
+            let count = ((packed >> (3 * value)) & 0b111) as usize;
+            idx += value_offsets[left][count];
+            left -= count;
+        }
+        idx as usize
+    }
+
+    fn rank(&self, bits: u64) -> u32 {
+        let suits = [
+            bits & SUIT_MASK,
+            (bits >> 13) & SUIT_MASK,
+            (bits >> 26) & SUIT_MASK,
+            (bits >> 39) & SUIT_MASK,
+        ];
+        if let Some(flush_suit) = suits.iter().find(|s| s.count_ones() >= 5) {
+            return self.flush[*flush_suit as usize];
+        }
+        let packed = suits.iter().map(|s| self.spread[*s as usize]).sum();
+        self.counts[self.count_index(packed, bits.count_ones() as usize)]
+    }
+}
+
+/// Rank the cards in a `Hand`'s bitset with the lookup tables.
+pub(crate) fn rank_fast(bits: u64) -> u32 {
+    assert!(
+        bits.count_ones() as usize <= MAX_CARDS,
+        "rank_fast can only rank up to seven cards"
+    );
+    tables().rank(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::core::{Deck, FlatDeck, Hand};
+
+    #[test]
+    fn test_encode_decode() {
+        let ranks = [
+            Rank::HighCard(0b11111),
+            Rank::OnePair(1 << 13 | 0b111),
+            Rank::FullHouse(1 << 25 | 1),
+            Rank::StraightFlush(9),
+        ];
+        for rank in ranks {
+            assert_eq!(rank, decode(encode(rank)));
+        }
+        assert!(encode(Rank::Flush(0b11111)) < encode(Rank::FullHouse(0)));
+        assert!(encode(Rank::OnePair(1 << 25)) < encode(Rank::TwoPair(0)));
+    }
+
+    #[test]
+    fn test_count_index_is_perfect() {
+        let tables = tables();
+        // Every slot is filled, nothing ranks as the zero high card.
+        assert_eq!(1, tables.counts.iter().filter(|r| **r == 0).count());
+        assert_eq!(0, tables.counts[0]);
+    }
+
+    #[test]
+    fn test_matches_rank() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut deck: FlatDeck = Deck::default().into();
+        for _ in 0..20_000 {
+            deck.shuffle(&mut rng);
+            for size in [5, 6, 7] {
+                let hand = Hand::new_with_cards(deck.sample(size));
+                assert_eq!(hand.rank(), decode(hand.rank_fast()), "{hand:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_flush_and_straight_flush() {
+        let royal = Hand::new_from_str("AsKsQsJsTs2d2c").unwrap();
+        let flush = Hand::new_from_str("As9sQsJsTs2d2c").unwrap();
+        let full_house = Hand::new_from_str("AsAdAcJsJd2d3c").unwrap();
+        assert_eq!(Rank::StraightFlush(9), decode(royal.rank_fast()));
+        assert!(royal.rank_fast() > full_house.rank_fast());
+        assert!(full_house.rank_fast() > flush.rank_fast());
+    }
+
+    #[test]
+    fn test_short_hands() {
+        let hand = Hand::new_from_str("AsKd").unwrap();
+        assert_eq!(hand.rank(), decode(hand.rank_fast()));
+        assert_eq!(Rank::HighCard(0), decode(Hand::new().rank_fast()));
+    }
+}
@@ -12,6 +12,10 @@ pub use self::card::{Card, Suit, Value};
 mod hand;
 /// Export the hand
 pub use self::hand::*;
+/// Flush and straight draws.
+mod draws;
+/// Export the draw types.
+pub use self::draws::StraightDraw;
 /// Code related to cards in flattened hands.
 mod flat_hand;
 /// Everything in there should be public.
@@ -35,7 +39,19 @@ pub use self::flat_deck::FlatDeck;
 /// 5 Card hand ranking code.
 mod rank;
 /// Export the trait and the results.
-pub use self::rank::{Rank, Rankable};
+pub use self::rank::{BadugiRank, LowRank, Rank, Rankable, ShortDeckRank};
+
+/// Lookup tables for fast seven card ranking.
+#[cfg(feature = "lookup-table")]
+mod lookup;
+#[cfg(feature = "lookup-table")]
+pub(crate) use self::lookup::decode as decode_fast_rank;
+
+/// Helpers for tests.
+#[cfg(test)]
+mod test_util;
+#[cfg(test)]
+pub(crate) use self::test_util::cards;
 
 // u16 backed player set.
 mod player_bit_set;
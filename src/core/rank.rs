@@ -1,6 +1,6 @@
-use crate::core::card::Card;
+use crate::core::card::{Card, Value};
 
-use super::{FlatHand, Hand};
+use super::{CardIter, FlatHand, Hand};
 
 /// All the different possible hand ranks.
 /// For each hand rank the u32 corresponds to
@@ -30,15 +30,288 @@ pub enum Rank {
     StraightFlush(u32),
 }
 
+/// Where the category of hand goes in `Rank::strength_key`, above every bit
+/// the values within a category use.
+pub(crate) const STRENGTH_CATEGORY_SHIFT: u32 = 26;
+
 /// Bit mask for the wheel (Ace, two, three, four, five)
 const WHEEL: u32 = 0b1_0000_0000_1111;
+/// Bit mask for the short deck wheel (Ace, six, seven, eight, nine)
+const SHORT_DECK_WHEEL: u32 = 0b1_0000_1111_0000;
+
+fn value_name(value: Value) -> &'static str {
+    match value {
+        Value::Two => "Two",
+        Value::Three => "Three",
+        Value::Four => "Four",
+        Value::Five => "Five",
+        Value::Six => "Six",
+        Value::Seven => "Seven",
+        Value::Eight => "Eight",
+        Value::Nine => "Nine",
+        Value::Ten => "Ten",
+        Value::Jack => "Jack",
+        Value::Queen => "Queen",
+        Value::King => "King",
+        Value::Ace => "Ace",
+    }
+}
+
+fn plural_value_name(value: Value) -> String {
+    match value {
+        Value::Six => "Sixes".to_string(),
+        _ => format!("{}s", value_name(value)),
+    }
+}
+
+/// The highest value in a 13 bit value set. `None` if the set is empty,
+/// which happens for a kicker the hand was too short to have.
+fn highest_value(value_set: u32) -> Option<Value> {
+    let value_set = value_set & 0x1FFF;
+    (value_set != 0).then(|| Value::from((31 - value_set.leading_zeros()) as u8))
+}
+
+impl Rank {
+    /// The rank as a single number where a higher number is a better hand,
+    /// kickers included. Comparing keys gives the same order as comparing
+    /// the ranks, and hands that tie have the same key. This is the same
+    /// number `Hand::rank_fast` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, Rankable};
+    ///
+    /// let ak = Hand::new_from_str("AsKs9s5s2s").unwrap().rank();
+    /// let aq = Hand::new_from_str("AdQd9d5d2d").unwrap().rank();
+    /// let ak_hearts = Hand::new_from_str("AhKh9h5h2h").unwrap().rank();
+    /// assert!(ak.strength_key() > aq.strength_key());
+    /// assert_eq!(ak.strength_key(), ak_hearts.strength_key());
+    /// ```
+    pub fn strength_key(&self) -> u32 {
+        let value = match *self {
+            Rank::HighCard(v)
+            | Rank::OnePair(v)
+            | Rank::TwoPair(v)
+            | Rank::ThreeOfAKind(v)
+            | Rank::Straight(v)
+            | Rank::Flush(v)
+            | Rank::FullHouse(v)
+            | Rank::FourOfAKind(v)
+            | Rank::StraightFlush(v) => v,
+        };
+        (u32::from(self.category()) << STRENGTH_CATEGORY_SHIFT) | value
+    }
+
+    /// Which kind of hand this is, from `0` for high card up to `8` for a
+    /// straight flush.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::Rank;
+    ///
+    /// assert_eq!(0, Rank::HighCard(0).category());
+    /// assert_eq!(6, Rank::FullHouse(0).category());
+    /// ```
+    pub fn category(&self) -> u8 {
+        match self {
+            Rank::HighCard(_) => 0,
+            Rank::OnePair(_) => 1,
+            Rank::TwoPair(_) => 2,
+            Rank::ThreeOfAKind(_) => 3,
+            Rank::Straight(_) => 4,
+            Rank::Flush(_) => 5,
+            Rank::FullHouse(_) => 6,
+            Rank::FourOfAKind(_) => 7,
+            Rank::StraightFlush(_) => 8,
+        }
+    }
+
+    /// Describe the hand the way a dealer would announce it, naming the top
+    /// kicker where one plays. Flushes and high cards are named by their
+    /// highest card.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, Rankable};
+    ///
+    /// let rank = Hand::new_from_str("KsKdKh3c3d").unwrap().rank();
+    /// assert_eq!("Full House, Kings full of Threes", rank.describe());
+    ///
+    /// let rank = Hand::new_from_str("AhAdKs9c4d").unwrap().rank();
+    /// assert_eq!("Pair of Aces, King kicker", rank.describe());
+    /// ```
+    pub fn describe(&self) -> String {
+        let with_kicker = |made: String, kickers: u32| match highest_value(kickers) {
+            Some(kicker) => format!("{made}, {} kicker", value_name(kicker)),
+            None => made,
+        };
+        // Straights store how far the high card is above the five.
+        let straight_high = |rank: u32| Value::from(rank as u8 + Value::Five as u8);
+
+        match *self {
+            Rank::HighCard(values) => match highest_value(values) {
+                Some(high) => with_kicker(
+                    format!("{} high", value_name(high)),
+                    values ^ (1 << high as u32),
+                ),
+                None => "Nothing".to_string(),
+            },
+            Rank::OnePair(v) => with_kicker(
+                format!(
+                    "Pair of {}",
+                    plural_value_name(Value::from((v >> 13).trailing_zeros() as u8))
+                ),
+                v,
+            ),
+            Rank::TwoPair(v) => {
+                let pairs = v >> 13;
+                let high = highest_value(pairs).unwrap_or(Value::Two);
+                let low = Value::from(pairs.trailing_zeros() as u8);
+                with_kicker(
+                    format!(
+                        "Two Pair, {} and {}",
+                        plural_value_name(high),
+                        plural_value_name(low)
+                    ),
+                    v,
+                )
+            }
+            Rank::ThreeOfAKind(v) => with_kicker(
+                format!(
+                    "Three of a Kind, {}",
+                    plural_value_name(Value::from((v >> 13).trailing_zeros() as u8))
+                ),
+                v,
+            ),
+            Rank::Straight(v) => format!("{}-high straight", value_name(straight_high(v))),
+            Rank::Flush(v) => format!(
+                "{}-high flush",
+                value_name(highest_value(v).unwrap_or(Value::Two))
+            ),
+            Rank::FullHouse(v) => format!(
+                "Full House, {} full of {}",
+                plural_value_name(Value::from((v >> 13).trailing_zeros() as u8)),
+                plural_value_name(Value::from((v & 0x1FFF).trailing_zeros() as u8))
+            ),
+            Rank::FourOfAKind(v) => with_kicker(
+                format!(
+                    "Four of a Kind, {}",
+                    plural_value_name(Value::from((v >> 13).trailing_zeros() as u8))
+                ),
+                v,
+            ),
+            Rank::StraightFlush(v) if straight_high(v) == Value::Ace => "Royal Flush".to_string(),
+            Rank::StraightFlush(v) => {
+                format!("{}-high straight flush", value_name(straight_high(v)))
+            }
+        }
+    }
+}
+
+/// The rank of a short deck (6-plus) hand.
+///
+/// Short deck uses the same hands as normal Hold'em, but with only 36 cards
+/// flushes are harder to make than full houses so a flush wins. The ace can
+/// still play low in a straight, as A-6-7-8-9, which is the lowest
+/// straight. Compare these rather than the wrapped `Rank`, which has the
+/// ordering of a full deck.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Copy)]
+pub struct ShortDeckRank(pub Rank);
+
+impl ShortDeckRank {
+    fn order_key(&self) -> (u8, u32) {
+        match self.0 {
+            Rank::HighCard(v) => (0, v),
+            Rank::OnePair(v) => (1, v),
+            Rank::TwoPair(v) => (2, v),
+            Rank::ThreeOfAKind(v) => (3, v),
+            Rank::Straight(v) => (4, v),
+            Rank::FullHouse(v) => (5, v),
+            Rank::Flush(v) => (6, v),
+            Rank::FourOfAKind(v) => (7, v),
+            Rank::StraightFlush(v) => (8, v),
+        }
+    }
+}
+
+impl Ord for ShortDeckRank {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order_key().cmp(&other.order_key())
+    }
+}
+
+impl PartialOrd for ShortDeckRank {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+/// The rank of a hand in a game where the lowest hand wins.
+///
+/// This wraps a `Rank` but orders it in the opposite direction, so the
+/// best low hand is the greatest `LowRank`. That way low hands can be
+/// compared with `>` and `max` just like high hands.
+///
+/// # Examples
+/// ```
+/// use rs_poker::core::{LowRank, Rank};
+///
+/// assert!(LowRank(Rank::HighCard(0)) > LowRank(Rank::OnePair(0)));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Copy)]
+pub struct LowRank(pub Rank);
+
+impl Ord for LowRank {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for LowRank {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The rank of a Badugi hand.
+///
+/// A badugi is the largest set of cards that all have different values and
+/// different suits. More cards is always better, and for the same number of
+/// cards the lowest highest card wins. Aces are low.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Copy)]
+pub struct BadugiRank {
+    /// The number of cards that make up the badugi.
+    pub card_count: u8,
+    /// A bitset of the values in the badugi with the ace as the lowest bit.
+    pub values: u32,
+}
+
+impl Ord for BadugiRank {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.card_count
+            .cmp(&other.card_count)
+            .then_with(|| other.values.cmp(&self.values))
+    }
+}
+
+impl PartialOrd for BadugiRank {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// Given a bitset of hand ranks. This method
 /// will determine if there's a straight, and will give the
 /// rank. Wheel is the lowest, broadway is the highest value.
 ///
 /// Returns None if the hand ranks represented don't correspond
 /// to a straight.
-fn rank_straight(value_set: u32) -> Option<u32> {
+pub(crate) fn rank_straight(value_set: u32) -> Option<u32> {
     // Example of something with a straight:
     //       0000111111100
     //       0001111111000
@@ -76,7 +349,12 @@ fn rank_straight(value_set: u32) -> Option<u32> {
     }
 }
 /// Keep only the most significant bit.
+///
+/// Zero stays zero, that happens when a short hand has no kicker.
 fn keep_highest(rank: u32) -> u32 {
+    if rank == 0 {
+        return 0;
+    }
     1 << (32 - rank.leading_zeros() - 1)
 }
 /// Keep the N most significant bits.
@@ -94,6 +372,85 @@ fn keep_n(rank: u32, to_keep: u32) -> u32 {
 fn find_flush(suit_value_sets: &[u32]) -> Option<usize> {
     suit_value_sets.iter().position(|sv| sv.count_ones() >= 5)
 }
+/// Rank the best five card hand in `cards`. Fewer cards are ranked as if
+/// the missing kickers were absent.
+fn rank_cards(cards: impl Iterator<Item = Card>) -> Rank {
+    let mut value_to_count: [u8; 13] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let mut count_to_value: [u32; 5] = [0, 0, 0, 0, 0];
+    let mut suit_value_sets: [u32; 4] = [0, 0, 0, 0];
+    let mut value_set: u32 = 0;
+
+    for c in cards {
+        let v = c.value as u8;
+        let s = c.suit as u8;
+        value_set |= 1 << v;
+        value_to_count[v as usize] += 1;
+        suit_value_sets[s as usize] |= 1 << v;
+    }
+
+    // Now rotate the value to count map.
+    for (value, &count) in value_to_count.iter().enumerate() {
+        count_to_value[count as usize] |= 1 << value;
+    }
+
+    // Find out if there's a flush
+    let flush: Option<usize> = find_flush(&suit_value_sets);
+
+    // If this is a flush then it could be a straight flush
+    // or a flush. So check only once.
+    if let Some(flush_idx) = flush {
+        // If we can find a straight in the flush then it's a straight flush
+        if let Some(rank) = rank_straight(suit_value_sets[flush_idx]) {
+            Rank::StraightFlush(rank)
+        } else {
+            // Else it's just a normal flush
+            let rank = keep_n(suit_value_sets[flush_idx], 5);
+            Rank::Flush(rank)
+        }
+    } else if count_to_value[4] != 0 {
+        // Four of a kind.
+        let high = keep_highest(value_set ^ count_to_value[4]);
+        Rank::FourOfAKind((count_to_value[4] << 13) | high)
+    } else if count_to_value[3] != 0 && count_to_value[3].count_ones() == 2 {
+        // There are two sets. So the best we can make is a full house.
+        let set = keep_highest(count_to_value[3]);
+        let pair = count_to_value[3] ^ set;
+        Rank::FullHouse((set << 13) | pair)
+    } else if count_to_value[3] != 0 && count_to_value[2] != 0 {
+        // there is a pair and a set.
+        let set = count_to_value[3];
+        let pair = keep_highest(count_to_value[2]);
+        Rank::FullHouse((set << 13) | pair)
+    } else if let Some(s_rank) = rank_straight(value_set) {
+        // If there's a straight return it now.
+        Rank::Straight(s_rank)
+    } else if count_to_value[3] != 0 {
+        // if there is a set then we need to keep 2 cards that
+        // aren't in the set.
+        let low = keep_n(value_set ^ count_to_value[3], 2);
+        Rank::ThreeOfAKind((count_to_value[3] << 13) | low)
+    } else if count_to_value[2].count_ones() >= 2 {
+        // Two pair
+        //
+        // That can be because we have 3 pairs and a high card.
+        // Or we could have two pair and two high cards.
+        let pairs = keep_n(count_to_value[2], 2);
+        let low = keep_highest(value_set ^ pairs);
+        Rank::TwoPair((pairs << 13) | low)
+    } else if count_to_value[2] == 0 {
+        // This means that there's no pair
+        // no sets, no straights, no flushes, so only a
+        // high card.
+        Rank::HighCard(keep_n(value_set, 5))
+    } else {
+        // Otherwise there's only one pair.
+        let pair = count_to_value[2];
+        // Keep the highest three cards not in the pair.
+        let low = keep_n(value_set ^ count_to_value[2], 3);
+        Rank::OnePair((pair << 13) | low)
+    }
+}
+
 /// Can this turn into a hand rank? There are default implementations for
 /// `Hand` and `Vec<Card>`.
 pub trait Rankable {
@@ -106,90 +463,277 @@ pub trait Rankable {
     /// hands). If you know that the hand only contains 5 cards then
     /// `rank_five` will be faster.
     ///
+    /// Slices of cards can be ranked directly, without building a hand.
+    /// They panic if there are fewer than five cards, use `partial_rank`
+    /// for those.
+    ///
     /// # Examples
     /// ```
-    /// use rs_poker::core::{FlatHand, Rank, Rankable};
+    /// use rs_poker::core::{Card, FlatHand, Rank, Rankable};
     ///
     /// let hand = FlatHand::new_from_str("2h2d8d8sKd6sTh").unwrap();
     /// let rank = hand.rank();
     /// assert!(Rank::TwoPair(0) <= rank);
     /// assert!(Rank::TwoPair(u32::max_value()) >= rank);
+    ///
+    /// let cards: Vec<Card> = hand.iter().copied().collect();
+    /// assert_eq!(rank, cards[..].rank());
     /// ```
     fn rank(&self) -> Rank {
-        let mut value_to_count: [u8; 13] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-        let mut count_to_value: [u32; 5] = [0, 0, 0, 0, 0];
-        let mut suit_value_sets: [u32; 4] = [0, 0, 0, 0];
+        rank_cards(self.cards())
+    }
+
+    /// Rank however many cards are present.
+    ///
+    /// With five or more cards this is the same as `rank`. With fewer
+    /// cards, for example a player that reaches showdown with an incomplete
+    /// hand, the missing cards are treated as absent kickers. So a lone pair
+    /// of aces is `OnePair` with no kickers and an empty hand is
+    /// `HighCard(0)`. Straights and flushes need all five cards. This never
+    /// panics.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_poker::core::{FlatHand, Rank, Rankable};
+    ///
+    /// let hand = FlatHand::new_from_str("AhAd").unwrap();
+    /// assert!(Rank::OnePair(0) <= hand.partial_rank());
+    /// assert!(Rank::TwoPair(0) > hand.partial_rank());
+    ///
+    /// let empty = FlatHand::default();
+    /// assert_eq!(Rank::HighCard(0), empty.partial_rank());
+    /// ```
+    fn partial_rank(&self) -> Rank {
+        // `rank_cards` handles every hand size, including missing kickers.
+        rank_cards(self.cards())
+    }
+
+    /// Rank the cards with short deck (6-plus) rules.
+    ///
+    /// The hand is ranked the same way as `rank`, except that A-6-7-8-9
+    /// counts as the lowest straight. The result orders flushes above full
+    /// houses.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_poker::core::{FlatHand, Rank, Rankable};
+    ///
+    /// let flush = FlatHand::new_from_str("AhJh9h7h6h")
+    ///     .unwrap()
+    ///     .rank_short_deck();
+    /// let full_house = FlatHand::new_from_str("KdKcKs9d9c")
+    ///     .unwrap()
+    ///     .rank_short_deck();
+    /// assert!(flush > full_house);
+    ///
+    /// let wheel = FlatHand::new_from_str("As6d7c8h9s")
+    ///     .unwrap()
+    ///     .rank_short_deck();
+    /// assert_eq!(Rank::Straight(0), wheel.0);
+    /// ```
+    fn rank_short_deck(&self) -> ShortDeckRank {
+        let rank = self.rank();
+        if rank >= Rank::FullHouse(0) {
+            return ShortDeckRank(rank);
+        }
+
         let mut value_set: u32 = 0;
+        let mut suit_value_sets: [u32; 4] = [0, 0, 0, 0];
+        for c in self.cards() {
+            value_set |= 1 << (c.value as u8);
+            suit_value_sets[c.suit as usize] |= 1 << (c.value as u8);
+        }
+
+        // The full deck ranking doesn't know about the short deck wheel.
+        // A higher straight was already found if there was one.
+        let short_wheel = |values: u32| values & SHORT_DECK_WHEEL == SHORT_DECK_WHEEL;
+        match rank {
+            Rank::Flush(_) if suit_value_sets.iter().any(|sv| short_wheel(*sv)) => {
+                ShortDeckRank(Rank::StraightFlush(0))
+            }
+            Rank::Straight(_) | Rank::Flush(_) => ShortDeckRank(rank),
+            _ if short_wheel(value_set) => ShortDeckRank(Rank::Straight(0)),
+            _ => ShortDeckRank(rank),
+        }
+    }
+
+    /// Rank an Omaha hand. These cards are the four hole cards and `board`
+    /// the community cards.
+    ///
+    /// Omaha hands have to use exactly two hole cards and exactly three
+    /// board cards, so every one of those combinations is ranked and the
+    /// best is returned. A full board gives C(4,2) * C(5,3) = 60 five card
+    /// hands.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there aren't exactly four hole cards or if the board
+    /// doesn't have three to five cards.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_poker::core::{Card, FlatHand, Hand, Rank, Rankable};
+    ///
+    /// let board: Vec<Card> = Hand::new_from_str("AsKsQsJs2d").unwrap().iter().collect();
+    /// // One spade isn't enough for a flush in Omaha.
+    /// let hero = FlatHand::new_from_str("Ts9c4c5c").unwrap();
+    /// assert!(Rank::Straight(0) <= hero.rank_omaha(&board));
+    /// assert!(Rank::Flush(0) > hero.rank_omaha(&board));
+    /// ```
+    fn rank_omaha(&self, board: &[Card]) -> Rank {
+        let hole: Vec<Card> = self.cards().collect();
+        assert_eq!(4, hole.len(), "Omaha hands need exactly four hole cards");
+        assert!(
+            (3..=5).contains(&board.len()),
+            "Omaha boards need three to five cards"
+        );
+
+        let mut best = Rank::HighCard(0);
+        for hole_pair in CardIter::new(&hole, 2) {
+            for board_three in CardIter::new(board, 3) {
+                let mut five = hole_pair.clone();
+                five.extend(board_three);
+                best = best.max(five.rank_five());
+            }
+        }
+        best
+    }
 
+    /// Rank the cards for deuce to seven lowball.
+    ///
+    /// Aces are always high and straights and flushes count against the
+    /// hand, so 7-5-4-3-2 offsuit is the best possible hand and A-2-3-4-5 is
+    /// just ace high. With more than five cards the best low five card hand
+    /// is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than five cards.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_poker::core::{FlatHand, Rankable};
+    ///
+    /// let nuts = FlatHand::new_from_str("7d5c4h3s2d")
+    ///     .unwrap()
+    ///     .rank_low_2to7();
+    /// let wheel = FlatHand::new_from_str("As2d3c4h5s")
+    ///     .unwrap()
+    ///     .rank_low_2to7();
+    /// assert!(nuts > wheel);
+    /// ```
+    fn rank_low_2to7(&self) -> LowRank {
+        let cards: Vec<Card> = self.cards().collect();
+        assert!(cards.len() >= 5, "Lowball hands need at least five cards");
+
+        CardIter::new(&cards, 5)
+            .map(|five| {
+                // The only place an ace plays low is the wheel, which in
+                // deuce to seven is ace high instead.
+                LowRank(match five.rank_five() {
+                    Rank::Straight(0) => Rank::HighCard(WHEEL),
+                    Rank::StraightFlush(0) => Rank::Flush(WHEEL),
+                    rank => rank,
+                })
+            })
+            .max()
+            .unwrap()
+    }
+
+    /// Rank the cards for ace to five lowball with an eight or better
+    /// qualifier, the low half of hi-lo split games.
+    ///
+    /// Aces are low and straights and flushes are ignored, so A-2-3-4-5 is
+    /// the best possible low. A low needs five cards of different values
+    /// that are all eight or lower, otherwise `None` is returned. With more
+    /// than five cards the best five are used.
+    ///
+    /// The returned rank is always `Rank::HighCard` with a bit set for each
+    /// value where the ace is the lowest bit.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_poker::core::{FlatHand, Rankable};
+    ///
+    /// let nuts = FlatHand::new_from_str("As2d3c4h5s")
+    ///     .unwrap()
+    ///     .rank_low_ace5();
+    /// let eight = FlatHand::new_from_str("8d5c4h3s2d")
+    ///     .unwrap()
+    ///     .rank_low_ace5();
+    /// assert!(nuts > eight);
+    ///
+    /// let no_low = FlatHand::new_from_str("9d5c4h3s2d")
+    ///     .unwrap()
+    ///     .rank_low_ace5();
+    /// assert_eq!(None, no_low);
+    /// ```
+    fn rank_low_ace5(&self) -> Option<LowRank> {
+        let mut value_set: u32 = 0;
         for c in self.cards() {
-            let v = c.value as u8;
-            let s = c.suit as u8;
-            value_set |= 1 << v;
-            value_to_count[v as usize] += 1;
-            suit_value_sets[s as usize] |= 1 << v;
+            // Shift everything up one so that the ace is the lowest bit.
+            let low_value = (c.value as u32 + 1) % 13;
+            if low_value <= 7 {
+                value_set |= 1 << low_value;
+            }
         }
 
-        // Now rotate the value to count map.
-        for (value, &count) in value_to_count.iter().enumerate() {
-            count_to_value[count as usize] |= 1 << value;
+        if value_set.count_ones() < 5 {
+            return None;
+        }
+        // Keep the five lowest values.
+        while value_set.count_ones() > 5 {
+            value_set = keep_highest(value_set) ^ value_set;
         }
+        Some(LowRank(Rank::HighCard(value_set)))
+    }
+
+    /// Rank the cards as a Badugi hand.
+    ///
+    /// Every set of up to four cards with distinct values and distinct
+    /// suits is considered and the best one is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_poker::core::{FlatHand, Rankable};
+    ///
+    /// let nuts = FlatHand::new_from_str("As2d3c4h").unwrap().rank_badugi();
+    /// assert_eq!(4, nuts.card_count);
+    ///
+    /// // Two spades means only three cards count.
+    /// let three = FlatHand::new_from_str("As2s3c4h").unwrap().rank_badugi();
+    /// assert_eq!(3, three.card_count);
+    /// assert!(nuts > three);
+    /// ```
+    fn rank_badugi(&self) -> BadugiRank {
+        let cards: Vec<Card> = self.cards().collect();
+        let mut best = BadugiRank {
+            card_count: 0,
+            values: 0,
+        };
 
-        // Find out if there's a flush
-        let flush: Option<usize> = find_flush(&suit_value_sets);
-
-        // If this is a flush then it could be a straight flush
-        // or a flush. So check only once.
-        if let Some(flush_idx) = flush {
-            // If we can find a straight in the flush then it's a straight flush
-            if let Some(rank) = rank_straight(suit_value_sets[flush_idx]) {
-                Rank::StraightFlush(rank)
-            } else {
-                // Else it's just a normal flush
-                let rank = keep_n(suit_value_sets[flush_idx], 5);
-                Rank::Flush(rank)
+        for size in (1..=cards.len().min(4)).rev() {
+            for subset in CardIter::new(&cards, size) {
+                let mut suit_set: u32 = 0;
+                let mut value_set: u32 = 0;
+                for c in &subset {
+                    suit_set |= 1 << (c.suit as u8);
+                    value_set |= 1 << ((c.value as u32 + 1) % 13);
+                }
+                if suit_set.count_ones() as usize == size && value_set.count_ones() as usize == size
+                {
+                    best = best.max(BadugiRank {
+                        card_count: size as u8,
+                        values: value_set,
+                    });
+                }
+            }
+            // Any bigger badugi beats every smaller one.
+            if best.card_count > 0 {
+                break;
             }
-        } else if count_to_value[4] != 0 {
-            // Four of a kind.
-            let high = keep_highest(value_set ^ count_to_value[4]);
-            Rank::FourOfAKind((count_to_value[4] << 13) | high)
-        } else if count_to_value[3] != 0 && count_to_value[3].count_ones() == 2 {
-            // There are two sets. So the best we can make is a full house.
-            let set = keep_highest(count_to_value[3]);
-            let pair = count_to_value[3] ^ set;
-            Rank::FullHouse((set << 13) | pair)
-        } else if count_to_value[3] != 0 && count_to_value[2] != 0 {
-            // there is a pair and a set.
-            let set = count_to_value[3];
-            let pair = keep_highest(count_to_value[2]);
-            Rank::FullHouse((set << 13) | pair)
-        } else if let Some(s_rank) = rank_straight(value_set) {
-            // If there's a straight return it now.
-            Rank::Straight(s_rank)
-        } else if count_to_value[3] != 0 {
-            // if there is a set then we need to keep 2 cards that
-            // aren't in the set.
-            let low = keep_n(value_set ^ count_to_value[3], 2);
-            Rank::ThreeOfAKind((count_to_value[3] << 13) | low)
-        } else if count_to_value[2].count_ones() >= 2 {
-            // Two pair
-            //
-            // That can be because we have 3 pairs and a high card.
-            // Or we could have two pair and two high cards.
-            let pairs = keep_n(count_to_value[2], 2);
-            let low = keep_highest(value_set ^ pairs);
-            Rank::TwoPair((pairs << 13) | low)
-        } else if count_to_value[2] == 0 {
-            // This means that there's no pair
-            // no sets, no straights, no flushes, so only a
-            // high card.
-            Rank::HighCard(keep_n(value_set, 5))
-        } else {
-            // Otherwise there's only one pair.
-            let pair = count_to_value[2];
-            // Keep the highest three cards not in the pair.
-            let low = keep_n(value_set ^ count_to_value[2], 3);
-            Rank::OnePair((pair << 13) | low)
         }
+        best
     }
 
     /// Rank this hand. It doesn't do any caching so it's left up to the user
@@ -298,16 +842,35 @@ impl Rankable for Vec<Card> {
     }
 }
 
+/// Slices can come from anywhere, so unlike hands they check that there
+/// are enough cards to rank.
+fn check_slice_len(len: usize) {
+    assert!(
+        len >= 5,
+        "Ranking a slice needs at least 5 cards but there were {len}"
+    );
+}
+
 impl Rankable for [Card] {
     fn cards(&self) -> impl Iterator<Item = Card> {
         self.iter().copied()
     }
+
+    fn rank(&self) -> Rank {
+        check_slice_len(self.len());
+        rank_cards(self.cards())
+    }
 }
 
 impl Rankable for &[Card] {
     fn cards(&self) -> impl Iterator<Item = Card> {
         self.iter().copied()
     }
+
+    fn rank(&self) -> Rank {
+        check_slice_len(self.len());
+        rank_cards(self.cards())
+    }
 }
 
 impl Rankable for Hand {
@@ -320,8 +883,96 @@ impl Rankable for Hand {
 mod tests {
     use super::*;
     use crate::core::card::*;
+    use crate::core::cards;
     use crate::core::flat_hand::*;
 
+    fn describe(s: &str) -> String {
+        Hand::new_from_str(s).unwrap().rank().describe()
+    }
+
+    fn key(s: &str) -> u32 {
+        Hand::new_from_str(s).unwrap().rank().strength_key()
+    }
+
+    #[test]
+    fn test_strength_key_kickers() {
+        assert!(key("AsKs9s5s2s") > key("AdQdJd9d8d"));
+        assert!(key("AsKs9s5s3s") > key("AdKd9d5d2d"));
+        // Trips decide a full house before the pair does.
+        assert!(key("KsKdKh3c3d") > key("QsQdQhAcAd"));
+        assert!(key("KsKdKh4c4d") > key("KsKdKh3c3d"));
+        assert!(key("2s2d2hAcAd") > key("AsKdQhJc9d"));
+
+        // Cards past the best five don't matter.
+        assert_eq!(key("AsAdKcQh9s2c3d"), key("AhAcKdQs9h4c5d"));
+        assert_eq!(key("KsKdKh3c3d"), key("KsKcKh3s3h"));
+    }
+
+    #[test]
+    fn test_strength_key_sorts_like_rank() {
+        let mut ranks: Vec<Rank> = [
+            "AsKs9s5s2s",
+            "KsKdKh3c3d",
+            "AsAdKcQh9s",
+            "2s3s4s5sAd",
+            "AsKdQhJcTd",
+            "7s7d7h7c2d",
+            "6s5d4h3c2d",
+            "AsQd9h5c2d",
+            "JsJd4h4c9d",
+            "9s8s7s6s5s",
+            "6s6dAh5c2d",
+        ]
+        .iter()
+        .map(|s| Hand::new_from_str(s).unwrap().rank())
+        .collect();
+        ranks.sort();
+        let keys: Vec<u32> = ranks.iter().map(Rank::strength_key).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_describe_each_rank() {
+        assert_eq!("Ace high, Queen kicker", describe("AsQd9h5c2d"));
+        assert_eq!("Pair of Sixes, Ace kicker", describe("6s6dAh5c2d"));
+        assert_eq!(
+            "Two Pair, Jacks and Fours, Nine kicker",
+            describe("JsJd4h4c9d")
+        );
+        assert_eq!(
+            "Three of a Kind, Sevens, King kicker",
+            describe("7s7d7hKc2d")
+        );
+        assert_eq!("Five-high straight", describe("As2d3h4c5d"));
+        assert_eq!("Ace-high straight", describe("AsKdQhJcTd"));
+        assert_eq!("Ace-high flush", describe("AdJd9d5d2d"));
+        assert_eq!("Full House, Kings full of Threes", describe("KsKdKh3c3d"));
+        assert_eq!("Four of a Kind, Twos, Ace kicker", describe("2s2d2h2cAd"));
+        assert_eq!("Nine-high straight flush", describe("9h8h7h6h5h"));
+        assert_eq!("Royal Flush", describe("AsKsQsJsTs"));
+    }
+
+    #[test]
+    fn test_describe_kickers() {
+        assert_eq!("Pair of Aces, King kicker", describe("AhAdKs9c4d"));
+        // Only the best five cards play.
+        assert_eq!("Pair of Aces, King kicker", describe("AhAdKs9c4d3s2h"));
+        // Seven cards with three pairs keep the best two.
+        assert_eq!(
+            "Two Pair, Kings and Queens, Six kicker",
+            describe("KsKdQhQc6d5h5s")
+        );
+        // Too few cards for a kicker.
+        assert_eq!(
+            "Pair of Queens",
+            Hand::new_from_str("QsQd")
+                .unwrap()
+                .partial_rank()
+                .describe()
+        );
+        assert_eq!("Full House, Threes full of Jacks", describe("3s3d3hJcJd"));
+    }
+
     #[test]
     fn test_keep_highest() {
         assert_eq!(0b100, keep_highest(0b111));
@@ -515,6 +1166,61 @@ mod tests {
         assert_eq!(Rank::TwoPair(pair_rank | low_rank), h.rank());
     }
 
+    /// The best rank of any five cards in the hand.
+    fn best_five_rank(h: &FlatHand) -> Rank {
+        crate::core::CardIter::new(&h[..], 5)
+            .map(|cards| cards.rank_five())
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_partial_rank_six_cards() {
+        for s in [
+            "2h2d8d8sKdKs",
+            "AsKsQsJsTs2d",
+            "3c3d3h9s9d2c",
+            "AhKd7c5s3d2h",
+        ] {
+            let h = FlatHand::new_from_str(s).unwrap();
+            assert_eq!(best_five_rank(&h), h.partial_rank(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_partial_rank_seven_cards() {
+        for s in [
+            "2h2d8d8sKdKsTh",
+            "AsKsQsJsTs2d2c",
+            "7c7d7h7s9d2c3h",
+            "Ah2d3c4s5d9hTc",
+        ] {
+            let h = FlatHand::new_from_str(s).unwrap();
+            assert_eq!(best_five_rank(&h), h.partial_rank(), "{s}");
+            assert_eq!(h.rank(), h.partial_rank());
+        }
+    }
+
+    #[test]
+    fn test_partial_rank_short_hands() {
+        assert_eq!(Rank::HighCard(0), FlatHand::default().partial_rank());
+
+        let quads = FlatHand::new_from_str("7c7d7h7s").unwrap();
+        assert_eq!(
+            Rank::FourOfAKind((1 << Value::Seven as u32) << 13),
+            quads.partial_rank()
+        );
+
+        let two_pair = FlatHand::new_from_str("7c7dKhKs").unwrap();
+        assert_eq!(
+            Rank::TwoPair(((1 << Value::Seven as u32) | (1 << Value::King as u32)) << 13),
+            two_pair.partial_rank()
+        );
+
+        let high = FlatHand::new_from_str("Ah").unwrap();
+        assert_eq!(Rank::HighCard(1 << Value::Ace as u32), high.partial_rank());
+    }
+
     #[test]
     fn test_rank_seven_two_pair() {
         let h = FlatHand::new_from_str("2h2d8d8sKd6sTh").unwrap();
@@ -522,4 +1228,283 @@ mod tests {
         let low_rank = 1 << Value::King as u32;
         assert_eq!(Rank::TwoPair(pair_rank | low_rank), h.rank());
     }
+
+    #[test]
+    fn test_rank_omaha_nut_flush_beats_set() {
+        let board = cards("Ah9h4h7c2s");
+        let nut_flush = FlatHand::new_from_str("KhQh3s3d").unwrap();
+        let set = FlatHand::new_from_str("9s9dTcJc").unwrap();
+
+        assert!(matches!(nut_flush.rank_omaha(&board), Rank::Flush(_)));
+        assert!(matches!(set.rank_omaha(&board), Rank::ThreeOfAKind(_)));
+        assert!(nut_flush.rank_omaha(&board) > set.rank_omaha(&board));
+    }
+
+    #[test]
+    fn test_rank_omaha_must_use_two_hole_cards() {
+        // Four of a kind on the board is only trips with a kicker, and a
+        // single heart can't make a flush.
+        let quads = cards("8h8c8d8s2h");
+        let hand = FlatHand::new_from_str("AhKhQhJc").unwrap();
+        assert!(matches!(hand.rank_omaha(&quads), Rank::ThreeOfAKind(_)));
+
+        let hearts = cards("Kh9h5h2c3d");
+        let one_heart = FlatHand::new_from_str("AhAsAdAc").unwrap();
+        assert!(matches!(one_heart.rank_omaha(&hearts), Rank::OnePair(_)));
+    }
+
+    #[test]
+    fn test_rank_omaha_straight() {
+        let board = cards("Ts9d6c6h2s");
+        let hand = FlatHand::new_from_str("8s7sKdKc").unwrap();
+        let expected = FlatHand::new_from_str("Ts9d8s7s6c").unwrap().rank_five();
+        assert_eq!(expected, hand.rank_omaha(&board));
+    }
+
+    #[test]
+    #[should_panic(expected = "exactly four hole cards")]
+    fn test_rank_omaha_wrong_hole_cards() {
+        let hand = FlatHand::new_from_str("AsKs").unwrap();
+        hand.rank_omaha(&cards("2c3c4c"));
+    }
+
+    #[test]
+    fn test_short_deck_flush_beats_full_house() {
+        let flush = FlatHand::new_from_str("AhJh9h7h6h").unwrap();
+        let full_house = FlatHand::new_from_str("KdKcKs9d9c").unwrap();
+        assert!(flush.rank() < full_house.rank());
+        assert!(flush.rank_short_deck() > full_house.rank_short_deck());
+
+        // Everything else keeps the normal order.
+        let quads = FlatHand::new_from_str("6s6c6d6hAs").unwrap();
+        let straight = FlatHand::new_from_str("TsJcQdKhAs").unwrap();
+        assert!(quads.rank_short_deck() > flush.rank_short_deck());
+        assert!(full_house.rank_short_deck() > straight.rank_short_deck());
+    }
+
+    #[test]
+    fn test_short_deck_wheel() {
+        let wheel = FlatHand::new_from_str("As6d7c8h9s").unwrap();
+        let six_high = FlatHand::new_from_str("6d7c8h9sTs").unwrap();
+        let trips = FlatHand::new_from_str("AsAdAc9s8d").unwrap();
+
+        assert_eq!(ShortDeckRank(Rank::Straight(0)), wheel.rank_short_deck());
+        assert!(six_high.rank_short_deck() > wheel.rank_short_deck());
+        assert!(wheel.rank_short_deck() > trips.rank_short_deck());
+
+        // Seven cards with a pair still find the wheel.
+        let seven = FlatHand::new_from_str("As6d7c8h9sAdKc").unwrap();
+        assert_eq!(ShortDeckRank(Rank::Straight(0)), seven.rank_short_deck());
+
+        // And suited it's a straight flush.
+        let suited = FlatHand::new_from_str("Ah6h7h8h9hKc").unwrap();
+        assert_eq!(
+            ShortDeckRank(Rank::StraightFlush(0)),
+            suited.rank_short_deck()
+        );
+    }
+
+    #[test]
+    fn test_low_2to7_nuts() {
+        let nuts = FlatHand::new_from_str("7d5c4h3s2d")
+            .unwrap()
+            .rank_low_2to7();
+        let second = FlatHand::new_from_str("7d6c4h3s2d")
+            .unwrap()
+            .rank_low_2to7();
+        let eight = FlatHand::new_from_str("8d5c4h3s2d")
+            .unwrap()
+            .rank_low_2to7();
+        assert_eq!(LowRank(Rank::HighCard(0b101111)), nuts);
+        assert!(nuts > second);
+        assert!(second > eight);
+    }
+
+    #[test]
+    fn test_low_2to7_wheel_is_not_a_straight() {
+        let wheel = FlatHand::new_from_str("As2d3c4h5s")
+            .unwrap()
+            .rank_low_2to7();
+        let king_high = FlatHand::new_from_str("Kd5c4h3s2d")
+            .unwrap()
+            .rank_low_2to7();
+        let straight = FlatHand::new_from_str("6d5c4h3s2d")
+            .unwrap()
+            .rank_low_2to7();
+        assert_eq!(LowRank(Rank::HighCard(WHEEL)), wheel);
+        // Ace high is worse than king high but better than any straight.
+        assert!(king_high > wheel);
+        assert!(wheel > straight);
+    }
+
+    #[test]
+    fn test_low_2to7_flush_penalty() {
+        let flush = FlatHand::new_from_str("7d5d4d3d2d")
+            .unwrap()
+            .rank_low_2to7();
+        let pair = FlatHand::new_from_str("AdAc4h3s2d")
+            .unwrap()
+            .rank_low_2to7();
+        let straight = FlatHand::new_from_str("6d5c4h3s2d")
+            .unwrap()
+            .rank_low_2to7();
+        assert!(pair > flush);
+        assert!(straight > flush);
+
+        let steel_wheel = FlatHand::new_from_str("Ad2d3d4d5d")
+            .unwrap()
+            .rank_low_2to7();
+        assert_eq!(LowRank(Rank::Flush(WHEEL)), steel_wheel);
+    }
+
+    #[test]
+    fn test_low_2to7_pairs() {
+        let low_pair = FlatHand::new_from_str("2d2c4h3s5d")
+            .unwrap()
+            .rank_low_2to7();
+        let high_pair = FlatHand::new_from_str("KdKc4h3s5d")
+            .unwrap()
+            .rank_low_2to7();
+        let two_pair = FlatHand::new_from_str("2d2c3h3s5d")
+            .unwrap()
+            .rank_low_2to7();
+        let ace_high = FlatHand::new_from_str("Ad7c4h3s5d")
+            .unwrap()
+            .rank_low_2to7();
+        assert!(ace_high > low_pair);
+        assert!(low_pair > high_pair);
+        assert!(high_pair > two_pair);
+    }
+
+    #[test]
+    fn test_low_2to7_best_of_seven() {
+        // Seven cards pick the best five, dropping the pair.
+        let hand = FlatHand::new_from_str("7d5c4h3s2dKcKd").unwrap();
+        assert_eq!(LowRank(Rank::HighCard(0b101111)), hand.rank_low_2to7());
+    }
+
+    #[test]
+    fn test_low_ace5_nuts() {
+        let nuts = FlatHand::new_from_str("As2d3c4h5s")
+            .unwrap()
+            .rank_low_ace5();
+        let six = FlatHand::new_from_str("6s2d3c4h5s")
+            .unwrap()
+            .rank_low_ace5();
+        let eight = FlatHand::new_from_str("8d7c4h3s2d")
+            .unwrap()
+            .rank_low_ace5();
+        assert_eq!(Some(LowRank(Rank::HighCard(0b11111))), nuts);
+        assert!(nuts > six);
+        assert!(six > eight);
+    }
+
+    #[test]
+    fn test_low_ace5_ignores_straights_and_flushes() {
+        let suited = FlatHand::new_from_str("Ad2d3d4d5d")
+            .unwrap()
+            .rank_low_ace5();
+        assert_eq!(Some(LowRank(Rank::HighCard(0b11111))), suited);
+    }
+
+    #[test]
+    fn test_low_ace5_qualifier() {
+        // Only four cards eight or lower.
+        let four_low = FlatHand::new_from_str("As2d3c4h9s").unwrap();
+        assert_eq!(None, four_low.rank_low_ace5());
+        let seven_cards = FlatHand::new_from_str("As2d3c8hKsQdJc").unwrap();
+        assert_eq!(None, seven_cards.rank_low_ace5());
+    }
+
+    #[test]
+    fn test_low_ace5_counterfeited() {
+        // A2 with a board of 2 3 7 K Q. The paired deuce kills the low.
+        let counterfeited = FlatHand::new_from_str("As2d2c3h7sKdQc").unwrap();
+        assert_eq!(None, counterfeited.rank_low_ace5());
+
+        // A2 with a board of 2 3 4 7 8 still has a low, but it's 7 high
+        // rather than the 5 high the A2 could have made.
+        let weaker = FlatHand::new_from_str("As2d2c3h4s7d8c").unwrap();
+        let wheel = FlatHand::new_from_str("As2d5c3h4s7d8c").unwrap();
+        assert_eq!(
+            Some(LowRank(Rank::HighCard(0b1001111))),
+            weaker.rank_low_ace5()
+        );
+        assert!(wheel.rank_low_ace5() > weaker.rank_low_ace5());
+    }
+
+    #[test]
+    fn test_badugi_nuts() {
+        let nuts = FlatHand::new_from_str("As2d3c4h").unwrap().rank_badugi();
+        assert_eq!(
+            BadugiRank {
+                card_count: 4,
+                values: 0b1111
+            },
+            nuts
+        );
+        let king_high = FlatHand::new_from_str("Ks2d3c4h").unwrap().rank_badugi();
+        assert!(nuts > king_high);
+
+        // Any four card badugi beats the best three card one.
+        let three = FlatHand::new_from_str("As2d3c3h").unwrap().rank_badugi();
+        assert!(king_high > three);
+    }
+
+    #[test]
+    fn test_badugi_collapses() {
+        // Paired
+        let paired = FlatHand::new_from_str("As2d3c3h").unwrap().rank_badugi();
+        assert_eq!(3, paired.card_count);
+        assert_eq!(0b111, paired.values);
+
+        // Two of a suit keeps the lower card.
+        let suited = FlatHand::new_from_str("As2d3cKc").unwrap().rank_badugi();
+        assert_eq!(3, suited.card_count);
+        assert_eq!(0b111, suited.values);
+
+        // Paired and suited down to two cards.
+        let two = FlatHand::new_from_str("As2s2hAh").unwrap().rank_badugi();
+        assert_eq!(
+            BadugiRank {
+                card_count: 2,
+                values: 0b11
+            },
+            two
+        );
+
+        // All one suit is a single card.
+        let one = FlatHand::new_from_str("As2s3s4s").unwrap().rank_badugi();
+        assert_eq!(1, one.card_count);
+        assert_eq!(0b1, one.values);
+    }
+
+    #[test]
+    fn test_badugi_compare_highest_card() {
+        let six = FlatHand::new_from_str("6s5d2c3h").unwrap().rank_badugi();
+        let seven = FlatHand::new_from_str("7sAd2c3h").unwrap().rank_badugi();
+        assert!(six > seven);
+    }
+
+    #[test]
+    fn test_rank_slices_like_hands() {
+        for hand in [
+            "AdKdQdJdTd",
+            "2h2d8d8sKd",
+            "2h2d8d8sKd6sTh",
+            "3c4d5h6s7c7d9h",
+        ] {
+            let cards: Vec<Card> = Hand::new_from_str(hand).unwrap().iter().collect();
+            let slice: &[Card] = &cards;
+            assert_eq!(Hand::new_from_str(hand).unwrap().rank(), slice.rank());
+            assert_eq!(cards.rank(), cards[..].rank());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 5 cards")]
+    fn test_rank_slice_too_short() {
+        let cards: Vec<Card> = Hand::new_from_str("AdKdQdJd").unwrap().iter().collect();
+        cards[..].rank();
+    }
 }
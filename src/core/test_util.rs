@@ -0,0 +1,10 @@
+//! Helpers shared by the tests.
+use super::Card;
+
+/// The cards written in `s`, like `"AsKd"`, in the order they're written.
+pub(crate) fn cards(s: &str) -> Vec<Card> {
+    s.as_bytes()
+        .chunks(2)
+        .map(|c| Card::try_from(std::str::from_utf8(c).unwrap()).unwrap())
+        .collect()
+}
@@ -0,0 +1,181 @@
+use crate::core::{Card, FlatHand, Hand};
+
+/// Suggested bluffing for a bet, from [`bluff_frequency`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BluffAdvice {
+    /// Value combos in hero's betting range that don't conflict with the
+    /// board.
+    pub value_combos: usize,
+    /// Bluff candidates in hero's range that don't conflict with the board.
+    pub bluff_combos: usize,
+    /// How often the bet has to get a fold to profit as a pure bluff,
+    /// `bet / (pot + bet)`.
+    pub alpha: f64,
+    /// Villain's calling combos left once the board and hero's cards are
+    /// removed.
+    pub calling_combos: usize,
+    /// How many more calling combos villain would have without hero's
+    /// blockers. `1.0` means hero blocks nothing.
+    pub blocker_factor: f64,
+    /// Suggested share of the betting range that should be bluffs.
+    pub bluff_frequency: f64,
+}
+
+impl BluffAdvice {
+    /// How many of the bluff candidates to bet, given the value combos.
+    pub fn suggested_bluff_combos(&self) -> f64 {
+        if self.bluff_frequency >= 1.0 {
+            return self.bluff_combos as f64;
+        }
+        let per_value = self.bluff_frequency / (1.0 - self.bluff_frequency);
+        (self.value_combos as f64 * per_value).min(self.bluff_combos as f64)
+    }
+}
+
+fn count_possible(range: &[FlatHand], dead: &Hand) -> usize {
+    range
+        .iter()
+        .filter(|combo| combo.iter().all(|c| !dead.contains(c)))
+        .count()
+}
+
+/// Suggest how often to bluff with a bet of `bet` into `pot`.
+///
+/// The balanced share of bluffs in a betting range is
+/// `bet / (pot + 2 * bet)`, which leaves villain indifferent to calling. That
+/// assumes villain holds every combo of their calling range. Hero's own hole
+/// cards rule some of those combos out, and the fewer calls villain can have
+/// the more often a bluff works, so the frequency is scaled up by
+/// `blocker_factor`. A bet of zero isn't a bluff, so its frequency is zero.
+///
+/// `hero` holds only hero's hole cards. The value and bluff ranges are hero's
+/// betting range split in two, they are counted against the board only since
+/// they describe hero's whole range rather than this hand.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand};
+/// use rs_poker::holdem::{RangeParser, bluff_frequency};
+///
+/// let board: Vec<Card> = Hand::new_from_str("Kd8s4c2h").unwrap().iter().collect();
+/// let value = RangeParser::parse_many("KK,88,44,AK").unwrap();
+/// let bluffs = RangeParser::parse_many("QJs,JTs,65s").unwrap();
+/// let calls = RangeParser::parse_many("AA,KQ,KJ").unwrap();
+///
+/// // A pot sized bet should be one third bluffs when hero blocks nothing.
+/// let hero = Hand::new_from_str("7s6s").unwrap();
+/// let advice = bluff_frequency(&hero, &board, &value, &bluffs, &calls, 10.0, 10.0);
+/// assert!((advice.bluff_frequency - 1.0 / 3.0).abs() < 1e-9);
+/// ```
+pub fn bluff_frequency(
+    hero: &Hand,
+    board: &[Card],
+    value_range: &[FlatHand],
+    bluff_range: &[FlatHand],
+    calling_range: &[FlatHand],
+    pot: f64,
+    bet: f64,
+) -> BluffAdvice {
+    let board_hand = Hand::new_with_cards(board.to_vec());
+    let mut hero_and_board = board_hand;
+    hero_and_board.extend(hero.iter());
+
+    let value_combos = count_possible(value_range, &board_hand);
+    let bluff_combos = count_possible(bluff_range, &board_hand);
+    let unblocked_calls = count_possible(calling_range, &board_hand);
+    let calling_combos = count_possible(calling_range, &hero_and_board);
+
+    let blocker_factor = if calling_combos == 0 {
+        f64::INFINITY
+    } else {
+        unblocked_calls as f64 / calling_combos as f64
+    };
+    let (alpha, bluff_frequency) = if bet > 0.0 {
+        let alpha = bet / (pot + bet);
+        // bet / (pot + 2 * bet), written in terms of alpha.
+        let balanced = alpha / (1.0 + alpha);
+        (alpha, (balanced * blocker_factor).clamp(0.0, 1.0))
+    } else {
+        (0.0, 0.0)
+    };
+
+    BluffAdvice {
+        value_combos,
+        bluff_combos,
+        alpha,
+        calling_combos,
+        blocker_factor,
+        bluff_frequency,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cards;
+    use crate::holdem::RangeParser;
+
+    /// AA, AQ and KT with the Kd on the board.
+    const CALLS: usize = 6 + 16 + 12;
+
+    #[test]
+    fn test_blockers_raise_frequency() {
+        let board = cards("Kd8s4c2h");
+        let value = RangeParser::parse_many("KK,88,AK").unwrap();
+        let bluffs = RangeParser::parse_many("QJs,JTs,A5s").unwrap();
+        let calls = RangeParser::parse_many("AA,AQ,KT").unwrap();
+
+        let no_blockers = Hand::new_from_str("Js9s").unwrap();
+        let ace_blocker = Hand::new_from_str("As5s").unwrap();
+
+        let base = bluff_frequency(&no_blockers, &board, &value, &bluffs, &calls, 10.0, 5.0);
+        let blocked = bluff_frequency(&ace_blocker, &board, &value, &bluffs, &calls, 10.0, 5.0);
+
+        assert_eq!(1.0, base.blocker_factor);
+        // AA loses 3 of 6 combos and AQ 4 of 16, KT is untouched.
+        assert_eq!(CALLS - 7, blocked.calling_combos);
+        assert!(blocked.blocker_factor > 1.0);
+        assert!(blocked.bluff_frequency > base.bluff_frequency);
+        assert!(blocked.suggested_bluff_combos() > base.suggested_bluff_combos());
+
+        // The ranges are hero's whole range so they don't change.
+        assert_eq!(base.value_combos, blocked.value_combos);
+        assert_eq!(base.bluff_combos, blocked.bluff_combos);
+    }
+
+    #[test]
+    fn test_balanced_frequency() {
+        let board = cards("Kd8s4c2h");
+        let value = RangeParser::parse_many("KK,88").unwrap();
+        let bluffs = RangeParser::parse_many("QJs").unwrap();
+        let calls = RangeParser::parse_many("AA,AQ,KT").unwrap();
+        let hero = Hand::new_from_str("Js9s").unwrap();
+
+        let advice = bluff_frequency(&hero, &board, &value, &bluffs, &calls, 10.0, 5.0);
+        // The board leaves three combos each of kings and eights.
+        assert_eq!(6, advice.value_combos);
+        assert_eq!(4, advice.bluff_combos);
+        assert_eq!(CALLS, advice.calling_combos);
+        assert!((advice.alpha - 1.0 / 3.0).abs() < 1e-9);
+        assert!((advice.bluff_frequency - 0.25).abs() < 1e-9);
+        // One bluff for every three value combos.
+        assert!((advice.suggested_bluff_combos() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_bet_and_no_calls() {
+        let board = cards("Kd8s4c2h");
+        let value = RangeParser::parse_many("KK").unwrap();
+        let bluffs = RangeParser::parse_many("QJs").unwrap();
+        let calls = RangeParser::parse_many("AsAd").unwrap();
+        // Hero holds the only calling combo.
+        let hero = Hand::new_from_str("AsAd").unwrap();
+
+        let advice = bluff_frequency(&hero, &board, &value, &bluffs, &calls, 0.0, 0.0);
+        assert_eq!(0, advice.calling_combos);
+        assert_eq!(0.0, advice.alpha);
+        assert_eq!(0.0, advice.bluff_frequency);
+        assert_eq!(0.0, advice.suggested_bluff_combos());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::core::{Card, CardBitSet};
+
+/// Iterator over every distinct way to deal `cards_to_come` more cards
+/// from the cards that are not yet known.
+///
+/// Completions are produced in a fixed order so repeated runs over the same
+/// inputs always yield the same sequence.
+#[derive(Debug, Clone)]
+struct BoardCompletions {
+    /// All the cards that could still be dealt.
+    remaining: Vec<Card>,
+    /// Offsets into `remaining` for the next completion.
+    idx: Vec<usize>,
+    /// Set once every completion has been returned.
+    done: bool,
+}
+
+impl BoardCompletions {
+    fn new(known: &[Card], cards_to_come: usize) -> Self {
+        let mut unknown = !CardBitSet::new();
+        for card in known {
+            unknown.remove(*card);
+        }
+        let remaining: Vec<Card> = unknown.into_iter().collect();
+        let done = cards_to_come > remaining.len();
+        Self {
+            remaining,
+            idx: (0..cards_to_come).collect(),
+            done,
+        }
+    }
+
+    /// Move `idx` forward to the next combination in lexicographic order.
+    /// Returns false when there are no more combinations.
+    fn advance(&mut self) -> bool {
+        let k = self.idx.len();
+        let n = self.remaining.len();
+        // Find the right most offset that can still be moved forward.
+        let mut i = k;
+        while i > 0 {
+            i -= 1;
+            if self.idx[i] < n - k + i {
+                self.idx[i] += 1;
+                for j in i + 1..k {
+                    self.idx[j] = self.idx[j - 1] + 1;
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Iterator for BoardCompletions {
+    type Item = Vec<Card>;
+
+    fn next(&mut self) -> Option<Vec<Card>> {
+        if self.done {
+            return None;
+        }
+        let result = self.idx.iter().map(|i| self.remaining[*i]).collect();
+        self.done = !self.advance();
+        Some(result)
+    }
+}
+
+/// Yield every distinct set of `cards_to_come` cards that could complete a
+/// board given the cards that are already known.
+///
+/// `known` should contain every card that can't be dealt, e.g. the hole cards
+/// of all players and the current board. Duplicates are ignored. Each
+/// completion is sorted in the deck's internal order and no completion is
+/// produced twice, so this is a building block for exact equity calculators.
+///
+/// When `cards_to_come` is zero a single empty completion is returned.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand};
+/// use rs_poker::holdem::board_completions;
+///
+/// // Hole cards and a flop are known, two more cards to come.
+/// let known: Vec<Card> = Hand::new_from_str("AsKs2h7d9c").unwrap().iter().collect();
+/// // 47 cards left in the deck choose 2
+/// assert_eq!(1081, board_completions(&known, 2).count());
+/// ```
+pub fn board_completions(known: &[Card], cards_to_come: usize) -> impl Iterator<Item = Vec<Card>> {
+    BoardCompletions::new(known, cards_to_come)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cards;
+
+    #[test]
+    fn test_turn_and_river_count() {
+        // Two players' hole cards plus a flop leaves 45 unseen cards.
+        let known = cards("AsKsQhQd2c7h9d");
+        let completions: Vec<Vec<Card>> = board_completions(&known, 2).collect();
+        assert_eq!(990, completions.len());
+
+        for completion in &completions {
+            assert_eq!(2, completion.len());
+            assert_ne!(completion[0], completion[1]);
+            assert!(completion.iter().all(|c| !known.contains(c)));
+        }
+
+        // Every completion is distinct
+        let unique: std::collections::HashSet<_> = completions.iter().cloned().collect();
+        assert_eq!(990, unique.len());
+    }
+
+    #[test]
+    fn test_river_only() {
+        let known = cards("AsKs2h7d9cTc");
+        assert_eq!(46, board_completions(&known, 1).count());
+    }
+
+    #[test]
+    fn test_zero_cards_to_come() {
+        let known = cards("AsKs2h7d9cTcJc");
+        let completions: Vec<Vec<Card>> = board_completions(&known, 0).collect();
+        assert_eq!(vec![Vec::<Card>::new()], completions);
+    }
+
+    #[test]
+    fn test_duplicate_known_ignored() {
+        let mut known = cards("AsKs");
+        known.extend(cards("As"));
+        assert_eq!(50, board_completions(&known, 1).count());
+    }
+
+    #[test]
+    fn test_too_many_cards_to_come() {
+        let known: Vec<Card> = crate::core::Deck::default().iter().take(50).collect();
+        assert_eq!(0, board_completions(&known, 3).count());
+        assert_eq!(1, board_completions(&known, 2).count());
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let known = cards("AsKs2h7d9c");
+        let a: Vec<Vec<Card>> = board_completions(&known, 2).collect();
+        let b: Vec<Vec<Card>> = board_completions(&known, 2).collect();
+        assert_eq!(a, b);
+    }
+}
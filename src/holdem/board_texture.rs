@@ -0,0 +1,173 @@
+use crate::core::{Card, Value};
+
+/// How many suits are on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuitTexture {
+    /// Every card is the same suit.
+    Monotone,
+    /// There are two suits.
+    TwoTone,
+    /// Three or more suits. On the flop every card is a different suit.
+    Rainbow,
+}
+
+/// How high the highest card on the board is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HighCardBucket {
+    /// Six high or lower.
+    Low,
+    /// Seven through nine high.
+    Middle,
+    /// Ten through king high.
+    Broadway,
+    /// Ace high.
+    Ace,
+}
+
+impl From<Value> for HighCardBucket {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Ace => Self::Ace,
+            Value::Ten | Value::Jack | Value::Queen | Value::King => Self::Broadway,
+            Value::Seven | Value::Eight | Value::Nine => Self::Middle,
+            _ => Self::Low,
+        }
+    }
+}
+
+/// The features of a board that change how hands play on it. See
+/// [`board_texture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoardTexture {
+    pub suits: SuitTexture,
+    /// At least three cards of one suit, so a flush is possible.
+    pub flush_possible: bool,
+    /// At least two cards share a value.
+    pub paired: bool,
+    /// Three different values fit inside five in a row, so a straight is
+    /// possible. Aces count high and low.
+    pub connected: bool,
+    pub high_card: HighCardBucket,
+}
+
+/// Classify a flop, turn, or river board for bucketing.
+///
+/// # Panics
+///
+/// Panics unless there are 3, 4, or 5 cards.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand};
+/// use rs_poker::holdem::{HighCardBucket, SuitTexture, board_texture};
+///
+/// let board: Vec<Card> = Hand::new_from_str("Ks9s4d").unwrap().iter().collect();
+/// let texture = board_texture(&board);
+/// assert_eq!(SuitTexture::TwoTone, texture.suits);
+/// assert_eq!(HighCardBucket::Broadway, texture.high_card);
+/// assert!(!texture.paired);
+/// assert!(!texture.connected);
+/// ```
+pub fn board_texture(board: &[Card]) -> BoardTexture {
+    assert!(
+        (3..=5).contains(&board.len()),
+        "A board has 3 to 5 cards, not {}",
+        board.len()
+    );
+
+    let mut suit_counts = [0; 4];
+    // Bit `value + 1` for each value, and bit 0 for an ace playing low.
+    let mut values: u16 = 0;
+    let mut paired = false;
+    for card in board {
+        suit_counts[card.suit as usize] += 1;
+        let bit = 1 << (card.value as u16 + 1);
+        paired |= values & bit != 0;
+        values |= bit;
+    }
+    if values & (1 << (Value::Ace as u16 + 1)) != 0 {
+        values |= 1;
+    }
+
+    let num_suits = suit_counts.iter().filter(|count| **count > 0).count();
+    let suits = match num_suits {
+        1 => SuitTexture::Monotone,
+        2 => SuitTexture::TwoTone,
+        _ => SuitTexture::Rainbow,
+    };
+    let connected = (0..=9).any(|low| ((values >> low) & 0b11111).count_ones() >= 3);
+    let high = board.iter().map(|card| card.value).max().unwrap();
+
+    BoardTexture {
+        suits,
+        flush_possible: suit_counts.iter().any(|count| *count >= 3),
+        paired,
+        connected,
+        high_card: high.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::Hand;
+
+    use super::*;
+
+    fn texture(board: &str) -> BoardTexture {
+        let cards: Vec<Card> = Hand::new_from_str(board).unwrap().iter().collect();
+        board_texture(&cards)
+    }
+
+    #[test]
+    fn test_monotone_flop() {
+        let t = texture("Ah8h3h");
+        assert_eq!(SuitTexture::Monotone, t.suits);
+        assert!(t.flush_possible);
+        assert!(!t.paired);
+        assert!(!t.connected);
+        assert_eq!(HighCardBucket::Ace, t.high_card);
+    }
+
+    #[test]
+    fn test_paired_rainbow_flop() {
+        let t = texture("7s7d2c");
+        assert_eq!(SuitTexture::Rainbow, t.suits);
+        assert!(!t.flush_possible);
+        assert!(t.paired);
+        assert!(!t.connected);
+        assert_eq!(HighCardBucket::Middle, t.high_card);
+    }
+
+    #[test]
+    fn test_connected_two_tone_flop() {
+        let t = texture("9s8s6d");
+        assert_eq!(SuitTexture::TwoTone, t.suits);
+        assert!(!t.flush_possible);
+        assert!(!t.paired);
+        assert!(t.connected);
+        assert_eq!(HighCardBucket::Middle, t.high_card);
+
+        // The wheel counts as connected.
+        assert!(texture("Ad4c2h").connected);
+        assert!(!texture("KdQc2h").connected);
+    }
+
+    #[test]
+    fn test_turn_and_river() {
+        let turn = texture("Ks9s4d2s");
+        assert_eq!(SuitTexture::TwoTone, turn.suits);
+        assert!(turn.flush_possible);
+
+        let river = texture("Ks9h4d2cKc");
+        assert_eq!(SuitTexture::Rainbow, river.suits);
+        assert!(river.paired);
+        assert_eq!(HighCardBucket::Broadway, river.high_card);
+    }
+
+    #[test]
+    #[should_panic(expected = "3 to 5 cards")]
+    fn test_too_few_cards() {
+        texture("AsKs");
+    }
+}
@@ -0,0 +1,125 @@
+use crate::core::{Card, CardIter, Hand, Rankable};
+
+/// The fewest hole cards that are needed to make hero's best hand on this
+/// board. Zero means the board plays.
+fn hole_cards_used(hero: &Hand, board: &[Card]) -> usize {
+    let board_hand = Hand::new_with_cards(board.to_vec());
+    let mut full = board_hand;
+    full.extend(hero.iter());
+    let best = full.rank();
+
+    let hole: Vec<Card> = hero.iter().collect();
+    // A hand needs five cards, so short boards need some hole cards.
+    let min_used = 5_usize.saturating_sub(board.len()).min(hole.len());
+    for used in min_used..hole.len() {
+        if used == 0 {
+            if board_hand.rank() == best {
+                return 0;
+            }
+            continue;
+        }
+        for cards in CardIter::new(&hole, used) {
+            let mut hand = board_hand;
+            hand.extend(cards);
+            if hand.rank() == best {
+                return used;
+            }
+        }
+    }
+    hole.len()
+}
+
+/// Does `new_card` counterfeit hero's hand?
+///
+/// A hand is counterfeited when the board improves in a way that makes some
+/// of hero's hole cards stop playing, without hero's hand getting any
+/// better. The classic example is a low two pair losing its lower pair when
+/// the board pairs a higher card, leaving every opponent with a better
+/// kicker or a higher pair ahead.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand, Suit, Value};
+/// use rs_poker::holdem::is_counterfeited;
+///
+/// let hero = Hand::new_from_str("7h6h").unwrap();
+/// let board: Vec<Card> = Hand::new_from_str("7c6dKs2c").unwrap().iter().collect();
+///
+/// // The king pairs the board and the sixes no longer play.
+/// assert!(is_counterfeited(
+///     &hero,
+///     &board,
+///     Card::new(Value::King, Suit::Diamond)
+/// ));
+/// ```
+pub fn is_counterfeited(hero: &Hand, board_before: &[Card], new_card: Card) -> bool {
+    let mut board_after = board_before.to_vec();
+    board_after.push(new_card);
+
+    let mut before = Hand::new_with_cards(board_before.to_vec());
+    before.extend(hero.iter());
+    let mut after = Hand::new_with_cards(board_after.clone());
+    after.extend(hero.iter());
+
+    // If hero made a better kind of hand it's not a counterfeit.
+    if after.rank().category() > before.rank().category() {
+        return false;
+    }
+
+    hole_cards_used(hero, &board_after) < hole_cards_used(hero, board_before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cards;
+    use crate::core::{Suit, Value};
+
+    #[test]
+    fn test_low_two_pair_counterfeited() {
+        let hero = Hand::new_from_str("7h6h").unwrap();
+        let before = cards("7c6dKs2c");
+        assert!(is_counterfeited(
+            &hero,
+            &before,
+            Card::new(Value::King, Suit::Diamond)
+        ));
+    }
+
+    #[test]
+    fn test_blank_not_counterfeited() {
+        let hero = Hand::new_from_str("7h6h").unwrap();
+        let before = cards("7c6dKs2c");
+        assert!(!is_counterfeited(
+            &hero,
+            &before,
+            Card::new(Value::Three, Suit::Spade)
+        ));
+    }
+
+    #[test]
+    fn test_improving_not_counterfeited() {
+        let hero = Hand::new_from_str("7h6h").unwrap();
+        let before = cards("7c6dKs2c");
+        // A full house isn't a counterfeit
+        assert!(!is_counterfeited(
+            &hero,
+            &before,
+            Card::new(Value::Seven, Suit::Spade)
+        ));
+    }
+
+    #[test]
+    fn test_river_board_plays() {
+        // Hero's pair of twos stops playing when the board makes two higher
+        // pairs and a better kicker.
+        let hero = Hand::new_from_str("2h2d").unwrap();
+        let before = cards("KsKdQcQh");
+        assert!(is_counterfeited(
+            &hero,
+            &before,
+            Card::new(Value::Ace, Suit::Spade)
+        ));
+    }
+}
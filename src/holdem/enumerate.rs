@@ -0,0 +1,162 @@
+use crate::core::{Card, Hand, Rank, Rankable};
+
+use super::board_completions;
+
+/// The most runouts `enumerate_equity` will go through.
+///
+/// That covers preflop heads up (C(48, 5) = 1,712,304 runouts) and three
+/// handed (C(46, 5) = 1,370,754), while anything with a flop out is tiny in
+/// comparison. Use `enumerate_equity_with_limit` to change it.
+pub const DEFAULT_MAX_RUNOUTS: u64 = 2_000_000;
+
+/// Exact equity of each hand by dealing every possible runout of `board`.
+///
+/// `hands` should only hold the hole cards. Ties split the pot between
+/// every tied player. Returns `None` if there would be more than
+/// `DEFAULT_MAX_RUNOUTS` runouts, or if the cards don't make a valid game
+/// (duplicates, too many cards).
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand};
+/// use rs_poker::holdem::enumerate_equity;
+///
+/// let hands = vec![
+///     Hand::new_from_str("AsAh").unwrap(),
+///     Hand::new_from_str("KsKh").unwrap(),
+/// ];
+/// let board: Vec<Card> = Hand::new_from_str("Kd7c2h").unwrap().iter().collect();
+/// let equity = enumerate_equity(hands, &board).unwrap();
+///
+/// // Aces need one of the last two aces to beat the set.
+/// assert!(equity[0] < 0.1);
+/// assert!((equity[0] + equity[1] - 1.0).abs() < 1e-9);
+/// ```
+pub fn enumerate_equity(hands: Vec<Hand>, board: &[Card]) -> Option<Vec<f64>> {
+    enumerate_equity_with_limit(hands, board, DEFAULT_MAX_RUNOUTS)
+}
+
+/// Same as `enumerate_equity` but with the limit on runouts given by the
+/// caller.
+pub fn enumerate_equity_with_limit(
+    hands: Vec<Hand>,
+    board: &[Card],
+    max_runouts: u64,
+) -> Option<Vec<f64>> {
+    let mut known = Hand::new_with_cards(board.to_vec());
+    if board.len() > 5 || known.count() != board.len() {
+        return None;
+    }
+    for hand in &hands {
+        if hand.count() + board.len() > 7 {
+            return None;
+        }
+        for card in hand.iter() {
+            if !known.insert(card) {
+                return None;
+            }
+        }
+    }
+
+    let cards_to_come = 5 - board.len();
+    let remaining = 52 - known.count() as u64;
+    if combinations(remaining, cards_to_come as u64) > max_runouts {
+        return None;
+    }
+
+    let mut values = vec![0.0; hands.len()];
+    let mut runouts: u64 = 0;
+    let known_cards: Vec<Card> = known.iter().collect();
+    for completion in board_completions(&known_cards, cards_to_come) {
+        runouts += 1;
+        let ranks: Vec<Rank> = hands
+            .iter()
+            .map(|h| {
+                let mut full = *h;
+                full.extend(board.iter().copied());
+                full.extend(completion.iter().copied());
+                full.rank()
+            })
+            .collect();
+        let Some(best) = ranks.iter().max() else {
+            continue;
+        };
+        let winners = ranks.iter().filter(|r| *r == best).count();
+        for (value, rank) in values.iter_mut().zip(ranks.iter()) {
+            if rank == best {
+                *value += 1.0 / winners as f64;
+            }
+        }
+    }
+
+    for value in values.iter_mut() {
+        *value /= runouts as f64;
+    }
+    Some(values)
+}
+
+/// n choose k without overflowing for the sizes of a deck.
+fn combinations(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cards;
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(1_712_304, combinations(48, 5));
+        assert_eq!(990, combinations(45, 2));
+        assert_eq!(1, combinations(44, 0));
+    }
+
+    #[test]
+    fn test_river_exact() {
+        // Villain has the flush on the turn and hero has a set, 44 cards left.
+        let hands = vec![
+            Hand::new_from_str("AsAd").unwrap(),
+            Hand::new_from_str("KhQh").unwrap(),
+        ];
+        let board = cards("Ah7h2h3c");
+        let equity = enumerate_equity(hands, &board).unwrap();
+        // Hero fills up or makes quads with 7s 7c 7d 2s 2c 2d 3s 3d 3h Ac.
+        assert!((equity[0] - 10.0 / 44.0).abs() < 1e-9, "{equity:?}");
+        assert!((equity[0] + equity[1] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_board_plays() {
+        let hands = vec![
+            Hand::new_from_str("2s3d").unwrap(),
+            Hand::new_from_str("2c3h").unwrap(),
+        ];
+        let board = cards("AsKdQcJhTs");
+        assert_eq!(Some(vec![0.5, 0.5]), enumerate_equity(hands, &board));
+    }
+
+    #[test]
+    fn test_too_many_runouts() {
+        let hands = vec![
+            Hand::new_from_str("AsAh").unwrap(),
+            Hand::new_from_str("KsKh").unwrap(),
+        ];
+        assert_eq!(None, enumerate_equity_with_limit(hands, &[], 1_000_000));
+    }
+
+    #[test]
+    fn test_invalid_cards() {
+        let hands = vec![
+            Hand::new_from_str("AsAh").unwrap(),
+            Hand::new_from_str("AsKh").unwrap(),
+        ];
+        assert_eq!(None, enumerate_equity(hands, &cards("2c3c4c")));
+        let hands = vec![Hand::new_from_str("AsAh").unwrap()];
+        assert_eq!(None, enumerate_equity(hands, &cards("2c3c4c5c6c7c")));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::core::{Card, Hand, Rank};
+
+use super::MonteCarloGame;
+
+/// The probability that hero's hand ends up beating a known villain hand
+/// strength by the river.
+///
+/// `hero` holds only the hole cards and `board` the community cards dealt
+/// so far. The rest of the board is sampled `sims` times and the result is
+/// the share of those runouts where hero's best five cards outrank
+/// `villain_rank`. Ties count as not beating it.
+///
+/// This answers questions like "how often do I get there against their
+/// probable flush" more directly than counting outs. Villain's cards aren't
+/// known, so they aren't removed from the deck.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand, Rankable};
+/// use rs_poker::holdem::prob_improve_to_beat;
+///
+/// let hero = Hand::new_from_str("8s8d").unwrap();
+/// let board: Vec<Card> = Hand::new_from_str("7h2h5h8c").unwrap().iter().collect();
+///
+/// // Villain already has the ace high flush, only a full house or quads
+/// // gets there.
+/// let villain = Hand::new_from_str("AhQh7h2h5h").unwrap().rank();
+/// let p = prob_improve_to_beat(hero, &board, villain, 1000);
+/// assert!(p > 0.1 && p < 0.35);
+/// ```
+pub fn prob_improve_to_beat(hero: Hand, board: &[Card], villain_rank: Rank, sims: usize) -> f64 {
+    let mut hand = hero;
+    hand.extend(board.iter().cloned());
+    MonteCarloGame::new(vec![hand])
+        .map(|mut g| g.estimate_rank_probability(sims, |rank| rank > villain_rank))
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Rankable;
+    use crate::core::cards;
+
+    #[test]
+    fn test_against_made_flush_on_turn() {
+        // A set facing a made flush on the turn has 10 outs to boat up out
+        // of 46 unseen cards.
+        let hero = Hand::new_from_str("8s8d").unwrap();
+        let villain = Hand::new_from_str("AhQh7h2h5h").unwrap().rank();
+        let p = prob_improve_to_beat(hero, &cards("7h2h5h8c"), villain, 20_000);
+        let expected = 10.0 / 46.0;
+        assert!((p - expected).abs() < 0.02, "{p} vs {expected}");
+    }
+
+    #[test]
+    fn test_complete_board_is_exact() {
+        let hero = Hand::new_from_str("AsKs").unwrap();
+        let on_board = cards("QsJsTs2d3c");
+        let straight = Hand::new_from_str("9dKdQhJhTh").unwrap().rank();
+
+        assert_eq!(1.0, prob_improve_to_beat(hero, &on_board, straight, 10));
+        let royal = Hand::new_from_str("AsKsQsJsTs").unwrap().rank();
+        assert_eq!(0.0, prob_improve_to_beat(hero, &on_board, royal, 10));
+    }
+
+    #[test]
+    fn test_cant_beat_royal_flush() {
+        let hero = Hand::new_from_str("2c7d").unwrap();
+        let royal = Hand::new_from_str("AsKsQsJsTs").unwrap().rank();
+        assert_eq!(0.0, prob_improve_to_beat(hero, &[], royal, 1000));
+    }
+}
@@ -7,9 +7,77 @@ pub use self::starting_hand::{StartingHand, Suitedness};
 /// simulation.
 mod monte_carlo_game;
 /// Export `MonteCarloGame`
-pub use self::monte_carlo_game::MonteCarloGame;
+pub use self::monte_carlo_game::{EquityResult, MonteCarloGame};
 
 /// Module with all the starting hand parsing code.
 mod parse;
 /// Export `RangeParser`
 pub use self::parse::RangeParser;
+
+/// Module for sets of starting hands.
+mod range;
+/// Export `Range`
+pub use self::range::Range;
+
+/// Module for simulating ranges against each other.
+mod range_game;
+/// Export `RangeMonteCarloGame`
+pub use self::range_game::{RangeGameResult, RangeMonteCarloGame};
+
+/// Module for enumerating the possible ways to complete a board.
+mod board_completions;
+/// Export `board_completions`
+pub use self::board_completions::board_completions;
+
+/// Module for exact equity by going through every runout.
+mod enumerate;
+/// Export `enumerate_equity`
+pub use self::enumerate::{DEFAULT_MAX_RUNOUTS, enumerate_equity, enumerate_equity_with_limit};
+
+/// Module for detecting when the board counterfeits a hand.
+mod counterfeit;
+/// Export `is_counterfeited`
+pub use self::counterfeit::is_counterfeited;
+
+/// Module for equity of a hand against a range and the reverse.
+mod range_equity;
+/// Export the range equity functions
+pub use self::range_equity::{RangeEquity, hand_vs_range_equity, range_vs_hand_equity};
+
+/// Module for exact starting hand against starting hand results on a board.
+mod pairwise;
+/// Export `pairwise_board_results`
+pub use self::pairwise::{MatchupResult, PairwiseBoardResults, pairwise_board_results};
+
+/// Module for the chance of improving past a known hand strength.
+mod improve;
+/// Export `prob_improve_to_beat`
+pub use self::improve::prob_improve_to_beat;
+
+/// Module for suggesting bluffing frequencies that account for blockers.
+mod bluffing;
+/// Export `bluff_frequency`
+pub use self::bluffing::{BluffAdvice, bluff_frequency};
+
+/// Module for looking up how strong a starting hand is.
+mod preflop_strength;
+/// Export `preflop_strength`
+pub use self::preflop_strength::preflop_strength;
+
+/// Module for the equity of every starting hand against a random hand.
+mod preflop_matrix;
+/// Export `preflop_equity_matrix`
+pub use self::preflop_matrix::preflop_equity_matrix;
+
+/// Module for classifying boards.
+mod board_texture;
+/// Export `board_texture`
+pub use self::board_texture::{BoardTexture, HighCardBucket, SuitTexture, board_texture};
+
+/// Module for counting the kinds of hands a range makes on a board.
+mod rank_distribution;
+/// Export `rank_distribution`
+pub use self::rank_distribution::rank_distribution;
+
+/// Module for heads up push or fold equilibrium ranges.
+pub mod pushfold;
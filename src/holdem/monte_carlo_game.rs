@@ -1,9 +1,84 @@
-use rand::rng;
+use rand::{Rng, SeedableRng, rng, rngs::StdRng};
 
-use crate::core::{CardBitSet, FlatDeck, Hand, PlayerBitSet, RSPokerError, Rank, Rankable};
+use crate::core::{Card, CardBitSet, FlatDeck, Hand, PlayerBitSet, RSPokerError, Rank};
+
+/// How many simulations each rayon task of `estimate_equity_parallel` runs.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 1_000;
+
+/// How one hand did over a number of simulated games.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EquityResult {
+    /// Games where this hand was the only best hand.
+    pub wins: u64,
+    /// Games where this hand tied with others for the best hand.
+    pub ties: u64,
+    /// The number of games simulated.
+    pub total: u64,
+    /// The share of the pot won in ties. Three way ties are worth a third
+    /// of a pot while two way ties are worth half.
+    pub tie_share: f64,
+    /// `split_ways[n]` is how many games this hand split the pot `n` ways.
+    /// The first two entries are always zero, a scoop is counted in `wins`.
+    pub split_ways: Vec<u64>,
+}
+
+impl EquityResult {
+    /// The share of the pot this hand is expected to win, with ties split
+    /// evenly between the tied hands.
+    pub fn equity(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.wins as f64 + self.tie_share) / self.total as f64
+    }
+
+    /// The expected share of the pot as an exact fraction, as a
+    /// `(numerator, denominator)` pair in lowest terms. A hand that chopped
+    /// every game two ways has a share of `(1, 2)`. No games at all is
+    /// `(0, 1)`.
+    pub fn pot_share(&self) -> (u64, u64) {
+        if self.total == 0 {
+            return (0, 1);
+        }
+        // Doing the sum over the lcm of the ways split keeps it exact.
+        let ways = (2..self.split_ways.len() as u64)
+            .filter(|n| self.split_ways[*n as usize] > 0)
+            .fold(1, lcm);
+        let numerator = self.wins * ways
+            + (2..self.split_ways.len())
+                .map(|n| self.split_ways[n] * (ways / n as u64))
+                .sum::<u64>();
+        let denominator = self.total * ways;
+        let divisor = gcd(numerator, denominator);
+        (numerator / divisor, denominator / divisor)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn add(&mut self, other: &EquityResult) {
+        self.wins += other.wins;
+        self.ties += other.ties;
+        self.total += other.total;
+        self.tie_share += other.tie_share;
+        if self.split_ways.len() < other.split_ways.len() {
+            self.split_ways.resize(other.split_ways.len(), 0);
+        }
+        for (mine, theirs) in self.split_ways.iter_mut().zip(&other.split_ways) {
+            *mine += theirs;
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a.max(1) } else { gcd(b, a % b) }
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
 
 /// Current state of a game.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MonteCarloGame {
     /// Flatten deck
     deck: FlatDeck,
@@ -15,11 +90,37 @@ pub struct MonteCarloGame {
     // The number of needed cards each round
     cards_needed: usize,
     current_offset: usize,
+    /// Used for every shuffle of the deck.
+    rng: StdRng,
 }
 
 impl MonteCarloGame {
     /// If we already have hands then lets start there.
     pub fn new(hands: Vec<Hand>) -> Result<Self, RSPokerError> {
+        Self::new_with_rng(hands, &mut rng())
+    }
+
+    /// Create a new game where all the shuffling is seeded from `rng`.
+    ///
+    /// Passing in a seeded rng makes every simulation reproducible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use rs_poker::core::Hand;
+    /// use rs_poker::holdem::MonteCarloGame;
+    ///
+    /// let hands = vec![
+    ///     Hand::new_from_str("AsKs").unwrap(),
+    ///     Hand::new_from_str("QdQh").unwrap(),
+    /// ];
+    /// let mut first =
+    ///     MonteCarloGame::new_with_rng(hands.clone(), &mut StdRng::seed_from_u64(42)).unwrap();
+    /// let mut second = MonteCarloGame::new_with_rng(hands, &mut StdRng::seed_from_u64(42)).unwrap();
+    /// assert_eq!(first.estimate_equity(100), second.estimate_equity(100));
+    /// ```
+    pub fn new_with_rng<R: Rng>(hands: Vec<Hand>, rng: &mut R) -> Result<Self, RSPokerError> {
         let mut deck = CardBitSet::default();
         let mut max_hand_size: usize = 0;
         let mut cards_needed = 0;
@@ -40,7 +141,7 @@ impl MonteCarloGame {
             }
         }
 
-        let num_community_cards = (7 - max_hand_size).max(0);
+        let num_community_cards = 7 - max_hand_size;
 
         let flat_deck: FlatDeck = deck.into();
         // Grab the deck.len() so that any call to shuffle_if_needed
@@ -54,9 +155,150 @@ impl MonteCarloGame {
             num_community_cards,
             cards_needed,
             current_offset: offset,
+            rng: StdRng::from_rng(rng),
         })
     }
 
+    /// Create a game where some of the board is already known. Each hand is
+    /// only the player's hole cards, which can be empty for a player whose
+    /// cards aren't known, and `board` is shared by all of them.
+    ///
+    /// Returns an error if a board card is also in a hand or there are more
+    /// than five board cards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs_poker::core::{Card, Hand};
+    /// use rs_poker::holdem::MonteCarloGame;
+    ///
+    /// let hands = vec![
+    ///     Hand::new_from_str("AsAd").unwrap(),
+    ///     Hand::new_from_str("KsKd").unwrap(),
+    /// ];
+    /// let board: Vec<Card> = Hand::new_from_str("Kh7c2d").unwrap().iter().collect();
+    /// let mut game = MonteCarloGame::with_board(hands, &board).unwrap();
+    /// let equity = game.estimate_equity(1000);
+    /// assert!(equity[1] > equity[0]);
+    /// ```
+    pub fn with_board(hands: Vec<Hand>, board: &[Card]) -> Result<Self, RSPokerError> {
+        Self::with_board_and_rng(hands, board, &mut rng())
+    }
+
+    /// Create a game with some of the board known, like
+    /// [`MonteCarloGame::with_board`], where the shuffling is seeded from
+    /// `rng`.
+    pub fn with_board_and_rng<R: Rng>(
+        hands: Vec<Hand>,
+        board: &[Card],
+        rng: &mut R,
+    ) -> Result<Self, RSPokerError> {
+        if board.len() > 5 {
+            return Err(RSPokerError::HoldemHandSize);
+        }
+        let hands = hands
+            .into_iter()
+            .map(|mut hand| {
+                for card in board {
+                    if !hand.insert(*card) {
+                        return Err(RSPokerError::DuplicateCardInHand(*card));
+                    }
+                }
+                Ok(hand)
+            })
+            .collect::<Result<Vec<Hand>, RSPokerError>>()?;
+        Self::new_with_rng(hands, rng)
+    }
+
+    /// Take cards that can't be dealt, for example ones that were seen
+    /// being folded or burned, out of the deck the runouts are dealt from.
+    ///
+    /// Returns an error if a dead card is in one of the hands, or if there
+    /// aren't enough cards left to finish the hand.
+    pub fn with_dead_cards(mut self, cards: &[Card]) -> Result<Self, RSPokerError> {
+        if let Some(card) = cards
+            .iter()
+            .find(|card| self.starting_hands.iter().any(|hand| hand.contains(card)))
+        {
+            return Err(RSPokerError::DuplicateCardInHand(*card));
+        }
+        let live: Vec<Card> = self.deck[..]
+            .iter()
+            .filter(|card| !cards.contains(card))
+            .copied()
+            .collect();
+        if live.len() < self.cards_per_runout() {
+            return Err(RSPokerError::NotEnoughCards);
+        }
+        self.deck = live.into();
+        // The deck order changed so start from a fresh shuffle.
+        self.current_offset = self.deck.len();
+        Ok(self)
+    }
+
+    /// How many cards each simulation deals, the rest of the board plus
+    /// any hole cards that hands are missing.
+    fn cards_per_runout(&self) -> usize {
+        let hole_cards: usize = self
+            .starting_hands
+            .iter()
+            .map(|hand| 7 - self.num_community_cards - hand.count())
+            .sum();
+        self.num_community_cards + hole_cards
+    }
+
+    /// Reveal another board card to every hand. The game keeps its deck and
+    /// rng so the next estimate only deals the rest of the runout, without
+    /// building a new game for each street.
+    ///
+    /// Returns an error if the card is already in a hand or dead, or if the
+    /// board is already complete.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs_poker::core::{Card, Hand};
+    /// use rs_poker::holdem::MonteCarloGame;
+    ///
+    /// let hands = vec![
+    ///     Hand::new_from_str("AsAd").unwrap(),
+    ///     Hand::new_from_str("KsKd").unwrap(),
+    /// ];
+    /// let mut game = MonteCarloGame::new(hands).unwrap();
+    /// for card in Hand::new_from_str("Kh7c2d9s3h").unwrap().iter() {
+    ///     game.add_board_card(card).unwrap();
+    ///     let equity = game.estimate_equity(1000);
+    ///     assert!((equity.iter().sum::<f32>() - 1.0).abs() < 1e-3);
+    /// }
+    /// // With the whole board out the set of kings always wins.
+    /// assert_eq!(vec![0.0, 1.0], game.estimate_equity(10));
+    /// ```
+    pub fn add_board_card(&mut self, card: Card) -> Result<(), RSPokerError> {
+        if self.num_community_cards == 0 {
+            return Err(RSPokerError::HoldemHandSize);
+        }
+        if !self.deck[..].contains(&card) {
+            return Err(RSPokerError::DuplicateCardInHand(card));
+        }
+
+        // Rebuild the deck in the order a new game would have it, so the
+        // runouts match a game created with the card already on the board.
+        let mut live = CardBitSet::new();
+        for c in self.deck[..].iter().filter(|c| **c != card) {
+            live.insert(*c);
+        }
+        self.deck = live.into();
+        for hand in &mut self.starting_hands {
+            hand.insert(card);
+        }
+        self.reset();
+        self.num_community_cards -= 1;
+        self.cards_needed -= self.hands.len();
+        // The dealt part of the deck may have had the card in it.
+        self.current_offset = self.deck.len();
+        Ok(())
+    }
+
     /// Simulate finishing a holdem game.
     ///
     /// This will fill out the board and then return the tuple
@@ -77,7 +319,7 @@ impl MonteCarloGame {
         }
 
         // Now get the best rank of all the possible hands.
-        self.hands.iter().map(|h| h.rank()).enumerate().fold(
+        self.hands.iter().map(rank_hand).enumerate().fold(
             (PlayerBitSet::default(), Rank::HighCard(0)),
             |(mut found, max_rank), (idx, rank)| {
                 match rank.cmp(&max_rank) {
@@ -113,8 +355,7 @@ impl MonteCarloGame {
     fn shuffle_if_needed(&mut self) {
         if self.current_offset + self.cards_needed >= self.deck.len() {
             self.current_offset = 0;
-            let mut rng = rng();
-            self.deck.shuffle(&mut rng);
+            self.deck.shuffle(&mut self.rng);
         }
     }
 
@@ -152,7 +393,35 @@ impl MonteCarloGame {
     /// assert!(equity[0] > equity[1]);
     /// ```
     pub fn estimate_equity(&mut self, iterations: usize) -> Vec<f32> {
-        let mut values = vec![0.0; self.hands.len()];
+        self.estimate_equity_detailed(iterations)
+            .iter()
+            .map(|r| r.equity() as f32)
+            .collect()
+    }
+
+    /// Simulate the game `iterations` times and count how often each hand
+    /// wins outright or ties for the best hand.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    /// use rs_poker::holdem::MonteCarloGame;
+    ///
+    /// // The board straight is the best hand for both players.
+    /// let hands = vec![
+    ///     Hand::new_from_str("2s3sTdJdQdKdAc").unwrap(),
+    ///     Hand::new_from_str("2h3hTdJdQdKdAc").unwrap(),
+    /// ];
+    /// let mut monte_sim = MonteCarloGame::new(hands).unwrap();
+    /// let results = monte_sim.estimate_equity_detailed(10);
+    ///
+    /// assert_eq!(10, results[0].ties);
+    /// assert_eq!(0, results[1].wins);
+    /// assert_eq!(0.5, results[1].equity());
+    /// ```
+    pub fn estimate_equity_detailed(&mut self, iterations: usize) -> Vec<EquityResult> {
+        let mut results = vec![EquityResult::default(); self.hands.len()];
         for _ in 0..iterations {
             let (winners, _) = self.simulate();
 
@@ -160,28 +429,213 @@ impl MonteCarloGame {
             self.reset();
             // each player gets the pot divided by the number of people with exactly the
             // same hand value. This is to make sure that ties are correctly valued.
-            let value = 1.0 / winners.count() as f32;
+            let winner_count = winners.count();
 
+            for result in results.iter_mut() {
+                result.total += 1;
+            }
             for idx in winners.ones() {
-                values[idx] += value;
+                if winner_count == 1 {
+                    results[idx].wins += 1;
+                } else {
+                    let result = &mut results[idx];
+                    result.ties += 1;
+                    result.tie_share += 1.0 / winner_count as f64;
+                    if result.split_ways.len() <= winner_count {
+                        result.split_ways.resize(winner_count + 1, 0);
+                    }
+                    result.split_ways[winner_count] += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Estimate equity like `estimate_equity` but split the simulations
+    /// across threads with rayon.
+    ///
+    /// The simulations are split into chunks of `PARALLEL_CHUNK_SIZE`. Each
+    /// chunk gets its own copy of the game, seeded with the next value from
+    /// this game's rng in chunk order, and the counts are added up in chunk
+    /// order. So a game made with `new_with_rng` gives the same results
+    /// every time, with any number of threads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    /// use rs_poker::holdem::MonteCarloGame;
+    ///
+    /// let hands = vec![
+    ///     Hand::new_from_str("AsAh").unwrap(),
+    ///     Hand::new_from_str("7c2d").unwrap(),
+    /// ];
+    /// let game = MonteCarloGame::new(hands).unwrap();
+    /// let equity = game.estimate_equity_parallel(10_000);
+    /// assert!(equity[0] > 0.8);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn estimate_equity_parallel(&self, iterations: usize) -> Vec<f32> {
+        use rayon::prelude::*;
+
+        let mut seeder = self.rng.clone();
+        let chunks: Vec<(u64, usize)> = (0..iterations.div_ceil(PARALLEL_CHUNK_SIZE))
+            .map(|i| {
+                let share = PARALLEL_CHUNK_SIZE.min(iterations - i * PARALLEL_CHUNK_SIZE);
+                (seeder.random(), share)
+            })
+            .collect();
+
+        let parts: Vec<Vec<EquityResult>> = chunks
+            .into_par_iter()
+            .map(|(seed, share)| {
+                let mut game = self.clone();
+                game.rng = StdRng::seed_from_u64(seed);
+                // Force a fresh shuffle with the new rng.
+                game.current_offset = game.deck.len();
+                game.estimate_equity_detailed(share)
+            })
+            .collect();
+        let mut merged = vec![EquityResult::default(); self.hands.len()];
+        for part in parts {
+            for (total, p) in merged.iter_mut().zip(part) {
+                total.add(&p);
             }
         }
+        merged.iter().map(|r| r.equity() as f32).collect()
+    }
+
+    /// Estimate how often the best hand at the end of a simulated game has a
+    /// rank that satisfies `predicate`.
+    ///
+    /// With a single hand in the game this is the probability of that hand
+    /// ending up with a matching rank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs_poker::core::{Hand, Rank};
+    /// use rs_poker::holdem::MonteCarloGame;
+    ///
+    /// let hero = Hand::new_from_str("AsKsQsJs2d").unwrap();
+    /// let mut monte_sim = MonteCarloGame::new(vec![hero]).unwrap();
+    ///
+    /// // Four spades with two cards to come get there about a third of the time.
+    /// let flush = monte_sim.estimate_rank_probability(1000, |r| {
+    ///     matches!(r, Rank::Flush(_) | Rank::StraightFlush(_))
+    /// });
+    /// assert!(flush > 0.25 && flush < 0.45);
+    /// ```
+    pub fn estimate_rank_probability<F: Fn(Rank) -> bool>(
+        &mut self,
+        iterations: usize,
+        predicate: F,
+    ) -> f64 {
+        let mut matched: usize = 0;
+        for _ in 0..iterations {
+            let (_, rank) = self.simulate();
+            self.reset();
+            if predicate(rank) {
+                matched += 1;
+            }
+        }
+        matched as f64 / iterations as f64
+    }
+
+    /// Estimate equity like `estimate_equity` but yield the running
+    /// estimate every `interval` simulations instead of only at the end.
+    ///
+    /// The last value yielded is the estimate after all `iterations`
+    /// simulations. This is useful for showing a converging number to users
+    /// while sampling is still happening.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rs_poker::core::Hand;
+    /// use rs_poker::holdem::MonteCarloGame;
+    ///
+    /// let hands = vec![
+    ///     Hand::new_from_str("AsAh").unwrap(),
+    ///     Hand::new_from_str("7c2d").unwrap(),
+    /// ];
+    /// let mut monte_sim = MonteCarloGame::new(hands).unwrap();
+    ///
+    /// let snapshots: Vec<Vec<f64>> = monte_sim.estimate_equity_stream(1000, 100).collect();
+    /// assert_eq!(10, snapshots.len());
+    /// assert!(snapshots[9][0] > snapshots[9][1]);
+    /// ```
+    pub fn estimate_equity_stream(
+        &mut self,
+        iterations: usize,
+        interval: usize,
+    ) -> impl Iterator<Item = Vec<f64>> + '_ {
+        EquityStream {
+            values: vec![0.0; self.hands.len()],
+            game: self,
+            completed: 0,
+            iterations,
+            interval: interval.max(1),
+        }
+    }
+}
+
+/// Iterator returned from `MonteCarloGame::estimate_equity_stream`.
+struct EquityStream<'a> {
+    game: &'a mut MonteCarloGame,
+    /// Total share of the pot won by each player so far.
+    values: Vec<f64>,
+    completed: usize,
+    iterations: usize,
+    interval: usize,
+}
+
+impl Iterator for EquityStream<'_> {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        if self.completed >= self.iterations {
+            return None;
+        }
+        let to_run = self.interval.min(self.iterations - self.completed);
+        for _ in 0..to_run {
+            let (winners, _) = self.game.simulate();
+            self.game.reset();
 
-        // Normalize later on in the hopes of not making
-        // each value actually zero
-        for v in values.iter_mut() {
-            *v /= iterations as f32;
+            let value = 1.0 / winners.count() as f64;
+            for idx in winners.ones() {
+                self.values[idx] += value;
+            }
         }
-        values
+        self.completed += to_run;
+
+        Some(
+            self.values
+                .iter()
+                .map(|v| v / self.completed as f64)
+                .collect(),
+        )
     }
 }
 
+#[cfg(not(feature = "lookup-table"))]
+fn rank_hand(hand: &Hand) -> Rank {
+    crate::core::Rankable::rank(hand)
+}
+
+/// With the lookup tables compiled in they are much faster than `rank`.
+#[cfg(feature = "lookup-table")]
+fn rank_hand(hand: &Hand) -> Rank {
+    crate::core::decode_fast_rank(hand.rank_fast())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::core::Card;
     use crate::core::Suit;
     use crate::core::Value;
+    use crate::core::cards;
 
     #[test]
     fn test_simulate_pocket_pair() {
@@ -226,6 +680,180 @@ mod test {
         assert!(result.1 >= Rank::ThreeOfAKind(0));
     }
 
+    #[test]
+    fn test_with_board_matches_inserted_board() {
+        let board: Vec<Card> = Hand::new_from_str("As3d4d").unwrap().iter().collect();
+        let hands: Vec<Hand> = ["AdAh", "2c2s"]
+            .iter()
+            .map(|s| Hand::new_from_str(s).unwrap())
+            .collect();
+        let g = MonteCarloGame::with_board(hands, &board).unwrap();
+        assert_eq!(2, g.num_community_cards);
+        assert!(g.starting_hands.iter().all(|h| h.count() == 5));
+        assert_eq!(52 - 7, g.deck.len());
+
+        let overlap = MonteCarloGame::with_board(vec![Hand::new_from_str("AsKs").unwrap()], &board);
+        assert!(matches!(
+            overlap,
+            Err(RSPokerError::DuplicateCardInHand(c)) if c == Card::try_from("As").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_add_board_card_matches_fresh_game() {
+        let hands: Vec<Hand> = ["AsAd", "KsKd", "8h9h"]
+            .iter()
+            .map(|s| Hand::new_from_str(s).unwrap())
+            .collect();
+        let board: Vec<Card> = Hand::new_from_str("Kh7h2d9s").unwrap().iter().collect();
+        let mut incremental =
+            MonteCarloGame::new_with_rng(hands.clone(), &mut StdRng::seed_from_u64(307)).unwrap();
+        for (dealt, card) in board.iter().enumerate() {
+            incremental.add_board_card(*card).unwrap();
+            let mut fresh = MonteCarloGame::with_board_and_rng(
+                hands.clone(),
+                &board[..=dealt],
+                &mut StdRng::seed_from_u64(307),
+            )
+            .unwrap();
+            // Start both from the same rng so they deal the same runouts.
+            fresh.rng = incremental.rng.clone();
+            assert_eq!(
+                fresh.estimate_equity(2_000),
+                incremental.estimate_equity(2_000)
+            );
+        }
+        assert_eq!(52 - 10, incremental.deck.len());
+
+        let mut done = MonteCarloGame::with_board(
+            hands,
+            &Hand::new_from_str("Kh7h2d9s3c")
+                .unwrap()
+                .iter()
+                .collect::<Vec<Card>>(),
+        )
+        .unwrap();
+        assert!(matches!(
+            done.add_board_card(Card::try_from("4c").unwrap()),
+            Err(RSPokerError::HoldemHandSize)
+        ));
+        assert!(matches!(
+            incremental.add_board_card(Card::try_from("As").unwrap()),
+            Err(RSPokerError::DuplicateCardInHand(_))
+        ));
+    }
+
+    #[test]
+    fn test_dead_cards_never_dealt() {
+        let hands = vec![
+            Hand::new_from_str("AsKs").unwrap(),
+            Hand::new_from_str("QdQh").unwrap(),
+        ];
+        let board: Vec<Card> = Hand::new_from_str("2s7s8d").unwrap().iter().collect();
+        // Most of the spades, as if they were folded by other players.
+        let dead: Vec<Card> = Hand::new_from_str("3s4s5s6s9sTsJsQs")
+            .unwrap()
+            .iter()
+            .collect();
+        let mut g = MonteCarloGame::with_board(hands, &board)
+            .unwrap()
+            .with_dead_cards(&dead)
+            .unwrap();
+        assert_eq!(52 - 7 - 8, g.deck.len());
+        for _ in 0..1_000 {
+            g.simulate();
+            for hand in &g.hands {
+                assert!(dead.iter().all(|c| !hand.contains(c)));
+            }
+            g.reset();
+        }
+    }
+
+    #[test]
+    fn test_bad_dead_cards() {
+        let hands = vec![
+            Hand::new_from_str("AsKs").unwrap(),
+            Hand::new_from_str("QdQh").unwrap(),
+        ];
+        let board = cards("2s7s8d");
+        let game = || MonteCarloGame::with_board(hands.clone(), &board).unwrap();
+        assert!(matches!(
+            game().with_dead_cards(&cards("3cKs")),
+            Err(RSPokerError::DuplicateCardInHand(c)) if c == Card::try_from("Ks").unwrap()
+        ));
+        assert!(matches!(
+            game().with_dead_cards(&cards("7s")),
+            Err(RSPokerError::DuplicateCardInHand(_))
+        ));
+
+        // Leave a single card for the turn and river.
+        let live: Vec<Card> = game().deck[..].to_vec();
+        assert!(matches!(
+            game().with_dead_cards(&live[1..]),
+            Err(RSPokerError::NotEnoughCards)
+        ));
+        assert!(game().with_dead_cards(&live[2..]).is_ok());
+    }
+
+    #[test]
+    fn test_with_board_and_rng_repeats() {
+        let hands = vec![
+            Hand::new_from_str("AsKs").unwrap(),
+            Hand::new_from_str("QdQh").unwrap(),
+        ];
+        let board = cards("2s7s8d");
+        let estimate = |seed| {
+            MonteCarloGame::with_board_and_rng(
+                hands.clone(),
+                &board,
+                &mut StdRng::seed_from_u64(seed),
+            )
+            .unwrap()
+            .estimate_equity(1_000)
+        };
+        assert_eq!(estimate(298), estimate(298));
+    }
+
+    #[test]
+    fn test_broadway_board_chops() {
+        let board: Vec<Card> = Hand::new_from_str("AsKdQhJcTd").unwrap().iter().collect();
+        let hands: Vec<Hand> = ["2c3c", "4h5h", "6s7d"]
+            .iter()
+            .map(|s| Hand::new_from_str(s).unwrap())
+            .collect();
+        let mut g = MonteCarloGame::with_board(hands, &board).unwrap();
+        let results = g.estimate_equity_detailed(100);
+
+        for result in &results {
+            assert_eq!(0, result.wins);
+            assert_eq!(vec![0, 0, 0, 100], result.split_ways);
+            assert_eq!((1, 3), result.pot_share());
+        }
+        let total: f64 = results.iter().map(|r| r.equity()).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pot_share_mixes_splits() {
+        let result = EquityResult {
+            wins: 1,
+            ties: 2,
+            total: 4,
+            tie_share: 0.5 + 1.0 / 3.0,
+            split_ways: vec![0, 0, 1, 1],
+        };
+        // (1 + 1/2 + 1/3) / 4 = 11/24
+        assert_eq!((11, 24), result.pot_share());
+        assert_eq!((0, 1), EquityResult::default().pot_share());
+
+        let scoop = EquityResult {
+            wins: 3,
+            total: 3,
+            ..Default::default()
+        };
+        assert_eq!((1, 1), scoop.pot_share());
+    }
+
     #[test]
     fn test_unseen_hole_cards() {
         let hands = vec![Hand::new_from_str("KsKd").unwrap(), Hand::default()];
@@ -325,6 +953,43 @@ mod test {
         assert!(equity[0] > equity[4]);
     }
 
+    #[test]
+    fn test_equity_stream_matches_estimate() {
+        // No cards left to come so every simulation is exactly the same.
+        let board = "2c7dTsJhKd";
+        let hands: Vec<Hand> = ["AsAh", "QcQs"]
+            .iter()
+            .map(|s| Hand::new_from_str(&format!("{s}{board}")).unwrap())
+            .collect();
+
+        let mut g = MonteCarloGame::new(hands.clone()).unwrap();
+        let snapshots: Vec<Vec<f64>> = g.estimate_equity_stream(250, 100).collect();
+        // 100, 200, then the final 50
+        assert_eq!(3, snapshots.len());
+
+        let mut g = MonteCarloGame::new(hands).unwrap();
+        let equity = g.estimate_equity(250);
+        let last = snapshots.last().unwrap();
+        for (streamed, estimated) in last.iter().zip(equity.iter()) {
+            assert_eq!(*streamed as f32, *estimated);
+        }
+    }
+
+    #[test]
+    fn test_equity_stream_converges() {
+        let hands: Vec<Hand> = ["AsAh", "KcKd"]
+            .iter()
+            .map(|s| Hand::new_from_str(s).unwrap())
+            .collect();
+        let mut g = MonteCarloGame::new(hands).unwrap();
+        let last = g.estimate_equity_stream(20_000, 1_000).last().unwrap();
+        let equity = g.estimate_equity(20_000);
+
+        // Aces are about an 82% favorite
+        assert!((last[0] - equity[0] as f64).abs() < 0.03);
+        assert!((last[0] + last[1] - 1.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_simulate_equity_lots_players() {
         let mut rng = rng();
@@ -344,6 +1009,84 @@ mod test {
             }
         }
     }
+    #[test]
+    fn test_seeded_games_are_stable() {
+        let hands = vec![
+            Hand::new_from_str("AsKs").unwrap(),
+            Hand::new_from_str("QdQh").unwrap(),
+            Hand::new_from_str("7c6c").unwrap(),
+        ];
+        let run = |seed: u64| {
+            let mut g =
+                MonteCarloGame::new_with_rng(hands.clone(), &mut StdRng::seed_from_u64(seed))
+                    .unwrap();
+            g.estimate_equity(500)
+        };
+
+        assert_eq!(run(1), run(1));
+        assert_eq!(run(2), run(2));
+        assert_ne!(run(1), run(2));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_equity() {
+        let hands = vec![
+            Hand::new_from_str("AsKs").unwrap(),
+            Hand::new_from_str("QdQh").unwrap(),
+        ];
+        let game = MonteCarloGame::new_with_rng(hands, &mut StdRng::seed_from_u64(7)).unwrap();
+        let equity = game.estimate_equity_parallel(20_000);
+        // Seeded games give the same answer on every call.
+        assert_eq!(equity, game.estimate_equity_parallel(20_000));
+        assert!((equity[0] + equity[1] - 1.0).abs() < 1e-4);
+        assert!((equity[1] - 0.54).abs() < 0.03, "{equity:?}");
+
+        // The thread count doesn't change the answer.
+        for threads in [1, 3] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            assert_eq!(
+                equity,
+                pool.install(|| game.estimate_equity_parallel(20_000))
+            );
+        }
+    }
+
+    #[test]
+    fn test_detailed_three_way_tie() {
+        // Royal flush on the board, everyone chops.
+        let hands = vec![
+            Hand::new_from_str("2c3dAsKsQsJsTs").unwrap(),
+            Hand::new_from_str("4c5dAsKsQsJsTs").unwrap(),
+            Hand::new_from_str("6c7dAsKsQsJsTs").unwrap(),
+        ];
+        let mut g = MonteCarloGame::new(hands).unwrap();
+        let results = g.estimate_equity_detailed(30);
+        for result in &results {
+            assert_eq!(0, result.wins);
+            assert_eq!(30, result.ties);
+            assert_eq!(30, result.total);
+            assert!((result.equity() - 1.0 / 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_detailed_matches_equity() {
+        let hands = vec![
+            Hand::new_from_str("AdAh").unwrap(),
+            Hand::new_from_str("7c2s").unwrap(),
+        ];
+        let mut g = MonteCarloGame::new(hands).unwrap();
+        let results = g.estimate_equity_detailed(2_000);
+        assert_eq!(results[0].ties, results[1].ties);
+        assert_eq!(2_000, results[0].wins + results[1].wins + results[0].ties);
+        assert!((results[0].equity() + results[1].equity() - 1.0).abs() < 1e-9);
+        assert!(results[0].equity() > 0.8);
+    }
+
     #[test]
     fn test_simulate_equity_cleaned_hands() {
         let mut rng = rng();
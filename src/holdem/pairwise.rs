@@ -0,0 +1,281 @@
+use crate::core::{Card, CardBitSet, CardIter, Hand, Rank, Rankable};
+
+use super::board_completions;
+
+/// How one starting hand does against another over every runout.
+///
+/// All counts are from the point of view of the first hand. On a complete
+/// board exactly one of them is one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct MatchupResult {
+    pub wins: u32,
+    pub ties: u32,
+    pub losses: u32,
+}
+
+impl MatchupResult {
+    /// The number of runouts that were evaluated.
+    pub fn total(&self) -> u32 {
+        self.wins + self.ties + self.losses
+    }
+
+    /// Share of the pot won, with ties split evenly.
+    pub fn equity(&self) -> f64 {
+        (f64::from(self.wins) + f64::from(self.ties) / 2.0) / f64::from(self.total())
+    }
+
+    /// The same result from the other hand's point of view.
+    pub fn flipped(&self) -> Self {
+        MatchupResult {
+            wins: self.losses,
+            ties: self.ties,
+            losses: self.wins,
+        }
+    }
+}
+
+/// The exact result of every starting hand against every other
+/// non-conflicting starting hand on a board.
+#[derive(Debug, Clone)]
+pub struct PairwiseBoardResults {
+    combos: Vec<Hand>,
+    /// Upper triangle of the matrix, row major, without the diagonal.
+    results: Vec<MatchupResult>,
+}
+
+impl PairwiseBoardResults {
+    /// The starting hands in the order used for indexing.
+    pub fn combos(&self) -> &[Hand] {
+        &self.combos
+    }
+
+    /// The number of starting hands. The matrix is `len() x len()`.
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Is there no starting hand at all.
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Result of the starting hand at index `i` against the one at `j`.
+    ///
+    /// Returns `None` if the two hands share a card or are the same.
+    pub fn get(&self, i: usize, j: usize) -> Option<MatchupResult> {
+        let n = self.combos.len();
+        if i == j || i >= n || j >= n {
+            return None;
+        }
+        let result = if i < j {
+            self.results[triangle_idx(n, i, j)]
+        } else {
+            self.results[triangle_idx(n, j, i)].flipped()
+        };
+        if result.total() == 0 {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Result of `hero` against `villain`, looked up by their hole cards.
+    pub fn result(&self, hero: &Hand, villain: &Hand) -> Option<MatchupResult> {
+        let i = self.combos.iter().position(|c| c == hero)?;
+        let j = self.combos.iter().position(|c| c == villain)?;
+        self.get(i, j)
+    }
+}
+
+fn triangle_idx(n: usize, i: usize, j: usize) -> usize {
+    // Rows before i contribute (n - 1) + (n - 2) + ... + (n - i) entries.
+    i * (2 * n - i - 1) / 2 + (j - i - 1)
+}
+
+/// Compute the exact win, tie, and loss counts for every pair of starting
+/// hands on a board.
+///
+/// A complete board is a single river evaluation per hand. With cards still
+/// to come every runout is enumerated, which is much more expensive: a turn
+/// is quick, a flop takes a while in release builds.
+///
+/// # Panics
+///
+/// Panics if the board has more than 5 cards.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand};
+/// use rs_poker::holdem::pairwise_board_results;
+///
+/// let board: Vec<Card> = Hand::new_from_str("AhKd7c5s2h").unwrap().iter().collect();
+/// let results = pairwise_board_results(&board);
+///
+/// // 47 cards left gives 1081 starting hands
+/// assert_eq!(1081, results.len());
+///
+/// let aces = Hand::new_from_str("AsAd").unwrap();
+/// let kings = Hand::new_from_str("KsKc").unwrap();
+/// assert_eq!(1, results.result(&aces, &kings).unwrap().wins);
+/// ```
+pub fn pairwise_board_results(board: &[Card]) -> PairwiseBoardResults {
+    assert!(board.len() <= 5, "A board has at most 5 cards");
+    let mut remaining = !CardBitSet::new();
+    for card in board {
+        remaining.remove(*card);
+    }
+    let remaining_cards: Vec<Card> = remaining.into_iter().collect();
+    let combos: Vec<Hand> = CardIter::new(&remaining_cards, 2)
+        .map(Hand::new_with_cards)
+        .collect();
+    let n = combos.len();
+    let mut results = vec![MatchupResult::default(); n * n.saturating_sub(1) / 2];
+
+    let full_board = Hand::new_with_cards(board.to_vec());
+    if board.len() == 5 {
+        // Nothing to come, every starting hand is ranked once.
+        let ranks: Vec<Option<Rank>> = combos
+            .iter()
+            .map(|combo| {
+                let mut hand = full_board;
+                hand.extend(combo.iter());
+                Some(hand.rank())
+            })
+            .collect();
+        tally(&combos, &ranks, &mut results);
+    } else {
+        for completion in board_completions(board, 5 - board.len()) {
+            let runout = Hand::new_with_cards(completion);
+            let mut runout_board = full_board;
+            runout_board.extend(runout.iter());
+
+            // Rank every starting hand that's possible with this runout.
+            let ranks: Vec<Option<Rank>> = combos
+                .iter()
+                .map(|combo| {
+                    if (*combo & runout).is_empty() {
+                        let mut hand = runout_board;
+                        hand.extend(combo.iter());
+                        Some(hand.rank())
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            tally(&combos, &ranks, &mut results);
+        }
+    }
+
+    PairwiseBoardResults { combos, results }
+}
+
+/// Add one board's result for every pair of starting hands that could both
+/// be dealt. `ranks` is `None` for a hand that uses one of the board cards.
+fn tally(combos: &[Hand], ranks: &[Option<Rank>], results: &mut [MatchupResult]) {
+    let n = combos.len();
+    let mut idx = 0;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if let (Some(a), Some(b)) = (ranks[i], ranks[j])
+                && (combos[i] & combos[j]).is_empty()
+            {
+                let result = &mut results[idx];
+                match a.cmp(&b) {
+                    std::cmp::Ordering::Greater => result.wins += 1,
+                    std::cmp::Ordering::Equal => result.ties += 1,
+                    std::cmp::Ordering::Less => result.losses += 1,
+                }
+            }
+            idx += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cards;
+
+    #[test]
+    fn test_complete_board_known_matchup() {
+        let results = pairwise_board_results(&cards("AhKd7c5s2h"));
+        assert_eq!(1081, results.len());
+
+        let aces = Hand::new_from_str("AsAd").unwrap();
+        let kings = Hand::new_from_str("KsKc").unwrap();
+        let expected = MatchupResult {
+            wins: 1,
+            ties: 0,
+            losses: 0,
+        };
+        assert_eq!(Some(expected), results.result(&aces, &kings));
+        assert_eq!(Some(expected.flipped()), results.result(&kings, &aces));
+
+        // A wheel beats a set
+        let wheel = Hand::new_from_str("3c4d").unwrap();
+        let set = Hand::new_from_str("7d7h").unwrap();
+        assert_eq!(1, results.result(&wheel, &set).unwrap().wins);
+    }
+
+    #[test]
+    fn test_complete_board_chop() {
+        let results = pairwise_board_results(&cards("TsJsQdKhAc"));
+        let a = Hand::new_from_str("2c3c").unwrap();
+        let b = Hand::new_from_str("4d5d").unwrap();
+        let result = results.result(&a, &b).unwrap();
+        assert_eq!(1, result.ties);
+        assert_eq!(0.5, result.equity());
+    }
+
+    #[test]
+    fn test_conflicting_hands() {
+        let results = pairwise_board_results(&cards("AhKd7c5s2h"));
+        let a = Hand::new_from_str("AsAd").unwrap();
+        let b = Hand::new_from_str("AsKs").unwrap();
+        assert_eq!(None, results.result(&a, &b));
+        assert_eq!(None, results.get(0, 0));
+    }
+
+    #[test]
+    fn test_turn_board_enumerates_rivers() {
+        let results = pairwise_board_results(&cards("AhKd7c5s"));
+        // 48 cards left gives 1128 starting hands.
+        assert_eq!(1128, results.len());
+
+        // Two disjoint hands leave 44 rivers.
+        let aces = Hand::new_from_str("AsAd").unwrap();
+        let kings = Hand::new_from_str("KsKc").unwrap();
+        let result = results.result(&aces, &kings).unwrap();
+        assert_eq!(44, result.total());
+        // Only the last king saves the kings.
+        assert_eq!(
+            MatchupResult {
+                wins: 43,
+                ties: 0,
+                losses: 1,
+            },
+            result
+        );
+
+        // A gutshot to the wheel against top pair.
+        let gutshot = Hand::new_from_str("3c4d").unwrap();
+        let top_pair = Hand::new_from_str("AcQc").unwrap();
+        let result = results.result(&gutshot, &top_pair).unwrap();
+        assert_eq!(44, result.total());
+        // The four deuces and the four sixes make a straight.
+        assert_eq!(8, result.wins);
+    }
+
+    #[test]
+    fn test_triangle_idx() {
+        let n = 5;
+        let mut expected = 0;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                assert_eq!(expected, triangle_idx(n, i, j));
+                expected += 1;
+            }
+        }
+    }
+}
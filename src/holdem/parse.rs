@@ -455,12 +455,13 @@ impl RangeParser {
                         }
                     }
                     Modifier::Range => {
+                        let pos = r_str.chars().count() - iter.clone().count();
                         let fr_char = iter.next().ok_or(RSPokerError::TooFewChars)?;
                         let sr_char = iter.next().ok_or(RSPokerError::TooFewChars)?;
-                        first_range.end =
-                            Value::from_char(fr_char).ok_or(RSPokerError::UnexpectedValueChar)?;
-                        second_range.end =
-                            Value::from_char(sr_char).ok_or(RSPokerError::UnexpectedValueChar)?;
+                        first_range.end = Value::from_char(fr_char)
+                            .ok_or(RSPokerError::UnknownValueChar(fr_char, pos))?;
+                        second_range.end = Value::from_char(sr_char)
+                            .ok_or(RSPokerError::UnknownValueChar(sr_char, pos + 1))?;
 
                         let first_gap = first_range.start.gap(second_range.start);
                         let second_gap = first_range.end.gap(second_range.end);
@@ -0,0 +1,82 @@
+use rand::Rng;
+
+use crate::core::{Card, Hand, Suit, Value};
+
+use super::MonteCarloGame;
+
+/// The value in each row and column of the matrix, aces first.
+fn value_at(idx: usize) -> Value {
+    Value::from(12 - idx as u8)
+}
+
+/// The equity of every starting hand against one random hand, laid out
+/// the way preflop charts are.
+///
+/// Rows and columns go from aces at `0` down to twos at `12`. Pairs are on
+/// the diagonal. Above the diagonal, `matrix[row][col]` with `row < col`,
+/// is the suited hand of the row and column values, and below it is the
+/// offsuit hand. So `matrix[0][1]` is AKs and `matrix[1][0]` is AKo.
+///
+/// Each entry comes from `sims` games of a `MonteCarloGame` with the
+/// other player's cards unknown, so the matrix takes `169 * sims` games.
+/// The games are all seeded from `rng`.
+///
+/// # Examples
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use rs_poker::holdem::preflop_equity_matrix;
+///
+/// let matrix = preflop_equity_matrix(500, &mut StdRng::seed_from_u64(315));
+/// // Aces are about 85% against a random hand.
+/// assert!(matrix[0][0] > 0.8);
+/// // Ace king suited is about 67%.
+/// assert!(matrix[0][1] > 0.6);
+/// ```
+pub fn preflop_equity_matrix(sims: usize, rng: &mut impl Rng) -> [[f64; 13]; 13] {
+    let mut matrix = [[0.0; 13]; 13];
+    for (row, entries) in matrix.iter_mut().enumerate() {
+        for (col, entry) in entries.iter_mut().enumerate() {
+            let (high, low) = (value_at(row.min(col)), value_at(row.max(col)));
+            let second_suit = if row < col { Suit::Spade } else { Suit::Heart };
+            let hero = Hand::new_with_cards(vec![
+                Card::new(high, Suit::Spade),
+                Card::new(low, second_suit),
+            ]);
+            let mut game = MonteCarloGame::new_with_rng(vec![hero, Hand::new()], rng)
+                .expect("Two hole cards against none is a valid game");
+            *entry = f64::from(game.estimate_equity(sims)[0]);
+        }
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn test_aces_best_and_seven_deuce_near_worst() {
+        let matrix = preflop_equity_matrix(2_000, &mut StdRng::seed_from_u64(315));
+        let mut all: Vec<f64> = matrix.iter().flatten().copied().collect();
+        all.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+        assert_eq!(all[0], matrix[0][0]);
+        // 72o is the deuce row and the seven column. Only a handful of
+        // other weak offsuit hands like 32o are worse.
+        let seven_deuce = matrix[12][7];
+        let worse = all.iter().filter(|e| **e < seven_deuce).count();
+        assert!(worse < 15, "{worse} hands below 72o");
+
+        // Suited hands are worth a few percent more than offsuit ones.
+        let mut advantage = 0.0;
+        for (row, entries) in matrix.iter().enumerate() {
+            for (col, suited) in entries.iter().enumerate().skip(row + 1) {
+                advantage += suited - matrix[col][row];
+            }
+        }
+        assert!(advantage / 78.0 > 0.01);
+    }
+}
@@ -0,0 +1,168 @@
+use std::sync::OnceLock;
+
+use crate::core::{Hand, Value};
+
+/// `PERCENTILES[high][low]` for the 169 starting hands. Suited hands are
+/// stored with the higher value first, offsuit hands and pairs with the
+/// lower value first.
+static PERCENTILES: OnceLock<[[f64; 13]; 13]> = OnceLock::new();
+
+/// Bill Chen's score for a starting hand. Higher is better, from 20 for
+/// aces down to -1 for the worst offsuit hands.
+fn chen_score(high: Value, low: Value, suited: bool) -> f64 {
+    let high_points = |value: Value| match value {
+        Value::Ace => 10.0,
+        Value::King => 8.0,
+        Value::Queen => 7.0,
+        Value::Jack => 6.0,
+        // Two is 1 point, up to ten at 5 points.
+        _ => (value as u8 + 2) as f64 / 2.0,
+    };
+
+    if high == low {
+        return (high_points(high) * 2.0).max(5.0);
+    }
+
+    let mut score = high_points(high);
+    if suited {
+        score += 2.0;
+    }
+    // The number of values missing between the two cards.
+    let gap = high.gap(low) - 1;
+    score -= match gap {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 2.0,
+        3 => 4.0,
+        _ => 5.0,
+    };
+    if gap <= 1 && high < Value::Queen {
+        score += 1.0;
+    }
+    score
+}
+
+fn build_percentiles() -> [[f64; 13]; 13] {
+    // Every starting hand with its score and how many combos of it there
+    // are.
+    let mut hands = vec![];
+    for high in Value::values() {
+        for low in Value::values() {
+            if low > high {
+                continue;
+            }
+            if low == high {
+                hands.push((high, low, false, chen_score(high, low, false), 6.0));
+            } else {
+                hands.push((high, low, true, chen_score(high, low, true), 4.0));
+                hands.push((high, low, false, chen_score(high, low, false), 12.0));
+            }
+        }
+    }
+
+    let total: f64 = hands.iter().map(|h| h.4).sum();
+    let mut percentiles = [[0.0; 13]; 13];
+    for &(high, low, suited, score, _) in &hands {
+        // Hands that score the same as this one count as half beaten, so
+        // the best hand is just below 1.0 and the worst just above 0.0.
+        let (below, equal) = hands.iter().fold((0.0, 0.0), |(below, equal), other| {
+            if other.3 < score {
+                (below + other.4, equal)
+            } else if other.3 == score {
+                (below, equal + other.4)
+            } else {
+                (below, equal)
+            }
+        });
+        let percentile = (below + equal / 2.0) / total;
+        let (row, col) = if suited { (high, low) } else { (low, high) };
+        percentiles[row as usize][col as usize] = percentile;
+    }
+    percentiles
+}
+
+/// How strong a two card starting hand is before the flop, from 0.0 for
+/// the worst hand to 1.0 for the best.
+///
+/// Hands are scored with the Chen formula, and the result is the share of
+/// the 1326 possible starting hands that score lower, with hands that tie
+/// counted as half. The 169 distinct starting hands are scored once and
+/// looked up after that, so this is cheap enough to call on every
+/// decision.
+///
+/// # Panics
+///
+/// Panics if the hand doesn't hold exactly two cards.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::Hand;
+/// use rs_poker::holdem::preflop_strength;
+///
+/// let aces = preflop_strength(&Hand::new_from_str("AsAd").unwrap());
+/// let suited_connectors = preflop_strength(&Hand::new_from_str("9s8s").unwrap());
+/// assert!(aces > suited_connectors);
+/// assert!(aces > 0.99);
+/// ```
+pub fn preflop_strength(hand: &Hand) -> f64 {
+    assert_eq!(2, hand.count(), "Preflop strength needs exactly two cards");
+    let mut cards = hand.iter();
+    let first = cards.next().unwrap();
+    let second = cards.next().unwrap();
+    let high = first.value.max(second.value);
+    let low = first.value.min(second.value);
+    let (row, col) = if first.suit == second.suit {
+        (high, low)
+    } else {
+        (low, high)
+    };
+    PERCENTILES.get_or_init(build_percentiles)[row as usize][col as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strength(s: &str) -> f64 {
+        preflop_strength(&Hand::new_from_str(s).unwrap())
+    }
+
+    #[test]
+    fn test_extremes() {
+        assert!(strength("AhAc") > 0.99);
+        assert!(strength("7h2c") < 0.1);
+        assert!(strength("AhAc") > strength("KhKc"));
+        assert!(strength("KhKc") > strength("AhKh"));
+    }
+
+    #[test]
+    fn test_suited_above_offsuit() {
+        for (suited, offsuit) in [("9s8s", "9s8h"), ("JdTd", "JdTc"), ("5c4c", "5c4h")] {
+            assert!(strength(suited) > strength(offsuit), "{suited} {offsuit}");
+        }
+        // Card order doesn't matter.
+        assert_eq!(strength("8s9s"), strength("9s8s"));
+    }
+
+    #[test]
+    fn test_chen_scores() {
+        assert_eq!(20.0, chen_score(Value::Ace, Value::Ace, false));
+        assert_eq!(5.0, chen_score(Value::Two, Value::Two, false));
+        assert_eq!(12.0, chen_score(Value::Ace, Value::King, true));
+        // Ten nine suited, 5 for the ten plus suited plus the connector
+        // bonus.
+        assert_eq!(8.0, chen_score(Value::Ten, Value::Nine, true));
+        assert_eq!(-1.5, chen_score(Value::Seven, Value::Two, false));
+    }
+
+    #[test]
+    fn test_every_hand_in_range() {
+        let percentiles = PERCENTILES.get_or_init(build_percentiles);
+        for row in percentiles {
+            for p in row {
+                assert!(*p > 0.0 && *p < 1.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Heads up push or fold ranges for short stacks.
+//!
+//! With a short enough stack the small blind's only sensible plays are to
+//! go all in or fold, and the big blind can only call or fold. The
+//! equilibrium of that game is the pair of ranges where neither player can
+//! do better by changing theirs.
+use std::sync::OnceLock;
+
+use rand::{SeedableRng, rngs::StdRng};
+
+use crate::core::{Card, Hand};
+
+use super::{MonteCarloGame, Range, StartingHand};
+
+/// Games simulated for each pair of starting hands.
+const MATCHUP_SIMS: usize = 100;
+/// Rounds the ranges must stay the same for to count as settled.
+const SETTLED_ROUNDS: usize = 50;
+/// The most rounds of best responses before giving up on the ranges
+/// settling.
+const MAX_ROUNDS: usize = 2_000;
+
+/// Everything about the 169 starting hands that the solver needs.
+struct Matchups {
+    /// Every combo of each starting hand.
+    combos: Vec<Vec<(Card, Card)>>,
+    /// `equity[i][j]` is the equity of hand `i` against hand `j`.
+    equity: Vec<Vec<f64>>,
+    /// `weights[i][j]` is how many combos of hand `j` are possible when
+    /// holding a combo of hand `i`.
+    weights: Vec<Vec<f64>>,
+}
+
+static MATCHUPS: OnceLock<Matchups> = OnceLock::new();
+
+impl Matchups {
+    fn build() -> Self {
+        let combos: Vec<Vec<(Card, Card)>> = StartingHand::all()
+            .iter()
+            .map(|hand| {
+                hand.possible_hands()
+                    .iter()
+                    .map(|cards| (cards[0], cards[1]))
+                    .collect()
+            })
+            .collect();
+        let disjoint = |a: &(Card, Card), b: &(Card, Card)| {
+            a.0 != b.0 && a.0 != b.1 && a.1 != b.0 && a.1 != b.1
+        };
+
+        let n = combos.len();
+        let mut equity = vec![vec![0.5; n]; n];
+        let mut weights = vec![vec![0.0; n]; n];
+        // Seeded so the ranges are the same every time.
+        let mut rng = StdRng::seed_from_u64(n as u64);
+        for i in 0..n {
+            let hero = combos[i][0];
+            for j in 0..n {
+                weights[i][j] = combos[j].iter().filter(|c| disjoint(&hero, c)).count() as f64;
+                if j < i {
+                    continue;
+                }
+                // Every starting hand has at least one combo that doesn't
+                // share a card with any single combo.
+                let villain = *combos[j].iter().find(|c| disjoint(&hero, c)).unwrap();
+                let hands = vec![
+                    Hand::new_with_cards(vec![hero.0, hero.1]),
+                    Hand::new_with_cards(vec![villain.0, villain.1]),
+                ];
+                let mut game = MonteCarloGame::new_with_rng(hands, &mut rng)
+                    .expect("Two disjoint starting hands are a valid game");
+                let results = game.estimate_equity_detailed(MATCHUP_SIMS);
+                equity[i][j] = results[0].equity();
+                equity[j][i] = results[1].equity();
+            }
+        }
+        Self {
+            combos,
+            equity,
+            weights,
+        }
+    }
+
+    /// The average equity of hand `hero` against the hands the other
+    /// player plays, where `freqs` is how often they play each one, and
+    /// each is counted by how many of its combos are possible. `None` if
+    /// the other player never plays anything possible.
+    fn equity_against(&self, hero: usize, freqs: &[f64]) -> Option<f64> {
+        let (equity, weight) =
+            freqs
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(equity, weight), (villain, freq)| {
+                    let w = freq * self.weights[hero][villain];
+                    (equity + w * self.equity[hero][villain], weight + w)
+                });
+        (weight > 0.0).then(|| equity / weight)
+    }
+
+    /// How likely the other player is to play their hand when holding
+    /// `hero`.
+    fn share_in(&self, hero: usize, freqs: &[f64]) -> f64 {
+        let total: f64 = self.weights[hero].iter().sum();
+        let played: f64 = freqs
+            .iter()
+            .zip(&self.weights[hero])
+            .map(|(freq, w)| freq * w)
+            .sum();
+        played / total
+    }
+
+    /// The hands played at least half the time.
+    fn to_range(&self, freqs: &[f64]) -> Range {
+        let mut range = Range::new();
+        for (combos, _) in self.combos.iter().zip(freqs).filter(|(_, f)| **f >= 0.5) {
+            for (first, second) in combos {
+                range.insert(*first, *second);
+            }
+        }
+        range
+    }
+}
+
+/// The small blind's best shoving range against the big blind calling
+/// with the frequencies in `calls`, for stacks of `stack` big blinds.
+fn best_shoves(matchups: &Matchups, calls: &[f64], stack: f64) -> Vec<bool> {
+    (0..calls.len())
+        .map(|hero| {
+            let called = matchups.share_in(hero, calls);
+            let equity = matchups.equity_against(hero, calls).unwrap_or(0.0);
+            // Folding loses the small blind. Shoving wins the big blind
+            // when it's folded, and the pot share less the stack when
+            // called.
+            let shove = (1.0 - called) + called * (equity * 2.0 * stack - stack);
+            shove > -0.5
+        })
+        .collect()
+}
+
+/// The big blind's best calling range against the frequencies in
+/// `shoves`.
+fn best_calls(matchups: &Matchups, shoves: &[f64], stack: f64) -> Vec<bool> {
+    (0..shoves.len())
+        .map(|hero| match matchups.equity_against(hero, shoves) {
+            // Folding loses the big blind already posted.
+            Some(equity) => equity * 2.0 * stack - stack > -1.0,
+            None => false,
+        })
+        .collect()
+}
+
+/// Add the `round`th response into the running average `freqs`.
+fn average_in(freqs: &mut [f64], response: &[bool], round: usize) {
+    let step = 1.0 / round as f64;
+    for (freq, play) in freqs.iter_mut().zip(response) {
+        *freq += step * (f64::from(u8::from(*play)) - *freq);
+    }
+}
+
+fn played(freqs: &[f64]) -> Vec<bool> {
+    freqs.iter().map(|f| *f >= 0.5).collect()
+}
+
+/// The heads up push or fold equilibrium for an effective stack of
+/// `eff_bb` big blinds, as the small blind's shoving range and the big
+/// blind's calling range. The blinds are a half and one big blind with no
+/// ante.
+///
+/// This is an approximate solver. The equity of each pair of starting hands
+/// is simulated once, with one combo of each, and card removal is counted
+/// when weighing the hands in a range. Then it plays fictitious play:
+/// every round each player finds their best response to how often the
+/// other has played each hand so far, and adds it into their own running
+/// average. The ranges returned are the hands played at least half the
+/// time. That stops once the ranges have stayed the same for 50 rounds in
+/// a row, or after 2,000 rounds if a few marginal hands keep going back
+/// and forth. Either way the hands on the edge of each range are close to
+/// break even.
+///
+/// The matchup equities are simulated the first time this is called, which
+/// takes a moment, and are reused after that.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::holdem::pushfold::nash_ranges;
+///
+/// let (shoves, calls) = nash_ranges(5.0);
+/// // Short stacks shove very wide, and call tighter than they shove.
+/// assert!(shoves.len() > calls.len());
+/// assert!(shoves.len() > 1326 / 2);
+/// ```
+pub fn nash_ranges(eff_bb: f64) -> (Range, Range) {
+    let matchups = MATCHUPS.get_or_init(Matchups::build);
+    // Start from the small blind shoving everything.
+    let mut shoves = vec![1.0; matchups.combos.len()];
+    let mut calls = vec![0.0; matchups.combos.len()];
+    let mut ranges = (vec![], vec![]);
+    let mut unchanged = 0;
+    for round in 1..=MAX_ROUNDS {
+        let next_calls = best_calls(matchups, &shoves, eff_bb);
+        average_in(&mut calls, &next_calls, round);
+        let next_shoves = best_shoves(matchups, &calls, eff_bb);
+        average_in(&mut shoves, &next_shoves, round + 1);
+
+        let next_ranges = (played(&shoves), played(&calls));
+        unchanged = if next_ranges == ranges {
+            unchanged + 1
+        } else {
+            0
+        };
+        ranges = next_ranges;
+        if unchanged == SETTLED_ROUNDS {
+            break;
+        }
+    }
+    (matchups.to_range(&shoves), matchups.to_range(&calls))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has(range: &Range, hand: &str) -> bool {
+        let cards: Vec<Card> = Hand::new_from_str(hand).unwrap().iter().collect();
+        range.contains(cards[0], cards[1])
+    }
+
+    #[test]
+    fn test_ten_big_blinds() {
+        let (shoves, calls) = nash_ranges(10.0);
+
+        // Tighter than any two, wider than premiums.
+        assert!(shoves.len() < 1326);
+        assert!(shoves.len() > 1326 / 3, "{} combos shoved", shoves.len());
+        for hand in ["AsAd", "AsKd", "7s7d", "As2d", "Ks9d", "Js8s"] {
+            assert!(has(&shoves, hand), "{hand} should shove");
+        }
+        assert!(!has(&shoves, "7s2d"));
+
+        assert!(calls.len() < shoves.len());
+        assert!(has(&calls, "AsAd"));
+        assert!(has(&calls, "As9d"));
+        assert!(!has(&calls, "9s4d"));
+    }
+
+    #[test]
+    fn test_deeper_is_tighter() {
+        let (shallow_shoves, shallow_calls) = nash_ranges(4.0);
+        let (deep_shoves, deep_calls) = nash_ranges(15.0);
+        assert!(deep_shoves.len() < shallow_shoves.len());
+        assert!(deep_calls.len() < shallow_calls.len());
+    }
+}
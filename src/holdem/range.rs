@@ -0,0 +1,262 @@
+use std::collections::BTreeSet;
+
+use crate::core::{Card, FlatHand, Hand, RSPokerError, Value};
+
+use super::{RangeParser, StartingHand, Suitedness, preflop_strength};
+
+/// A set of two card starting hands, like the range a player could be
+/// holding.
+///
+/// Every combo is stored once with the higher card first, so overlapping
+/// parts of a range don't count twice.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Range {
+    combos: BTreeSet<(Card, Card)>,
+}
+
+impl Range {
+    /// Create an empty range.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a range written in the common comma separated notation.
+    ///
+    /// Each part can be anything that `RangeParser::parse_one` accepts,
+    /// like `QQ+`, `ATs+`, `KJo`, or `87s-54s`. An `X` as the second card
+    /// stands for any other value, so `AXs` is every suited ace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::holdem::Range;
+    ///
+    /// let range = Range::parse("AKs, QQ+, 87s-54s").unwrap();
+    /// // 4 AKs, 3 * 6 pairs and 4 * 4 suited connectors.
+    /// assert_eq!(38, range.len());
+    /// assert_eq!(8, range.hand_classes().len());
+    /// ```
+    pub fn parse(range_str: &str) -> Result<Self, RSPokerError> {
+        let mut range = Self::new();
+        for part in range_str.split(',').map(str::trim) {
+            if part.is_empty() {
+                return Err(RSPokerError::TooFewChars);
+            }
+            for hand in Self::parse_part(part)? {
+                range.insert(hand[0], hand[1]);
+            }
+        }
+        Ok(range)
+    }
+
+    /// The strongest `p` fraction of the 169 starting hand classes, by
+    /// `preflop_strength`, with every combo of each.
+    ///
+    /// The number of classes is rounded to the nearest whole class, and
+    /// `p` is clamped to between 0 and 1. Classes that are equally strong
+    /// are taken in the order of `StartingHand::all`, so a larger `p`
+    /// always contains every hand of a smaller one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::holdem::Range;
+    ///
+    /// let range = Range::top_percent(0.1);
+    /// assert_eq!(17, range.hand_classes().len());
+    /// assert_eq!(1326, Range::top_percent(1.0).len());
+    /// ```
+    pub fn top_percent(p: f64) -> Self {
+        let mut classes: Vec<(f64, StartingHand)> = StartingHand::all()
+            .into_iter()
+            .map(|class| {
+                let hand = &class.possible_hands()[0];
+                (
+                    preflop_strength(&Hand::new_with_cards(hand.iter().copied().collect())),
+                    class,
+                )
+            })
+            .collect();
+        // The sort is stable, so ties keep a fixed order.
+        classes.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let count = (p.clamp(0.0, 1.0) * classes.len() as f64).round() as usize;
+        let mut range = Self::new();
+        for (_, class) in classes.into_iter().take(count) {
+            for hand in class.possible_hands() {
+                range.insert(hand[0], hand[1]);
+            }
+        }
+        range
+    }
+
+    fn parse_part(part: &str) -> Result<Vec<FlatHand>, RSPokerError> {
+        let mut chars = part.chars();
+        let first = chars.next().ok_or(RSPokerError::TooFewChars)?;
+        let second = chars.next().ok_or(RSPokerError::TooFewChars)?;
+        if second != 'X' && second != 'x' {
+            return RangeParser::parse_one(part);
+        }
+
+        let first_value =
+            Value::from_char(first).ok_or(RSPokerError::UnknownValueChar(first, 0))?;
+        let rest = chars.as_str();
+        let mut hands = Vec::new();
+        for value in Value::values() {
+            if value != first_value {
+                let expanded = format!("{}{}{}", first, value.to_char(), rest);
+                hands.extend(RangeParser::parse_one(&expanded)?);
+            }
+        }
+        Ok(hands)
+    }
+
+    /// Add a single combo to the range.
+    pub fn insert(&mut self, first: Card, second: Card) {
+        self.combos.insert(Self::order(first, second));
+    }
+
+    /// Is this exact combo in the range?
+    pub fn contains(&self, first: Card, second: Card) -> bool {
+        self.combos.contains(&Self::order(first, second))
+    }
+
+    /// Every combo in the range, with the higher card first.
+    pub fn combos(&self) -> Vec<(Card, Card)> {
+        self.combos.iter().copied().collect()
+    }
+
+    /// The number of combos in the range.
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Is the range empty?
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Every one of the 169 starting hand classes that has at least one
+    /// combo in the range. Pairs are reported as `Suitedness::OffSuit`
+    /// the same way as `StartingHand::all`.
+    pub fn hand_classes(&self) -> Vec<StartingHand> {
+        let classes: BTreeSet<(Value, Value, bool)> = self
+            .combos
+            .iter()
+            .map(|(a, b)| (a.value, b.value, a.suit == b.suit))
+            .collect();
+        classes
+            .into_iter()
+            .map(|(high, low, suited)| {
+                let suitedness = match suited {
+                    true => Suitedness::Suited,
+                    false => Suitedness::OffSuit,
+                };
+                StartingHand::default(high, low, suitedness)
+            })
+            .collect()
+    }
+
+    fn order(first: Card, second: Card) -> (Card, Card) {
+        if first > second {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::Suit;
+
+    use super::*;
+
+    #[test]
+    fn test_pairs_plus() {
+        let range = Range::parse("22+").unwrap();
+        assert_eq!(13 * 6, range.len());
+        assert_eq!(13, range.hand_classes().len());
+    }
+
+    #[test]
+    fn test_suited_plus() {
+        let range = Range::parse("ATs+").unwrap();
+        assert_eq!(16, range.len());
+        for (a, b) in range.combos() {
+            assert_eq!(Value::Ace, a.value);
+            assert!(b.value >= Value::Ten);
+            assert_eq!(a.suit, b.suit);
+        }
+    }
+
+    #[test]
+    fn test_offsuit() {
+        let range = Range::parse("KJo").unwrap();
+        assert_eq!(12, range.len());
+        assert!(range.contains(
+            Card::new(Value::Jack, Suit::Heart),
+            Card::new(Value::King, Suit::Spade)
+        ));
+        assert!(!range.contains(
+            Card::new(Value::Jack, Suit::Spade),
+            Card::new(Value::King, Suit::Spade)
+        ));
+    }
+
+    #[test]
+    fn test_dash_range() {
+        let range = Range::parse("87s-54s").unwrap();
+        assert_eq!(16, range.len());
+        assert_eq!(4, range.hand_classes().len());
+    }
+
+    #[test]
+    fn test_wildcard() {
+        let suited = Range::parse("AXs").unwrap();
+        assert_eq!(12 * 4, suited.len());
+        let offsuit = Range::parse("Axo").unwrap();
+        assert_eq!(12 * 12, offsuit.len());
+        let any = Range::parse("KX").unwrap();
+        assert_eq!(12 * 16, any.len());
+        assert_eq!(24, any.hand_classes().len());
+    }
+
+    #[test]
+    fn test_overlap_is_deduped() {
+        let range = Range::parse("AKs, AQs+, AXs").unwrap();
+        assert_eq!(12 * 4, range.len());
+        let pairs = Range::parse("QQ+,KK+,AA").unwrap();
+        assert_eq!(18, pairs.len());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Range::parse("").is_err());
+        assert!(Range::parse("AK,").is_err());
+        assert!(Range::parse("ZXs").is_err());
+        assert!(Range::parse("AAs").is_err());
+    }
+
+    #[test]
+    fn test_top_percent() {
+        let has = |range: &Range, hand: &str| {
+            let cards: Vec<Card> = Hand::new_from_str(hand).unwrap().iter().collect();
+            range.contains(cards[0], cards[1])
+        };
+        let top_five = Range::top_percent(0.05);
+        assert!(has(&top_five, "AsAd"));
+        assert!(has(&top_five, "KhKc"));
+        assert!(!has(&top_five, "7s2d"));
+        assert!(Range::top_percent(0.0).is_empty());
+
+        let mut last = Range::new();
+        for p in [0.05, 0.1, 0.15, 0.25, 0.5, 0.75, 1.0] {
+            let range = Range::top_percent(p);
+            assert!(last.combos().iter().all(|(a, b)| range.contains(*a, *b)));
+            assert!(range.len() > last.len());
+            last = range;
+        }
+        assert!(has(&last, "7s2d"));
+    }
+}
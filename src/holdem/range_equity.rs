@@ -0,0 +1,220 @@
+use rand::Rng;
+
+use crate::core::{Card, FlatHand, Hand, RSPokerError};
+
+use super::MonteCarloGame;
+
+/// Equity of a hand or range against the other side of a matchup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeEquity {
+    /// Equity averaged over every combo that could be dealt, each combo
+    /// counted equally.
+    pub equity: f64,
+    /// The equity for each combo in the range that doesn't conflict with the
+    /// other cards, in the same order as the range.
+    pub combos: Vec<(FlatHand, f64)>,
+}
+
+impl RangeEquity {
+    fn from_combos(combos: Vec<(FlatHand, f64)>) -> Self {
+        let equity = if combos.is_empty() {
+            0.0
+        } else {
+            combos.iter().map(|(_, e)| e).sum::<f64>() / combos.len() as f64
+        };
+        RangeEquity { equity, combos }
+    }
+}
+
+/// Build the full hand for some hole cards. Returns None if the hole cards
+/// can't be dealt given the cards already in use.
+fn combo_hand(combo: &FlatHand, used: &Hand, board: &[Card]) -> Option<Hand> {
+    if combo.iter().any(|c| used.contains(c)) {
+        return None;
+    }
+    let mut hand = Hand::new_with_cards(board.to_vec());
+    hand.extend(combo.iter().cloned());
+    Some(hand)
+}
+
+/// Add the board to a fixed hand. Returns an error if they share a card.
+fn with_board(hole_cards: Hand, board: &[Card]) -> Result<Hand, RSPokerError> {
+    let mut hand = hole_cards;
+    for card in board {
+        if !hand.insert(*card) {
+            return Err(RSPokerError::DuplicateCardInHand(*card));
+        }
+    }
+    Ok(hand)
+}
+
+/// Hold the two hands up to each other and return the first's equity.
+fn heads_up_equity<R: Rng>(
+    first: Hand,
+    second: Hand,
+    sims: usize,
+    rng: &mut R,
+) -> Result<f64, RSPokerError> {
+    let mut game = MonteCarloGame::new_with_rng(vec![first, second], rng)?;
+    Ok(f64::from(game.estimate_equity(sims)[0]))
+}
+
+/// Equity of a fixed hand against every combo in a villain's range.
+///
+/// `hero` should only hold the hole cards, `board` is added to both sides.
+/// Villain combos that share a card with hero or the board are skipped. Each
+/// combo is simulated `sims` times, with the shuffles seeded from `rng`.
+/// The per combo numbers are hero's equity against that combo.
+///
+/// Returns an error if hero shares a card with the board or the hands
+/// can't be played out, for example with more than five board cards.
+///
+/// # Examples
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use rs_poker::core::{Card, Hand};
+/// use rs_poker::holdem::{RangeParser, hand_vs_range_equity};
+///
+/// let hero = Hand::new_from_str("AsAh").unwrap();
+/// let villain = RangeParser::parse_one("KK").unwrap();
+/// let mut rng = StdRng::seed_from_u64(210);
+/// let result = hand_vs_range_equity(hero, &villain, &[], 1000, &mut rng).unwrap();
+///
+/// assert_eq!(6, result.combos.len());
+/// assert!(result.equity > 0.7);
+/// ```
+pub fn hand_vs_range_equity<R: Rng>(
+    hero: Hand,
+    villain_range: &[FlatHand],
+    board: &[Card],
+    sims: usize,
+    rng: &mut R,
+) -> Result<RangeEquity, RSPokerError> {
+    let hero_hand = with_board(hero, board)?;
+
+    let combos = villain_range
+        .iter()
+        .filter_map(|combo| combo_hand(combo, &hero_hand, board).map(|v| (combo, v)))
+        .map(|(combo, villain)| {
+            Ok((
+                combo.clone(),
+                heads_up_equity(hero_hand, villain, sims, rng)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, RSPokerError>>()?;
+    Ok(RangeEquity::from_combos(combos))
+}
+
+/// Equity of every combo in hero's range against a fixed villain hand.
+///
+/// This is the mirror of `hand_vs_range_equity`. It's useful for seeing how
+/// a whole betting range does against a specific hand that calls.
+///
+/// # Examples
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use rs_poker::core::Hand;
+/// use rs_poker::holdem::{RangeParser, range_vs_hand_equity};
+///
+/// let hero = RangeParser::parse_one("AKs").unwrap();
+/// let villain = Hand::new_from_str("QcQd").unwrap();
+/// let mut rng = StdRng::seed_from_u64(210);
+/// let result = range_vs_hand_equity(&hero, villain, &[], 1000, &mut rng).unwrap();
+///
+/// assert_eq!(4, result.combos.len());
+/// assert!(result.equity < 0.6);
+/// ```
+pub fn range_vs_hand_equity<R: Rng>(
+    hero_range: &[FlatHand],
+    villain: Hand,
+    board: &[Card],
+    sims: usize,
+    rng: &mut R,
+) -> Result<RangeEquity, RSPokerError> {
+    let villain_hand = with_board(villain, board)?;
+
+    let combos = hero_range
+        .iter()
+        .filter_map(|combo| combo_hand(combo, &villain_hand, board).map(|h| (combo, h)))
+        .map(|(combo, hero)| {
+            Ok((
+                combo.clone(),
+                heads_up_equity(hero, villain_hand, sims, rng)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, RSPokerError>>()?;
+    Ok(RangeEquity::from_combos(combos))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::core::cards;
+    use crate::holdem::RangeParser;
+
+    #[test]
+    fn test_matches_hand_vs_range_swapped() {
+        // With a complete board every simulation is the same so the numbers
+        // are exact.
+        let board = cards("2c7dTsJh3d");
+        let range = RangeParser::parse_many("AA,KTs,72o").unwrap();
+        let hand = Hand::new_from_str("QhQd").unwrap();
+
+        let mut rng = StdRng::seed_from_u64(210);
+        let range_vs_hand = range_vs_hand_equity(&range, hand, &board, 10, &mut rng).unwrap();
+        let hand_vs_range = hand_vs_range_equity(hand, &range, &board, 10, &mut rng).unwrap();
+
+        assert_eq!(range_vs_hand.combos.len(), hand_vs_range.combos.len());
+        assert!((range_vs_hand.equity - (1.0 - hand_vs_range.equity)).abs() < 1e-6);
+        for ((hero_combo, hero_eq), (villain_combo, villain_eq)) in
+            range_vs_hand.combos.iter().zip(hand_vs_range.combos.iter())
+        {
+            assert_eq!(hero_combo, villain_combo);
+            assert!((hero_eq - (1.0 - villain_eq)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_blocked_combos_skipped() {
+        let range = RangeParser::parse_one("AA").unwrap();
+        let hand = Hand::new_from_str("AsKd").unwrap();
+        let result =
+            range_vs_hand_equity(&range, hand, &[], 10, &mut StdRng::seed_from_u64(210)).unwrap();
+        // Only the three combos without the ace of spades remain.
+        assert_eq!(3, result.combos.len());
+    }
+
+    #[test]
+    fn test_empty_range() {
+        let hand = Hand::new_from_str("AsKd").unwrap();
+        let result =
+            range_vs_hand_equity(&[], hand, &[], 10, &mut StdRng::seed_from_u64(210)).unwrap();
+        assert_eq!(0.0, result.equity);
+        assert!(result.combos.is_empty());
+    }
+
+    #[test]
+    fn test_same_seed_same_equity() {
+        let range = RangeParser::parse_many("KK,AQs,JTs").unwrap();
+        let hand = Hand::new_from_str("AsKd").unwrap();
+        let run = |seed| {
+            hand_vs_range_equity(hand, &range, &[], 200, &mut StdRng::seed_from_u64(seed)).unwrap()
+        };
+        assert_eq!(run(210), run(210));
+    }
+
+    #[test]
+    fn test_hand_on_board_is_an_error() {
+        let range = RangeParser::parse_one("KK").unwrap();
+        let hand = Hand::new_from_str("AsKd").unwrap();
+        let board = cards("As7c2d");
+        assert!(matches!(
+            hand_vs_range_equity(hand, &range, &board, 10, &mut StdRng::seed_from_u64(210)),
+            Err(RSPokerError::DuplicateCardInHand(_))
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+use rand::{Rng, SeedableRng, rng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::core::{Card, CardBitSet, Hand, RSPokerError, Rank, Rankable};
+
+use super::Range;
+
+/// The results of simulating ranges against each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeGameResult {
+    /// The share of the pots won by each player.
+    pub equity: Vec<f64>,
+    /// How many times each player won the whole pot.
+    pub wins: Vec<usize>,
+    /// How many times each player split the pot with someone else.
+    pub ties: Vec<usize>,
+    /// How many times each player lost.
+    pub losses: Vec<usize>,
+    /// The number of simulated games.
+    pub iterations: usize,
+}
+
+/// A Monte Carlo game where each player holds a range rather than a known
+/// hand.
+///
+/// Every simulation deals each player a combo from their range and then
+/// finishes the board. Deals where two players would hold the same card are
+/// thrown away and dealt again, so every valid combination of hands is just
+/// as likely. That keeps card removal effects correct.
+#[derive(Debug)]
+pub struct RangeMonteCarloGame {
+    /// The combos for each player that don't conflict with the board.
+    ranges: Vec<Vec<(Card, Card)>>,
+    board: Vec<Card>,
+    /// Used for every deal.
+    rng: StdRng,
+}
+
+impl RangeMonteCarloGame {
+    /// Create a new game for `ranges` on `board`.
+    ///
+    /// Combos that share a card with the board are removed. Returns an
+    /// error if there are no ranges, if the board is too big, or if
+    /// there's no way to deal every player a hand from their range at the
+    /// same time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rs_poker::holdem::{Range, RangeMonteCarloGame};
+    ///
+    /// let hero = Range::parse("QQ+").unwrap();
+    /// let villain = Range::parse("AKs,JJ").unwrap();
+    /// let mut game = RangeMonteCarloGame::new(vec![hero, villain], vec![]).unwrap();
+    /// let result = game.estimate_equity(1000);
+    ///
+    /// assert!(result.equity[0] > result.equity[1]);
+    /// ```
+    pub fn new(ranges: Vec<Range>, board: Vec<Card>) -> Result<Self, RSPokerError> {
+        Self::new_with_rng(ranges, board, &mut rng())
+    }
+
+    /// Create a new game where all the dealing is seeded from `rng`.
+    ///
+    /// Passing in a seeded rng makes every simulation reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use rs_poker::holdem::{Range, RangeMonteCarloGame};
+    ///
+    /// let ranges = vec![
+    ///     Range::parse("QQ+").unwrap(),
+    ///     Range::parse("AKs,JJ").unwrap(),
+    /// ];
+    /// let mut first =
+    ///     RangeMonteCarloGame::new_with_rng(ranges.clone(), vec![], &mut StdRng::seed_from_u64(7))
+    ///         .unwrap();
+    /// let mut second =
+    ///     RangeMonteCarloGame::new_with_rng(ranges, vec![], &mut StdRng::seed_from_u64(7)).unwrap();
+    /// assert_eq!(first.estimate_equity(100), second.estimate_equity(100));
+    /// ```
+    pub fn new_with_rng<R: Rng>(
+        ranges: Vec<Range>,
+        board: Vec<Card>,
+        rng: &mut R,
+    ) -> Result<Self, RSPokerError> {
+        if ranges.is_empty() {
+            return Err(RSPokerError::NoRanges);
+        }
+        if board.len() > 5 {
+            return Err(RSPokerError::HoldemHandSize);
+        }
+        let board_set = Hand::new_with_cards(board.clone());
+        let ranges: Vec<Vec<(Card, Card)>> = ranges
+            .iter()
+            .map(|r| {
+                r.combos()
+                    .into_iter()
+                    .filter(|(a, b)| !board_set.contains(a) && !board_set.contains(b))
+                    .collect()
+            })
+            .collect();
+
+        if !Self::can_deal(&ranges, 0, &mut CardBitSet::new()) {
+            return Err(RSPokerError::ImpossibleRanges);
+        }
+        Ok(Self {
+            ranges,
+            board,
+            rng: StdRng::from_rng(rng),
+        })
+    }
+
+    /// Check that every player can get a combo without any collisions.
+    fn can_deal(ranges: &[Vec<(Card, Card)>], player: usize, used: &mut CardBitSet) -> bool {
+        if player == ranges.len() {
+            return true;
+        }
+        for (a, b) in &ranges[player] {
+            if used.contains(*a) || used.contains(*b) {
+                continue;
+            }
+            used.insert(*a);
+            used.insert(*b);
+            let found = Self::can_deal(ranges, player + 1, used);
+            used.remove(*a);
+            used.remove(*b);
+            if found {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Deal each player a combo, starting over if any cards collide.
+    fn deal_hands<R: Rng>(&self, rng: &mut R) -> (Vec<Hand>, CardBitSet) {
+        'deal: loop {
+            let mut used = CardBitSet::new();
+            for card in &self.board {
+                used.insert(*card);
+            }
+            let mut hands = Vec::with_capacity(self.ranges.len());
+            for range in &self.ranges {
+                let (a, b) = *range.choose(rng).unwrap();
+                if used.contains(a) || used.contains(b) {
+                    continue 'deal;
+                }
+                used.insert(a);
+                used.insert(b);
+                hands.push(Hand::new_with_cards(vec![a, b]));
+            }
+            return (hands, used);
+        }
+    }
+
+    /// Simulate `iterations` games and return the results for every player.
+    pub fn estimate_equity(&mut self, iterations: usize) -> RangeGameResult {
+        let players = self.ranges.len();
+        let mut result = RangeGameResult {
+            equity: vec![0.0; players],
+            wins: vec![0; players],
+            ties: vec![0; players],
+            losses: vec![0; players],
+            iterations,
+        };
+        // `deal_hands` borrows self, so work on a copy and put it back after.
+        let mut rng = self.rng.clone();
+
+        for _ in 0..iterations {
+            let (mut hands, mut used) = self.deal_hands(&mut rng);
+            let mut board = self.board.clone();
+            while board.len() < 5 {
+                let card = (!used).sample_one(&mut rng).unwrap();
+                used.insert(card);
+                board.push(card);
+            }
+
+            let ranks: Vec<Rank> = hands
+                .iter_mut()
+                .map(|h| {
+                    h.extend(board.iter().copied());
+                    h.rank()
+                })
+                .collect();
+            let best = *ranks.iter().max().unwrap();
+            let winners = ranks.iter().filter(|r| **r == best).count();
+
+            for (idx, rank) in ranks.iter().enumerate() {
+                if *rank != best {
+                    result.losses[idx] += 1;
+                } else if winners == 1 {
+                    result.wins[idx] += 1;
+                    result.equity[idx] += 1.0;
+                } else {
+                    result.ties[idx] += 1;
+                    result.equity[idx] += 1.0 / winners as f64;
+                }
+            }
+        }
+
+        self.rng = rng;
+
+        if iterations > 0 {
+            for e in result.equity.iter_mut() {
+                *e /= iterations as f64;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aces_vs_kings() {
+        let aces = Range::parse("AA").unwrap();
+        let kings = Range::parse("KK").unwrap();
+        let mut game = RangeMonteCarloGame::new(vec![aces, kings], vec![]).unwrap();
+        let result = game.estimate_equity(5_000);
+
+        assert_eq!(5_000, result.iterations);
+        assert!((result.equity[0] - 0.82).abs() < 0.03, "{result:?}");
+        for idx in 0..2 {
+            assert_eq!(
+                5_000,
+                result.wins[idx] + result.ties[idx] + result.losses[idx]
+            );
+        }
+        assert_eq!(result.ties[0], result.ties[1]);
+        assert_eq!(result.wins[0], result.losses[1]);
+    }
+
+    #[test]
+    fn test_board_conflicts_removed() {
+        // With the ace of spades on the board only 3 combos of aces are left,
+        // and they all make a set.
+        let aces = Range::parse("AA").unwrap();
+        let kings = Range::parse("KK").unwrap();
+        let board = Hand::new_from_str("As7d2c").unwrap().iter().collect();
+        let mut game = RangeMonteCarloGame::new(vec![aces, kings], board).unwrap();
+        assert_eq!(3, game.ranges[0].len());
+        let result = game.estimate_equity(2_000);
+        assert!(result.equity[0] > 0.9);
+    }
+
+    #[test]
+    fn test_card_removal() {
+        // The only aces left for villain are the ones hero doesn't have, so
+        // villain always has AA or nothing.
+        let hero = Range::parse("AsAh").unwrap();
+        let villain = Range::parse("AA,KsKh").unwrap();
+        let mut game = RangeMonteCarloGame::new(vec![hero, villain], vec![]).unwrap();
+        let (hands, _) = game.deal_hands(&mut rng());
+        assert_eq!(2, hands[1].count());
+
+        // Hero vs the AdAc or KsKh is tied or far ahead.
+        let result = game.estimate_equity(2_000);
+        assert!(result.ties[0] > 0);
+        assert!(result.equity[0] > 0.6);
+    }
+
+    #[test]
+    fn test_impossible_ranges() {
+        let hero = Range::parse("AsAh").unwrap();
+        let villain = Range::parse("AsKs").unwrap();
+        assert!(matches!(
+            RangeMonteCarloGame::new(vec![hero, villain], vec![]),
+            Err(RSPokerError::ImpossibleRanges)
+        ));
+
+        let empty = Range::new();
+        assert!(RangeMonteCarloGame::new(vec![empty], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_no_ranges() {
+        assert!(matches!(
+            RangeMonteCarloGame::new(vec![], vec![]),
+            Err(RSPokerError::NoRanges)
+        ));
+    }
+
+    #[test]
+    fn test_same_seed_same_result() {
+        let ranges = vec![
+            Range::parse("TT+,AQs+").unwrap(),
+            Range::parse("77+,KQs").unwrap(),
+        ];
+        let board: Vec<Card> = Hand::new_from_str("Kd8s2h").unwrap().iter().collect();
+        let mut first = RangeMonteCarloGame::new_with_rng(
+            ranges.clone(),
+            board.clone(),
+            &mut StdRng::seed_from_u64(3),
+        )
+        .unwrap();
+        let mut second =
+            RangeMonteCarloGame::new_with_rng(ranges, board, &mut StdRng::seed_from_u64(3))
+                .unwrap();
+        assert_eq!(first.estimate_equity(500), second.estimate_equity(500));
+        // Both games carry on from the same place.
+        assert_eq!(first.estimate_equity(500), second.estimate_equity(500));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::core::{Card, Hand, Rankable};
+
+use super::Range;
+
+/// Count how many combos in `range` make each kind of hand on `board`.
+///
+/// Every combo is ranked with the board, and the count for its category
+/// goes up by one. The result is indexed in the same order as `Rank`, high
+/// card at 0 up to straight flush at 8. Combos that share a card with the
+/// board can't be dealt, so they aren't counted.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::core::{Card, Hand};
+/// use rs_poker::holdem::{Range, rank_distribution};
+///
+/// let range = Range::parse("QQ+").unwrap();
+/// let board: Vec<Card> = Hand::new_from_str("Ks8d2c").unwrap().iter().collect();
+/// let counts = rank_distribution(&range, &board);
+///
+/// // Three combos of kings make a set, the rest are an overpair or
+/// // under the king.
+/// assert_eq!(3, counts[3]);
+/// assert_eq!(12, counts[1]);
+/// assert_eq!(15, counts.iter().sum::<u64>());
+/// ```
+pub fn rank_distribution(range: &Range, board: &[Card]) -> [u64; 9] {
+    let board_hand = Hand::new_with_cards(board.to_vec());
+    let mut counts = [0; 9];
+    for (first, second) in range.combos() {
+        if board_hand.contains(&first) || board_hand.contains(&second) {
+            continue;
+        }
+        let mut hand = board_hand;
+        hand.insert(first);
+        hand.insert(second);
+        counts[usize::from(hand.rank().category())] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cards;
+
+    #[test]
+    fn test_paired_board_makes_trips_and_boats() {
+        // Every suited seven and every pocket pair.
+        let range = Range::parse("7Xs,22+").unwrap();
+        let dry = rank_distribution(&range, &cards("7h3d2c"));
+        let paired = rank_distribution(&range, &cards("7h7d2c"));
+
+        // Only the sets of sevens, threes and deuces.
+        assert_eq!(9, dry[3]);
+        assert_eq!(0, dry[6]);
+        assert_eq!(0, dry[7]);
+
+        // Now every suited seven has trips, or a full house with the deuce.
+        // The deuces fill up and the last two sevens make quads.
+        assert_eq!(22, paired[3]);
+        assert_eq!(4, paired[6]);
+        assert_eq!(1, paired[7]);
+
+        let total = |counts: [u64; 9]| counts.iter().sum::<u64>();
+        assert_eq!(103, total(dry));
+        assert_eq!(93, total(paired));
+    }
+
+    #[test]
+    fn test_skips_conflicting_combos() {
+        let range = Range::parse("AA").unwrap();
+        let counts = rank_distribution(&range, &cards("AsKd2c"));
+        assert_eq!(3, counts[3]);
+        assert_eq!(3, counts.iter().sum::<u64>());
+    }
+}
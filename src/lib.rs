@@ -168,7 +168,7 @@
 //!
 //! ```
 //! use rs_poker::arena::{
-//!     AgentGenerator, CloneGameStateGenerator, GameState,
+//!     AgentGenerator, Chips, CloneGameStateGenerator, GameState,
 //!     agent::{CallingAgentGenerator, RandomAgentGenerator},
 //!     competition::{HoldemCompetition, StandardSimulationIterator},
 //! };
@@ -177,8 +177,8 @@
 //!     Box::<CallingAgentGenerator>::default(),
 //!     Box::<CallingAgentGenerator>::default(),
 //! ];
-//! let stacks = vec![100.0; 3];
-//! let game_state = GameState::new_starting(stacks, 10.0, 5.0, 0.0, 0);
+//! let stacks = vec![Chips::new(100); 3];
+//! let game_state = GameState::new_starting(stacks, Chips::new(10), Chips::new(5), Chips::ZERO, 0);
 //! let sim_gen = StandardSimulationIterator::new(
 //!     agent_gens,
 //!     vec![], // no historians
@@ -187,7 +187,6 @@
 //! let mut competition = HoldemCompetition::new(sim_gen);
 //! let _first_results = competition.run(100).unwrap();
 //! ```
-#![feature(assert_matches)]
 #![feature(mapped_lock_guards)]
 #![deny(clippy::all)]
 
@@ -0,0 +1,899 @@
+//! Conversion between open hand histories and the arena's types.
+//!
+//! Exporting turns the actions recorded during a simulation into a
+//! `HandHistory`. Importing replays a `HandHistory` onto a `GameState`,
+//! producing the same kind of action sequence the simulation records. That
+//! lets hands played elsewhere be inspected with the arena's analysis tools.
+//!
+//! Amounts on betting actions are the chips the action adds to the pot, not
+//! the total the player has bet on the street. So a call of a bet of 20 from
+//! a player that posted a blind of 10 has an amount of 10.
+//!
+//! The arena counts whole chips, so importing fails on a hand history with
+//! an amount that isn't a whole number.
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::arena::action::{
+    Action as ArenaAction, AgentAction, AwardPayload, CardVisibility, DealStartingHandPayload,
+    ForcedBetPayload, ForcedBetType, GameStartPayload, PlayedActionPayload, PlayerSitPayload,
+    ShowdownPayload,
+};
+use crate::arena::errors::GameStateError;
+use crate::arena::game_state::{MAX_PLAYERS, Round};
+use crate::arena::{Chips, GameState};
+use crate::core::Card;
+
+use super::hand_history::{
+    Action, ActionObj, GameType, HandHistory, PlayerObj, PlayerWinsObj, PotObj, RoundObj,
+};
+
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum ImportError {
+    #[error("Only Hold'em hand histories can be imported")]
+    UnsupportedGameType,
+    #[error("Player {0} isn't seated at the table")]
+    UnknownPlayer(u64),
+    #[error("No player sits in the dealer seat {0}")]
+    UnknownDealerSeat(u64),
+    #[error("Unknown street {0}")]
+    UnknownStreet(String),
+    #[error("Can't import the action {0:?}")]
+    UnsupportedAction(Action),
+    #[error("Streets are out of order")]
+    RoundOutOfOrder,
+    #[error("Player {found} acted when it was player {expected}'s turn")]
+    OutOfTurn { expected: usize, found: usize },
+    #[error("Invalid bet: {0}")]
+    InvalidBet(#[from] GameStateError),
+    #[error("Invalid table: {0}")]
+    InvalidTable(GameStateError),
+    #[error("{0} isn't a whole number of chips")]
+    FractionalChips(f32),
+}
+
+/// The chips in a hand history amount, which has to be a whole number.
+fn whole_chips(amount: f32) -> Result<Chips, ImportError> {
+    if amount.is_finite() && amount.fract() == 0.0 {
+        Ok(Chips::new(amount as i64))
+    } else {
+        Err(ImportError::FractionalChips(amount))
+    }
+}
+
+/// A hand replayed from a hand history.
+#[derive(Debug, Clone)]
+pub struct ImportedHand {
+    /// Every action in the order it happened.
+    pub actions: Vec<ArenaAction>,
+    /// The game state right after the action with the same index.
+    pub game_states: Vec<GameState>,
+}
+
+impl ImportedHand {
+    /// The game state once the whole hand has been replayed.
+    pub fn final_game_state(&self) -> Option<&GameState> {
+        self.game_states.last()
+    }
+}
+
+fn street_name(round: Round) -> Option<&'static str> {
+    match round {
+        Round::Preflop => Some("Preflop"),
+        Round::Flop => Some("Flop"),
+        Round::Turn => Some("Turn"),
+        Round::River => Some("River"),
+        Round::Showdown => Some("Showdown"),
+        _ => None,
+    }
+}
+
+fn street_round(street: &str) -> Result<Round, ImportError> {
+    match street.to_ascii_lowercase().as_str() {
+        "preflop" => Ok(Round::Preflop),
+        "flop" => Ok(Round::Flop),
+        "turn" => Ok(Round::Turn),
+        "river" => Ok(Round::River),
+        "showdown" => Ok(Round::Showdown),
+        _ => Err(ImportError::UnknownStreet(street.to_string())),
+    }
+}
+
+/// Builds up the rounds of a hand history one action at a time.
+#[derive(Default)]
+struct Exporter {
+    rounds: Vec<RoundObj>,
+    pending_cards: Vec<Card>,
+    action_number: u64,
+    hole_cards: HashMap<usize, Vec<Card>>,
+}
+
+impl Exporter {
+    /// Get the round for the street, starting it if it's not the latest.
+    fn round(&mut self, street: &str) -> &mut RoundObj {
+        if self.rounds.last().is_none_or(|r| r.street != street) {
+            let cards = if self.pending_cards.is_empty() {
+                None
+            } else {
+                Some(std::mem::take(&mut self.pending_cards))
+            };
+            self.rounds.push(RoundObj {
+                id: self.rounds.len() as u64,
+                street: street.to_string(),
+                cards,
+                actions: vec![],
+            });
+        }
+        self.rounds.last_mut().unwrap()
+    }
+
+    fn push(&mut self, street: &str, idx: usize, action: Action, amount: f32, is_allin: bool) {
+        let action_number = self.action_number;
+        self.action_number += 1;
+        self.round(street).actions.push(ActionObj {
+            action_number,
+            player_id: idx as u64,
+            action,
+            amount,
+            is_allin,
+            cards: None,
+        });
+    }
+
+    fn deal_card(&mut self, idx: usize, card: Card) {
+        self.hole_cards.entry(idx).or_default().push(card);
+        let action_number = self.action_number;
+        let round = self.round("Preflop");
+        // Both hole cards go into a single dealt cards action.
+        if let Some(last) = round.actions.last_mut()
+            && last.action == Action::DealtCards
+            && last.player_id == idx as u64
+        {
+            last.cards.get_or_insert_with(Vec::new).push(card);
+            return;
+        }
+        round.actions.push(ActionObj {
+            action_number,
+            player_id: idx as u64,
+            action: Action::DealtCards,
+            amount: 0.0,
+            is_allin: false,
+            cards: Some(vec![card]),
+        });
+        self.action_number += 1;
+    }
+
+    fn played(&mut self, payload: &PlayedActionPayload) {
+        let Some(street) = street_name(payload.round) else {
+            return;
+        };
+        let amount = payload.final_player_bet - payload.starting_player_bet;
+        let action = if payload.action == AgentAction::Fold {
+            Action::Fold
+        } else if amount <= Chips::ZERO {
+            Action::Check
+        } else if payload.final_bet <= payload.starting_bet {
+            Action::Call
+        } else if payload.starting_bet <= Chips::ZERO {
+            Action::Bet
+        } else {
+            Action::Raise
+        };
+        let is_allin = action != Action::Fold && payload.player_stack <= Chips::ZERO;
+        self.push(street, payload.idx, action, amount.as_f32(), is_allin);
+    }
+
+    fn showdown(&mut self, payload: &ShowdownPayload) {
+        let (action, cards) = if payload.rank.is_some() {
+            (
+                Action::ShowsCards,
+                self.hole_cards.get(&payload.idx).cloned(),
+            )
+        } else {
+            (Action::MucksCards, None)
+        };
+        self.push("Showdown", payload.idx, action, 0.0, false);
+        self.round("Showdown").actions.last_mut().unwrap().cards = cards;
+    }
+}
+
+/// Export the actions recorded for one simulated hand as a hand history.
+///
+/// Players are identified by their seat index, sitting in seat `idx + 1`.
+/// The actions should be a complete hand as recorded by a historian, starting
+/// with the `GameStart` action.
+pub fn export_hand(id: u128, actions: &[ArenaAction]) -> HandHistory {
+    let mut hand = HandHistory {
+        spec_version: "1.4.7".to_string(),
+        site_name: "rs_poker".to_string(),
+        network_name: "rs_poker".to_string(),
+        internal_version: env!("CARGO_PKG_VERSION").to_string(),
+        tournament: false,
+        tournament_info: None,
+        game_number: id.to_string(),
+        start_date_utc: None,
+        table_name: "arena".to_string(),
+        table_handle: None,
+        table_skin: None,
+        game_type: GameType::Holdem,
+        bet_limit: None,
+        table_size: 0,
+        currency: String::new(),
+        dealer_seat: 0,
+        small_blind_amount: 0.0,
+        big_blind_amount: 0.0,
+        ante_amount: 0.0,
+        hero_player_id: None,
+        players: vec![],
+        rounds: vec![],
+        pots: vec![],
+        tournament_bounties: None,
+    };
+    let mut exporter = Exporter::default();
+    let mut stacks: HashMap<usize, Chips> = HashMap::new();
+    let mut wins: Vec<PlayerWinsObj> = vec![];
+    let mut rake = Chips::ZERO;
+
+    for action in actions {
+        match action {
+            ArenaAction::GameStart(payload) => {
+                hand.small_blind_amount = payload.small_blind.as_f32();
+                hand.big_blind_amount = payload.big_blind.as_f32();
+                hand.ante_amount = payload.ante.as_f32();
+            }
+            ArenaAction::PlayerSit(payload) => {
+                // The dealer is the first player to sit.
+                if hand.players.is_empty() {
+                    hand.dealer_seat = payload.idx as u64 + 1;
+                }
+                stacks.insert(payload.idx, payload.player_stack);
+                hand.players.push(PlayerObj {
+                    id: payload.idx as u64,
+                    seat: payload.idx as u64 + 1,
+                    name: format!("Player {}", payload.idx),
+                    display: None,
+                    starting_stack: payload.player_stack.as_f32(),
+                    player_bounty: None,
+                    is_sitting_out: None,
+                });
+            }
+            ArenaAction::DealStartingHand(payload) => exporter.deal_card(payload.idx, payload.card),
+            ArenaAction::ForcedBet(payload) => {
+                // The payload has the nominal bet, work out what was posted.
+                let stack = stacks.entry(payload.idx).or_insert(payload.player_stack);
+                let posted = *stack - payload.player_stack;
+                *stack = payload.player_stack;
+                let ohh_action = match payload.forced_bet_type {
+                    ForcedBetType::Ante => Action::PostAnte,
+                    ForcedBetType::SmallBlind => Action::PostSmallBlind,
+                    ForcedBetType::BigBlind => Action::PostBigBlind,
+                    // Open hand history has no bring-in, the closest is an
+                    // extra blind.
+                    ForcedBetType::BringIn => Action::PostExtraBlind,
+                    ForcedBetType::Straddle => Action::Straddle,
+                };
+                exporter.push(
+                    "Preflop",
+                    payload.idx,
+                    ohh_action,
+                    posted.as_f32(),
+                    payload.player_stack <= Chips::ZERO,
+                );
+            }
+            ArenaAction::RoundAdvance(round) => {
+                if let Some(street) = street_name(*round)
+                    && *round != Round::Showdown
+                {
+                    exporter.round(street);
+                }
+            }
+            ArenaAction::PlayedAction(payload) => {
+                stacks.insert(payload.idx, payload.player_stack);
+                exporter.played(payload);
+            }
+            ArenaAction::FailedAction(payload) => {
+                stacks.insert(payload.result.idx, payload.result.player_stack);
+                exporter.played(&payload.result);
+            }
+            ArenaAction::DealCommunity(card) => exporter.pending_cards.push(*card),
+            // Hand histories don't record burned cards.
+            ArenaAction::BurnCard(_) => {}
+            // Hand histories only have the one board.
+            ArenaAction::DealRunout(_) => {}
+            // Players show or muck in the order they had to at showdown.
+            ArenaAction::Showdown(payload) => exporter.showdown(payload),
+            // The pot is already on each action.
+            ArenaAction::PotUpdate(_) => {}
+            ArenaAction::Award(payload) => {
+                rake += payload.rake;
+                match wins.iter_mut().find(|w| w.player_id == payload.idx as u64) {
+                    Some(win) => win.win_amount += payload.award_amount.as_f32(),
+                    None => wins.push(PlayerWinsObj {
+                        player_id: payload.idx as u64,
+                        win_amount: payload.award_amount.as_f32(),
+                        cashout_amount: None,
+                        cashout_fee: None,
+                        bonus_amount: None,
+                        contributed_rake: None,
+                    }),
+                }
+            }
+        }
+    }
+
+    hand.table_size = hand.players.len() as u64;
+    hand.players.sort_by_key(|p| p.seat);
+    hand.rounds = exporter.rounds;
+    if !wins.is_empty() {
+        hand.pots.push(PotObj {
+            number: 0,
+            amount: wins.iter().map(|w| w.win_amount).sum::<f32>() + rake.as_f32(),
+            rake: (rake > Chips::ZERO).then_some(rake.as_f32()),
+            jackpot: None,
+            player_wins: wins,
+        });
+    }
+    hand
+}
+
+/// Replays hand history actions onto a game state.
+struct Importer {
+    game_state: GameState,
+    actions: Vec<ArenaAction>,
+    game_states: Vec<GameState>,
+    player_idx: HashMap<u64, usize>,
+}
+
+impl Importer {
+    fn record(&mut self, action: ArenaAction) {
+        self.actions.push(action);
+        self.game_states.push(self.game_state.clone());
+    }
+
+    fn idx(&self, player_id: u64) -> Result<usize, ImportError> {
+        self.player_idx
+            .get(&player_id)
+            .copied()
+            .ok_or(ImportError::UnknownPlayer(player_id))
+    }
+
+    /// Advance the game state until it's in `round`.
+    fn advance_to(&mut self, round: Round) -> Result<(), ImportError> {
+        while self.game_state.round != round {
+            if self.game_state.round == Round::Complete {
+                return Err(ImportError::RoundOutOfOrder);
+            }
+            self.game_state.advance_round();
+            self.record(ArenaAction::RoundAdvance(self.game_state.round));
+        }
+        Ok(())
+    }
+
+    fn deal_community(&mut self, cards: &[Card]) {
+        for card in cards {
+            self.record(ArenaAction::DealCommunity(*card));
+        }
+        for hand in &mut self.game_state.hands {
+            hand.extend(cards.iter().cloned());
+        }
+        self.game_state.board.extend(cards.iter().cloned());
+    }
+
+    fn forced_bet(
+        &mut self,
+        idx: usize,
+        amount: f32,
+        forced_bet_type: ForcedBetType,
+    ) -> Result<(), ImportError> {
+        let amount = whole_chips(amount)?;
+        let round = if forced_bet_type == ForcedBetType::Ante {
+            Round::Ante
+        } else {
+            Round::Preflop
+        };
+        self.advance_to(round)?;
+
+        // Forced bets come in whatever order the site posted them.
+        self.game_state.round_data.to_act_idx = idx;
+        self.game_state.do_bet(amount, true)?;
+        match forced_bet_type {
+            ForcedBetType::Ante => self.game_state.round_data.needs_action.disable(idx),
+            ForcedBetType::SmallBlind => self.game_state.sb_posted = true,
+            ForcedBetType::BigBlind => self.game_state.bb_posted = true,
+            ForcedBetType::BringIn | ForcedBetType::Straddle => {}
+        }
+
+        let payload = ForcedBetPayload {
+            bet: amount,
+            player_stack: self.game_state.stacks[idx],
+            idx,
+            forced_bet_type,
+        };
+        self.game_state.forced_bets.push(payload.clone());
+        self.record(ArenaAction::ForcedBet(payload));
+        Ok(())
+    }
+
+    fn deal_hole_cards(&mut self, idx: usize, cards: &[Card]) -> Result<(), ImportError> {
+        self.advance_to(Round::DealPreflop)?;
+        for card in cards {
+            self.record(ArenaAction::DealStartingHand(DealStartingHandPayload {
+                card: *card,
+                idx,
+                visibility: CardVisibility::Hidden,
+            }));
+        }
+        self.game_state.hands[idx].extend(cards.iter().cloned());
+        Ok(())
+    }
+
+    fn show_cards(
+        &mut self,
+        street: Round,
+        idx: usize,
+        action: &Action,
+        cards: &[Card],
+    ) -> Result<(), ImportError> {
+        // Hole cards that weren't dealt face up to us are learned here.
+        for card in cards {
+            self.game_state.hands[idx].insert(*card);
+        }
+        if street == Round::Showdown {
+            self.advance_to(Round::Showdown)?;
+            let shown = *action == Action::ShowsCards;
+            self.record(ArenaAction::Showdown(ShowdownPayload {
+                idx,
+                hand: shown.then(|| self.game_state.hands[idx]),
+                rank: shown.then(|| self.game_state.rank_hand(idx)),
+            }));
+        }
+        Ok(())
+    }
+
+    fn voluntary(&mut self, idx: usize, action: &Action, amount: f32) -> Result<(), ImportError> {
+        let expected = self.game_state.to_act_idx();
+        if expected != idx {
+            return Err(ImportError::OutOfTurn {
+                expected,
+                found: idx,
+            });
+        }
+
+        let starting_bet = self.game_state.current_round_bet();
+        let starting_player_bet = self.game_state.current_round_player_bet(idx);
+        let starting_min_raise = self.game_state.current_round_min_raise();
+        let starting_pot = self.game_state.total_pot;
+        let round = self.game_state.round;
+
+        let agent_action = if *action == Action::Fold {
+            AgentAction::Fold
+        } else {
+            let amount = if *action == Action::Check {
+                Chips::ZERO
+            } else {
+                whole_chips(amount)?
+            };
+            self.game_state
+                .do_bet(starting_player_bet + amount, false)?;
+            AgentAction::Bet(self.game_state.current_round_player_bet(idx))
+        };
+
+        let payload = PlayedActionPayload {
+            action: agent_action.clone(),
+            idx,
+            round,
+            player_stack: self.game_state.stacks[idx],
+            starting_pot,
+            final_pot: self.game_state.total_pot,
+            starting_bet,
+            final_bet: self.game_state.current_round_bet(),
+            starting_min_raise,
+            final_min_raise: self.game_state.current_round_min_raise(),
+            starting_player_bet,
+            final_player_bet: self.game_state.current_round_player_bet(idx),
+            players_active: self.game_state.player_active,
+            players_all_in: self.game_state.player_all_in,
+        };
+        if agent_action == AgentAction::Fold {
+            // Folds are recorded with who was in the hand before the fold.
+            self.record(ArenaAction::PlayedAction(payload));
+            self.game_state.fold();
+        } else {
+            self.record(ArenaAction::PlayedAction(payload));
+        }
+        Ok(())
+    }
+
+    fn action(&mut self, street: Round, action: &ActionObj) -> Result<(), ImportError> {
+        let idx = self.idx(action.player_id)?;
+        match action.action {
+            Action::DealtCards => {
+                self.deal_hole_cards(idx, action.cards.as_deref().unwrap_or_default())
+            }
+            Action::PostAnte => self.forced_bet(idx, action.amount, ForcedBetType::Ante),
+            Action::PostSmallBlind => {
+                self.forced_bet(idx, action.amount, ForcedBetType::SmallBlind)
+            }
+            Action::PostBigBlind => self.forced_bet(idx, action.amount, ForcedBetType::BigBlind),
+            Action::Straddle => self.forced_bet(idx, action.amount, ForcedBetType::Straddle),
+            Action::Fold | Action::Check | Action::Call | Action::Bet | Action::Raise => {
+                self.advance_to(street)?;
+                self.voluntary(idx, &action.action, action.amount)
+            }
+            Action::ShowsCards | Action::MucksCards => self.show_cards(
+                street,
+                idx,
+                &action.action,
+                action.cards.as_deref().unwrap_or_default(),
+            ),
+            _ => Err(ImportError::UnsupportedAction(action.action.clone())),
+        }
+    }
+
+    fn round(&mut self, round: &RoundObj) -> Result<(), ImportError> {
+        let street = street_round(&round.street)?;
+        if street != Round::Preflop && street != Round::Showdown {
+            // The deal round always comes right before the betting round.
+            let deal_round = match street {
+                Round::Flop => Round::DealFlop,
+                Round::Turn => Round::DealTurn,
+                _ => Round::DealRiver,
+            };
+            self.advance_to(deal_round)?;
+            self.deal_community(round.cards.as_deref().unwrap_or_default());
+            self.advance_to(street)?;
+        }
+        for action in &round.actions {
+            self.action(street, action)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replay a Hold'em hand history, reconstructing the game state after every
+/// action.
+///
+/// Players are seated in seat order. The actions produced follow the same
+/// order as those a `HoldemSimulation` records, so the same historians and
+/// analysis code can be used on both. Winnings are taken from the hand
+/// history's pots rather than worked out from a showdown.
+pub fn import_hand(hand: &HandHistory) -> Result<ImportedHand, ImportError> {
+    if hand.game_type != GameType::Holdem {
+        return Err(ImportError::UnsupportedGameType);
+    }
+
+    if !(2..=MAX_PLAYERS).contains(&hand.players.len()) {
+        return Err(ImportError::InvalidTable(GameStateError::PlayerCount(
+            hand.players.len(),
+        )));
+    }
+    let mut players: Vec<&PlayerObj> = hand.players.iter().collect();
+    players.sort_by_key(|p| p.seat);
+    let dealer_idx = players
+        .iter()
+        .position(|p| p.seat == hand.dealer_seat)
+        .ok_or(ImportError::UnknownDealerSeat(hand.dealer_seat))?;
+
+    let stacks = players
+        .iter()
+        .map(|p| whole_chips(p.starting_stack))
+        .collect::<Result<_, _>>()?;
+    let big_blind = whole_chips(hand.big_blind_amount)?;
+    let small_blind = whole_chips(hand.small_blind_amount)?;
+    let ante = whole_chips(hand.ante_amount)?;
+    let game_state = GameState::new_starting(stacks, big_blind, small_blind, ante, dealer_idx);
+    let mut importer = Importer {
+        game_state,
+        actions: vec![],
+        game_states: vec![],
+        player_idx: players
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| (p.id, idx))
+            .collect(),
+    };
+
+    importer.record(ArenaAction::GameStart(GameStartPayload {
+        ante,
+        small_blind,
+        big_blind,
+    }));
+    // Sit everyone the same way the simulation does.
+    while importer.game_state.current_round_num_active_players() > 0 {
+        let idx = importer.game_state.to_act_idx();
+        let player_stack = importer.game_state.stacks[idx];
+        importer.record(ArenaAction::PlayerSit(PlayerSitPayload {
+            idx,
+            player_stack,
+        }));
+        importer.game_state.round_data.needs_action.disable(idx);
+        importer.game_state.round_data.advance_action();
+    }
+    importer.advance_to(Round::Ante)?;
+
+    for round in &hand.rounds {
+        importer.round(round)?;
+    }
+
+    // Hands that weren't folded out are decided at showdown.
+    let left = importer.game_state.player_active | importer.game_state.player_all_in;
+    if left.count() > 1 {
+        importer.advance_to(Round::Showdown)?;
+    }
+
+    for pot in &hand.pots {
+        let total_pot = whole_chips(pot.amount)?;
+        for (i, win) in pot.player_wins.iter().enumerate() {
+            let idx = importer.idx(win.player_id)?;
+            // The pot's rake goes on its first award.
+            let rake = match pot.rake {
+                Some(rake) if i == 0 => whole_chips(rake)?,
+                _ => Chips::ZERO,
+            };
+            let award_amount = whole_chips(win.win_amount)?;
+            importer.game_state.award(idx, award_amount);
+            importer.game_state.total_rake += rake;
+            importer.record(ArenaAction::Award(AwardPayload {
+                total_pot,
+                award_amount,
+                rank: None,
+                hand: None,
+                idx,
+                rake,
+            }));
+        }
+    }
+
+    if importer.game_state.round != Round::Complete {
+        importer.game_state.complete();
+        importer.record(ArenaAction::RoundAdvance(Round::Complete));
+    }
+
+    Ok(ImportedHand {
+        actions: importer.actions,
+        game_states: importer.game_states,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::arena::{
+        Agent, HoldemSimulationBuilder, StackedDeck,
+        action::AgentAction,
+        agent::{CallingAgent, RandomAgent, VecReplayAgent},
+        historian::VecHistorian,
+    };
+    use crate::core::{Rank, cards};
+    use crate::open_hand_history::{append_hand, read_hands};
+
+    /// The parts of a betting action that a hand history keeps.
+    fn betting(actions: &[ArenaAction]) -> Vec<(usize, Round, Chips, Chips)> {
+        actions
+            .iter()
+            .filter_map(|a| match a {
+                ArenaAction::PlayedAction(p) => Some(p),
+                ArenaAction::FailedAction(f) => Some(&f.result),
+                _ => None,
+            })
+            .map(|p| (p.idx, p.round, p.final_player_bet, p.final_pot))
+            .collect()
+    }
+
+    fn forced(actions: &[ArenaAction]) -> Vec<(usize, Chips)> {
+        actions
+            .iter()
+            .filter_map(|a| match a {
+                ArenaAction::ForcedBet(p) => Some((p.idx, p.player_stack)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn community(actions: &[ArenaAction]) -> Vec<Card> {
+        actions
+            .iter()
+            .filter_map(|a| match a {
+                ArenaAction::DealCommunity(c) => Some(*c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Who showed what at showdown, in order.
+    fn showdown(actions: &[ArenaAction]) -> Vec<(usize, Option<Rank>)> {
+        actions
+            .iter()
+            .filter_map(|a| match a {
+                ArenaAction::Showdown(p) => Some((p.idx, p.rank)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip_simulated_hands() {
+        let mut rng = StdRng::seed_from_u64(217);
+        for _ in 0..20 {
+            let hist = VecHistorian::new();
+            let storage = hist.get_storage();
+            // Seed the agents too so that every run plays the same hands.
+            let mut random_agent = || -> Box<dyn Agent> {
+                Box::new(RandomAgent::new_with_rng(
+                    vec![0.25, 0.30, 0.50],
+                    vec![0.5, 0.6, 0.45],
+                    &mut rng,
+                ))
+            };
+            let agents: Vec<Box<dyn Agent>> = vec![
+                random_agent(),
+                random_agent(),
+                Box::new(CallingAgent),
+                random_agent(),
+            ];
+            let game_state = GameState::new_starting(
+                vec![Chips::new(100); 4],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::new(1),
+                1,
+            );
+            let mut sim = HoldemSimulationBuilder::default()
+                .game_state(game_state)
+                .agents(agents)
+                .historians(vec![Box::new(hist)])
+                .build()
+                .unwrap();
+            sim.run(&mut rng);
+
+            let recorded: Vec<ArenaAction> =
+                storage.borrow().iter().map(|r| r.action.clone()).collect();
+
+            // Write it out and read it back in.
+            let temp_file = NamedTempFile::new().unwrap();
+            append_hand(temp_file.path(), export_hand(sim.id, &recorded)).unwrap();
+            let hands = read_hands(temp_file.path()).unwrap();
+            assert_eq!(1, hands.len());
+
+            let imported = import_hand(&hands[0]).unwrap();
+            assert_eq!(betting(&recorded), betting(&imported.actions));
+            assert_eq!(forced(&recorded), forced(&imported.actions));
+            assert_eq!(community(&recorded), community(&imported.actions));
+            assert_eq!(showdown(&recorded), showdown(&imported.actions));
+
+            let final_state = imported.final_game_state().unwrap();
+            assert_eq!(sim.game_state.stacks, final_state.stacks);
+            assert_eq!(sim.game_state.board, final_state.board);
+            assert_eq!(sim.game_state.hands, final_state.hands);
+            assert_eq!(Round::Complete, final_state.round);
+            assert_eq!(imported.actions.len(), imported.game_states.len());
+        }
+    }
+
+    #[test]
+    fn test_export_mucked_hand() {
+        // The button bets the river with aces and gets called by seven
+        // high, which is mucked.
+        let stacked = StackedDeck::new()
+            .hole_cards(0, cards("AsAd"))
+            .hole_cards(1, cards("7c2d"))
+            .board(cards("Kh8h3s4c9d"));
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(VecReplayAgent::new_with_default(
+                vec![
+                    AgentAction::Call,
+                    AgentAction::Call,
+                    AgentAction::Call,
+                    AgentAction::Bet(Chips::new(20)),
+                ],
+                AgentAction::Call,
+            )),
+            Box::new(VecReplayAgent::new_with_default(vec![], AgentAction::Call)),
+        ];
+        let hist = VecHistorian::new();
+        let storage = hist.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(GameState::new_starting(
+                vec![Chips::new(100); 2],
+                Chips::new(10),
+                Chips::new(5),
+                Chips::ZERO,
+                0,
+            ))
+            .agents(agents)
+            .stacked_deck(stacked)
+            .muck_losing_hands(true)
+            .historians(vec![Box::new(hist)])
+            .build()
+            .unwrap();
+        sim.run(&mut StdRng::seed_from_u64(306));
+
+        let recorded: Vec<ArenaAction> =
+            storage.borrow().iter().map(|r| r.action.clone()).collect();
+        let hand = export_hand(sim.id, &recorded);
+
+        let round = hand.rounds.last().unwrap();
+        assert_eq!("Showdown", round.street);
+        let shown: Vec<(u64, Action, Option<Vec<Card>>)> = round
+            .actions
+            .iter()
+            .map(|a| (a.player_id, a.action.clone(), a.cards.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, Action::ShowsCards, Some(cards("AsAd"))),
+                (1, Action::MucksCards, None),
+            ],
+            shown
+        );
+
+        let imported = import_hand(&hand).unwrap();
+        assert_eq!(showdown(&recorded), showdown(&imported.actions));
+        assert_eq!(None, showdown(&imported.actions)[1].1);
+    }
+
+    #[test]
+    fn test_out_of_turn() {
+        let game_state = GameState::new_starting(
+            vec![Chips::new(100); 3],
+            Chips::new(10),
+            Chips::new(5),
+            Chips::ZERO,
+            0,
+        );
+        let hist = VecHistorian::new();
+        let storage = hist.get_storage();
+        let mut sim = HoldemSimulationBuilder::default()
+            .game_state(game_state)
+            .agents(vec![
+                Box::new(CallingAgent),
+                Box::new(CallingAgent),
+                Box::new(CallingAgent),
+            ])
+            .historians(vec![Box::new(hist)])
+            .build()
+            .unwrap();
+        sim.run(&mut rand::rng());
+
+        let recorded: Vec<ArenaAction> =
+            storage.borrow().iter().map(|r| r.action.clone()).collect();
+        let mut hand = export_hand(sim.id, &recorded);
+
+        // Swap who made the first voluntary action.
+        let preflop = &mut hand.rounds[0];
+        let first = preflop
+            .actions
+            .iter_mut()
+            .find(|a| a.action == Action::Call)
+            .unwrap();
+        first.player_id = (first.player_id + 1) % 3;
+
+        assert!(matches!(
+            import_hand(&hand),
+            Err(ImportError::OutOfTurn { .. })
+        ));
+    }
+
+    #[test]
+    fn test_player_count_checked() {
+        let hand = export_hand(0, &[]);
+        assert_eq!(
+            ImportError::InvalidTable(GameStateError::PlayerCount(0)),
+            import_hand(&hand).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_unsupported_game_type() {
+        let mut hand = export_hand(0, &[]);
+        hand.game_type = GameType::Omaha;
+        assert_eq!(
+            ImportError::UnsupportedGameType,
+            import_hand(&hand).unwrap_err()
+        );
+    }
+}
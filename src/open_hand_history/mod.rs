@@ -1,9 +1,15 @@
 //! This module provides the open hand history format handling for
 //! `rs_poker`. It includes parsing, serialization, and deserialization of
 //! hand histories in the open format.
+#[cfg(feature = "arena")]
+mod convert;
 mod hand_history;
+mod reader;
 mod serde_utils;
 mod writer;
 
+#[cfg(feature = "arena")]
+pub use convert::*;
 pub use hand_history::*;
+pub use reader::*;
 pub use writer::*;
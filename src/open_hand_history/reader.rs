@@ -0,0 +1,83 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::hand_history::{HandHistory, OpenHandHistoryWrapper};
+
+/// Reads every hand history from a file in JSON Lines format.
+///
+/// This is the format written by [`super::append_hand`]. Blank lines between
+/// hands are skipped.
+pub fn read_hands(path: &Path) -> io::Result<Vec<HandHistory>> {
+    let file = File::open(path)?;
+    parse_hands(BufReader::new(file))
+}
+
+/// Parses hand histories in JSON Lines format from any reader.
+pub fn parse_hands<R: BufRead>(reader: R) -> io::Result<Vec<HandHistory>> {
+    let mut hands = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let wrapped: OpenHandHistoryWrapper = serde_json::from_str(line)?;
+        hands.push(wrapped.ohh);
+    }
+    Ok(hands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_hand_history::{GameType, append_hand};
+    use tempfile::NamedTempFile;
+
+    fn hand(game_number: &str) -> HandHistory {
+        HandHistory {
+            spec_version: "1.4.7".to_string(),
+            site_name: "Test Site".to_string(),
+            network_name: "Test Network".to_string(),
+            internal_version: "1.0".to_string(),
+            tournament: false,
+            tournament_info: None,
+            game_number: game_number.to_string(),
+            start_date_utc: None,
+            table_name: "Test Table".to_string(),
+            table_handle: None,
+            table_skin: None,
+            game_type: GameType::Holdem,
+            bet_limit: None,
+            table_size: 9,
+            currency: "USD".to_string(),
+            dealer_seat: 1,
+            small_blind_amount: 1.0,
+            big_blind_amount: 2.0,
+            ante_amount: 0.0,
+            hero_player_id: None,
+            players: vec![],
+            rounds: vec![],
+            pots: vec![],
+            tournament_bounties: None,
+        }
+    }
+
+    #[test]
+    fn test_read_appended_hands() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        append_hand(path, hand("1")).unwrap();
+        append_hand(path, hand("2")).unwrap();
+
+        let hands = read_hands(path).unwrap();
+        assert_eq!(vec![hand("1"), hand("2")], hands);
+    }
+
+    #[test]
+    fn test_parse_invalid_json() {
+        let input = "{\"ohh\": 7}\n";
+        assert!(parse_hands(input.as_bytes()).is_err());
+    }
+}
@@ -44,10 +44,14 @@ where
         Result(T),
     }
 
-    match Wrapper::deserialize(deserializer)? {
-        Wrapper::String(s) if s.is_empty() => Ok(None),
-        Wrapper::String(_) => Err(serde::de::Error::custom("expected empty string or vector")),
-        Wrapper::Result(v) => Ok(Some(v)),
+    // A missing value is written out as null, so accept that too.
+    match Option::<Wrapper<T>>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Wrapper::String(s)) if s.is_empty() => Ok(None),
+        Some(Wrapper::String(_)) => {
+            Err(serde::de::Error::custom("expected empty string or vector"))
+        }
+        Some(Wrapper::Result(v)) => Ok(Some(v)),
     }
 }
 
@@ -133,4 +137,24 @@ mod tests {
             deserialized.date.unwrap().to_rfc3339()
         );
     }
+
+    #[derive(Debug, Deserialize)]
+    struct TestCards {
+        #[serde(default, deserialize_with = "empty_string_is_none")]
+        cards: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_empty_string_is_none() {
+        let parsed: TestCards = serde_json::from_str(r#"{"cards": ""}"#).unwrap();
+        assert_eq!(None, parsed.cards);
+
+        let parsed: TestCards = serde_json::from_str(r#"{"cards": null}"#).unwrap();
+        assert_eq!(None, parsed.cards);
+
+        let parsed: TestCards = serde_json::from_str(r#"{"cards": [1, 2]}"#).unwrap();
+        assert_eq!(Some(vec![1, 2]), parsed.cards);
+
+        assert!(serde_json::from_str::<TestCards>(r#"{"cards": "x"}"#).is_err());
+    }
 }
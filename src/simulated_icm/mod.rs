@@ -102,6 +102,130 @@ pub fn simulate_icm_tournament(chip_stacks: &[i32], payments: &[i32]) -> Vec<i32
     winnings
 }
 
+/// The most players `icm_equity` and `icm_pressure` will compute for. The
+/// work can double with every extra player, so past this
+/// `simulate_icm_tournament` is the better choice.
+pub const MAX_ICM_PLAYERS: usize = 20;
+
+/// Compute the expected payout of every player using the Malmuth-Harville
+/// ICM model.
+///
+/// Unlike `simulate_icm_tournament` this is exact and repeatable. It walks
+/// every set of players that could finish in the paid places, so it's only
+/// practical for at most [`MAX_ICM_PLAYERS`] players.
+///
+/// # Arguments
+///
+/// * `chip_stacks` - The chip stacks of each player in the tournament.
+/// * `payments` - The payout schedule for the tournament.
+///
+/// # Panics
+///
+/// Panics with more than [`MAX_ICM_PLAYERS`] players.
+pub fn icm_equity(chip_stacks: &[i32], payments: &[i32]) -> Vec<f64> {
+    let num_players = chip_stacks.len();
+    assert!(
+        num_players <= MAX_ICM_PLAYERS,
+        "Exact ICM supports at most {MAX_ICM_PLAYERS} players, not {num_players}"
+    );
+    let mut equity = vec![0.0; num_players];
+    let places = payments.len().min(num_players);
+    let total: f64 = chip_stacks.iter().map(|c| f64::from(*c)).sum();
+
+    // Each level maps a set of players who have already taken the top places
+    // to the probability of that happening.
+    let mut level: Vec<(u64, f64)> = vec![(0, 1.0)];
+    for payment in payments.iter().take(places) {
+        let mut next: std::collections::HashMap<u64, f64> = std::collections::HashMap::new();
+        for (placed, prob) in level {
+            let placed_chips: f64 = (0..num_players)
+                .filter(|idx| placed & (1 << idx) != 0)
+                .map(|idx| f64::from(chip_stacks[idx]))
+                .sum();
+            let remaining = total - placed_chips;
+            let left = num_players - placed.count_ones() as usize;
+
+            for idx in (0..num_players).filter(|idx| placed & (1 << idx) == 0) {
+                // Once only busted players are left they split what remains.
+                let chance = if remaining > 0.0 {
+                    f64::from(chip_stacks[idx]) / remaining
+                } else {
+                    1.0 / left as f64
+                };
+                if chance == 0.0 {
+                    continue;
+                }
+                equity[idx] += prob * chance * f64::from(*payment);
+                *next.entry(placed | (1 << idx)).or_default() += prob * chance;
+            }
+        }
+        level = next.into_iter().collect();
+    }
+    equity
+}
+
+/// How much tighter than chip EV should `hero` play because of pay jumps.
+///
+/// This is the bubble factor of hero against the biggest other stack at the
+/// table: the ICM value hero would lose by losing an all in for the
+/// effective stack divided by the value hero would gain by winning it. A
+/// value of 1.0 means chips and prize money are worth the same, larger
+/// values mean hero needs more equity than pot odds alone suggest.
+///
+/// # Arguments
+///
+/// * `chip_stacks` - The chip stacks of each player in the tournament.
+/// * `payments` - The payout schedule for the tournament.
+/// * `hero` - The index of the player to compute the pressure for.
+///
+/// # Panics
+///
+/// Panics with more than [`MAX_ICM_PLAYERS`] players.
+///
+/// # Examples
+///
+/// ```
+/// use rs_poker::simulated_icm::icm_pressure;
+///
+/// // Winner take all means chips are linear with prize money.
+/// let pressure = icm_pressure(&[100, 100, 100], &[300], 0);
+/// assert!((pressure - 1.0).abs() < 1e-9);
+///
+/// // On the bubble the pressure is much higher.
+/// let bubble = icm_pressure(&[100, 100, 100, 100], &[50, 30, 20], 0);
+/// assert!(bubble > 1.0);
+/// ```
+pub fn icm_pressure(chip_stacks: &[i32], payments: &[i32], hero: usize) -> f64 {
+    let villain = match (0..chip_stacks.len())
+        .filter(|idx| *idx != hero && chip_stacks[*idx] > 0)
+        .max_by_key(|idx| chip_stacks[*idx])
+    {
+        Some(villain) => villain,
+        None => return 1.0,
+    };
+    let effective = chip_stacks[hero].min(chip_stacks[villain]);
+
+    let now = icm_equity(chip_stacks, payments)[hero];
+
+    let mut win_stacks = chip_stacks.to_vec();
+    win_stacks[hero] += effective;
+    win_stacks[villain] -= effective;
+    let win = icm_equity(&win_stacks, payments)[hero];
+
+    let mut lose_stacks = chip_stacks.to_vec();
+    lose_stacks[hero] -= effective;
+    lose_stacks[villain] += effective;
+    let lose = icm_equity(&lose_stacks, payments)[hero];
+
+    let gain = win - now;
+    let risk = now - lose;
+    if gain <= 0.0 {
+        if risk > 0.0 { f64::INFINITY } else { 1.0 }
+    } else {
+        risk / gain
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,6 +271,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_icm_equity_sums_to_prize_pool() {
+        let equity = icm_equity(&[500, 300, 150, 50], &[50, 30, 20]);
+        let total: f64 = equity.iter().sum();
+        assert!((total - 100.0).abs() < 1e-9);
+        // Bigger stacks are worth more
+        assert!(equity[0] > equity[1]);
+        assert!(equity[1] > equity[2]);
+        assert!(equity[2] > equity[3]);
+    }
+
+    #[test]
+    fn test_icm_equity_even_stacks() {
+        let equity = icm_equity(&[100, 100, 100], &[50, 30, 20]);
+        for e in equity {
+            assert!((e - 100.0 / 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_icm_equity_busted_player() {
+        let equity = icm_equity(&[100, 100, 0], &[50, 30, 20]);
+        assert!((equity[2] - 20.0).abs() < 1e-9);
+        assert!((equity[0] - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_icm_pressure_bubble_vs_in_the_money() {
+        let payments = vec![50, 30, 20];
+        // Four left with three paid
+        let bubble = icm_pressure(&[100, 100, 100, 100], &payments, 0);
+        // Everyone left is already paid
+        let in_money = icm_pressure(&[100, 100, 100], &payments, 0);
+
+        assert!(in_money > 1.0);
+        assert!(bubble > in_money);
+    }
+
+    #[test]
+    fn test_icm_pressure_winner_take_all() {
+        let pressure = icm_pressure(&[400, 250, 100], &[100], 1);
+        assert!((pressure - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 20 players")]
+    fn test_icm_equity_too_many_players() {
+        icm_equity(&[100; MAX_ICM_PLAYERS + 1], &[50, 30, 20]);
+    }
+
     #[test]
     fn about_same() {
         let stacks = vec![1000, 1000, 999];